@@ -42,11 +42,11 @@ impl SessionStore {
 
     /// Get the current session store from the root context, or create a new one if it doesn't exist.
     fn get_current_session() -> Self {
-        dioxus::prelude::consume_context_from_scope::<Self>(dioxus::prelude::ScopeId::ROOT)
+        dioxus::core::consume_context_from_scope::<Self>(dioxus::prelude::ScopeId::ROOT)
             .map_or_else(
                 || {
                     let session = Self::new();
-                    dioxus::prelude::provide_root_context(session.clone());
+                    dioxus::core::provide_root_context(session.clone());
                     session
                 },
                 |s| s,
@@ -38,6 +38,7 @@ pub use persistence::{
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use dioxus::core::{ReactiveContext, current_scope_id, generation, needs_update};
 use dioxus::prelude::*;
 use serde::{Serialize, de::DeserializeOwned};
 use std::any::Any;
@@ -401,10 +402,7 @@ where
     pub fn new(key: S::Key, data: T) -> Self {
         Self {
             key,
-            data: Signal::new_in_scope(
-                data,
-                current_scope_id().expect("must be called from inside of the dioxus context"),
-            ),
+            data: Signal::new_in_scope(data, current_scope_id()),
         }
     }
 }
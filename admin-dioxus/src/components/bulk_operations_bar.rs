@@ -0,0 +1,179 @@
+use crate::components::ConfirmDialog;
+use crate::store::{use_bulk, BulkItemStatus, BulkOperation};
+use crate::ui::shadcn::{Button, ButtonSize, ButtonVariant, Progress};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+const DESTRUCTIVE_CLASS: &str = "text-red-600 border-red-200 dark:border-red-800 dark:hover:bg-red-950/20 hover:bg-red-50";
+
+#[derive(Props, PartialEq, Clone)]
+pub struct BulkOperationsBarProps {
+    pub selected_ids: Signal<Vec<i32>>,
+    pub operations: Vec<BulkOperation>,
+    /// Row names for the confirm dialog and failure report; ids without one show as `#id`.
+    #[props(default)]
+    pub names: HashMap<i32, String>,
+    /// Called once a run finishes, e.g. to reload the list.
+    #[props(default)]
+    pub on_complete: EventHandler<()>,
+}
+
+/// Selection bar for list screens. Each operation asks for one confirmation, then runs over
+/// the selection with per-item progress and a report of the items that failed.
+#[component]
+pub fn BulkOperationsBar(props: BulkOperationsBarProps) -> Element {
+    let bulk = use_bulk();
+    let mut confirm_open = use_signal(|| false);
+    let mut pending = use_signal(|| None::<BulkOperation>);
+    let mut selected_ids = props.selected_ids;
+    let on_complete = props.on_complete;
+
+    // Only show jobs started from this screen
+    let job = bulk
+        .job
+        .read()
+        .clone()
+        .filter(|job| props.operations.contains(&job.operation));
+
+    let mut run = {
+        let names = props.names.clone();
+        move |operation: BulkOperation| {
+            let items: Vec<(i32, String)> = selected_ids
+                .peek()
+                .iter()
+                .map(|id| {
+                    let name = names.get(id).cloned().unwrap_or_else(|| format!("#{}", id));
+                    (*id, name)
+                })
+                .collect();
+            selected_ids.set(Vec::new());
+            spawn(async move {
+                use_bulk().run(operation, items).await;
+                on_complete.call(());
+            });
+        }
+    };
+
+    let selected_count = selected_ids.read().len();
+    let (confirm_title, confirm_description) = match *pending.read() {
+        Some(operation) => {
+            let noun = operation.noun(selected_count);
+            let title = format!("{} {} {}?", operation.label(), selected_count, noun);
+            let description = if operation.is_destructive() {
+                format!(
+                    "The selected {} will be {}. This action cannot be undone.",
+                    noun,
+                    operation.past_tense()
+                )
+            } else {
+                format!("The selected {} will be {}.", noun, operation.past_tense())
+            };
+            (title, description)
+        }
+        None => (String::new(), String::new()),
+    };
+
+    rsx! {
+        if let Some(job) = job {
+            {
+                let total = job.items.len();
+                let noun = job.operation.noun(total);
+                let failures: Vec<(String, String)> = job
+                    .failures()
+                    .into_iter()
+                    .map(|item| {
+                        let message = match &item.status {
+                            BulkItemStatus::Failed(message) => message.clone(),
+                            _ => String::new(),
+                        };
+                        (item.label.clone(), message)
+                    })
+                    .collect();
+                let finished = job.is_finished();
+                rsx! {
+                    div { class: "w-full bg-transparent border border-zinc-200 dark:border-zinc-800 rounded-md px-4 py-3 shadow-sm space-y-3",
+                        div { class: "flex items-center justify-between gap-4",
+                            span { class: "text-sm text-muted-foreground",
+                                if finished {
+                                    "{job.succeeded()} of {total} {noun} {job.operation.past_tense()}"
+                                } else {
+                                    "{job.operation.label()}: {job.processed()} of {total} {noun}…"
+                                }
+                            }
+                            if finished {
+                                div { class: "flex items-center gap-2",
+                                    if !failures.is_empty() {
+                                        Button {
+                                            variant: ButtonVariant::Outline,
+                                            size: ButtonSize::Sm,
+                                            onclick: move |_| {
+                                                spawn(async move {
+                                                    use_bulk().retry_failed().await;
+                                                    on_complete.call(());
+                                                });
+                                            },
+                                            "Retry failed"
+                                        }
+                                    }
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        size: ButtonSize::Sm,
+                                        onclick: move |_| bulk.dismiss(),
+                                        "Dismiss"
+                                    }
+                                }
+                            }
+                        }
+                        Progress { value: job.percent() }
+                        if !failures.is_empty() {
+                            div { class: "space-y-1",
+                                p { class: "text-sm font-medium text-red-600", "{failures.len()} failed" }
+                                ul { class: "text-xs text-muted-foreground space-y-1 max-h-40 overflow-y-auto",
+                                    for (label, message) in failures {
+                                        li {
+                                            span { class: "font-medium text-foreground", "{label}" }
+                                            ": {message}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if selected_count > 0 {
+            div { class: "w-full flex items-center justify-between bg-transparent border border-zinc-200 dark:border-zinc-800 rounded-md px-4 py-3 shadow-sm",
+                span { class: "text-sm text-muted-foreground", "{selected_count} selected" }
+                div { class: "flex items-center gap-2",
+                    for operation in props.operations.iter().copied() {
+                        Button {
+                            key: "{operation:?}",
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Sm,
+                            class: if operation.is_destructive() { DESTRUCTIVE_CLASS.to_string() } else { String::new() },
+                            disabled: bulk.is_running(),
+                            onclick: move |_| {
+                                pending.set(Some(operation));
+                                confirm_open.set(true);
+                            },
+                            "{operation.label()}"
+                        }
+                    }
+                }
+            }
+        }
+        ConfirmDialog {
+            is_open: confirm_open,
+            title: confirm_title,
+            description: confirm_description,
+            confirm_label: pending().map(|op| op.label()).unwrap_or("Confirm").to_string(),
+            on_confirm: move |_| {
+                if let Some(operation) = pending.take() {
+                    run(operation);
+                }
+            },
+            on_cancel: move |_| pending.set(None),
+        }
+    }
+}
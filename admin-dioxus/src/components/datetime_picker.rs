@@ -0,0 +1,81 @@
+use chrono::{DateTime, Local, Utc};
+use dioxus::prelude::*;
+
+use crate::utils::dates::{
+    format_datetime_local, parse_datetime_local, ScheduleZone, SCHEDULE_OFFSETS,
+};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct DateTimePickerProps {
+    /// Selected instant; shown as wall-clock time in the chosen zone
+    pub value: Option<DateTime<Utc>>,
+    /// Called with the instant for the entered time, or `None` while it is incomplete
+    pub onchange: EventHandler<Option<DateTime<Utc>>>,
+    #[props(default)]
+    pub class: Option<String>,
+}
+
+/// Date-time input with a time zone select. The entered wall-clock time is read in the
+/// selected zone, so "09:00 UTC+05:30" and "03:30 UTC" give the same instant.
+#[component]
+pub fn DateTimePicker(props: DateTimePickerProps) -> Element {
+    let mut zone = use_signal(|| ScheduleZone::Local);
+    let mut wall = use_signal(|| {
+        props
+            .value
+            .map(|at| format_datetime_local(&ScheduleZone::Local.from_utc(at)))
+            .unwrap_or_default()
+    });
+
+    let emit = move |zone: ScheduleZone, text: &str| {
+        props
+            .onchange
+            .call(parse_datetime_local(text).and_then(|w| zone.to_utc(w)));
+    };
+
+    let zones: Vec<ScheduleZone> = [ScheduleZone::Local, ScheduleZone::Utc]
+        .into_iter()
+        .chain(SCHEDULE_OFFSETS.iter().map(|m| ScheduleZone::Offset(*m)))
+        .collect();
+    let skipped = parse_datetime_local(&wall()).is_some_and(|w| zone().to_utc(w).is_none());
+
+    rsx! {
+        div { class: props.class.clone().unwrap_or_else(|| "space-y-2".to_string()),
+            div { class: "flex flex-col gap-2 sm:flex-row",
+                input {
+                    class: "h-9 flex-1 rounded-md border border-border/70 bg-transparent px-3 text-sm text-foreground focus:border-ring focus:ring-2 focus:ring-ring/40",
+                    r#type: "datetime-local",
+                    value: wall(),
+                    oninput: move |event| {
+                        let text = event.value();
+                        emit(zone(), &text);
+                        wall.set(text);
+                    },
+                }
+                select {
+                    class: "h-9 rounded-md border border-border/70 bg-transparent px-2 text-sm text-foreground",
+                    onchange: move |event| {
+                        if let Some(next) = ScheduleZone::from_key(&event.value()) {
+                            zone.set(next);
+                            emit(next, &wall.peek());
+                        }
+                    },
+                    for option_zone in zones {
+                        option {
+                            value: option_zone.key(),
+                            selected: option_zone == zone(),
+                            {option_zone.label()}
+                        }
+                    }
+                }
+            }
+            if skipped {
+                p { class: "text-xs text-destructive", "This time does not exist in the selected zone (daylight saving change)." }
+            } else if let (Some(at), false) = (props.value, zone() == ScheduleZone::Local) {
+                p { class: "text-xs text-muted-foreground",
+                    {format!("{} in your browser time", at.with_timezone(&Local).format("%b %-d, %Y %H:%M"))}
+                }
+            }
+        }
+    }
+}
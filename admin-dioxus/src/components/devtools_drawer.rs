@@ -0,0 +1,205 @@
+use dioxus::prelude::*;
+
+use crate::components::sonner::{use_sonner, ToastOptions};
+use crate::services::api_client::HttpMethod;
+use crate::store::{use_devtools, RequestLogEntry, RequestLogFilter};
+use crate::ui::shadcn::{Badge, BadgeVariant, Button, ButtonSize, ButtonVariant};
+
+const METHODS: [HttpMethod; 4] = [
+    HttpMethod::Get,
+    HttpMethod::Post,
+    HttpMethod::Put,
+    HttpMethod::Delete,
+];
+
+fn pretty_body(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| body.to_string())
+}
+
+/// API request log for debug builds: filter, inspect, copy as curl and re-send.
+/// Rendered once inside `SonnerToaster`; release builds render nothing.
+#[component]
+pub fn DevtoolsDrawer() -> Element {
+    let devtools = use_devtools();
+    let sonner = use_sonner();
+    let mut search = use_signal(String::new);
+    let mut method = use_signal(|| None::<HttpMethod>);
+    let mut errors_only = use_signal(|| false);
+    let mut selected = use_signal(|| None::<u64>);
+
+    if !crate::store::devtools_enabled() {
+        return rsx! {};
+    }
+
+    let open = *devtools.open.read();
+    let total = devtools.log.read().len();
+    let entries = devtools.filtered(&RequestLogFilter {
+        search: search(),
+        method: method(),
+        errors_only: errors_only(),
+    });
+    let selected_entry = selected().and_then(|id| entries.iter().find(|e| e.id == id).cloned());
+
+    let copy_curl = move |entry: RequestLogEntry| {
+        let curl = serde_json::to_string(&entry.to_curl()).unwrap_or_default();
+        spawn(async move {
+            let _ = document::eval(&format!("navigator.clipboard.writeText({curl})")).await;
+            sonner.success("Copied as curl".to_string(), ToastOptions::default());
+        });
+    };
+
+    rsx! {
+        button {
+            r#type: "button",
+            class: "fixed bottom-4 right-4 z-50 rounded-full border border-border bg-background px-3 py-1.5 font-mono text-xs text-muted-foreground shadow-md hover:text-foreground",
+            title: "Toggle request devtools",
+            onclick: move |_| devtools.toggle(),
+            "API · {total}"
+        }
+
+        if open {
+            div { class: "fixed inset-x-0 bottom-0 z-50 flex h-[45vh] flex-col border-t border-border bg-background text-foreground shadow-2xl",
+                div { class: "flex flex-wrap items-center gap-2 border-b border-border px-4 py-2",
+                    span { class: "text-sm font-semibold", "Requests" }
+                    input {
+                        class: "h-8 w-56 rounded-md border border-border bg-transparent px-2 text-xs",
+                        placeholder: "Filter by path or request id",
+                        value: search(),
+                        oninput: move |e| search.set(e.value()),
+                    }
+                    select {
+                        class: "h-8 rounded-md border border-border bg-transparent px-2 text-xs",
+                        onchange: move |e| {
+                            method.set(METHODS.into_iter().find(|m| m.as_str() == e.value()));
+                        },
+                        option { value: "", "All methods" }
+                        for m in METHODS {
+                            option { value: m.as_str(), selected: method() == Some(m), "{m}" }
+                        }
+                    }
+                    label { class: "flex items-center gap-1 text-xs text-muted-foreground",
+                        input {
+                            r#type: "checkbox",
+                            checked: errors_only(),
+                            onchange: move |e| errors_only.set(e.checked()),
+                        }
+                        "Errors only"
+                    }
+                    div { class: "ml-auto flex gap-2",
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Sm,
+                            onclick: move |_| {
+                                devtools.clear();
+                                selected.set(None);
+                            },
+                            "Clear"
+                        }
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            size: ButtonSize::Sm,
+                            onclick: move |_| devtools.toggle(),
+                            "Close"
+                        }
+                    }
+                }
+
+                div { class: "grid min-h-0 flex-1 grid-cols-1 md:grid-cols-2",
+                    div { class: "min-h-0 overflow-y-auto border-r border-border font-mono text-xs",
+                        if entries.is_empty() {
+                            div { class: "p-6 text-center text-muted-foreground", "No requests recorded." }
+                        }
+                        for entry in entries.iter().cloned() {
+                            button {
+                                key: "{entry.id}",
+                                r#type: "button",
+                                class: if selected() == Some(entry.id) { "flex w-full items-center gap-3 bg-muted px-4 py-1.5 text-left" } else { "flex w-full items-center gap-3 px-4 py-1.5 text-left hover:bg-muted/50" },
+                                onclick: move |_| selected.set(Some(entry.id)),
+                                span { class: "w-12 shrink-0 font-semibold", "{entry.method}" }
+                                span { class: if entry.is_error() { "w-12 shrink-0 text-destructive" } else { "w-12 shrink-0 text-emerald-600 dark:text-emerald-400" },
+                                    "{entry.status_label()}"
+                                }
+                                span { class: "flex-1 truncate", title: entry.url.clone(), "{entry.path}" }
+                                span { class: "shrink-0 text-muted-foreground", "{entry.duration_ms} ms" }
+                            }
+                        }
+                    }
+
+                    div { class: "min-h-0 space-y-3 overflow-y-auto p-4 text-xs",
+                        if let Some(entry) = selected_entry {
+                            div { class: "flex flex-wrap items-center gap-2",
+                                Badge { variant: BadgeVariant::Secondary, "{entry.method}" }
+                                span { class: "break-all font-mono", "{entry.url}" }
+                            }
+                            div { class: "grid grid-cols-[8rem_1fr] gap-x-3 gap-y-1 text-muted-foreground",
+                                span { "Status" }
+                                span { class: "text-foreground", "{entry.status_label()}" }
+                                span { "Started" }
+                                span { class: "text-foreground", {entry.started_at.format("%H:%M:%S%.3f").to_string()} }
+                                span { "Duration" }
+                                span { class: "text-foreground", "{entry.duration_ms} ms" }
+                                if let Some(request_id) = &entry.request_id {
+                                    span { "Request ID" }
+                                    span { class: "font-mono text-foreground", "{request_id}" }
+                                }
+                                if let Some(error) = &entry.transport_error {
+                                    span { "Transport error" }
+                                    span { class: "text-destructive", "{error}" }
+                                }
+                                if let Some(error) = &entry.decode_error {
+                                    span { "Decode error" }
+                                    span { class: "text-destructive", "{error}" }
+                                }
+                            }
+                            div { class: "flex gap-2",
+                                Button {
+                                    variant: ButtonVariant::Outline,
+                                    size: ButtonSize::Sm,
+                                    onclick: {
+                                        let entry = entry.clone();
+                                        move |_| copy_curl(entry.clone())
+                                    },
+                                    "Copy as curl"
+                                }
+                                Button {
+                                    variant: ButtonVariant::Outline,
+                                    size: ButtonSize::Sm,
+                                    disabled: !entry.can_resend(),
+                                    onclick: move |_| {
+                                        spawn(async move { use_devtools().resend(entry.id).await });
+                                    },
+                                    "Re-send"
+                                }
+                            }
+                            if entry.multipart {
+                                p { class: "text-muted-foreground", "Multipart upload; the file body is not recorded." }
+                            }
+                            if entry.redacted {
+                                p { class: "text-muted-foreground", "Credentials were masked; the request cannot be re-sent." }
+                            }
+                            if let Some(payload) = &entry.payload {
+                                p { class: "font-medium", "Payload" }
+                                pre { class: "overflow-x-auto rounded-md bg-muted p-2 font-mono",
+                                    "{serde_json::to_string_pretty(payload).unwrap_or_default()}"
+                                }
+                            }
+                            if let Some(body) = &entry.response_body {
+                                p { class: "font-medium",
+                                    if entry.decode_error.is_some() { "Raw response" } else { "Response" }
+                                }
+                                pre { class: "max-h-72 overflow-auto rounded-md bg-muted p-2 font-mono",
+                                    "{pretty_body(body)}"
+                                }
+                            }
+                        } else {
+                            div { class: "pt-6 text-center text-muted-foreground", "Select a request to inspect it." }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
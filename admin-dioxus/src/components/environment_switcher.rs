@@ -0,0 +1,38 @@
+use dioxus::prelude::*;
+
+use crate::config::{runtime_config, set_active_profile, ACTIVE_PROFILE};
+
+/// Picker for the API profile the admin talks to (dev, staging, prod, ...).
+#[component]
+pub fn EnvironmentSwitcher(#[props(default)] class: String) -> Element {
+    // Subscribe so the picker reflects switches made elsewhere.
+    let _active = ACTIVE_PROFILE.read();
+    let config = runtime_config();
+    let active = config.active_profile().clone();
+
+    rsx! {
+        div { class: "space-y-1 {class}",
+            label { class: "block text-xs font-medium text-muted-foreground", "API environment" }
+            select {
+                class: "w-full rounded-md border border-border/70 bg-transparent px-3 py-2 text-sm text-foreground transition-colors duration-200 focus:border-ring focus:ring-2 focus:ring-ring/40",
+                value: active.name.clone(),
+                onchange: move |e| {
+                    set_active_profile(&e.value());
+                },
+                for profile in config.profiles.iter() {
+                    option {
+                        key: "{profile.name}",
+                        value: profile.name.clone(),
+                        selected: profile.name == active.name,
+                        "{profile.label()}"
+                    }
+                }
+            }
+            p {
+                class: if active.is_secure() { "truncate text-xs font-mono text-muted-foreground" } else { "truncate text-xs font-mono text-amber-600 dark:text-amber-400" },
+                title: active.base_url(),
+                "{active.base_url()}"
+            }
+        }
+    }
+}
@@ -22,6 +22,9 @@ pub struct ListToolbarProps {
     pub status_selected: String,
     /// Called when status is selected
     pub on_status_select: EventHandler<String>,
+    /// Whether to render the status select; lists without an active flag hide it
+    #[props(default = true)]
+    pub show_status: bool,
 }
 
 /// Generic list toolbar with a search input and a status select.
@@ -51,21 +54,23 @@ pub fn ListToolbar(props: ListToolbarProps) -> Element {
                 }
 
                 // Status filter
-                div { class: "flex w-full items-center gap-2 md:w-auto",
-                    div { class: "w-full md:w-48 relative",
-                        label { class: "sr-only", r#for: "status", "Status" }
-                        Select {
-                            groups: vec![SelectGroup::new(
-                                "Status".to_string(),
-                                vec!["All".to_string(), "Active".to_string(), "Inactive".to_string()],
-                            )],
-                            selected: Some(props.status_selected.clone()),
-                            placeholder: "All status".to_string(),
-                            on_select: move |value| {
-                                props.on_status_select.call(value);
+                if props.show_status {
+                    div { class: "flex w-full items-center gap-2 md:w-auto",
+                        div { class: "w-full md:w-48 relative",
+                            label { class: "sr-only", r#for: "status", "Status" }
+                            Select {
+                                groups: vec![SelectGroup::new(
+                                    "Status".to_string(),
+                                    vec!["All".to_string(), "Active".to_string(), "Inactive".to_string()],
+                                )],
+                                selected: Some(props.status_selected.clone()),
+                                placeholder: "All status".to_string(),
+                                on_select: move |value| {
+                                    props.on_status_select.call(value);
+                                }
                             }
+                            if props.disabled { div { class: "absolute inset-0 z-10 cursor-not-allowed bg-transparent" } }
                         }
-                        if props.disabled { div { class: "absolute inset-0 z-10 cursor-not-allowed bg-transparent" } }
                     }
                 }
             }
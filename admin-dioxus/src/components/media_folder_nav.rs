@@ -0,0 +1,281 @@
+use crate::components::{ConfirmDialog, SimpleInput};
+use crate::hooks::{use_state_frame_toast, StateFrameToastConfig};
+use crate::store::{child_folders, folder_path, use_media, MediaFolderPayload};
+use crate::ui::shadcn::{Button, ButtonVariant};
+use dioxus::prelude::*;
+use hmziq_dioxus_free_icons::{icons::ld_icons::LdFolder, Icon};
+
+/// What the name input is for.
+#[derive(Clone, PartialEq)]
+enum NameEdit {
+    Create,
+    Rename(i32),
+}
+
+/// Breadcrumbs and subfolders of the media library folder being browsed.
+#[component]
+pub fn MediaFolderNav(
+    /// The folder being browsed; `None` is the whole library
+    current: Option<i32>,
+    on_navigate: EventHandler<Option<i32>>,
+    /// Makes the breadcrumbs and subfolders drop targets for dragged media, called with the
+    /// folder dropped on (`None` takes the media out of its folder)
+    #[props(default)]
+    on_drop: Option<EventHandler<Option<i32>>>,
+    /// Shows controls to create, rename and delete folders
+    #[props(default)]
+    editable: bool,
+) -> Element {
+    let media_state = use_media();
+    let mut name_edit = use_signal(|| None::<NameEdit>);
+    let mut name = use_signal(String::new);
+    let mut confirm_delete = use_signal(|| false);
+
+    use_effect(move || {
+        spawn(async move {
+            media_state.folders().await;
+        });
+    });
+
+    use_state_frame_toast(
+        &media_state.folder_save,
+        StateFrameToastConfig {
+            loading_title: "Saving folder...".into(),
+            success_title: Some("Folder saved".into()),
+            error_title: Some("Failed to save folder".into()),
+            ..Default::default()
+        },
+    );
+
+    let folders = media_state.folders.read().data.clone().unwrap_or_default();
+    let path: Vec<(i32, String)> = current
+        .map(|id| {
+            folder_path(&folders, id)
+                .into_iter()
+                .map(|folder| (folder.id, folder.name.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let children: Vec<(i32, String, i64)> = child_folders(&folders, current)
+        .into_iter()
+        .map(|folder| (folder.id, folder.name.clone(), folder.media_count))
+        .collect();
+    let current_folder =
+        current.and_then(|id| folders.iter().find(|folder| folder.id == id).cloned());
+    let parent_id = current_folder.as_ref().and_then(|folder| folder.parent_id);
+    let current_name = current_folder
+        .as_ref()
+        .map(|folder| folder.name.clone())
+        .unwrap_or_default();
+
+    let mut save_name = move || {
+        let value = name.peek().trim().to_string();
+        let Some(edit) = name_edit.peek().clone() else {
+            return;
+        };
+        if value.is_empty() {
+            return;
+        }
+        name_edit.set(None);
+        spawn(async move {
+            match edit {
+                NameEdit::Create => {
+                    media_state
+                        .create_folder(MediaFolderPayload {
+                            name: value,
+                            parent_id: current,
+                        })
+                        .await
+                }
+                NameEdit::Rename(id) => {
+                    media_state
+                        .rename_folder(
+                            id,
+                            MediaFolderPayload {
+                                name: value,
+                                parent_id,
+                            },
+                        )
+                        .await
+                }
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "space-y-2",
+            div { class: "flex flex-wrap items-center gap-1 text-sm",
+                FolderLink {
+                    target: None,
+                    label: "All media".to_string(),
+                    active: current.is_none(),
+                    on_navigate,
+                    on_drop,
+                }
+                for (id, folder_name) in path {
+                    span { key: "{id}", class: "flex items-center gap-1",
+                        span { class: "text-muted-foreground", "/" }
+                        FolderLink {
+                            target: Some(id),
+                            label: folder_name,
+                            active: current == Some(id),
+                            on_navigate,
+                            on_drop,
+                        }
+                    }
+                }
+                if editable {
+                    div { class: "ml-auto flex items-center gap-2",
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            class: "h-8 px-3",
+                            onclick: move |_| {
+                                name.set(String::new());
+                                name_edit.set(Some(NameEdit::Create));
+                            },
+                            "New Folder"
+                        }
+                        if let Some(id) = current {
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                class: "h-8 px-3",
+                                onclick: move |_| {
+                                    name.set(current_name.clone());
+                                    name_edit.set(Some(NameEdit::Rename(id)));
+                                },
+                                "Rename"
+                            }
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                class: "h-8 px-3 text-destructive",
+                                onclick: move |_| confirm_delete.set(true),
+                                "Delete"
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(edit) = name_edit() {
+                form {
+                    class: "flex items-center gap-2",
+                    onsubmit: move |e: Event<FormData>| {
+                        e.prevent_default();
+                        save_name();
+                    },
+                    div { class: "w-full md:w-72",
+                        SimpleInput {
+                            value: name(),
+                            placeholder: Some("Folder name".to_string()),
+                            class: Some("h-9 px-3".to_string()),
+                            oninput: Some(EventHandler::new(move |value: String| name.set(value))),
+                        }
+                    }
+                    Button {
+                        r#type: "submit",
+                        class: "h-9 px-3",
+                        disabled: name().trim().is_empty(),
+                        if edit == NameEdit::Create { "Create" } else { "Save" }
+                    }
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        class: "h-9 px-3",
+                        onclick: move |_| name_edit.set(None),
+                        "Cancel"
+                    }
+                }
+            }
+
+            if !children.is_empty() {
+                div { class: "flex flex-wrap gap-2",
+                    for (id, folder_name, count) in children {
+                        FolderLink {
+                            key: "{id}",
+                            target: Some(id),
+                            label: folder_name,
+                            count: Some(count),
+                            on_navigate,
+                            on_drop,
+                        }
+                    }
+                }
+            }
+
+            if let Some(id) = current {
+                ConfirmDialog {
+                    is_open: confirm_delete,
+                    title: "Delete folder?".to_string(),
+                    description: "Its files and subfolders move up to the parent folder.".to_string(),
+                    confirm_label: "Delete".to_string(),
+                    on_confirm: move |_| {
+                        confirm_delete.set(false);
+                        on_navigate.call(parent_id);
+                        spawn(async move {
+                            media_state.delete_folder(id).await;
+                        });
+                    },
+                    on_cancel: move |_| {},
+                }
+            }
+        }
+    }
+}
+
+/// A breadcrumb (`count` unset) or subfolder chip that opens `target` and, with `on_drop`,
+/// accepts dragged media.
+#[component]
+fn FolderLink(
+    target: Option<i32>,
+    label: String,
+    #[props(default)] count: Option<i64>,
+    #[props(default)] active: bool,
+    on_navigate: EventHandler<Option<i32>>,
+    on_drop: Option<EventHandler<Option<i32>>>,
+) -> Element {
+    let mut over = use_signal(|| false);
+    let chip = count.is_some();
+    let tone = if over() {
+        "bg-primary/10 border-ring"
+    } else if active {
+        "font-medium text-foreground"
+    } else {
+        "text-muted-foreground hover:text-foreground"
+    };
+    let shape = if chip {
+        "inline-flex items-center gap-1.5 rounded-md border border-border px-3 py-1.5 hover:bg-muted/40"
+    } else {
+        "rounded px-1.5 py-0.5 border border-transparent"
+    };
+
+    rsx! {
+        button {
+            r#type: "button",
+            class: "text-sm transition-colors {shape} {tone}",
+            title: if on_drop.is_some() && target.is_none() { "Drop files here to take them out of their folder" } else { "" },
+            onclick: move |_| on_navigate.call(target),
+            ondragover: move |e| {
+                if on_drop.is_some() {
+                    e.prevent_default();
+                    if !*over.peek() {
+                        over.set(true);
+                    }
+                }
+            },
+            ondragleave: move |_| over.set(false),
+            ondrop: move |e| {
+                e.prevent_default();
+                over.set(false);
+                if let Some(on_drop) = on_drop {
+                    on_drop.call(target);
+                }
+            },
+            if chip {
+                Icon { icon: LdFolder, class: "h-4 w-4" }
+            }
+            "{label}"
+            if let Some(count) = count {
+                span { class: "text-xs text-muted-foreground", "{count}" }
+            }
+        }
+    }
+}
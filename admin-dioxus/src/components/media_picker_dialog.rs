@@ -1,8 +1,10 @@
 //! MediaPickerDialog - A modal dialog for browsing and selecting media files
 //! or uploading new ones. Used by the RichTextEditor and other components.
 
-use crate::components::MediaUploadZone;
-use crate::store::{use_media, Media, MediaListQuery, MediaReference};
+use crate::components::{MediaFolderNav, MediaUploadZone};
+use crate::store::{
+    use_media, Media, MediaListQuery, MediaReference, ResponsiveImage, THUMBNAIL_IMAGE_WIDTH,
+};
 use crate::ui::custom::AppPortal;
 use crate::ui::shadcn::{Badge, Button, ButtonVariant, Checkbox};
 use crate::utils::dates::format_short_date_dt;
@@ -183,6 +185,17 @@ pub fn MediaPickerDialog(props: MediaPickerDialogProps) -> Element {
                     div { class: "flex-1 overflow-y-auto p-4",
                         if current_tab() == "browse" {
                             // Browse tab
+                            div { class: "mb-4",
+                                MediaFolderNav {
+                                    current: filters.read().folder_id,
+                                    on_navigate: move |folder_id: Option<i32>| {
+                                        let mut q = filters();
+                                        q.page = 1;
+                                        q.folder_id = folder_id;
+                                        filters.set(q);
+                                    },
+                                }
+                            }
                             if list_loading {
                                 div { class: "flex items-center justify-center py-12",
                                     span { class: "loading loading-spinner loading-lg" }
@@ -216,7 +229,7 @@ pub fn MediaPickerDialog(props: MediaPickerDialogProps) -> Element {
                                                     let media_clone = media.clone();
                                                     let media_id = media.id;
                                                     let media_mime = media.mime_type.clone();
-                                                    let media_file_url = media.file_url.clone();
+                                                    let media_thumbnail_url = ResponsiveImage::from_media(media, THUMBNAIL_IMAGE_WIDTH).src;
                                                     let media_obj_key = media.object_key.clone();
                                                     let media_size = media.size;
                                                     let media_created = media.created_at.clone();
@@ -243,7 +256,7 @@ pub fn MediaPickerDialog(props: MediaPickerDialogProps) -> Element {
                                                             td { class: "w-16 py-2 px-3",
                                                                 if is_image(&media_mime) {
                                                                     img {
-                                                                        src: "{media_file_url}",
+                                                                        src: "{media_thumbnail_url}",
                                                                         alt: "{media_obj_key}",
                                                                         class: "w-12 h-12 object-cover rounded"
                                                                     }
@@ -1,4 +1,4 @@
-use crate::store::Media;
+use crate::store::{Media, ResponsiveImage, PREVIEW_IMAGE_SIZES, PREVIEW_IMAGE_WIDTH, WEBP_MIME};
 use crate::ui::shadcn::{Button, ButtonVariant};
 use dioxus::prelude::*;
 use hmziq_dioxus_free_icons::{
@@ -27,16 +27,28 @@ pub fn MediaPreviewItem(
         .last()
         .unwrap_or("Unknown")
         .to_string();
+    let image = ResponsiveImage::from_media(&media, PREVIEW_IMAGE_WIDTH);
 
     rsx! {
         div { class: "group relative rounded-lg border border-border/60 bg-card overflow-hidden transition-all hover:border-border hover:shadow-sm",
             // Preview section
             div { class: "relative aspect-video bg-muted/30 flex items-center justify-center overflow-hidden",
                 if is_image {
-                    img {
-                        src: "{media.file_url}",
-                        alt: "{alt}",
-                        class: "w-full h-full object-cover"
+                    picture { class: "block w-full h-full",
+                        if let Some(webp_srcset) = image.webp_srcset {
+                            source {
+                                r#type: WEBP_MIME,
+                                "srcset": "{webp_srcset}",
+                                "sizes": PREVIEW_IMAGE_SIZES,
+                            }
+                        }
+                        img {
+                            src: "{image.src}",
+                            srcset: image.srcset,
+                            sizes: PREVIEW_IMAGE_SIZES,
+                            alt: "{alt}",
+                            class: "w-full h-full object-cover"
+                        }
                     }
                 } else {
                     div { class: "flex flex-col items-center justify-center gap-2 text-muted-foreground",
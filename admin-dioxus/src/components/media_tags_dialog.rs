@@ -0,0 +1,170 @@
+use crate::components::SimpleInput;
+use crate::hooks::{use_state_frame_map_toast, StateFrameToastConfig};
+use crate::store::{parse_tags, use_media, Media};
+use crate::ui::custom::AppPortal;
+use crate::ui::shadcn::{Badge, BadgeVariant, Button, ButtonVariant};
+use dioxus::prelude::*;
+use hmziq_dioxus_free_icons::{icons::ld_icons::LdX, Icon};
+
+/// Most suggestions shown under the tag input.
+const MAX_SUGGESTIONS: usize = 8;
+
+#[component]
+pub fn MediaTagsDialog(is_open: Signal<bool>, media: Media) -> Element {
+    let media_state = use_media();
+    let media_id = media.id;
+    let mut tags = use_signal(|| media.tags.clone());
+    let mut input = use_signal(String::new);
+
+    // Start from the media's tags each time the dialog opens, and refresh suggestions
+    use_effect(use_reactive!(|(media,)| {
+        if *is_open.read() {
+            tags.set(media.tags.clone());
+            input.set(String::new());
+            spawn(async move {
+                media_state.tags().await;
+            });
+        }
+    }));
+
+    use_state_frame_map_toast(
+        &media_state.tags_update,
+        media_id,
+        StateFrameToastConfig {
+            loading_title: "Saving tags...".into(),
+            success_title: Some("Tags saved".into()),
+            error_title: Some("Failed to save tags".into()),
+            ..Default::default()
+        },
+    );
+
+    let mut add_input = move || {
+        let mut current = tags.peek().clone();
+        for tag in parse_tags(&input.peek()) {
+            if !current.contains(&tag) {
+                current.push(tag);
+            }
+        }
+        tags.set(current);
+        input.set(String::new());
+    };
+
+    if !*is_open.read() {
+        return rsx! {};
+    }
+
+    let query = input().trim().to_lowercase();
+    let suggestions: Vec<String> = media_state
+        .tags
+        .read()
+        .data
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|tag| !tags.read().contains(tag) && tag.contains(&query))
+        .take(MAX_SUGGESTIONS)
+        .collect();
+    let saving = media_state
+        .tags_update
+        .read()
+        .get(&media_id)
+        .is_some_and(|frame| frame.is_loading());
+
+    rsx! {
+        AppPortal {
+            z_index: "1100",
+            div {
+                class: "fixed inset-0 bg-black/50",
+                onclick: move |_| is_open.set(false),
+            }
+
+            div {
+                class: "fixed left-[50%] top-[50%] translate-x-[-50%] translate-y-[-50%] w-full max-w-lg",
+                onclick: move |e| e.stop_propagation(),
+
+                div { class: "bg-background rounded-lg border shadow-lg flex flex-col",
+                    div { class: "flex items-center justify-between p-6 border-b",
+                        h2 { class: "text-lg font-semibold", "Media Tags" }
+                        button {
+                            onclick: move |_| is_open.set(false),
+                            class: "rounded-xs opacity-70 hover:opacity-100 transition-opacity",
+                            Icon { icon: LdX, width: 20, height: 20 }
+                        }
+                    }
+
+                    div { class: "p-6 space-y-4",
+                        div { class: "flex flex-wrap gap-2",
+                            if tags.read().is_empty() {
+                                span { class: "text-sm text-muted-foreground", "No tags yet." }
+                            }
+                            for tag in tags() {
+                                Badge {
+                                    key: "{tag}",
+                                    variant: BadgeVariant::Secondary,
+                                    class: "gap-1",
+                                    "{tag}"
+                                    button {
+                                        class: "opacity-60 hover:opacity-100",
+                                        onclick: move |_| tags.write().retain(|t| *t != tag),
+                                        Icon { icon: LdX, width: 12, height: 12 }
+                                    }
+                                }
+                            }
+                        }
+                        form {
+                            class: "flex items-center gap-2",
+                            onsubmit: move |e: Event<FormData>| {
+                                e.prevent_default();
+                                add_input();
+                            },
+                            SimpleInput {
+                                value: input(),
+                                placeholder: Some("Add tags, separated by commas".to_string()),
+                                class: Some("h-9 px-3".to_string()),
+                                oninput: Some(EventHandler::new(move |value: String| input.set(value))),
+                            }
+                            Button {
+                                r#type: "submit",
+                                variant: ButtonVariant::Outline,
+                                class: "h-9 px-3",
+                                disabled: parse_tags(&input()).is_empty(),
+                                "Add"
+                            }
+                        }
+                        if !suggestions.is_empty() {
+                            div { class: "flex flex-wrap gap-1.5",
+                                for tag in suggestions {
+                                    button {
+                                        key: "{tag}",
+                                        class: "rounded-md border border-dashed border-border px-2 py-0.5 text-xs text-muted-foreground hover:text-foreground",
+                                        onclick: move |_| tags.write().push(tag.clone()),
+                                        "+ {tag}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "p-6 border-t flex justify-end gap-2",
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            onclick: move |_| is_open.set(false),
+                            "Cancel"
+                        }
+                        Button {
+                            disabled: saving,
+                            onclick: move |_| {
+                                is_open.set(false);
+                                let tags = tags();
+                                spawn(async move {
+                                    media_state.set_tags(media_id, tags).await;
+                                });
+                            },
+                            "Save"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
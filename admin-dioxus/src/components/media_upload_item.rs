@@ -3,7 +3,7 @@ use crate::ui::shadcn::{Button, ButtonSize, ButtonVariant, Progress};
 use crate::utils::file_helpers::format_file_size;
 use dioxus::prelude::*;
 use hmziq_dioxus_free_icons::{
-    icons::ld_icons::{LdCheck, LdCircle, LdLoader, LdPencil, LdX},
+    icons::ld_icons::{LdCheck, LdCircle, LdImage, LdLoader, LdPencil, LdX},
     Icon,
 };
 
@@ -44,9 +44,28 @@ pub fn MediaUploadItem(props: MediaUploadItemProps) -> Element {
     let is_uploading = matches!(status, Some(UploadStatus::Uploading));
     let is_success = matches!(status, Some(UploadStatus::Success));
     let is_error = matches!(status, Some(UploadStatus::Error(_)));
+    let duplicate_of = match &status {
+        Some(UploadStatus::Duplicate(existing)) => Some(existing.clone()),
+        _ => None,
+    };
 
     // Determine status classes and icons
     let (status_class, status_icon, status_text) = match &status {
+        Some(UploadStatus::Queued) => (
+            "border-zinc-200 dark:border-zinc-800",
+            rsx! { Icon { icon: LdLoader, class: "h-4 w-4 text-zinc-400" } },
+            "Waiting to upload...",
+        ),
+        Some(UploadStatus::Optimizing) => (
+            "border-blue-200 dark:border-blue-800",
+            rsx! { Icon { icon: LdLoader, class: "h-4 w-4 text-blue-600 dark:text-blue-400 animate-spin" } },
+            "Optimizing...",
+        ),
+        Some(UploadStatus::Duplicate(_)) => (
+            "border-amber-200 dark:border-amber-800",
+            rsx! { Icon { icon: LdImage, class: "h-4 w-4 text-amber-600 dark:text-amber-400" } },
+            "Already in the media library",
+        ),
         Some(UploadStatus::Uploading) => (
             "border-blue-200 dark:border-blue-800",
             rsx! { Icon { icon: LdLoader, class: "h-4 w-4 text-blue-600 dark:text-blue-400 animate-spin" } },
@@ -148,6 +167,37 @@ pub fn MediaUploadItem(props: MediaUploadItemProps) -> Element {
                 }
             }
 
+            // Same file already uploaded: reuse it or upload a second copy
+            if let Some(existing) = duplicate_of {
+                div { class: "flex items-center gap-3",
+                    img {
+                        src: "{existing.file_url}",
+                        alt: "Existing media",
+                        class: "w-10 h-10 object-cover rounded border border-zinc-200 dark:border-zinc-700",
+                    }
+                    div { class: "flex-1" }
+                    Button {
+                        r#type: "button".to_string(),
+                        size: ButtonSize::Sm,
+                        onclick: {
+                            let blob_url = blob_url.clone();
+                            move |_| media_state.use_existing_upload(&blob_url)
+                        },
+                        "Use existing"
+                    }
+                    Button {
+                        r#type: "button".to_string(),
+                        variant: ButtonVariant::Outline,
+                        size: ButtonSize::Sm,
+                        onclick: {
+                            let blob_url = blob_url.clone();
+                            move |_| media_state.upload_anyway(&blob_url)
+                        },
+                        "Upload anyway"
+                    }
+                }
+            }
+
             // Progress bar (only show during upload)
             if is_uploading {
                 Progress {
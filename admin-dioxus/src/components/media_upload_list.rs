@@ -23,7 +23,10 @@ pub fn MediaUploadList(props: MediaUploadListProps) -> Element {
 
         for blob_url in &props.blob_urls {
             match media_state.get_upload_status(blob_url) {
-                Some(UploadStatus::Uploading) => uploading += 1,
+                Some(UploadStatus::Queued)
+                | Some(UploadStatus::Optimizing)
+                | Some(UploadStatus::Duplicate(_))
+                | Some(UploadStatus::Uploading) => uploading += 1,
                 Some(UploadStatus::Success) => success += 1,
                 Some(UploadStatus::Error(_)) => error += 1,
                 None => uploading += 1, // Preparing/not started yet
@@ -1,8 +1,11 @@
 use crate::store::{use_media, MediaReference, MediaUploadPayload};
-use crate::ui::shadcn::{Button, ButtonVariant};
-use crate::utils::file_helpers::validate_file_type;
+use crate::ui::shadcn::{Button, ButtonSize, ButtonVariant};
+use crate::utils::file_helpers::{format_file_size, validate_file_type};
 use dioxus::prelude::*;
-use hmziq_dioxus_free_icons::{icons::ld_icons::LdUpload, Icon};
+use hmziq_dioxus_free_icons::{
+    icons::ld_icons::{LdUpload, LdX},
+    Icon,
+};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
@@ -50,6 +53,10 @@ pub fn MediaUploadZone(props: MediaUploadZoneProps) -> Element {
         )
     });
 
+    let media_state = use_media();
+    use_hook(|| media_state.restore_interrupted_uploads());
+    let interrupted = media_state.interrupted_uploads.read().clone();
+
     // Pre-clone props for event handlers
     let reference_type = props.reference_type.clone();
     let max_files = props.max_files;
@@ -224,6 +231,45 @@ pub fn MediaUploadZone(props: MediaUploadZoneProps) -> Element {
                     "Select Files"
                 }
             }
+
+            // Chunked uploads that stopped part-way; picking the same file resumes them
+            if !interrupted.is_empty() {
+                div { class: "mt-6 space-y-2 text-left",
+                    p { class: "text-xs font-medium text-muted-foreground",
+                        "Interrupted uploads. Select the same file again to continue where it stopped."
+                    }
+                    for upload in interrupted {
+                        div {
+                            key: "{upload.fingerprint}",
+                            class: "flex items-center gap-3 rounded-md border border-zinc-200 dark:border-zinc-800 px-3 py-2",
+                            div { class: "flex-1 min-w-0",
+                                p { class: "text-sm truncate", "{upload.file_name}" }
+                                p { class: "text-xs text-muted-foreground",
+                                    {
+                                        format!(
+                                            "{} of {} uploaded",
+                                            format_file_size(upload.uploaded_bytes as i64),
+                                            format_file_size(upload.total_size as i64),
+                                        )
+                                    }
+                                }
+                            }
+                            Button {
+                                r#type: "button".to_string(),
+                                variant: ButtonVariant::Ghost,
+                                size: ButtonSize::Icon,
+                                class: "h-8 w-8 flex-shrink-0".to_string(),
+                                onclick: {
+                                    let fingerprint = upload.fingerprint.clone();
+                                    move |_| media_state.discard_interrupted_upload(&fingerprint)
+                                },
+                                Icon { icon: LdX, class: "h-4 w-4" }
+                                span { class: "sr-only", "Discard" }
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -288,6 +334,7 @@ async fn process_files_async(
                 reference_type: reference_type.clone(),
                 width: None, // Could be calculated if needed
                 height: None,
+                optimize: true,
             };
 
             // Initiate upload (returns blob URL immediately)
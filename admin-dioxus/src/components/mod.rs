@@ -16,6 +16,8 @@ pub use editor_js_host::*;
 
 mod color_picker;
 pub use color_picker::*;
+mod datetime_picker;
+pub use datetime_picker::*;
 mod tag;
 pub use tag::*;
 mod page_header;
@@ -60,6 +62,18 @@ pub use media_upload_list::*;
 mod confirm_dialog;
 pub use confirm_dialog::*;
 
+mod bulk_operations_bar;
+pub use bulk_operations_bar::*;
+
+mod environment_switcher;
+pub use environment_switcher::*;
+
+mod rollback_toasts;
+pub use rollback_toasts::*;
+
+mod devtools_drawer;
+pub use devtools_drawer::*;
+
 mod post_success_dialog;
 pub use post_success_dialog::*;
 
@@ -81,6 +95,12 @@ pub use media_picker_dialog::*;
 mod media_usage_dialog;
 pub use media_usage_dialog::*;
 
+mod media_folder_nav;
+pub use media_folder_nav::*;
+
+mod media_tags_dialog;
+pub use media_tags_dialog::*;
+
 pub mod analytics;
 
 mod animated_grid;
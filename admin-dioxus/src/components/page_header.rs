@@ -28,11 +28,20 @@ pub fn PageHeader(props: PageHeaderProps) -> Element {
             ("posts".to_string(), Some(Route::PostsListScreen {})),
             ("add".to_string(), None),
         ],
+        Route::PostsCalendarScreen {} => vec![
+            ("posts".to_string(), Some(Route::PostsListScreen {})),
+            ("calendar".to_string(), None),
+        ],
         Route::PostsEditScreen { id } => vec![
             ("posts".to_string(), Some(Route::PostsListScreen {})),
             (id.to_string(), Some(Route::PostsViewScreen { id })),
             ("edit".to_string(), None),
         ],
+        Route::PostsRevisionsScreen { id } => vec![
+            ("posts".to_string(), Some(Route::PostsListScreen {})),
+            (id.to_string(), Some(Route::PostsViewScreen { id })),
+            ("revisions".to_string(), None),
+        ],
         Route::PostsViewScreen { id } => vec![
             ("posts".to_string(), Some(Route::PostsListScreen {})),
             (id.to_string(), None),
@@ -54,6 +63,16 @@ pub fn PageHeader(props: PageHeaderProps) -> Element {
             (id.to_string(), None),
             ("edit".to_string(), None),
         ],
+        Route::SeriesAddScreen {} => vec![
+            ("series".to_string(), Some(Route::SeriesListScreen {})),
+            ("add".to_string(), None),
+        ],
+        Route::SeriesEditScreen { id } => vec![
+            ("series".to_string(), Some(Route::SeriesListScreen {})),
+            (id.to_string(), None),
+            ("edit".to_string(), None),
+        ],
+        Route::SeriesListScreen {} => vec![("series".to_string(), None)],
         Route::TagsAddScreen {} => vec![
             ("tags".to_string(), Some(Route::TagsListScreen {})),
             ("add".to_string(), None),
@@ -68,6 +87,10 @@ pub fn PageHeader(props: PageHeaderProps) -> Element {
             ("media".to_string(), Some(Route::MediaListScreen {})),
             ("upload".to_string(), None),
         ],
+        Route::MediaDuplicatesScreen {} => vec![
+            ("media".to_string(), Some(Route::MediaListScreen {})),
+            ("duplicates".to_string(), None),
+        ],
         Route::MediaListScreen {} => vec![("media".to_string(), None)],
         Route::UsersAddScreen {} => vec![
             ("users".to_string(), Some(Route::UsersListScreen {})),
@@ -79,6 +102,7 @@ pub fn PageHeader(props: PageHeaderProps) -> Element {
             ("edit".to_string(), None),
         ],
         Route::UsersListScreen {} => vec![("users".to_string(), None)],
+        Route::OutboxScreen {} => vec![("outbox".to_string(), None)],
         Route::SonnerDemoScreen {} => {
             vec![("demo".to_string(), None), ("sonner".to_string(), None)]
         }
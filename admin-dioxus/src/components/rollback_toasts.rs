@@ -0,0 +1,35 @@
+use dioxus::prelude::*;
+
+use crate::components::sonner::{use_sonner, Action, ToastOptions};
+use crate::store::MUTATION_ROLLBACKS;
+
+/// Turns rolled-back optimistic mutations into error toasts with a "Retry" action.
+/// Rendered once inside `SonnerToaster`.
+#[component]
+pub fn RollbackToasts() -> Element {
+    let sonner = use_sonner();
+
+    use_effect(move || {
+        if MUTATION_ROLLBACKS.read().is_empty() {
+            return;
+        }
+        let rollbacks = std::mem::take(&mut *MUTATION_ROLLBACKS.write());
+        for rollback in rollbacks {
+            let title = format!(
+                "Couldn't save {}: {}. Changes were reverted.",
+                rollback.label,
+                rollback.error.message()
+            );
+            let mut options = ToastOptions::default();
+            if let Some(retry) = rollback.retry {
+                let action =
+                    Action::with_on_click("Retry".to_string(), Callback::new(move |_| retry()))
+                        .with_dismiss_toast(true);
+                options = options.with_action(Some(action));
+            }
+            sonner.error(title, options);
+        }
+    });
+
+    rsx! {}
+}
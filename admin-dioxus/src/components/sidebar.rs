@@ -1,10 +1,15 @@
 use dioxus::prelude::*;
 use hmziq_dioxus_free_icons::icons::ld_icons::{
-    LdAreaChart, LdFileText, LdFolder, LdHome, LdImage, LdLogOut, LdPlus, LdTag, LdUser,
+    LdAreaChart, LdCloudOff, LdFileText, LdFolder, LdHome, LdImage, LdLibrary, LdLogOut, LdPlus,
+    LdTag, LdUser,
 };
 use hmziq_dioxus_free_icons::Icon;
 
-use crate::{components::ConfirmDialog, router::Route, store::use_auth};
+use crate::{
+    components::{ConfirmDialog, EnvironmentSwitcher},
+    router::Route,
+    store::{use_auth, use_outbox},
+};
 
 #[derive(Props, PartialEq, Clone)]
 pub struct SidebarModuleLinkProps {
@@ -62,6 +67,8 @@ pub fn Sidebar(expanded: Signal<bool>, toggle: EventHandler<()>) -> Element {
     let auth_store = use_auth();
     let current_route = use_route::<Route>();
     let mut logout_dialog_open = use_signal(|| false);
+    let nav = use_navigator();
+    let outbox_count = use_outbox().entries.read().len();
 
     let is_active = |route: Route| -> bool {
         std::mem::discriminant(&current_route) == std::mem::discriminant(&route)
@@ -159,6 +166,16 @@ pub fn Sidebar(expanded: Signal<bool>, toggle: EventHandler<()>) -> Element {
                     label: "Tags",
                     is_active: is_active(Route::TagsListScreen {}),
                     on_close: move |_| toggle.call(()),
+                }
+                SidebarModuleLink {
+                    main_route: Route::SeriesListScreen {},
+                    add_route: Some(Route::SeriesAddScreen {}),
+                    icon: rsx! {
+                        Icon { icon: LdLibrary }
+                    },
+                    label: "Series",
+                    is_active: is_active(Route::SeriesListScreen {}),
+                    on_close: move |_| toggle.call(()),
                 }
                     SidebarModuleLink {
                     main_route: Route::MediaListScreen {},
@@ -184,6 +201,19 @@ pub fn Sidebar(expanded: Signal<bool>, toggle: EventHandler<()>) -> Element {
             }
 
                 div { class: "border-t border-zinc-300 dark:border-zinc-800 transition-colors duration-300",
+                    EnvironmentSwitcher { class: "px-5 py-3" }
+                    if outbox_count > 0 {
+                        button {
+                            class: "flex w-full items-center pl-5 pr-3 h-12 text-sm font-medium cursor-pointer text-amber-700 dark:text-amber-400 hover:bg-zinc-300 dark:hover:bg-zinc-900/90 transition-colors duration-200",
+                            onclick: move |_| {
+                                nav.push(Route::OutboxScreen {});
+                                toggle.call(());
+                            },
+                            Icon { icon: LdCloudOff, width: 18, height: 18 }
+                            span { class: "ml-3", "Outbox" }
+                            span { class: "ml-auto rounded-full bg-amber-100 px-2 text-xs dark:bg-amber-900/30", "{outbox_count}" }
+                        }
+                    }
                     button {
                         class: "flex w-full items-center flex-1 pl-5 pr-3 h-15 text-sm font-medium cursor-pointer text-zinc-600 dark:text-zinc-300 hover:bg-zinc-300 hover:text-zinc-800 dark:hover:bg-zinc-900/90 dark:hover:text-white transition-colors duration-200",
                         onclick: handle_logout_click,
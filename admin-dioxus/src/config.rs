@@ -1,3 +1,10 @@
+use crate::env::{APP_API_URL, APP_CSRF_TOKEN};
+use crate::utils::persist;
+use dioxus::prelude::*;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
 #[derive(Clone, PartialEq)]
 pub struct DarkMode(pub bool);
 
@@ -7,40 +14,299 @@ impl DarkMode {
     }
 }
 
-// pub struct Config {
-//     values: HashMap<String, String>,
-// }
+/// Served alongside the app bundle; lists the backends this build may talk to.
+#[cfg(target_arch = "wasm32")]
+pub const CONFIG_JSON_PATH: &str = "/config.json";
+/// Query parameter used to pick a configured profile by name, e.g. `?api=staging`.
+#[cfg(target_arch = "wasm32")]
+pub const API_QUERY_PARAM: &str = "api";
+const LOCAL_PROFILE: &str = "local";
+
+/// A named backend the admin can point at (dev, staging, prod, ...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiProfile {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Host with optional scheme, e.g. `localhost:9999` or `https://api.example.com`.
+    pub api_url: String,
+    #[serde(default)]
+    pub csrf_token: Option<String>,
+}
+
+impl ApiProfile {
+    pub fn new(name: impl Into<String>, api_url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            label: None,
+            api_url: api_url.into(),
+            csrf_token: None,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Absolute base URL without a trailing slash. Bare hosts default to `http://`.
+    pub fn base_url(&self) -> String {
+        let url = self.api_url.trim().trim_end_matches('/');
+        if url.starts_with("http://") || url.starts_with("https://") {
+            url.to_string()
+        } else {
+            format!("http://{}", url)
+        }
+    }
+
+    pub fn csrf_token(&self) -> String {
+        self.csrf_token
+            .clone()
+            .unwrap_or_else(|| APP_CSRF_TOKEN.to_string())
+    }
+
+    pub fn is_secure(&self) -> bool {
+        self.base_url().starts_with("https://")
+    }
+}
+
+/// Shape of the served `config.json`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ServedConfig {
+    #[serde(default)]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<ApiProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeConfig {
+    pub profiles: Vec<ApiProfile>,
+    pub active: String,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl RuntimeConfig {
+    /// Single profile built from the compile-time `.env` values.
+    pub fn from_env() -> Self {
+        let mut local = ApiProfile::new(LOCAL_PROFILE, APP_API_URL);
+        local.label = Some("Local".to_string());
+        Self {
+            profiles: vec![local],
+            active: LOCAL_PROFILE.to_string(),
+        }
+    }
+
+    /// Add or replace profiles from a served config and honour its default selection.
+    pub fn merge_served(&mut self, served: ServedConfig) {
+        for profile in served.profiles {
+            match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+                Some(existing) => *existing = profile,
+                None => self.profiles.push(profile),
+            }
+        }
+        if let Some(default) = served.default_profile {
+            self.select(&default);
+        }
+    }
+
+    /// Select a profile by name. Only profiles from `.env` or `config.json` can be selected,
+    /// never a URL, so a crafted link cannot send credentials to another host. Returns
+    /// `false` when no profile has that name.
+    pub fn select(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if !self.profiles.iter().any(|p| p.name == name) {
+            return false;
+        }
+        self.active = name.to_string();
+        true
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&ApiProfile> {
+        self.profiles.iter().find(|p| p.name == name.trim())
+    }
+
+    pub fn active_profile(&self) -> &ApiProfile {
+        self.profiles
+            .iter()
+            .find(|p| p.name == self.active)
+            .or_else(|| self.profiles.first())
+            .expect("runtime config always has at least one profile")
+    }
+}
+
+static RUNTIME_CONFIG: Lazy<RwLock<RuntimeConfig>> =
+    Lazy::new(|| RwLock::new(RuntimeConfig::from_env()));
+
+/// Bumped whenever the active profile changes so components can re-render.
+pub static ACTIVE_PROFILE: GlobalSignal<Option<String>> = Signal::global(|| None);
+
+pub fn runtime_config() -> RuntimeConfig {
+    RUNTIME_CONFIG.read().map(|c| c.clone()).unwrap_or_default()
+}
+
+pub fn active_profile() -> ApiProfile {
+    RUNTIME_CONFIG
+        .read()
+        .map(|c| c.active_profile().clone())
+        .unwrap_or_else(|_| RuntimeConfig::from_env().active_profile().clone())
+}
+
+/// Switch the active profile, persist the choice and reset session-bound stores so the
+/// auth guard re-initialises against the new backend.
+pub fn set_active_profile(selector: &str) -> bool {
+    let changed = match RUNTIME_CONFIG.write() {
+        Ok(mut config) => config.select(selector),
+        Err(_) => false,
+    };
+    if changed {
+        persist::set_api_profile(selector);
+        *ACTIVE_PROFILE.write() = Some(active_profile().name);
+        crate::store::use_auth().reset_session();
+    }
+    changed
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_served_config() -> Option<ServedConfig> {
+    let response = gloo_net::http::Request::get(CONFIG_JSON_PATH)
+        .send()
+        .await
+        .ok()?;
+    if !response.ok() {
+        return None;
+    }
+    response.json::<ServedConfig>().await.ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn fetch_served_config() -> Option<ServedConfig> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+fn query_profile() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(API_QUERY_PARAM)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn query_profile() -> Option<String> {
+    None
+}
+
+/// Ask before following a link that switches the backend.
+#[cfg(target_arch = "wasm32")]
+fn confirm_switch(profile: &ApiProfile) -> bool {
+    let message = format!(
+        "This link switches the API environment to {} ({}). Your session will be sent to \
+         that server. Continue?",
+        profile.label(),
+        profile.base_url()
+    );
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(&message).ok())
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn confirm_switch(_profile: &ApiProfile) -> bool {
+    false
+}
+
+/// Resolve the active profile at startup. Later sources win: `.env` defaults, the served
+/// `config.json`, the persisted choice, then the `?api=` query parameter. The query
+/// parameter only names a configured profile and takes effect after the user confirms.
+pub async fn load_runtime_config() {
+    let mut config = RuntimeConfig::from_env();
+    if let Some(served) = fetch_served_config().await {
+        config.merge_served(served);
+    }
+    if let Some(saved) = persist::get_api_profile() {
+        config.select(&saved);
+    }
+    if let Some(name) = query_profile() {
+        let switch = config
+            .profile(&name)
+            .filter(|profile| profile.name != config.active)
+            .is_some_and(confirm_switch);
+        if switch && config.select(&name) {
+            persist::set_api_profile(&name);
+        }
+    }
 
-// static CONFIG: OnceLock<Config> = OnceLock::new();
+    let active = config.active_profile().name.clone();
+    if let Ok(mut current) = RUNTIME_CONFIG.write() {
+        *current = config;
+    }
+    *ACTIVE_PROFILE.write() = Some(active);
+}
 
-// impl Config {
-//     fn new() -> Self {
-//         // // Try to load .env file
-//         let _ = dotenv();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-//         let mut values = HashMap::new();
+    #[test]
+    fn base_url_keeps_scheme_and_defaults_to_http() {
+        assert_eq!(
+            ApiProfile::new("a", "localhost:9999").base_url(),
+            "http://localhost:9999"
+        );
+        let prod = ApiProfile::new("prod", "https://api.example.com/");
+        assert_eq!(prod.base_url(), "https://api.example.com");
+        assert!(prod.is_secure());
+    }
 
-//         // Add your environment variables here
-//         if let Ok(api_url) = env::var("API_URL") {
-//             values.insert("API_URL".to_string(), api_url);
-//         } else {
-//             // Default fallback value
-//             values.insert("API_URL".to_string(), "localhost:8888".to_string());
-//         }
+    #[test]
+    fn served_profiles_merge_and_select_default() {
+        let mut config = RuntimeConfig::from_env();
+        let served: ServedConfig = serde_json::from_str(
+            r#"{
+                "default_profile": "staging",
+                "profiles": [
+                    { "name": "staging", "label": "Staging", "api_url": "https://staging.example.com", "csrf_token": "abc" },
+                    { "name": "prod", "api_url": "https://api.example.com" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        config.merge_served(served);
 
-//         Config { values }
-//     }
+        assert_eq!(config.profiles.len(), 3);
+        assert_eq!(config.active_profile().label(), "Staging");
+        assert_eq!(config.active_profile().csrf_token(), "abc");
+        assert_eq!(
+            config.profiles[2].csrf_token(),
+            APP_CSRF_TOKEN,
+            "missing tokens fall back to the build default"
+        );
+    }
 
-//     pub fn get(key: &str) -> Option<String> {
-//         let config = CONFIG.get_or_init(Config::new);
-//         config.values.get(key).cloned()
-//     }
+    #[test]
+    fn select_accepts_only_configured_profiles() {
+        let mut config = RuntimeConfig::from_env();
+        config
+            .profiles
+            .push(ApiProfile::new("staging", "https://staging.example.com"));
+        assert!(!config.select("missing"));
+        assert_eq!(config.active, LOCAL_PROFILE);
 
-//     pub fn api_url() -> String {
-//         Self::get("API_URL").unwrap_or_else(|| "localhost:8888".to_string())
-//     }
+        for url in ["https://evil.example", "evil.example", "127.0.0.1:8888"] {
+            assert!(!config.select(url), "{url} is not a profile");
+            assert_eq!(config.active, LOCAL_PROFILE);
+        }
+        assert_eq!(config.profiles.len(), 2, "no ad-hoc profile is added");
 
-//     pub fn api_base_url() -> String {
-//         format!("http://{}", Self::api_url())
-//     }
-// }
+        assert!(config.select(" staging "));
+        assert_eq!(
+            config.active_profile().base_url(),
+            "https://staging.example.com"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Periodic autosave of the blog form.
+//!
+//! Every change is written to local storage under the post id (or a "new post" slot), so a
+//! crashed tab or an expired session can be recovered on the next visit. Unscheduled draft
+//! posts are also saved to the server. Published and scheduled posts are not: a published post
+//! would take the change live at once, a scheduled one when its publish time comes.
+
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use gloo_timers::future::sleep;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::form::BlogForm;
+use crate::hooks::OxForm;
+use crate::store::{use_post, PostContent, PostEditPayload, PostSeo};
+use crate::utils::persist;
+use crate::utils::post_diff::{diff_blocks, BlockDiff, FieldChange};
+
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Snapshot of the blog form as saved to local storage.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlogDraft {
+    pub saved_at: DateTime<Utc>,
+    pub title: String,
+    pub content: String,
+    pub slug: String,
+    pub excerpt: String,
+    /// Server URL of the featured image. Blob URLs of in-flight uploads do not survive a
+    /// reload, so such images are left out.
+    pub featured_image_url: Option<String>,
+    pub featured_image_media_id: Option<i32>,
+    pub is_published: bool,
+    pub scheduled: bool,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub category_id: Option<i32>,
+    pub tag_ids: Vec<i32>,
+    pub series_id: Option<i32>,
+    pub series_position: Option<i32>,
+    #[serde(default)]
+    pub seo: PostSeo,
+}
+
+/// What a draft changes relative to the server copy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DraftDiff {
+    pub title: Option<FieldChange>,
+    pub excerpt: Option<FieldChange>,
+    pub blocks: Vec<BlockDiff>,
+    /// Whether anything besides title, excerpt and content differs.
+    pub metadata_changed: bool,
+}
+
+impl BlogDraft {
+    fn slot(post_id: Option<i32>) -> String {
+        post_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "new".to_string())
+    }
+
+    pub fn capture(form: &BlogForm, saved_at: DateTime<Utc>) -> Self {
+        let featured_image_url = form
+            .featured_image_blob_url
+            .clone()
+            .filter(|url| !url.starts_with("blob:"));
+        Self {
+            saved_at,
+            title: form.title.clone(),
+            content: form.content.clone(),
+            slug: form.slug.clone(),
+            excerpt: form.excerpt.clone(),
+            featured_image_media_id: featured_image_url
+                .as_ref()
+                .and(form.featured_image_media_id),
+            featured_image_url,
+            is_published: form.is_published,
+            scheduled: form.scheduled,
+            publish_at: form.publish_at,
+            category_id: form.category_id,
+            tag_ids: form.tag_ids.clone(),
+            series_id: form.series_id,
+            series_position: form.series_position,
+            seo: form.seo.clone(),
+        }
+    }
+
+    pub fn to_form(&self) -> BlogForm {
+        BlogForm {
+            title: self.title.clone(),
+            content: self.content.clone(),
+            slug: self.slug.clone(),
+            excerpt: self.excerpt.clone(),
+            featured_image_blob_url: self.featured_image_url.clone(),
+            featured_image_media_id: self.featured_image_media_id,
+            is_published: self.is_published,
+            scheduled: self.scheduled,
+            publish_at: self.publish_at,
+            category_id: self.category_id,
+            tag_ids: self.tag_ids.clone(),
+            series_id: self.series_id,
+            series_position: self.series_position,
+            seo: self.seo.clone(),
+        }
+    }
+
+    pub fn load(post_id: Option<i32>) -> Option<Self> {
+        let json = persist::get_post_draft(&Self::slot(post_id))?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn save(&self, post_id: Option<i32>) {
+        if let Ok(json) = serde_json::to_string(self) {
+            persist::set_post_draft(&Self::slot(post_id), &json);
+        }
+    }
+
+    pub fn discard(post_id: Option<i32>) {
+        persist::remove_post_draft(&Self::slot(post_id));
+    }
+
+    /// The draft is worth offering only when it holds something the server copy does not.
+    pub fn differs_from(&self, base: &BlogForm) -> bool {
+        Self::capture(base, self.saved_at) != *self
+    }
+
+    pub fn diff(&self, base: &BlogForm) -> DraftDiff {
+        let blocks_of = |json: &str| {
+            serde_json::from_str::<PostContent>(json)
+                .map(|content| content.blocks)
+                .unwrap_or_default()
+        };
+        let mut base_meta = Self::capture(base, self.saved_at);
+        let mut draft_meta = self.clone();
+        for meta in [&mut base_meta, &mut draft_meta] {
+            meta.title.clear();
+            meta.excerpt.clear();
+            meta.content.clear();
+        }
+
+        DraftDiff {
+            title: FieldChange::between(&base.title, &self.title),
+            excerpt: FieldChange::between(&base.excerpt, &self.excerpt),
+            blocks: diff_blocks(&blocks_of(&base.content), &blocks_of(&self.content)),
+            metadata_changed: base_meta != draft_meta,
+        }
+    }
+}
+
+/// Fields a background save may touch. Status, relations and scheduling stay as they are
+/// on the server until the author saves the form.
+fn server_payload(form: &BlogForm) -> Option<PostEditPayload> {
+    let content = serde_json::from_str::<PostContent>(&form.content).ok()?;
+    Some(PostEditPayload {
+        title: Some(form.title.clone()).filter(|title| !title.is_empty()),
+        content: Some(content),
+        excerpt: Some(form.excerpt.clone()).filter(|excerpt| !excerpt.is_empty()),
+        ..PostEditPayload::default()
+    })
+}
+
+/// Autosave `form` every few seconds while it has unsaved changes. Paused while `paused`
+/// is true, e.g. while the recovery prompt is open. `server_drafts` also saves to the
+/// server for existing posts; pass it only for drafts that are neither published nor scheduled.
+pub fn use_blog_autosave(
+    form: Signal<OxForm<BlogForm>>,
+    post_id: Option<i32>,
+    server_drafts: bool,
+    paused: Memo<bool>,
+) {
+    let mut last_saved = use_signal(|| form.peek().data.clone());
+
+    use_future(move || async move {
+        loop {
+            sleep(AUTOSAVE_INTERVAL).await;
+            if *paused.peek() {
+                continue;
+            }
+            let current = form.peek().data.clone();
+            if current == *last_saved.peek() {
+                continue;
+            }
+
+            BlogDraft::capture(&current, Utc::now()).save(post_id);
+            last_saved.set(current.clone());
+
+            if let (Some(id), true) = (post_id, server_drafts) {
+                if let Some(payload) = server_payload(&current) {
+                    use_post().autosave(id, payload).await;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form() -> BlogForm {
+        BlogForm {
+            title: "Hello".into(),
+            content:
+                r#"{"time":1,"blocks":[{"type":"paragraph","data":{"text":"One"}}],"version":"2"}"#
+                    .into(),
+            slug: "hello".into(),
+            category_id: Some(3),
+            ..BlogForm::new()
+        }
+    }
+
+    #[test]
+    fn drafts_skip_blob_urls_and_round_trip() {
+        let mut with_blob = form();
+        with_blob.featured_image_blob_url = Some("blob:http://localhost/abc".into());
+        with_blob.featured_image_media_id = Some(9);
+        let draft = BlogDraft::capture(&with_blob, Utc::now());
+        assert_eq!(draft.featured_image_url, None);
+        assert_eq!(draft.featured_image_media_id, None);
+
+        let base = form();
+        let draft = BlogDraft::capture(&base, Utc::now());
+        assert_eq!(draft.to_form(), base);
+        assert!(!draft.differs_from(&base));
+    }
+
+    #[test]
+    fn diff_reports_changed_fields_and_blocks() {
+        let base = form();
+        let mut edited = form();
+        edited.title = "Hello again".into();
+        edited.content = r#"{"time":2,"blocks":[{"type":"paragraph","data":{"text":"One"}},{"type":"paragraph","data":{"text":"Two"}}],"version":"2"}"#.into();
+        let draft = BlogDraft::capture(&edited, Utc::now());
+
+        assert!(draft.differs_from(&base));
+        let diff = draft.diff(&base);
+        assert_eq!(diff.title.unwrap().after, "Hello again");
+        assert!(diff.excerpt.is_none());
+        assert!(!diff.metadata_changed);
+        assert_eq!(diff.blocks.iter().filter(|b| b.is_change()).count(), 1);
+        assert!(matches!(diff.blocks[1], BlockDiff::Added(_)));
+
+        edited.tag_ids = vec![1];
+        assert!(
+            BlogDraft::capture(&edited, Utc::now())
+                .diff(&base)
+                .metadata_changed
+        );
+    }
+}
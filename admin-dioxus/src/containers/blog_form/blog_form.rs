@@ -1,21 +1,32 @@
+use chrono::Utc;
 use dioxus::{logger::tracing, prelude::*};
 use futures_util::StreamExt;
 
+use super::autosave::{use_blog_autosave, BlogDraft};
+use super::draft_recovery::DraftRecoveryPrompt;
 use super::form::{use_blog_form, BlogForm};
+use super::markdown_import::MarkdownImportDialog;
+use super::quality_panel::{quality_report, QualityPanel};
+use super::seo_panel::SeoPanel;
 // use crate::components::editor::RichTextEditor; // Moved to legacy - using TypeScript editor instead
+use crate::components::sonner::{use_sonner, ToastOptions};
 use crate::components::{
-    AppInput, ConfirmDialog, EditorJsHost, ImageEditorModal, MediaUploadItem, MediaUploadZone,
-    PostSuccessDialog,
+    AppInput, ConfirmDialog, DateTimePicker, EditorJsHost, ImageEditorModal, MediaUploadItem,
+    MediaUploadZone, PostSuccessDialog,
 };
-use crate::hooks::use_previous;
+use crate::hooks::{use_previous, use_server_field_errors, OxFormModel};
 use crate::router::Route;
 use crate::store::{
     use_categories, use_image_editor, use_media, use_post, use_tag, MediaReference,
-    MediaUploadPayload, PostContent, PostCreatePayload, PostEditPayload, PostStatus,
+    MediaUploadPayload, PostContent, PostCreatePayload, PostEditPayload, PostSchedulePayload,
+    PostStatus,
 };
 use crate::ui::shadcn::{
     Badge, BadgeVariant, Button, ButtonVariant, Checkbox, Combobox, ComboboxItem, Skeleton,
 };
+use crate::utils::content_quality::QualityRules;
+use crate::utils::js_bridge::editorjs_render;
+use crate::utils::markdown::markdown_to_content;
 use serde_json;
 use wasm_bindgen::{closure::Closure, JsCast};
 use web_sys::{Blob, Url};
@@ -39,6 +50,11 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
 
     // Initialize form with existing post data if editing
     let mut initial_form = use_signal(|| None::<BlogForm>);
+    // Series placement as last loaded from the server, to diff against on save
+    let mut saved_series = use_signal(|| None::<(i32, Option<i32>)>);
+    // Whether the post was scheduled when last loaded or saved, to unschedule it on save
+    let mut saved_scheduled = use_signal(|| false);
+    let sonner = use_sonner();
     let is_edit_mode = post_id.is_some();
 
     // Fetch existing post if editing
@@ -55,6 +71,7 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
         spawn(async move {
             categories.list().await;
             tags.list().await;
+            posts.series_list().await;
         });
     });
 
@@ -75,23 +92,21 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                             .map(|m| m.file_url.clone()),
                         featured_image_media_id: post.featured_image.as_ref().map(|m| m.id),
                         is_published: post.status == PostStatus::Published,
+                        scheduled: post.is_scheduled(),
+                        publish_at: post.published_at.filter(|_| post.is_scheduled()),
                         category_id: Some(post.category.id),
                         tag_ids: post.tags.iter().map(|t| t.id).collect(),
+                        series_id: post.series.as_ref().map(|s| s.id),
+                        series_position: post.series.as_ref().and_then(|s| s.position),
+                        seo: post.seo.clone(),
                     };
+                    saved_series.set(form.series_placement());
+                    saved_scheduled.set(form.scheduled);
                     initial_form.set(Some(form));
                 }
             }
         } else {
-            // New post: prefill content from localStorage draft if present
-            let mut form = BlogForm::new();
-            if let Some(window) = web_sys::window() {
-                if let Ok(Some(storage)) = window.local_storage() {
-                    if let Ok(Some(draft)) = storage.get_item("blog_form_draft_content") {
-                        form.content = draft;
-                    }
-                }
-            }
-            initial_form.set(Some(form));
+            initial_form.set(Some(BlogForm::new()));
         }
     });
 
@@ -111,6 +126,54 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
     let blog_form_hook = use_blog_form(form_data.clone().unwrap());
     let mut form = blog_form_hook.form;
     let mut auto_slug = blog_form_hook.auto_slug;
+    let submit_error = match post_id {
+        Some(id) => posts
+            .edit
+            .read()
+            .get(&id)
+            .and_then(|frame| frame.error.clone()),
+        None => posts.add.read().error.clone(),
+    };
+    use_server_field_errors(form, submit_error);
+
+    // Offer an autosaved draft once, when it holds changes the loaded post does not
+    let base_form = form_data.clone().unwrap();
+    let mut recovered_draft =
+        use_signal(|| BlogDraft::load(post_id).filter(|draft| draft.differs_from(&base_form)));
+    let recovery_pending = use_memo(move || recovered_draft.read().is_some());
+    // Only unscheduled drafts are autosaved to the server; published posts would go live at
+    // once and scheduled ones at their publish time
+    let server_drafts = !base_form.is_published && !base_form.scheduled;
+    use_blog_autosave(form, post_id, server_drafts, recovery_pending);
+
+    let restore_draft = move |_| {
+        let Some(draft) = recovered_draft.take() else {
+            return;
+        };
+        let restored = draft.to_form();
+        if let Err(err) = editorjs_render(&restored.content) {
+            gloo_console::error!("[BlogForm] Failed to render restored draft:", err);
+        }
+        let mut form_mut = form.write();
+        for (name, value) in restored.to_map() {
+            form_mut.update_field(&name, value);
+        }
+        form_mut.data = restored;
+    };
+    let discard_draft = move |_| {
+        recovered_draft.set(None);
+        BlogDraft::discard(post_id);
+    };
+
+    let mut markdown_import_open = use_signal(|| false);
+    let import_markdown = move |markdown: String| {
+        let content = serde_json::to_string(&markdown_to_content(&markdown)).unwrap_or_default();
+        if let Err(err) = editorjs_render(&content) {
+            gloo_console::error!("[BlogForm] Failed to render imported Markdown:", err);
+        }
+        form.write().update_field("content", content);
+    };
+
     // Use coroutine to handle editor changes from JavaScript
     let editor_change_handler = {
         let form_signal = form;
@@ -119,13 +182,7 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
             let mut form_signal = form_signal;
 
             while let Some(detail) = rx.next().await {
-                form_signal.write().update_field("content", detail.clone());
-
-                if let Some(window) = web_sys::window() {
-                    if let Ok(Some(storage)) = window.local_storage() {
-                        let _ = storage.set_item("blog_form_draft_content", &detail);
-                    }
-                }
+                form_signal.write().update_field("content", detail);
             }
         })
     };
@@ -228,6 +285,7 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                     reference_type: Some(MediaReference::Post),
                     width: None,
                     height: None,
+                    optimize: true,
                 };
 
                 match media_state.upload(payload).await {
@@ -261,6 +319,7 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                     reference_type: Some(MediaReference::Post),
                     width: None,
                     height: None,
+                    optimize: true,
                 };
 
                 match media_state.upload(payload).await {
@@ -322,12 +381,8 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
         if let Some(prev) = prev_success {
             if !prev && any_success {
                 // Success state just transitioned from false to true
-                // Clean up localStorage draft
-                if let Some(window) = web_sys::window() {
-                    if let Ok(Some(storage)) = window.local_storage() {
-                        let _ = storage.remove_item("blog_form_draft_content");
-                    }
-                }
+                // The autosaved draft is now on the server
+                BlogDraft::discard(post_id);
 
                 // Open success dialog
                 success_dialog_open.set(true);
@@ -335,12 +390,202 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
         }
     }));
 
+    let save_post = move || {
+        let form_data = form.read();
+
+        // Validate required fields
+        if form_data.data.title.is_empty() {
+            tracing::error!("Title is required");
+            return;
+        }
+        if form_data.data.content.is_empty() {
+            tracing::error!("Content is required");
+            return;
+        }
+        if form_data.data.slug.is_empty() {
+            tracing::error!("Slug is required");
+            return;
+        }
+        if form_data.data.category_id.is_none() {
+            tracing::error!("Category is required");
+            return;
+        }
+        if let Some(message) = form_data.data.schedule_error(Utc::now()) {
+            tracing::error!("{}", message);
+            return;
+        }
+
+        if let Some(id) = post_id {
+            // Edit existing post
+            let payload = PostEditPayload {
+                title: Some(form_data.data.title.clone()),
+                content: Some(
+                    serde_json::from_str::<PostContent>(&form_data.data.content).unwrap(),
+                ),
+                slug: Some(form_data.data.slug.clone()),
+                excerpt: if form_data.data.excerpt.is_empty() {
+                    None
+                } else {
+                    Some(form_data.data.excerpt.clone())
+                },
+                featured_image_id: form_data.data.featured_image_media_id,
+                status: Some(if form_data.data.is_published {
+                    PostStatus::Published
+                } else {
+                    PostStatus::Draft
+                }),
+                category_id: form_data.data.category_id,
+                tag_ids: Some(form_data.data.tag_ids.clone()),
+                // Leaving schedule mode clears the pending publish time, or the post would
+                // still go live at it
+                published_at: (saved_scheduled() && !form_data.data.scheduled)
+                    .then(|| form_data.data.is_published.then(Utc::now)),
+                seo: Some(form_data.data.seo.clone()),
+            };
+
+            let placement = form_data.data.series_placement();
+            let publish_at = form_data.data.schedule_target();
+            spawn(async move {
+                posts.edit(id, payload).await;
+                let saved = posts.edit.peek().get(&id).is_some_and(|f| f.is_success());
+                if saved {
+                    if posts.series_assign(id, saved_series(), placement).await {
+                        saved_series.set(placement);
+                    } else {
+                        sonner.error(
+                            "Post saved, but its series could not be updated".to_string(),
+                            ToastOptions::default(),
+                        );
+                    }
+                    if let Some(publish_at) = publish_at {
+                        posts
+                            .schedule(PostSchedulePayload {
+                                post_id: id,
+                                publish_at,
+                            })
+                            .await;
+                        if posts
+                            .schedule
+                            .peek()
+                            .get(&id)
+                            .is_some_and(|f| f.is_success())
+                        {
+                            saved_scheduled.set(true);
+                        } else {
+                            sonner.error(
+                                "Post saved, but it could not be scheduled".to_string(),
+                                ToastOptions::default(),
+                            );
+                        }
+                    } else {
+                        saved_scheduled.set(false);
+                    }
+                }
+            });
+        } else {
+            // Create new post
+            let payload = PostCreatePayload {
+                title: form_data.data.title.clone(),
+                content: serde_json::from_str::<PostContent>(&form_data.data.content).unwrap(),
+                slug: form_data.data.slug.clone(),
+                excerpt: if form_data.data.excerpt.is_empty() {
+                    None
+                } else {
+                    Some(form_data.data.excerpt.clone())
+                },
+                featured_image_id: form_data.data.featured_image_media_id,
+                is_published: form_data.data.is_published,
+                category_id: form_data.data.category_id.unwrap(),
+                tag_ids: form_data.data.tag_ids.clone(),
+                published_at: None,
+                seo: Some(form_data.data.seo.clone()),
+            };
+
+            let placement = form_data.data.series_placement();
+            let publish_at = form_data.data.schedule_target();
+            spawn(async move {
+                posts.add(payload).await;
+                let created = {
+                    let add = posts.add.peek();
+                    add.is_success()
+                        .then(|| add.data.as_ref().map(|post| post.id))
+                        .flatten()
+                };
+                if let Some(new_id) = created {
+                    if !posts.series_assign(new_id, None, placement).await {
+                        sonner.error(
+                            "Post created, but it could not be added to its series".to_string(),
+                            ToastOptions::default(),
+                        );
+                    }
+                    if let Some(publish_at) = publish_at {
+                        posts
+                            .schedule(PostSchedulePayload {
+                                post_id: new_id,
+                                publish_at,
+                            })
+                            .await;
+                        if !posts
+                            .schedule
+                            .peek()
+                            .get(&new_id)
+                            .is_some_and(|f| f.is_success())
+                        {
+                            sonner.error(
+                                "Post created, but it could not be scheduled".to_string(),
+                                ToastOptions::default(),
+                            );
+                        }
+                    }
+                }
+            });
+        }
+    };
+
+    // Publishing or scheduling runs the content checks first; drafts are saved as they are
+    let quality_rules = use_signal(QualityRules::load);
+    let mut quality_confirm_open = use_signal(|| false);
+    let mut quality_blocked = use_signal(|| None::<String>);
+    let mut request_save = move || {
+        let data = form.peek().data.clone();
+        quality_blocked.set(None);
+        if data.is_published || data.scheduled {
+            if let Some(report) = quality_report(&data, &quality_rules.peek()) {
+                let blocking = report.blocking().count();
+                if blocking > 0 {
+                    quality_blocked.set(Some(format!(
+                        "Fix {} blocking content issue{} before publishing",
+                        blocking,
+                        if blocking == 1 { "" } else { "s" }
+                    )));
+                    return;
+                }
+                if report.warnings().next().is_some() {
+                    quality_confirm_open.set(true);
+                    return;
+                }
+            }
+        }
+        save_post();
+    };
+
     rsx! {
         div {
             h1 { class: "sr-only",
                 if is_edit_mode { "Edit Blog Post" } else { "New Blog Post" }
             }
 
+            MarkdownImportDialog { is_open: markdown_import_open, on_import: import_markdown }
+
+            if let Some(draft) = recovered_draft.read().clone() {
+                DraftRecoveryPrompt {
+                    draft,
+                    base: initial_form.read().clone().unwrap_or_else(BlogForm::new),
+                    on_restore: restore_draft,
+                    on_discard: discard_draft,
+                }
+            }
+
             // Show error message if submission failed
             {
                 let add_state = posts.add.read();
@@ -384,9 +629,16 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                 div { class: "lg:col-span-2 space-y-8",
                     // Post details card
                     div { class: "rounded-xl border border-border/70 bg-transparent",
-                        div { class: "px-6 py-6",
-                            h2 { class: "text-lg font-semibold", "Post Content" }
-                            p { class: "text-sm text-muted-foreground", "Write and format your blog post content." }
+                        div { class: "px-6 py-6 flex items-start justify-between gap-4",
+                            div {
+                                h2 { class: "text-lg font-semibold", "Post Content" }
+                                p { class: "text-sm text-muted-foreground", "Write and format your blog post content." }
+                            }
+                            Button {
+                                variant: ButtonVariant::Outline,
+                                onclick: move |_| markdown_import_open.set(true),
+                                "Import Markdown"
+                            }
                         }
                         div { class: "px-6 py-6 space-y-6",
                             form {
@@ -472,6 +724,10 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                             }
                         }
                     }
+
+                    QualityPanel { form, rules: quality_rules }
+
+                    SeoPanel { form }
                 }
 
                 // Sidebar column
@@ -483,22 +739,46 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                             p { class: "text-sm text-muted-foreground", "Organize and categorize your post." }
                         }
                         div { class: "px-6 py-6 space-y-6",
-                    // Published status switch
-                    div { class: "flex items-center justify-between",
-                        div { class: "space-y-0.5",
-                            label { class: "block text-sm font-medium text-foreground", "Published" }
-                            p { class: "text-xs text-muted-foreground",
-                                if form.read().data.is_published { "This post is publicly visible." } else { "This post is saved as draft." }
+                    // Publishing: draft, publish now, or schedule
+                    div { class: "space-y-3",
+                        label { class: "block text-sm font-medium text-foreground", "Publishing" }
+                        {
+                            let data = form.read().data.clone();
+                            let mode = if data.scheduled { 2 } else if data.is_published { 1 } else { 0 };
+                            rsx! {
+                                div { class: "grid grid-cols-3 gap-1 rounded-md border border-border/70 p-1",
+                                    for (index, label) in ["Draft", "Publish", "Schedule"].into_iter().enumerate() {
+                                        button {
+                                            key: "{label}",
+                                            r#type: "button",
+                                            class: if mode == index { "rounded px-2 py-1.5 text-xs font-medium bg-primary text-primary-foreground" } else { "rounded px-2 py-1.5 text-xs font-medium text-muted-foreground hover:bg-muted/50" },
+                                            onclick: move |_| {
+                                                let mut form_mut = form.write();
+                                                form_mut.data.is_published = index == 1;
+                                                form_mut.data.scheduled = index == 2;
+                                            },
+                                            "{label}"
+                                        }
+                                    }
+                                }
+                                p { class: "text-xs text-muted-foreground",
+                                    match mode {
+                                        1 => "This post is publicly visible.",
+                                        2 => "This post stays a draft until the publish time.",
+                                        _ => "This post is saved as draft.",
+                                    }
+                                }
+                                if data.scheduled {
+                                    DateTimePicker {
+                                        value: data.publish_at,
+                                        onchange: move |at| { form.write().data.publish_at = at; },
+                                    }
+                                    if let Some(message) = data.schedule_error(Utc::now()) {
+                                        p { class: "text-xs text-destructive", "{message}" }
+                                    }
+                                }
                             }
                         }
-                        input {
-                            class: "relative h-6 w-11 cursor-pointer appearance-none rounded-full bg-border/50 transition-colors duration-200 checked:bg-primary focus:ring-2 focus:ring-ring/40 before:pointer-events-none before:absolute before:h-5 before:w-5 before:translate-x-0.5 before:translate-y-0.5 before:rounded-full before:bg-white before:shadow-sm before:transition-transform before:duration-200 checked:before:translate-x-5",
-                            r#type: "checkbox",
-                            checked: form.read().data.is_published,
-                            onchange: move |event| {
-                                form.write().data.is_published = event.checked();
-                            },
-                        }
                     }
 
                     div { class: "h-px bg-border/60" }
@@ -618,6 +898,77 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                             })
                         }
                     }
+
+                    div { class: "h-px bg-border/60" }
+
+                    // Series selection
+                    div { class: "space-y-3",
+                        label { class: "block text-sm font-medium text-foreground", "Series" }
+                        p { class: "text-xs text-muted-foreground", "Add this post as a part of a multi-part series" }
+                        {
+                            let series_list = posts.series_list.read();
+                            let items: Vec<ComboboxItem> = series_list
+                                .data
+                                .as_ref()
+                                .map(|d| &d.data)
+                                .unwrap_or(&vec![])
+                                .iter()
+                                .map(|s| ComboboxItem { value: s.id.to_string(), label: s.name.clone() })
+                                .collect();
+                            let current_val = form.read().data.series_id.map(|id| id.to_string());
+                            let has_series = current_val.is_some();
+
+                            rsx! {
+                                if series_list.is_loading() && items.is_empty() {
+                                    Skeleton { class: Some("h-10 w-full".to_string()) }
+                                } else {
+                                    div { class: "flex items-center gap-2",
+                                        Combobox {
+                                            items,
+                                            placeholder: "No series".to_string(),
+                                            value: current_val,
+                                            width: "w-full".to_string(),
+                                            onvaluechange: Some(EventHandler::new(move |val: Option<String>| {
+                                                let mut form_mut = form.write();
+                                                form_mut.data.series_id = val.and_then(|v| v.parse::<i32>().ok());
+                                                if form_mut.data.series_id.is_none() {
+                                                    form_mut.data.series_position = None;
+                                                }
+                                            })),
+                                        }
+                                        if has_series {
+                                            Button {
+                                                variant: ButtonVariant::Ghost,
+                                                onclick: move |_| {
+                                                    let mut form_mut = form.write();
+                                                    form_mut.data.series_id = None;
+                                                    form_mut.data.series_position = None;
+                                                },
+                                                "Clear"
+                                            }
+                                        }
+                                    }
+                                    if has_series {
+                                        div { class: "flex items-center gap-3",
+                                            label { class: "text-sm text-muted-foreground whitespace-nowrap", r#for: "series-position", "Part" }
+                                            input {
+                                                id: "series-position",
+                                                class: "h-9 w-24 rounded-md border border-border/70 bg-transparent px-3 text-sm text-foreground focus:border-ring focus:ring-2 focus:ring-ring/40",
+                                                r#type: "number",
+                                                min: "1",
+                                                placeholder: "Last",
+                                                value: form.read().data.series_position.map(|p| p.to_string()).unwrap_or_default(),
+                                                oninput: move |event| {
+                                                    form.write().data.series_position = event.value().parse::<i32>().ok().filter(|p| *p > 0);
+                                                },
+                                            }
+                                            span { class: "text-xs text-muted-foreground", "Leave empty to add it as the last part." }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                         }
                     }
 
@@ -681,7 +1032,10 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
             }
 
             // Form actions
-            div { class: "flex justify-end gap-4",
+            div { class: "flex items-center justify-end gap-4",
+                if let Some(message) = quality_blocked() {
+                    p { class: "text-sm text-destructive", "{message}" }
+                }
                 Button {
                     variant: ButtonVariant::Ghost,
                     onclick: move |_| {
@@ -699,75 +1053,7 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                         };
                         add_state.is_loading() || edit_state.as_ref().map_or(false, |s| s.is_loading())
                     },
-                    onclick: move |_| {
-                        let form_data = form.read();
-
-                        // Validate required fields
-                        if form_data.data.title.is_empty() {
-                            tracing::error!("Title is required");
-                            return;
-                        }
-                        if form_data.data.content.is_empty() {
-                            tracing::error!("Content is required");
-                            return;
-                        }
-                        if form_data.data.slug.is_empty() {
-                            tracing::error!("Slug is required");
-                            return;
-                        }
-                        if form_data.data.category_id.is_none() {
-                            tracing::error!("Category is required");
-                            return;
-                        }
-
-                        if let Some(id) = post_id {
-                            // Edit existing post
-                            let payload = PostEditPayload {
-                                        title: Some(form_data.data.title.clone()),
-                                        content: Some(serde_json::from_str::<PostContent>(&form_data.data.content).unwrap()),
-                                        slug: Some(form_data.data.slug.clone()),
-                                        excerpt: if form_data.data.excerpt.is_empty() {
-                                            None
-                                        } else {
-                                            Some(form_data.data.excerpt.clone())
-                                        },
-                                        featured_image_id: form_data.data.featured_image_media_id,
-                                        status: Some(if form_data.data.is_published {
-                                            PostStatus::Published
-                                        } else {
-                                            PostStatus::Draft
-                                        }),
-                                        category_id: form_data.data.category_id,
-                                        tag_ids: Some(form_data.data.tag_ids.clone()),
-                                        published_at: None,
-                                    };
-
-                                    spawn(async move {
-                                        posts.edit(id, payload).await;
-                                    });
-                                } else {
-                                    // Create new post
-                                    let payload = PostCreatePayload {
-                                        title: form_data.data.title.clone(),
-                                        content: serde_json::from_str::<PostContent>(&form_data.data.content).unwrap(),
-                                        slug: form_data.data.slug.clone(),
-                                        excerpt: if form_data.data.excerpt.is_empty() {
-                                            None
-                                        } else {
-                                            Some(form_data.data.excerpt.clone())
-                                        },
-                                        featured_image_id: form_data.data.featured_image_media_id,
-                                        is_published: form_data.data.is_published,
-                                        category_id: form_data.data.category_id.unwrap(),
-                                        tag_ids: form_data.data.tag_ids.clone(),
-                                        published_at: None,
-                                    };
-
-                                    spawn(async move {
-                                        posts.add(payload).await;
-                                    });
-                                }
-                            },
+                    onclick: move |_| request_save(),
                             {
                                 let add_state = posts.add.read();
                                 let edit_state = if let Some(id) = post_id {
@@ -800,6 +1086,25 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                 on_cancel: handle_edit_skip,
             }
 
+            // Publish despite content warnings
+            ConfirmDialog {
+                is_open: quality_confirm_open,
+                title: "Publish with content warnings?".to_string(),
+                description: {
+                    let warnings = quality_report(&form.read().data, &quality_rules.read())
+                        .map_or(0, |report| report.warnings().count());
+                    format!(
+                        "The content checks found {} warning{}. Review them in Content Checks or publish anyway.",
+                        warnings,
+                        if warnings == 1 { "" } else { "s" }
+                    )
+                },
+                confirm_label: "Publish Anyway".to_string(),
+                cancel_label: "Review".to_string(),
+                on_confirm: move |_| save_post(),
+                on_cancel: move |_| {},
+            }
+
             // Image editor modal
             ImageEditorModal {
                 on_save: handle_editor_save,
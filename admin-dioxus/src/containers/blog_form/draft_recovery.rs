@@ -0,0 +1,69 @@
+use dioxus::prelude::*;
+
+use super::autosave::BlogDraft;
+use super::form::BlogForm;
+use crate::ui::shadcn::{Button, ButtonVariant};
+use crate::utils::dates::ScheduleZone;
+use crate::utils::post_diff::{block_kind, block_preview, BlockDiff};
+
+/// Banner offering to restore an autosaved draft that differs from the server copy, with a
+/// summary of what the draft changes.
+#[component]
+pub fn DraftRecoveryPrompt(
+    draft: BlogDraft,
+    base: BlogForm,
+    on_restore: EventHandler<()>,
+    on_discard: EventHandler<()>,
+) -> Element {
+    let diff = draft.diff(&base);
+    let saved_at = ScheduleZone::Local
+        .from_utc(draft.saved_at)
+        .format("%b %-d, %Y at %H:%M")
+        .to_string();
+    let changed_blocks: Vec<(&'static str, String, String)> = diff
+        .blocks
+        .iter()
+        .filter_map(|change| match change {
+            BlockDiff::Unchanged(_) => None,
+            BlockDiff::Added(block) => Some(("+", block_kind(block), block_preview(block))),
+            BlockDiff::Removed(block) => Some(("−", block_kind(block), block_preview(block))),
+            BlockDiff::Modified { after, .. } => {
+                Some(("~", block_kind(after), block_preview(after)))
+            }
+        })
+        .collect();
+
+    rsx! {
+        div { class: "rounded-md border border-amber-300 bg-amber-50/60 p-4 mb-6 space-y-3 dark:border-amber-900/50 dark:bg-amber-900/10",
+            div { class: "flex flex-wrap items-center justify-between gap-3",
+                p { class: "text-sm font-medium", "Recover unsaved changes from {saved_at}?" }
+                div { class: "flex items-center gap-2",
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        onclick: move |_| on_discard.call(()),
+                        "Discard"
+                    }
+                    Button { onclick: move |_| on_restore.call(()), "Restore" }
+                }
+            }
+            ul { class: "text-xs text-muted-foreground space-y-1 max-h-48 overflow-y-auto",
+                if let Some(title) = diff.title {
+                    li { "Title: “{title.before}” → “{title.after}”" }
+                }
+                if diff.excerpt.is_some() {
+                    li { "Excerpt changed" }
+                }
+                if diff.metadata_changed {
+                    li { "Settings changed (category, tags, image, series, publishing or SEO)" }
+                }
+                for (marker , kind , preview) in changed_blocks {
+                    li { class: "truncate",
+                        span { class: "font-mono mr-1", "{marker}" }
+                        span { class: "font-medium text-foreground mr-1", "{kind}" }
+                        "{preview}"
+                    }
+                }
+            }
+        }
+    }
+}
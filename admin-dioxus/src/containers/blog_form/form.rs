@@ -1,8 +1,10 @@
+use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
 use std::collections::HashMap;
 use validator::{Validate, ValidationError};
 
 use crate::hooks::{OxForm, OxFormModel};
+use crate::store::PostSeo;
 
 #[derive(Debug, Validate, Clone, PartialEq)]
 pub struct BlogForm {
@@ -24,9 +26,20 @@ pub struct BlogForm {
 
     pub is_published: bool,
 
+    // Scheduled posts are saved as drafts, then handed to the schedule endpoint
+    pub scheduled: bool,
+    pub publish_at: Option<DateTime<Utc>>,
+
     pub category_id: Option<i32>,
 
     pub tag_ids: Vec<i32>,
+
+    // Series placement; synced through the series endpoints after the post is saved
+    pub series_id: Option<i32>,
+    pub series_position: Option<i32>,
+
+    // Search and social metadata, edited in the SEO panel
+    pub seo: PostSeo,
 }
 
 fn validate_slug(slug: &str) -> Result<(), ValidationError> {
@@ -49,8 +62,13 @@ impl BlogForm {
             featured_image_blob_url: None,
             featured_image_media_id: None,
             is_published: false,
+            scheduled: false,
+            publish_at: None,
             category_id: None,
             tag_ids: vec![],
+            series_id: None,
+            series_position: None,
+            seo: PostSeo::default(),
         }
     }
 
@@ -60,6 +78,26 @@ impl BlogForm {
         self.featured_image_blob_url.is_some() && self.featured_image_media_id.is_none()
     }
 
+    /// Publish time to schedule after saving, when scheduling is on.
+    pub fn schedule_target(&self) -> Option<DateTime<Utc>> {
+        self.scheduled.then_some(self.publish_at).flatten()
+    }
+
+    /// Error to show under the schedule picker, if any.
+    pub fn schedule_error(&self, now: DateTime<Utc>) -> Option<&'static str> {
+        match (self.scheduled, self.publish_at) {
+            (false, _) => None,
+            (true, None) => Some("Pick a date and time to publish"),
+            (true, Some(at)) if at <= now => Some("Publish time must be in the future"),
+            (true, Some(_)) => None,
+        }
+    }
+
+    /// `(series_id, position)` to pass to `PostState::series_assign`.
+    pub fn series_placement(&self) -> Option<(i32, Option<i32>)> {
+        self.series_id.map(|id| (id, self.series_position))
+    }
+
     pub fn sanitize_slug(text: &str) -> String {
         let text = text.to_lowercase();
         let text = regex::Regex::new(r"[^\w\s-]")
@@ -93,6 +131,19 @@ impl OxFormModel for BlogForm {
         if let Some(category_id) = self.category_id {
             map.insert("category_id".to_string(), category_id.to_string());
         }
+        let seo = &self.seo;
+        for (name, value) in [
+            ("meta_title", &seo.meta_title),
+            ("meta_description", &seo.meta_description),
+            ("canonical_url", &seo.canonical_url),
+            ("og_title", &seo.og_title),
+            ("og_description", &seo.og_description),
+            ("focus_keyword", &seo.focus_keyword),
+        ] {
+            map.insert(name.to_string(), value.clone().unwrap_or_default());
+        }
+        map.insert("noindex".to_string(), seo.noindex.to_string());
+        map.insert("nofollow".to_string(), seo.nofollow.to_string());
         // Featured image and tag IDs are handled separately
         map
     }
@@ -105,9 +156,27 @@ impl OxFormModel for BlogForm {
             "excerpt" => self.excerpt = value.to_string(),
             "is_published" => self.is_published = value.parse().unwrap_or(false),
             "category_id" => self.category_id = value.parse().ok(),
+            "meta_title" => self.seo.meta_title = optional(value),
+            "meta_description" => self.seo.meta_description = optional(value),
+            "canonical_url" => self.seo.canonical_url = optional(value),
+            "og_title" => self.seo.og_title = optional(value),
+            "og_description" => self.seo.og_description = optional(value),
+            "focus_keyword" => self.seo.focus_keyword = optional(value),
+            "noindex" => self.seo.noindex = value.parse().unwrap_or(false),
+            "nofollow" => self.seo.nofollow = value.parse().unwrap_or(false),
             _ => {}
         }
     }
+
+    /// The payload nests SEO fields under `seo` (`seo.meta_title`); the form keeps them flat.
+    fn server_field(name: &str) -> &str {
+        name.strip_prefix("seo.").unwrap_or(name)
+    }
+}
+
+/// Blank SEO fields are left unset so they fall back to the post's own values.
+fn optional(value: &str) -> Option<String> {
+    Some(value.to_string()).filter(|value| !value.trim().is_empty())
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
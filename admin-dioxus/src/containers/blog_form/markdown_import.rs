@@ -0,0 +1,69 @@
+use crate::ui::custom::AppPortal;
+use crate::ui::shadcn::{Button, ButtonVariant};
+use dioxus::prelude::*;
+use hmziq_dioxus_free_icons::{icons::ld_icons::LdX, Icon};
+
+/// Dialog for pasting Markdown written elsewhere. The pasted text is handed to `on_import`;
+/// converting it and filling the editor is up to the caller.
+#[component]
+pub fn MarkdownImportDialog(mut is_open: Signal<bool>, on_import: EventHandler<String>) -> Element {
+    let mut markdown = use_signal(String::new);
+
+    if !*is_open.read() {
+        return rsx! {};
+    }
+
+    let mut close = move || {
+        is_open.set(false);
+        markdown.set(String::new());
+    };
+
+    rsx! {
+        AppPortal {
+            z_index: "60".to_string(),
+            div { class: "fixed inset-0 bg-black/50", onclick: move |_| close() }
+
+            div {
+                class: "fixed left-[50%] top-[50%] translate-x-[-50%] translate-y-[-50%] w-full max-w-2xl",
+                onclick: move |e| e.stop_propagation(),
+
+                div { class: "bg-background rounded-lg border p-6 shadow-lg",
+                    div { class: "flex items-start justify-between mb-4",
+                        div {
+                            h2 { class: "text-lg font-semibold", "Import Markdown" }
+                            p { class: "text-sm text-muted-foreground mt-1",
+                                "Paste Markdown to replace the current content. Headings, lists, checklists, code, quotes, tables and images are converted to editor blocks."
+                            }
+                        }
+                        button {
+                            onclick: move |_| close(),
+                            class: "rounded-xs opacity-70 hover:opacity-100 transition-opacity",
+                            Icon { icon: LdX, width: 20, height: 20 }
+                        }
+                    }
+                    textarea {
+                        class: "w-full h-80 resize-y rounded-md border border-border/70 bg-transparent px-4 py-3 font-mono text-sm text-foreground placeholder:text-muted-foreground focus:border-ring focus:ring-2 focus:ring-ring/40",
+                        placeholder: "# Title\n\nWrite or paste Markdown here…",
+                        value: markdown(),
+                        oninput: move |event| markdown.set(event.value()),
+                    }
+                    div { class: "flex justify-end gap-2 mt-6",
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            onclick: move |_| close(),
+                            "Cancel"
+                        }
+                        Button {
+                            disabled: markdown.read().trim().is_empty(),
+                            onclick: move |_| {
+                                on_import.call(markdown());
+                                close();
+                            },
+                            "Import"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,10 @@
+mod autosave;
 mod blog_form;
+mod draft_recovery;
 mod form;
+mod markdown_import;
+mod quality_panel;
+mod seo;
+mod seo_panel;
 
 pub use blog_form::BlogFormContainer;
@@ -0,0 +1,155 @@
+use dioxus::prelude::*;
+
+use super::form::BlogForm;
+use crate::hooks::OxForm;
+use crate::store::PostContent;
+use crate::utils::content_quality::{
+    check_content, readability_label, QualityReport, QualityRule, QualityRules, RuleAction,
+};
+
+const SELECT_CLASS: &str =
+    "h-8 rounded-md border border-border/70 bg-transparent px-2 text-xs text-foreground";
+const NUMBER_CLASS: &str =
+    "h-8 w-20 rounded-md border border-border/70 bg-transparent px-2 text-xs text-foreground";
+
+/// Checks the current content against `rules`; content that does not parse yet has no
+/// report.
+pub fn quality_report(form: &BlogForm, rules: &QualityRules) -> Option<QualityReport> {
+    serde_json::from_str::<PostContent>(&form.content)
+        .ok()
+        .map(|content| check_content(&content, rules))
+}
+
+/// Content checks card of the blog form: length, reading time and readability, the issues
+/// found, and how each rule is enforced when publishing.
+#[component]
+pub fn QualityPanel(form: Signal<OxForm<BlogForm>>, rules: Signal<QualityRules>) -> Element {
+    let mut show_rules = use_signal(|| false);
+    let current_rules = rules.read().clone();
+    let report = quality_report(&form.read().data, &current_rules).unwrap_or_default();
+    let stats = report.stats.clone();
+    let mut update_rules = move |change: &dyn Fn(&mut QualityRules)| {
+        let mut current = rules.write();
+        change(&mut current);
+        current.save();
+    };
+
+    rsx! {
+        div { class: "rounded-xl border border-border/70 bg-transparent",
+            div { class: "px-6 pt-6 flex items-start justify-between gap-4",
+                div {
+                    h2 { class: "text-lg font-semibold", "Content Checks" }
+                    p { class: "text-sm text-muted-foreground",
+                        "Checked again when you publish or schedule. Blocking issues must be fixed first."
+                    }
+                }
+                button {
+                    r#type: "button",
+                    class: "text-xs font-medium text-muted-foreground hover:text-foreground",
+                    onclick: move |_| show_rules.toggle(),
+                    if show_rules() { "Hide rules" } else { "Rules" }
+                }
+            }
+            div { class: "px-6 py-6 space-y-6",
+                div { class: "grid grid-cols-3 gap-4",
+                    div {
+                        p { class: "text-xs text-muted-foreground", "Words" }
+                        p { class: "text-lg font-semibold", "{stats.words}" }
+                    }
+                    div {
+                        p { class: "text-xs text-muted-foreground", "Reading time" }
+                        p { class: "text-lg font-semibold", "{stats.reading_minutes} min" }
+                    }
+                    div {
+                        p { class: "text-xs text-muted-foreground", "Reading ease" }
+                        if let Some(score) = stats.readability {
+                            p { class: "text-lg font-semibold",
+                                {format!("{:.0}", score)}
+                                span { class: "ml-1 text-xs font-normal text-muted-foreground",
+                                    {readability_label(score)}
+                                }
+                            }
+                        } else {
+                            p { class: "text-lg font-semibold", "–" }
+                        }
+                    }
+                }
+
+                if report.issues.is_empty() {
+                    p { class: "text-sm text-muted-foreground", "No issues found." }
+                } else {
+                    ul { class: "space-y-1 text-sm",
+                        for issue in report.issues.clone() {
+                            li { class: "flex items-start gap-2",
+                                span {
+                                    class: if issue.action == RuleAction::Block { "mt-1.5 h-2 w-2 shrink-0 rounded-full bg-red-500" } else { "mt-1.5 h-2 w-2 shrink-0 rounded-full bg-amber-500" },
+                                }
+                                span { class: "text-muted-foreground",
+                                    if let Some(block) = issue.block {
+                                        span { class: "font-medium text-foreground", "Block {block}: " }
+                                    }
+                                    "{issue.message}"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if show_rules() {
+                    div { class: "h-px bg-border/60" }
+                    div { class: "space-y-3",
+                        for rule in QualityRule::ALL {
+                            div { key: "{rule.label()}", class: "flex items-center justify-between gap-4",
+                                span { class: "text-sm", {rule.label()} }
+                                select {
+                                    class: SELECT_CLASS,
+                                    onchange: move |event| {
+                                        if let Some(action) = RuleAction::from_label(&event.value()) {
+                                            update_rules(&|rules| rules.set_action(rule, action));
+                                        }
+                                    },
+                                    for action in RuleAction::ALL {
+                                        option {
+                                            value: action.label(),
+                                            selected: current_rules.action(rule) == action,
+                                            {action.label()}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex items-center justify-between gap-4",
+                            span { class: "text-sm", "Minimum words" }
+                            input {
+                                class: NUMBER_CLASS,
+                                r#type: "number",
+                                min: "0",
+                                value: "{current_rules.min_words}",
+                                onchange: move |event| {
+                                    if let Ok(words) = event.value().parse::<usize>() {
+                                        update_rules(&|rules| rules.min_words = words);
+                                    }
+                                },
+                            }
+                        }
+                        div { class: "flex items-center justify-between gap-4",
+                            span { class: "text-sm", "Minimum reading ease" }
+                            input {
+                                class: NUMBER_CLASS,
+                                r#type: "number",
+                                min: "0",
+                                max: "100",
+                                value: "{current_rules.min_readability}",
+                                onchange: move |event| {
+                                    if let Ok(score) = event.value().parse::<f64>() {
+                                        update_rules(&|rules| rules.min_readability = score);
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
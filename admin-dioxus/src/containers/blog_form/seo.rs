@@ -0,0 +1,317 @@
+//! What search engines and social networks will show for a post, and checks on how well
+//! the SEO fields are filled in.
+
+use std::ops::RangeInclusive;
+
+use super::form::BlogForm;
+use crate::store::{EditorJsBlock, PostContent};
+use crate::utils::post_render::inline_text;
+
+/// Titles outside this range are padded or cut off in search results.
+pub const TITLE_LENGTH: RangeInclusive<usize> = 30..=60;
+/// Descriptions outside this range are replaced or cut off in search results.
+pub const DESCRIPTION_LENGTH: RangeInclusive<usize> = 70..=160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeoLevel {
+    Good,
+    Warning,
+    Problem,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeoCheck {
+    pub level: SeoLevel,
+    pub message: String,
+}
+
+impl SeoCheck {
+    fn new(level: SeoLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+}
+
+/// Title, description and image as rendered, after falling back to the post's own fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeoPreview {
+    pub title: String,
+    pub description: String,
+    /// Breadcrumb-style URL, e.g. `example.com › posts › hello`.
+    pub display_url: String,
+    pub social_title: String,
+    pub social_description: String,
+    pub image_url: Option<String>,
+}
+
+impl SeoPreview {
+    pub fn from_form(form: &BlogForm) -> Self {
+        let seo = &form.seo;
+        let title = seo.meta_title.clone().unwrap_or_else(|| form.title.clone());
+        let description = seo
+            .meta_description
+            .clone()
+            .or_else(|| Some(form.excerpt.clone()).filter(|excerpt| !excerpt.is_empty()))
+            .unwrap_or_else(|| first_paragraph(&form.content));
+        Self {
+            display_url: display_url(seo.canonical_url.as_deref(), &form.slug),
+            social_title: seo.og_title.clone().unwrap_or_else(|| title.clone()),
+            social_description: seo
+                .og_description
+                .clone()
+                .unwrap_or_else(|| description.clone()),
+            image_url: form.featured_image_blob_url.clone(),
+            title,
+            description,
+        }
+    }
+}
+
+/// Text of the first paragraph block, which search engines often use when there is no
+/// description.
+pub fn first_paragraph(content_json: &str) -> String {
+    serde_json::from_str::<PostContent>(content_json)
+        .ok()
+        .and_then(|content| {
+            content.blocks.into_iter().find_map(|block| match block {
+                EditorJsBlock::Paragraph { data, .. } => {
+                    Some(inline_text(&data.text)).filter(|text| !text.trim().is_empty())
+                }
+                _ => None,
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// The URL as search results print it: host and path segments joined by `›`. Without a
+/// canonical URL the post's own path is shown under a placeholder host.
+pub fn display_url(canonical_url: Option<&str>, slug: &str) -> String {
+    let url = match canonical_url {
+        Some(url) => url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_end_matches('/')
+            .to_string(),
+        None => format!("your-site › posts › {}", slug),
+    };
+    url.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join(" › ")
+}
+
+/// Cut `text` to at most `max` characters on a word boundary, adding an ellipsis.
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max.saturating_sub(1)).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
+
+fn contains_keyword(text: &str, keyword: &str) -> bool {
+    let normalize = |s: &str| {
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    normalize(text).contains(&normalize(keyword))
+}
+
+fn length_check(
+    label: &str,
+    length: usize,
+    range: &RangeInclusive<usize>,
+    missing: &str,
+) -> SeoCheck {
+    if length == 0 {
+        SeoCheck::new(SeoLevel::Problem, missing)
+    } else if length < *range.start() {
+        SeoCheck::new(
+            SeoLevel::Warning,
+            format!(
+                "{} is short ({} characters); aim for {}–{}",
+                label,
+                length,
+                range.start(),
+                range.end()
+            ),
+        )
+    } else if length > *range.end() {
+        SeoCheck::new(
+            SeoLevel::Warning,
+            format!(
+                "{} is {} characters and will be cut off after about {}",
+                label,
+                length,
+                range.end()
+            ),
+        )
+    } else {
+        SeoCheck::new(
+            SeoLevel::Good,
+            format!("{} length is good ({} characters)", label, length),
+        )
+    }
+}
+
+/// Checks shown under the previews, problems first.
+pub fn seo_checks(form: &BlogForm) -> Vec<SeoCheck> {
+    let preview = SeoPreview::from_form(form);
+    let seo = &form.seo;
+    let mut checks = vec![
+        length_check(
+            "Title",
+            preview.title.trim().chars().count(),
+            &TITLE_LENGTH,
+            "Add a title",
+        ),
+        length_check(
+            "Description",
+            preview.description.trim().chars().count(),
+            &DESCRIPTION_LENGTH,
+            "Add a meta description or excerpt",
+        ),
+    ];
+
+    match seo.focus_keyword.as_deref().map(str::trim) {
+        Some(keyword) if !keyword.is_empty() => {
+            let slug_keyword = BlogForm::sanitize_slug(keyword);
+            let places = [
+                ("title", contains_keyword(&preview.title, keyword)),
+                (
+                    "slug",
+                    !slug_keyword.is_empty() && form.slug.contains(&slug_keyword),
+                ),
+                (
+                    "first paragraph",
+                    contains_keyword(&first_paragraph(&form.content), keyword),
+                ),
+                (
+                    "description",
+                    contains_keyword(&preview.description, keyword),
+                ),
+            ];
+            for (place, found) in places {
+                checks.push(if found {
+                    SeoCheck::new(
+                        SeoLevel::Good,
+                        format!("Focus keyword appears in the {}", place),
+                    )
+                } else {
+                    SeoCheck::new(
+                        SeoLevel::Warning,
+                        format!("Focus keyword is missing from the {}", place),
+                    )
+                });
+            }
+        }
+        _ => checks.push(SeoCheck::new(
+            SeoLevel::Warning,
+            "Set a focus keyword to check where it appears",
+        )),
+    }
+
+    if let Some(url) = &seo.canonical_url {
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            checks.push(SeoCheck::new(
+                SeoLevel::Problem,
+                "Canonical URL must be a full http(s) address",
+            ));
+        }
+    }
+    if seo.noindex {
+        checks.push(SeoCheck::new(
+            SeoLevel::Warning,
+            "Search engines are asked not to index this post",
+        ));
+    }
+    if preview.image_url.is_none() {
+        checks.push(SeoCheck::new(
+            SeoLevel::Warning,
+            "Add a featured image so social cards show a picture",
+        ));
+    }
+
+    checks.sort_by_key(|check| match check.level {
+        SeoLevel::Problem => 0,
+        SeoLevel::Warning => 1,
+        SeoLevel::Good => 2,
+    });
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form() -> BlogForm {
+        BlogForm {
+            title: "Getting started with Rust on the web".into(),
+            slug: "rust-on-the-web".into(),
+            content: r#"{"time":1,"blocks":[{"type":"header","data":{"text":"Intro","level":2}},{"type":"paragraph","data":{"text":"Learn <b>Rust</b>&nbsp;and WebAssembly."}}],"version":"2"}"#.into(),
+            ..BlogForm::new()
+        }
+    }
+
+    #[test]
+    fn preview_falls_back_to_post_fields() {
+        let mut form = form();
+        let preview = SeoPreview::from_form(&form);
+        assert_eq!(preview.title, "Getting started with Rust on the web");
+        assert_eq!(preview.description, "Learn Rust and WebAssembly.");
+        assert_eq!(preview.social_title, preview.title);
+        assert_eq!(preview.display_url, "your-site › posts › rust-on-the-web");
+
+        form.excerpt = "An excerpt".into();
+        form.seo.og_title = Some("Share me".into());
+        form.seo.canonical_url = Some("https://example.com/blog/rust/".into());
+        let preview = SeoPreview::from_form(&form);
+        assert_eq!(preview.description, "An excerpt");
+        assert_eq!(preview.social_description, "An excerpt");
+        assert_eq!(preview.social_title, "Share me");
+        assert_eq!(preview.display_url, "example.com › blog › rust");
+    }
+
+    #[test]
+    fn first_paragraph_decodes_entities() {
+        let content = r#"{"time":1,"blocks":[{"type":"paragraph","data":{"text":"<i>Vec&lt;T&gt;</i> &amp; friends&#33;"}}],"version":"2"}"#;
+        assert_eq!(first_paragraph(content), "Vec<T> & friends!");
+    }
+
+    #[test]
+    fn checks_cover_lengths_and_focus_keyword() {
+        let mut form = form();
+        form.seo.focus_keyword = Some("rust".into());
+        form.seo.canonical_url = Some("example.com/rust".into());
+        let checks = seo_checks(&form);
+
+        assert_eq!(checks[0].level, SeoLevel::Problem);
+        assert!(checks[0].message.starts_with("Canonical URL"));
+        let message = |text: &str| checks.iter().find(|c| c.message.contains(text)).unwrap();
+        assert_eq!(message("Title length").level, SeoLevel::Good);
+        assert_eq!(message("Description is short").level, SeoLevel::Warning);
+        assert_eq!(message("in the slug").level, SeoLevel::Good);
+        assert_eq!(message("in the first paragraph").level, SeoLevel::Good);
+        assert_eq!(message("featured image").level, SeoLevel::Warning);
+
+        form.seo.focus_keyword = Some("web assembly".into());
+        let checks = seo_checks(&form);
+        assert!(checks
+            .iter()
+            .any(|c| c.message == "Focus keyword is missing from the slug"));
+    }
+
+    #[test]
+    fn truncate_breaks_on_words() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("one two three four", 12), "one two…");
+    }
+}
@@ -0,0 +1,210 @@
+use dioxus::prelude::*;
+
+use super::form::BlogForm;
+use super::seo::{seo_checks, truncate, SeoLevel, SeoPreview, DESCRIPTION_LENGTH, TITLE_LENGTH};
+use crate::components::AppInput;
+use crate::hooks::OxForm;
+use crate::ui::shadcn::Checkbox;
+
+const TEXTAREA_CLASS: &str = "w-full h-20 resize-none rounded-md border border-border/70 bg-transparent px-4 py-3 text-sm text-foreground placeholder:text-muted-foreground transition-colors duration-200 focus:border-ring focus:ring-2 focus:ring-ring/40";
+
+fn counter_class(length: usize, max: usize) -> &'static str {
+    if length > max {
+        "text-xs text-amber-600"
+    } else {
+        "text-xs text-muted-foreground"
+    }
+}
+
+/// SEO card of the blog form: meta, canonical, robots and social fields with a live
+/// search-result preview, social card previews and checks.
+#[component]
+pub fn SeoPanel(form: Signal<OxForm<BlogForm>>) -> Element {
+    let data = form.read().data.clone();
+    let field = |name: &str| {
+        form.read()
+            .get_field(name)
+            .map(|field| field.value.clone())
+            .unwrap_or_default()
+    };
+    let error = |name: &str| form.read().get_field(name).and_then(|field| field.error.clone());
+    let preview = SeoPreview::from_form(&data);
+    let checks = seo_checks(&data);
+    let title_length = preview.title.chars().count();
+    let description_length = preview.description.chars().count();
+    let meta_description = field("meta_description");
+    let og_description = field("og_description");
+    let meta_description_error = error("meta_description");
+    let og_description_error = error("og_description");
+    let host = preview
+        .display_url
+        .split(" › ")
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let title_max = *TITLE_LENGTH.end();
+    let description_max = *DESCRIPTION_LENGTH.end();
+
+    rsx! {
+        div { class: "rounded-xl border border-border/70 bg-transparent",
+            div { class: "px-6 pt-6",
+                h2 { class: "text-lg font-semibold", "Search & Social" }
+                p { class: "text-sm text-muted-foreground",
+                    "How the post appears in search results and when shared. Empty fields fall back to the title, excerpt and featured image."
+                }
+            }
+            div { class: "px-6 py-6 space-y-6",
+                AppInput {
+                    name: "focus_keyword",
+                    form,
+                    label: "Focus keyword",
+                    placeholder: "Phrase this post should rank for",
+                }
+                div { class: "space-y-1",
+                    AppInput {
+                        name: "meta_title",
+                        form,
+                        label: "Meta title",
+                        placeholder: data.title.clone(),
+                    }
+                    p { class: counter_class(title_length, title_max),
+                        "{title_length} / {title_max} characters"
+                    }
+                }
+                div { class: "space-y-2",
+                    label { class: "block text-sm font-medium text-foreground", "Meta description" }
+                    textarea {
+                        class: TEXTAREA_CLASS,
+                        placeholder: "Defaults to the excerpt or first paragraph",
+                        value: meta_description,
+                        oninput: move |event| {
+                            form.write().update_field("meta_description", event.value());
+                        },
+                    }
+                    if let Some(error) = meta_description_error {
+                        p { class: "text-sm text-destructive", "{error}" }
+                    }
+                    p { class: counter_class(description_length, description_max),
+                        "{description_length} / {description_max} characters"
+                    }
+                }
+                AppInput {
+                    name: "canonical_url",
+                    form,
+                    label: "Canonical URL",
+                    r#type: "url",
+                    placeholder: "https://example.com/original-post",
+                }
+                div { class: "space-y-2",
+                    label { class: "block text-sm font-medium text-foreground", "Robots" }
+                    div { class: "flex flex-wrap gap-6",
+                        label { class: "flex items-center gap-2 cursor-pointer text-sm",
+                            Checkbox {
+                                checked: data.seo.noindex,
+                                onchange: move |checked: bool| {
+                                    form.write().update_field("noindex", checked.to_string());
+                                },
+                            }
+                            "noindex"
+                        }
+                        label { class: "flex items-center gap-2 cursor-pointer text-sm",
+                            Checkbox {
+                                checked: data.seo.nofollow,
+                                onchange: move |checked: bool| {
+                                    form.write().update_field("nofollow", checked.to_string());
+                                },
+                            }
+                            "nofollow"
+                        }
+                    }
+                }
+                AppInput {
+                    name: "og_title",
+                    form,
+                    label: "Social title",
+                    placeholder: preview.title.clone(),
+                }
+                div { class: "space-y-2",
+                    label { class: "block text-sm font-medium text-foreground", "Social description" }
+                    textarea {
+                        class: TEXTAREA_CLASS,
+                        placeholder: "Defaults to the meta description",
+                        value: og_description,
+                        oninput: move |event| {
+                            form.write().update_field("og_description", event.value());
+                        },
+                    }
+                    if let Some(error) = og_description_error {
+                        p { class: "text-sm text-destructive", "{error}" }
+                    }
+                }
+
+                div { class: "h-px bg-border/60" }
+
+                // Search result preview
+                div { class: "space-y-2",
+                    p { class: "text-xs font-medium uppercase tracking-wide text-muted-foreground", "Search result" }
+                    div { class: "rounded-md border border-border/70 bg-white p-4 dark:bg-zinc-950",
+                        p { class: "text-xs text-zinc-600 dark:text-zinc-400 truncate", "{preview.display_url}" }
+                        p { class: "text-lg leading-snug text-[#1a0dab] dark:text-[#8ab4f8]",
+                            {truncate(&preview.title, title_max)}
+                        }
+                        p { class: "text-sm text-zinc-700 dark:text-zinc-300",
+                            {truncate(&preview.description, description_max)}
+                        }
+                    }
+                }
+
+                // Social card previews
+                div { class: "grid grid-cols-1 gap-4 md:grid-cols-2",
+                    div { class: "space-y-2",
+                        p { class: "text-xs font-medium uppercase tracking-wide text-muted-foreground", "X / Twitter" }
+                        div { class: "overflow-hidden rounded-2xl border border-border/70",
+                            if let Some(url) = preview.image_url.clone() {
+                                img { class: "aspect-[1.91/1] w-full object-cover", src: url }
+                            } else {
+                                div { class: "aspect-[1.91/1] w-full bg-muted" }
+                            }
+                            div { class: "px-3 py-2",
+                                p { class: "text-xs text-muted-foreground truncate", "{host}" }
+                                p { class: "text-sm font-medium truncate", "{preview.social_title}" }
+                                p { class: "text-xs text-muted-foreground line-clamp-2", "{preview.social_description}" }
+                            }
+                        }
+                    }
+                    div { class: "space-y-2",
+                        p { class: "text-xs font-medium uppercase tracking-wide text-muted-foreground", "Open Graph" }
+                        div { class: "overflow-hidden rounded-md border border-border/70",
+                            if let Some(url) = preview.image_url.clone() {
+                                img { class: "aspect-[1.91/1] w-full object-cover", src: url }
+                            } else {
+                                div { class: "aspect-[1.91/1] w-full bg-muted" }
+                            }
+                            div { class: "bg-muted/40 px-3 py-2",
+                                p { class: "text-xs uppercase text-muted-foreground truncate", "{host}" }
+                                p { class: "text-sm font-semibold truncate", "{preview.social_title}" }
+                                p { class: "text-xs text-muted-foreground line-clamp-1", "{preview.social_description}" }
+                            }
+                        }
+                    }
+                }
+
+                // Checks
+                ul { class: "space-y-1 text-sm",
+                    for check in checks {
+                        li { class: "flex items-start gap-2",
+                            span {
+                                class: match check.level {
+                                    SeoLevel::Good => "mt-1.5 h-2 w-2 shrink-0 rounded-full bg-green-500",
+                                    SeoLevel::Warning => "mt-1.5 h-2 w-2 shrink-0 rounded-full bg-amber-500",
+                                    SeoLevel::Problem => "mt-1.5 h-2 w-2 shrink-0 rounded-full bg-red-500",
+                                },
+                            }
+                            span { class: "text-muted-foreground", "{check.message}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -4,10 +4,10 @@ use super::form::{use_categories_form, CategoryForm};
 use crate::components::{
     AppInput, ColorPicker, ConfirmDialog, ImageEditorModal, MediaUploadItem, MediaUploadZone,
 };
-use crate::hooks::OxForm;
+use crate::hooks::{use_server_field_errors, OxForm};
 use crate::router::Route;
 use crate::store::{
-    use_categories, use_image_editor, use_media, MediaReference, MediaUploadPayload,
+    use_categories, use_image_editor, use_media, AppError, MediaReference, MediaUploadPayload,
 };
 use crate::ui::custom::AppPortal;
 use crate::ui::shadcn::{
@@ -25,6 +25,9 @@ pub struct CategoryFormContainerProps {
     pub title: Option<String>,
     #[props(default)]
     pub submit_label: Option<String>,
+    /// Error of the store frame `on_submit` writes to; field errors are shown inline.
+    #[props(default)]
+    pub error: Option<AppError>,
 }
 
 #[component]
@@ -34,6 +37,7 @@ pub fn CategoryFormContainer(props: CategoryFormContainerProps) -> Element {
     let reset_template = initial_category_form.clone();
     let category_form_hook = use_categories_form(initial_category_form);
     let mut form = category_form_hook.form;
+    use_server_field_errors(form, props.error.clone());
     let mut reset_dialog_open = use_signal(|| false);
     let is_form_dirty = form.read().is_dirty();
     let cats_state = use_categories();
@@ -132,6 +136,7 @@ pub fn CategoryFormContainer(props: CategoryFormContainerProps) -> Element {
                     reference_type: Some(MediaReference::Category),
                     width: None,
                     height: None,
+                    optimize: true,
                 };
 
                 match media_state.upload(payload).await {
@@ -175,6 +180,7 @@ pub fn CategoryFormContainer(props: CategoryFormContainerProps) -> Element {
                     reference_type: Some(MediaReference::Category),
                     width: None,
                     height: None,
+                    optimize: true,
                 };
 
                 match media_state.upload(payload).await {
@@ -206,6 +206,13 @@ impl OxFormModel for CategoryForm {
         map
     }
 
+    fn server_field(name: &str) -> &str {
+        match name {
+            "is_active" => "active",
+            other => other,
+        }
+    }
+
     fn update_field(&mut self, name: String, value: &str) {
         match name.as_str() {
             "name" => self.name = value.to_string(),
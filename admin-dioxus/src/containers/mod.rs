@@ -2,6 +2,7 @@ mod auth_guard;
 mod blog_form;
 mod category_form;
 mod nav_bar;
+mod series_form;
 mod tag_form;
 mod user_form;
 
@@ -9,5 +10,6 @@ pub use auth_guard::*;
 pub use blog_form::*;
 pub use category_form::*;
 pub use nav_bar::*;
+pub use series_form::*;
 pub use tag_form::*;
 pub use user_form::*;
@@ -4,6 +4,7 @@ use hmziq_dioxus_free_icons::Icon;
 
 use crate::components::{Sidebar, UserAvatar};
 use crate::config::DarkMode;
+use crate::hooks::use_outbox_sync;
 use crate::{router::Route, store::use_auth, utils::persist};
 
 #[component]
@@ -12,6 +13,7 @@ pub fn NavBarContainer() -> Element {
     let auth_user = auth_store.user.read();
     let mut sidebar_open = use_signal(|| false);
     let mut dark_theme = use_context_provider(|| Signal::new(DarkMode(true)));
+    use_outbox_sync();
 
     use_effect(move || {
         spawn(async move {
@@ -0,0 +1,123 @@
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use validator::{Validate, ValidationError};
+
+use crate::hooks::{OxForm, OxFormModel};
+use crate::store::{Series, SeriesCreatePayload, SeriesEditPayload};
+
+#[derive(Debug, Validate, Clone, PartialEq)]
+pub struct SeriesForm {
+    #[validate(length(min = 1, message = "Name is required"))]
+    pub name: String,
+
+    #[validate(length(min = 1, message = "Slug is required"))]
+    #[validate(custom(function = "validate_slug"))]
+    pub slug: String,
+
+    pub description: String,
+}
+
+fn validate_slug(slug: &str) -> Result<(), ValidationError> {
+    let regex = regex::Regex::new(r"^[a-z0-9-_]+$").unwrap();
+    if !regex.is_match(slug) {
+        return Err(ValidationError::new(
+            "Slug can only contain lowercase letters, numbers, hyphens and underscores",
+        ));
+    }
+    Ok(())
+}
+
+impl SeriesForm {
+    pub fn new() -> Self {
+        SeriesForm {
+            name: String::new(),
+            slug: String::new(),
+            description: String::new(),
+        }
+    }
+
+    pub fn from_series(series: &Series) -> Self {
+        SeriesForm {
+            name: series.name.clone(),
+            slug: series.slug.clone(),
+            description: series.description.clone().unwrap_or_default(),
+        }
+    }
+
+    pub fn sanitize_slug(text: &str) -> String {
+        let text = text.to_lowercase();
+        let text = regex::Regex::new(r"[^\w\s-]")
+            .unwrap()
+            .replace_all(&text, "")
+            .to_string();
+        let text = regex::Regex::new(r"\s+")
+            .unwrap()
+            .replace_all(&text, "-")
+            .to_string();
+        let text = regex::Regex::new(r"-+")
+            .unwrap()
+            .replace_all(&text, "-")
+            .to_string();
+        let text = regex::Regex::new(r"^-+|-+$")
+            .unwrap()
+            .replace_all(&text, "")
+            .to_string();
+        text
+    }
+
+    fn description(&self) -> Option<String> {
+        if self.description.trim().is_empty() {
+            None
+        } else {
+            Some(self.description.clone())
+        }
+    }
+
+    // Convert the form to the backend create payload contract
+    pub fn to_add_payload(&self) -> SeriesCreatePayload {
+        SeriesCreatePayload {
+            name: self.name.clone(),
+            slug: self.slug.clone(),
+            description: self.description(),
+        }
+    }
+
+    // Convert the form to the backend edit payload contract
+    pub fn to_edit_payload(&self) -> SeriesEditPayload {
+        SeriesEditPayload {
+            name: Some(self.name.clone()),
+            slug: Some(self.slug.clone()),
+            description: self.description(),
+        }
+    }
+}
+
+impl OxFormModel for SeriesForm {
+    fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("name".to_string(), self.name.clone());
+        map.insert("slug".to_string(), self.slug.clone());
+        map.insert("description".to_string(), self.description.clone());
+        map
+    }
+
+    fn update_field(&mut self, name: String, value: &str) {
+        match name.as_str() {
+            "name" => self.name = value.to_string(),
+            "slug" => self.slug = value.to_string(),
+            "description" => self.description = value.to_string(),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UseSeriesForm {
+    pub form: Signal<OxForm<SeriesForm>>,
+}
+
+pub fn use_series_form(initial_state: SeriesForm) -> UseSeriesForm {
+    let form_signal: Signal<OxForm<SeriesForm>> = use_signal(move || OxForm::new(initial_state));
+
+    UseSeriesForm { form: form_signal }
+}
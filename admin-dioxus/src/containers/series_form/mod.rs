@@ -0,0 +1,5 @@
+mod form;
+mod series_form;
+
+pub use form::SeriesForm;
+pub use series_form::SeriesFormContainer;
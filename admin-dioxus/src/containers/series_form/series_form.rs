@@ -0,0 +1,152 @@
+use dioxus::prelude::*;
+
+use super::form::{use_series_form, SeriesForm};
+use crate::components::AppInput;
+use crate::hooks::{use_server_field_errors, OxForm};
+use crate::router::Route;
+use crate::store::AppError;
+use crate::ui::custom::AppPortal;
+use crate::ui::shadcn::{Button, ButtonSize, ButtonVariant};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct SeriesFormContainerProps {
+    #[props(default)]
+    pub initial: Option<SeriesForm>,
+    pub on_submit: EventHandler<SeriesForm>,
+    #[props(default)]
+    pub title: Option<String>,
+    #[props(default)]
+    pub submit_label: Option<String>,
+    /// Error of the store frame `on_submit` writes to; field errors are shown inline.
+    #[props(default)]
+    pub error: Option<AppError>,
+}
+
+#[component]
+pub fn SeriesFormContainer(props: SeriesFormContainerProps) -> Element {
+    let nav = use_navigator();
+    let initial_series_form = props.initial.clone().unwrap_or_else(SeriesForm::new);
+    let reset_template = initial_series_form.clone();
+    let series_form_hook = use_series_form(initial_series_form);
+    let mut form = series_form_hook.form;
+    use_server_field_errors(form, props.error.clone());
+    let mut reset_dialog_open = use_signal(|| false);
+    let is_form_dirty = form.read().is_dirty();
+
+    rsx! {
+        div {
+            if let Some(t) = props.title.clone() { h1 { class: "sr-only", {t} } }
+
+            div { class: "grid grid-cols-1 gap-10 lg:grid-cols-3",
+                div { class: "lg:col-span-2 space-y-8",
+                    div { class: "rounded-xl border border-border/70 bg-transparent",
+                        div { class: "px-6 py-6",
+                            h2 { class: "text-lg font-semibold", "Series details" }
+                            p { class: "text-sm text-muted-foreground", "Name the series and describe what readers will learn across its parts." }
+                        }
+                        div { class: "px-6 py-6 space-y-6",
+                            AppInput { name: "name", form, label: "Name", placeholder: "e.g. Building a CLI in Rust" }
+
+                            div { class: "h-px bg-border/60" }
+
+                            div { class: "space-y-3",
+                                div { class: "flex items-center justify-between",
+                                    label { class: "block text-sm font-medium text-foreground", "Slug" }
+                                    Button { variant: ButtonVariant::Outline, size: ButtonSize::Sm,
+                                        onclick: move |_| {
+                                            let name_value = form.peek().get_field("name").unwrap().value.clone();
+                                            if !name_value.is_empty() {
+                                                let sanitized = SeriesForm::sanitize_slug(&name_value);
+                                                form.write().update_field("slug", sanitized);
+                                            }
+                                        },
+                                        "Generate from name"
+                                    }
+                                }
+                                AppInput { form, name: "slug", r#type: "text", placeholder: "building-a-cli-in-rust" }
+                                div { class: "flex items-center gap-2",
+                                    span { class: "text-xs text-muted-foreground", "URL preview:" }
+                                    code { class: "rounded border border-border/60 bg-transparent px-1.5 py-0.5 text-xs text-muted-foreground",
+                                        {
+                                            let slug = form.read().data.slug.clone();
+                                            let safe = if slug.trim().is_empty() { "your-series-slug".to_string() } else { slug };
+                                            format!("/series/{}", safe)
+                                        }
+                                    }
+                                }
+                            }
+
+                            div { class: "h-px bg-border/60" }
+
+                            div { class: "space-y-3",
+                                label { class: "block text-sm font-medium text-foreground", "Description" }
+                                textarea {
+                                    class: "w-full h-32 resize-none rounded-md border border-border/70 bg-transparent px-4 py-3 text-sm text-foreground placeholder:text-muted-foreground transition-colors duration-200 focus:border-ring focus:ring-2 focus:ring-ring/40",
+                                    placeholder: "What does this series cover, and who is it for?",
+                                    value: form.read().data.description.clone(),
+                                    oninput: move |event| { form.write().update_field("description", event.value()); }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "space-y-8 lg:sticky lg:top-28 h-fit",
+                    div { class: "flex items-start gap-3 rounded-lg border border-border/60 bg-transparent p-5",
+                        div { class: "mt-0.5 h-4 w-4 rounded-full border border-border/40" }
+                        div { class: "space-y-1",
+                            p { class: "text-sm font-medium text-foreground", "Adding posts" }
+                            p { class: "text-sm text-muted-foreground", "Posts join a series from the post editor, where you also set their part number." }
+                        }
+                    }
+
+                    div { class: "flex gap-3 pt-4",
+                        Button { class: "flex-1 w-auto", variant: ButtonVariant::Outline,
+                            onclick: move |_| {
+                                if form.peek().is_dirty() {
+                                    reset_dialog_open.set(true);
+                                } else {
+                                    nav.push(Route::SeriesListScreen {});
+                                }
+                            },
+                            {if is_form_dirty { "Reset" } else { "Cancel" }}
+                        }
+                        Button { class: "flex-1 w-auto",
+                            onclick: move |_| {
+                                let submit = props.on_submit;
+                                form.write().on_submit(move |val| { submit.call(val); });
+                            },
+                            {props.submit_label.clone().unwrap_or_else(|| "Save Series".to_string())}
+                        }
+                    }
+                }
+            }
+        }
+        if reset_dialog_open() {
+            AppPortal {
+                class: "bg-black/20 backdrop-blur-sm flex items-center justify-center px-4",
+                div { class: "w-full max-w-md rounded-lg border border-border/60 bg-background p-6 shadow-lg",
+                    div { class: "space-y-2",
+                        h2 { class: "text-lg font-semibold", "Reset form?" }
+                        p { class: "text-sm text-muted-foreground", "All changes will be cleared and the form will return to its default state." }
+                    }
+                    div { class: "mt-6 flex justify-end gap-2",
+                        Button { variant: ButtonVariant::Outline,
+                            onclick: move |_| {
+                                reset_dialog_open.set(false);
+                            },
+                            "Cancel"
+                        }
+                        Button { variant: ButtonVariant::Destructive,
+                            onclick: move |_| {
+                                form.set(OxForm::new(reset_template.clone()));
+                                reset_dialog_open.set(false);
+                            },
+                            "Reset form"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -158,6 +158,13 @@ impl OxFormModel for TagForm {
         map
     }
 
+    fn server_field(name: &str) -> &str {
+        match name {
+            "is_active" => "active",
+            other => other,
+        }
+    }
+
     fn update_field(&mut self, name: String, value: &str) {
         match name.as_str() {
             "name" => self.name = value.to_string(),
@@ -2,9 +2,9 @@ use dioxus::prelude::*;
 
 use super::form::{use_tag_form, TagForm};
 use crate::components::{AppInput, ColorPicker, TagBadge, TagSize};
-use crate::hooks::OxForm;
+use crate::hooks::{use_server_field_errors, OxForm};
 use crate::router::Route;
-use crate::store::Tag;
+use crate::store::{AppError, Tag};
 use crate::ui::custom::AppPortal;
 use crate::ui::shadcn::{Button, ButtonSize, ButtonVariant, Checkbox};
 use crate::utils::colors::get_contrast_yiq;
@@ -18,6 +18,9 @@ pub struct TagFormContainerProps {
     pub title: Option<String>,
     #[props(default)]
     pub submit_label: Option<String>,
+    /// Error of the store frame `on_submit` writes to; field errors are shown inline.
+    #[props(default)]
+    pub error: Option<AppError>,
 }
 
 #[component]
@@ -27,6 +30,7 @@ pub fn TagFormContainer(props: TagFormContainerProps) -> Element {
     let reset_template = initial_tag_form.clone();
     let tag_form_hook = use_tag_form(initial_tag_form);
     let mut form = tag_form_hook.form;
+    use_server_field_errors(form, props.error.clone());
     let mut reset_dialog_open = use_signal(|| false);
     let is_form_dirty = form.read().is_dirty();
 
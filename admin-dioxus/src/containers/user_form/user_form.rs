@@ -8,9 +8,9 @@ use crate::components::{
     AppInput, ConfirmDialog, ImageEditorModal, MediaPreviewItem, MediaUploadItem, MediaUploadZone,
     PasswordInput,
 };
-use crate::hooks::OxForm;
+use crate::hooks::{use_server_field_errors, OxForm};
 use crate::router::Route;
-use crate::store::{use_image_editor, use_media, MediaReference, MediaUploadPayload};
+use crate::store::{use_image_editor, use_media, AppError, MediaReference, MediaUploadPayload};
 use crate::ui::custom::AppPortal;
 use crate::ui::shadcn::{Button, ButtonVariant, Checkbox};
 
@@ -23,6 +23,9 @@ pub struct UserFormContainerProps {
     pub title: Option<String>,
     #[props(default)]
     pub submit_label: Option<String>,
+    /// Error of the store frame `on_submit` writes to; field errors are shown inline.
+    #[props(default)]
+    pub error: Option<AppError>,
 }
 
 #[component]
@@ -32,6 +35,7 @@ pub fn UserFormContainer(props: UserFormContainerProps) -> Element {
     let reset_template = initial_user_form.clone();
     let user_form_hook = use_user_form(initial_user_form);
     let mut form = user_form_hook.form;
+    use_server_field_errors(form, props.error.clone());
     let mut reset_dialog_open = use_signal(|| false);
     let is_form_dirty = form.read().is_dirty();
     let is_update = form.read().data.is_update;
@@ -78,6 +82,7 @@ pub fn UserFormContainer(props: UserFormContainerProps) -> Element {
                     reference_type: Some(MediaReference::User),
                     width: None,
                     height: None,
+                    optimize: true,
                 };
 
                 match media_state.upload(payload).await {
@@ -106,6 +111,7 @@ pub fn UserFormContainer(props: UserFormContainerProps) -> Element {
                 reference_type: Some(MediaReference::User),
                 width: None,
                 height: None,
+                optimize: true,
             };
 
             match media_state.upload(payload).await {
@@ -1,5 +1,5 @@
 mod use_form;
-pub use use_form::{OxForm, OxFormModel};
+pub use use_form::{use_server_field_errors, OxForm, OxFormModel};
 
 mod use_previous;
 pub use use_previous::use_previous;
@@ -24,6 +24,9 @@ pub use use_state_frame_toast::{
 mod use_list_screen;
 pub use use_list_screen::*;
 
+mod use_outbox_sync;
+pub use use_outbox_sync::use_outbox_sync;
+
 use dioxus::prelude::*;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
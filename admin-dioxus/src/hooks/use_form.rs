@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
 use dioxus::logger::tracing;
+use dioxus::prelude::*;
 use validator::{Validate, ValidationErrors};
 
+use crate::store::AppError;
+
 #[derive(Debug, Clone)]
 pub struct OxFieldFrame {
     pub name: String,
@@ -41,6 +44,12 @@ impl OxFieldFrame {
 pub trait OxFormModel: Validate + Clone + PartialEq {
     fn to_map(&self) -> HashMap<String, String>;
     fn update_field(&mut self, name: String, value: &str);
+
+    /// Form field that shows server errors reported for the payload field `name`.
+    /// Override where the form and payload names differ (e.g. `is_active` vs `active`).
+    fn server_field(name: &str) -> &str {
+        name
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -51,6 +60,9 @@ pub struct OxForm<T: OxFormModel> {
     pub active_field: Option<String>,
 
     pub submit_count: u32,
+    /// Messages from the last failed submit, keyed by form field. A field's entry is
+    /// dropped once it is edited; client-side validation errors take precedence.
+    server_errors: HashMap<String, String>,
 }
 
 impl<T: OxFormModel> OxForm<T> {
@@ -68,6 +80,7 @@ impl<T: OxFormModel> OxForm<T> {
             active_field: None,
             has_errors: false,
             submit_count: 0,
+            server_errors: HashMap::new(),
         }
     }
 
@@ -77,6 +90,7 @@ impl<T: OxFormModel> OxForm<T> {
             field.value = value.clone();
             field.dirty = field.value != field.default_value;
         }
+        self.server_errors.remove(name);
 
         // Update the underlying data model
         self.data.update_field(name.to_string(), &value);
@@ -129,7 +143,7 @@ impl<T: OxFormModel> OxForm<T> {
         }
 
         // Validate using validator crate
-        match self.data.validate() {
+        let valid = match self.data.validate() {
             Ok(()) => {
                 self.has_errors = false;
                 true
@@ -139,6 +153,33 @@ impl<T: OxFormModel> OxForm<T> {
                 self.has_errors = true;
                 false
             }
+        };
+        self.apply_server_errors();
+        valid
+    }
+
+    /// Show per-field messages from a failed submit (see [`AppError::field_errors`]).
+    /// Replaces any previous server errors; fields the form doesn't have are ignored.
+    pub fn set_server_errors(&mut self, errors: HashMap<String, String>) {
+        // Server errors only ever answer a submit.
+        if self.submit_count == 0 {
+            return;
+        }
+        self.server_errors = errors
+            .into_iter()
+            .map(|(name, message)| (T::server_field(&name).to_string(), message))
+            .filter(|(name, _)| self.fields.contains_key(name))
+            .collect();
+        self.validate();
+    }
+
+    fn apply_server_errors(&mut self) {
+        for (name, message) in &self.server_errors {
+            if let Some(field) = self.fields.get_mut(name) {
+                if field.error.is_none() {
+                    field.set_error(Some(message.clone()));
+                }
+            }
         }
     }
 
@@ -159,6 +200,7 @@ impl<T: OxFormModel> OxForm<T> {
 
     pub fn on_submit(&mut self, callback: impl Fn(T)) {
         self.submit_count += 1;
+        self.server_errors.clear();
 
         tracing::info!("pub fn on_submit(&");
 
@@ -167,3 +209,78 @@ impl<T: OxFormModel> OxForm<T> {
         }
     }
 }
+
+/// Push field errors from a failed submit into `form` whenever `error` changes.
+/// Pass the `error` of the store frame the form submits to.
+pub fn use_server_field_errors<T: OxFormModel + 'static>(
+    mut form: Signal<OxForm<T>>,
+    error: Option<AppError>,
+) {
+    use_effect(use_reactive!(|(error,)| {
+        let errors = error.map(|e| e.field_errors()).unwrap_or_default();
+        if errors.is_empty() && form.peek().server_errors.is_empty() {
+            return;
+        }
+        form.write().set_server_errors(errors);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Validate, Clone, PartialEq)]
+    struct SlugForm {
+        #[validate(length(min = 1, message = "Name is required"))]
+        name: String,
+        slug: String,
+    }
+
+    impl OxFormModel for SlugForm {
+        fn to_map(&self) -> HashMap<String, String> {
+            HashMap::from([
+                ("name".to_string(), self.name.clone()),
+                ("slug".to_string(), self.slug.clone()),
+            ])
+        }
+
+        fn update_field(&mut self, name: String, value: &str) {
+            match name.as_str() {
+                "name" => self.name = value.to_string(),
+                "slug" => self.slug = value.to_string(),
+                _ => {}
+            }
+        }
+    }
+
+    fn error(form: &OxForm<SlugForm>, name: &str) -> Option<String> {
+        form.get_field(name).and_then(|field| field.error.clone())
+    }
+
+    #[test]
+    fn server_errors_show_until_the_field_is_edited() {
+        let mut form = OxForm::new(SlugForm {
+            name: "Rust".into(),
+            slug: "rust".into(),
+        });
+        let taken = HashMap::from([
+            ("slug".to_string(), "Slug already taken".to_string()),
+            ("name".to_string(), "Name is reserved".to_string()),
+            ("unknown".to_string(), "ignored".to_string()),
+        ]);
+
+        form.set_server_errors(taken.clone());
+        assert_eq!(error(&form, "slug"), None, "ignored before a submit");
+
+        form.on_submit(|_| {});
+        form.set_server_errors(taken);
+        assert_eq!(error(&form, "slug").as_deref(), Some("Slug already taken"));
+
+        form.update_field("name", String::new());
+        assert_eq!(error(&form, "name").as_deref(), Some("Name is required"));
+        assert_eq!(error(&form, "slug").as_deref(), Some("Slug already taken"));
+
+        form.update_field("slug", "rust-lang".into());
+        assert_eq!(error(&form, "slug"), None);
+    }
+}
@@ -0,0 +1,26 @@
+use dioxus::prelude::*;
+
+use crate::store::{use_auth, use_outbox};
+
+/// Restore the persisted outbox and replay it once a user is signed in and whenever the
+/// browser fires `online`. Mount once, in the app shell.
+pub fn use_outbox_sync() {
+    use_hook(|| use_outbox().restore());
+
+    use_effect(move || {
+        if use_auth().user.read().is_some() {
+            spawn(async move { use_outbox().replay().await });
+        }
+    });
+
+    use_effect(move || {
+        let mut eval = document::eval("window.addEventListener('online', () => dioxus.send(true))");
+        spawn(async move {
+            while eval.recv::<bool>().await.is_ok() {
+                if use_auth().user.peek().is_some() {
+                    use_outbox().replay().await;
+                }
+            }
+        });
+    });
+}
@@ -769,6 +769,7 @@ pub fn RichTextEditor(props: RichTextEditorProps) -> Element {
                     reference_type: Some(MediaReference::Post),
                     width: None,
                     height: None,
+                    optimize: true,
                 };
 
                 match media_state_ref.upload(payload).await {
@@ -846,6 +847,7 @@ pub fn RichTextEditor(props: RichTextEditorProps) -> Element {
                 reference_type: Some(MediaReference::Post),
                 width: None,
                 height: None,
+                optimize: true,
             };
 
             match media_state_ref.upload(payload).await {
@@ -125,42 +125,7 @@ pub fn sanitize_html(input: &str) -> String {
     builder.clean(input).to_string()
 }
 
-/// Sanitizes a URL to ensure it's safe for use in links or embeds.
-pub fn sanitize_url(url: &str) -> Option<String> {
-    let trimmed = url.trim();
-
-    // Reject javascript:, data:, vbscript:, and other dangerous protocols
-    let lower = trimmed.to_lowercase();
-    if lower.starts_with("javascript:")
-        || lower.starts_with("data:")
-        || lower.starts_with("vbscript:")
-        || lower.starts_with("file:")
-    {
-        return None;
-    }
-
-    // Accept http, https, mailto
-    if lower.starts_with("http://")
-        || lower.starts_with("https://")
-        || lower.starts_with("mailto:")
-        || lower.starts_with("//")
-    {
-        return Some(trimmed.to_string());
-    }
-
-    // Reject if it looks suspicious (contains control characters, etc.)
-    if trimmed.chars().any(|c| c.is_control()) {
-        return None;
-    }
-
-    // Accept relative URLs
-    if trimmed.starts_with('/') || trimmed.starts_with("./") || trimmed.starts_with("../") {
-        return Some(trimmed.to_string());
-    }
-
-    // Default: prepend https:// if no scheme
-    Some(format!("https://{}", trimmed))
-}
+pub use crate::utils::urls::sanitize_url;
 
 /// Extracts plain text from HTML, stripping all tags.
 pub fn html_to_text(html: &str) -> String {
@@ -243,24 +208,6 @@ mod tests {
         assert!(!output.contains("alert"));
     }
 
-    #[test]
-    fn it_sanitizes_javascript_urls() {
-        let url = "javascript:alert('xss')";
-        assert!(sanitize_url(url).is_none());
-    }
-
-    #[test]
-    fn it_allows_https_urls() {
-        let url = "https://example.com";
-        assert_eq!(sanitize_url(url).unwrap(), "https://example.com");
-    }
-
-    #[test]
-    fn it_prepends_https_to_bare_domains() {
-        let url = "example.com";
-        assert_eq!(sanitize_url(url).unwrap(), "https://example.com");
-    }
-
     #[test]
     fn it_validates_hex_colors() {
         assert!(is_safe_color("#ff0000"));
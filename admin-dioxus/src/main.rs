@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 
 use crate::components::sonner::SonnerToaster;
+use crate::components::{DevtoolsDrawer, RollbackToasts};
 use crate::utils::persist;
 
 pub mod components;
@@ -20,6 +21,8 @@ pub mod utils;
 use utils::js_bridge;
 
 fn main() {
+    // Native builds keep SDK storage (the offline outbox) in the app's data directory.
+    dioxus_storage::set_dir!();
     dioxus::launch(App);
 }
 
@@ -29,6 +32,9 @@ const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 fn App() -> Element {
     // let toast = use_context_provider(|| Signal::new(ToastManager::default()));
 
+    // Resolve the API profile (served config, persisted choice, ?api=) before any request goes out.
+    let runtime_config = use_resource(config::load_runtime_config);
+
     // Initialize document theme from persistent storage on app mount.
     use_effect(|| {
         let stored = persist::get_theme();
@@ -60,7 +66,11 @@ fn App() -> Element {
         }
         // document::Link { rel: "stylesheet", href: asset!("/assets/tailwind.css") }
         SonnerToaster {
-            Router::<crate::router::Route> {}
+            RollbackToasts {}
+            if runtime_config.read().is_some() {
+                Router::<crate::router::Route> {}
+            }
+            DevtoolsDrawer {}
         }
     }
 }
@@ -10,12 +10,19 @@ use crate::screens::CategoriesEditScreen;
 use crate::screens::CategoriesListScreen;
 use crate::screens::HomeScreen;
 use crate::screens::LoginScreen;
+use crate::screens::MediaDuplicatesScreen;
 use crate::screens::MediaListScreen;
 use crate::screens::MediaUploadScreen;
+use crate::screens::OutboxScreen;
 use crate::screens::PostsAddScreen;
+use crate::screens::PostsCalendarScreen;
 use crate::screens::PostsEditScreen;
 use crate::screens::PostsListScreen;
+use crate::screens::PostsRevisionsScreen;
 use crate::screens::PostsViewScreen;
+use crate::screens::SeriesAddScreen;
+use crate::screens::SeriesEditScreen;
+use crate::screens::SeriesListScreen;
 use crate::screens::SonnerDemoScreen;
 use crate::screens::TagsAddScreen;
 use crate::screens::TagsEditScreen;
@@ -40,8 +47,12 @@ pub enum Route {
 
     #[route("/posts/add")]
     PostsAddScreen {},
+    #[route("/posts/calendar")]
+    PostsCalendarScreen {},
     #[route("/posts/:id/edit")]
     PostsEditScreen { id: i32 },
+    #[route("/posts/:id/revisions")]
+    PostsRevisionsScreen { id: i32 },
     #[route("/posts/:id")]
     PostsViewScreen { id: i32 },
     #[route("/posts")]
@@ -54,6 +65,13 @@ pub enum Route {
     #[route("/categories/:id/edit")]
     CategoriesEditScreen { id: i32 },
 
+    #[route("/series/add")]
+    SeriesAddScreen {},
+    #[route("/series/:id/edit")]
+    SeriesEditScreen { id: i32 },
+    #[route("/series")]
+    SeriesListScreen {},
+
     #[route("/tags/add")]
     TagsAddScreen {},
     #[route("/tags/:id/edit")]
@@ -63,6 +81,8 @@ pub enum Route {
 
     #[route("/media/upload")]
     MediaUploadScreen {},
+    #[route("/media/duplicates")]
+    MediaDuplicatesScreen {},
     #[route("/media")]
     MediaListScreen {},
 
@@ -73,6 +93,9 @@ pub enum Route {
     #[route("/users")]
     UsersListScreen {},
 
+    #[route("/outbox")]
+    OutboxScreen {},
+
 
 
     #[route("/demo/sonner")]
@@ -56,6 +56,7 @@ pub fn CategoriesAddScreen() -> Element {
             // Content: render reusable form component; submission handled here
             div { class: "container mx-auto px-4 py-10 md:py-12",
                     CategoryFormContainer {
+                        error: categories.add.read().error.clone(),
                         title: Some("New Category".to_string()),
                         submit_label: Some("Create Category".to_string()),
                         on_submit: move |val: CategoryForm| {
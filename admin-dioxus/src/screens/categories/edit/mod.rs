@@ -107,6 +107,7 @@ pub fn CategoriesEditScreen(id: i32) -> Element {
                     FormTwoColumnSkeleton {}
                 } else if let Some(initial) = initial_form.clone() {
                     CategoryFormContainer {
+                        error: categories.edit.read().get(&id).and_then(|frame| frame.error.clone()),
                         title: Some("Edit Category".to_string()),
                         submit_label: Some("Save Changes".to_string()),
                         initial: Some(initial.clone()),
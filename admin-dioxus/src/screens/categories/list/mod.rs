@@ -1,12 +1,15 @@
 use dioxus::prelude::*;
+use std::collections::HashMap;
 
 use crate::components::{
-    DataTableScreen, HeaderColumn, ListEmptyState, ListToolbarProps, PageHeaderProps,
-    SkeletonCellConfig, SkeletonTableRows,
+    BulkOperationsBar, DataTableScreen, HeaderColumn, ListEmptyState, ListToolbarProps,
+    PageHeaderProps, SkeletonCellConfig, SkeletonTableRows,
 };
 use crate::hooks::{use_list_screen_with_handlers, ListScreenConfig};
 use crate::router::Route;
-use crate::store::{use_categories, CategoriesListQuery, Category, ListQuery, ListStore};
+use crate::store::{
+    use_categories, BulkOperation, CategoriesListQuery, Category, ListQuery, ListStore,
+};
 use crate::types::Order;
 use crate::ui::shadcn::{
     Badge, BadgeVariant, Button, ButtonVariant, Checkbox, DropdownMenu, DropdownMenuContent,
@@ -156,40 +159,23 @@ pub fn CategoriesListScreen() -> Element {
                     None => "All".to_string(),
                 },
                 on_status_select: EventHandler::new(handle_status_select),
+                show_status: true,
             }),
             on_prev: move |_| { handlers.handle_prev.call(current_page); },
             on_next: move |_| { handlers.handle_next.call(current_page); },
-            // Render selection actions between toolbar and table (below_toolbar slot)
-            below_toolbar: if !selected_ids.read().is_empty() {
-                Some(rsx! {
-                    div { class: "w-full flex items-center justify-between bg-transparent border border-zinc-200 dark:border-zinc-800 rounded-md px-4 py-3 shadow-sm",
-                        span { class: "text-sm text-muted-foreground", "{selected_ids.read().len()} selected" }
-                        div { class: "flex items-center gap-2",
-                            Button { variant: ButtonVariant::Outline, class: "h-8",
-                                onclick: {
-                                    let mut selected_ids = selected_ids;
-                                    move |_| { selected_ids.set(Vec::new()); }
-                                },
-                                "Activate"
-                            }
-                            Button { variant: ButtonVariant::Outline, class: "h-8",
-                                onclick: {
-                                    let mut selected_ids = selected_ids;
-                                    move |_| { selected_ids.set(Vec::new()); }
-                                },
-                                "Deactivate"
-                            }
-                            Button { variant: ButtonVariant::Outline, class: "h-8 text-red-600 border-red-200 dark:border-red-800 dark:hover:bg-red-950/20 hover:bg-red-50",
-                                onclick: {
-                                    let mut selected_ids = selected_ids;
-                                    move |_| { selected_ids.set(Vec::new()); }
-                                },
-                                "Delete"
-                            }
-                        }
-                    }
-                })
-            } else { None },
+            // Bulk actions for the selection, plus progress of a running bulk job
+            below_toolbar: Some(rsx! {
+                BulkOperationsBar {
+                    selected_ids,
+                    operations: vec![
+                        BulkOperation::CategoryActivate,
+                        BulkOperation::CategoryDeactivate,
+                        BulkOperation::CategoryDelete,
+                    ],
+                    names: categories.iter().map(|category| (category.id, category.name.clone())).collect::<HashMap<_, _>>(),
+                    on_complete: move |_| list_state.trigger_reload(),
+                }
+            }),
             // Table body content only - headers are now handled by DataTableScreen
             if categories.is_empty() {
                 if list_loading && !has_data {
@@ -8,8 +8,8 @@ use crate::screens::login::mouse_tracking_card::MouseTrackingCard;
 use crate::ui::shadcn::Button;
 use crate::{
     components::{
-        AnimatedGridBackground, AnimatedGridCircles, AppInput, ErrorDetails, ErrorDetailsVariant,
-        GridContext,
+        AnimatedGridBackground, AnimatedGridCircles, AppInput, EnvironmentSwitcher, ErrorDetails,
+        ErrorDetailsVariant, GridContext,
     },
     store::use_auth,
 };
@@ -91,6 +91,7 @@ pub fn LoginScreen() -> Element {
                             span { "Login" }
                         }
                     }
+                    EnvironmentSwitcher { class: "mt-4" }
                     p { class: "text-sm text-center text-zinc-600 dark:text-zinc-400 mt-4 transition-colors duration-300",
                         "Don't have an account? "
                         a {
@@ -0,0 +1,238 @@
+use dioxus::prelude::*;
+
+use crate::components::{ConfirmDialog, PageHeader};
+use crate::hooks::{use_state_frame_map_toast, StateFrameToastConfig};
+use crate::router::Route;
+use crate::store::{
+    keep_candidate, use_media, wasted_bytes, Media, MediaDuplicateGroup, MediaUsageDetails,
+};
+use crate::ui::shadcn::{Badge, BadgeVariant, Button, ButtonVariant};
+use crate::utils::dates::format_short_date_dt;
+use crate::utils::file_helpers::{format_file_size, is_image};
+
+fn file_name(media: &Media) -> String {
+    media
+        .object_key
+        .rsplit('/')
+        .next()
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+fn reference_count(details: &MediaUsageDetails) -> usize {
+    details.posts.len() + details.categories.len() + details.users.len()
+}
+
+/// Where a copy is used, e.g. `Post "Hello" (featured_image)`.
+fn reference_labels(details: &MediaUsageDetails) -> Vec<String> {
+    let posts = details
+        .posts
+        .iter()
+        .map(|usage| format!("Post \"{}\" ({})", usage.post.title, usage.field_name));
+    let categories = details.categories.iter().map(|usage| {
+        format!(
+            "Category \"{}\" ({})",
+            usage.category.name, usage.field_name
+        )
+    });
+    let users = details
+        .users
+        .iter()
+        .map(|usage| format!("User \"{}\" ({})", usage.user.name, usage.field_name));
+    posts.chain(categories).chain(users).collect()
+}
+
+/// Groups of identical files in the library, each mergeable into a single copy.
+#[component]
+pub fn MediaDuplicatesScreen() -> Element {
+    let media_state = use_media();
+    let nav = use_navigator();
+
+    use_effect(move || {
+        spawn(async move {
+            media_state.duplicates().await;
+        });
+    });
+
+    let frame = media_state.duplicates.read().clone();
+    let groups = frame.data.clone().unwrap_or_default();
+    let group_count = groups.len();
+    let total_wasted: i64 = groups.iter().map(wasted_bytes).sum();
+
+    rsx! {
+        div { class: "min-h-screen bg-transparent text-foreground",
+            PageHeader {
+                title: "Duplicate Media".to_string(),
+                description: "Files uploaded more than once, matched by content hash".to_string(),
+                actions: Some(rsx! {
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        onclick: move |_| { nav.push(Route::MediaListScreen {}); },
+                        "Back to Media"
+                    }
+                }),
+            }
+
+            div { class: "container mx-auto px-4 pb-10 space-y-6",
+                if let Some(message) = frame.error_message() {
+                    div { class: "rounded-md border border-destructive/40 px-3 py-2 text-sm text-destructive", "{message}" }
+                } else if frame.is_loading() && groups.is_empty() {
+                    div { class: "py-6 text-sm text-muted-foreground", "Looking for duplicates..." }
+                } else if groups.is_empty() {
+                    div { class: "rounded-md border border-dashed border-border py-10 text-center text-sm text-muted-foreground",
+                        "No duplicate files found."
+                    }
+                } else {
+                    p { class: "text-sm text-muted-foreground",
+                        "{group_count} files have copies, using {format_file_size(total_wasted)} of extra storage."
+                    }
+                }
+                for group in groups {
+                    DuplicateGroupCard { key: "{group.content_hash}", group }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DuplicateGroupCard(group: MediaDuplicateGroup) -> Element {
+    let media_state = use_media();
+    let default_keep = keep_candidate(&group).map_or(0, |media| media.id);
+    let mut keep_id = use_signal(|| default_keep);
+    let mut confirm_open = use_signal(|| false);
+
+    // References of every copy, so the merge can show what moves
+    let ids: Vec<i32> = group.media.iter().map(|media| media.id).collect();
+    use_effect(use_reactive!(|(ids,)| {
+        for id in ids {
+            spawn(async move { media_state.usage_details(id).await });
+        }
+    }));
+
+    use_state_frame_map_toast(
+        &media_state.merge,
+        keep_id(),
+        StateFrameToastConfig {
+            loading_title: "Merging duplicates...".into(),
+            success_title: Some("Duplicates merged".into()),
+            error_title: Some("Failed to merge duplicates".into()),
+            ..Default::default()
+        },
+    );
+
+    let usage = media_state.usage_details.read().clone();
+    let details = |id: i32| usage.get(&id).and_then(|frame| frame.data.clone());
+    let duplicate_ids: Vec<i32> = group
+        .media
+        .iter()
+        .map(|media| media.id)
+        .filter(|id| *id != keep_id())
+        .collect();
+    let moved_references: usize = group
+        .media
+        .iter()
+        .filter(|media| media.id != keep_id())
+        .map(|media| {
+            details(media.id).map_or(media.usage_count.max(0) as usize, |details| {
+                reference_count(&details)
+            })
+        })
+        .sum();
+    let duplicate_count = duplicate_ids.len();
+    let merging = media_state
+        .merge
+        .read()
+        .get(&keep_id())
+        .is_some_and(|frame| frame.is_loading());
+    let short_hash: String = group.content_hash.chars().take(12).collect();
+
+    rsx! {
+        div { class: "rounded-xl border border-border/70 bg-transparent",
+            div { class: "px-6 pt-6 flex flex-wrap items-center gap-3",
+                h2 { class: "text-base font-semibold", "{group.media.len()} copies" }
+                Badge { variant: BadgeVariant::Secondary,
+                    "{format_file_size(wasted_bytes(&group))} wasted"
+                }
+                code { class: "text-xs text-muted-foreground", "{short_hash}" }
+            }
+            div { class: "px-6 py-4 space-y-2",
+                for media in group.media.clone() {
+                    label {
+                        key: "{media.id}",
+                        class: if media.id == keep_id() { "flex items-start gap-3 rounded-md border border-ring bg-muted/40 p-3 cursor-pointer" } else { "flex items-start gap-3 rounded-md border border-border p-3 cursor-pointer hover:bg-muted/30" },
+                        input {
+                            r#type: "radio",
+                            name: "keep-{group.content_hash}",
+                            class: "mt-1",
+                            checked: media.id == keep_id(),
+                            onchange: move |_| keep_id.set(media.id),
+                        }
+                        if is_image(&media.mime_type) {
+                            img {
+                                src: "{media.file_url}",
+                                alt: "{file_name(&media)}",
+                                class: "w-12 h-12 object-cover rounded border border-zinc-200 dark:border-zinc-700",
+                            }
+                        }
+                        div { class: "flex-1 min-w-0 space-y-1",
+                            div { class: "flex items-center gap-2",
+                                span { class: "text-sm font-medium truncate", "{file_name(&media)}" }
+                                if media.id == keep_id() {
+                                    Badge { class: "text-xs", "Keep" }
+                                }
+                            }
+                            p { class: "text-xs text-muted-foreground",
+                                "{format_file_size(media.size)} · uploaded {format_short_date_dt(&media.created_at)}"
+                            }
+                            match details(media.id) {
+                                Some(details) if reference_count(&details) > 0 => rsx! {
+                                    ul { class: "text-xs text-muted-foreground",
+                                        for reference in reference_labels(&details) {
+                                            li { "{reference}" }
+                                        }
+                                    }
+                                },
+                                Some(_) => rsx! {
+                                    p { class: "text-xs text-muted-foreground", "Not used anywhere" }
+                                },
+                                None => rsx! {
+                                    p { class: "text-xs text-muted-foreground", "Loading usage..." }
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "px-6 pb-6 flex flex-wrap items-center justify-between gap-3",
+                p { class: "text-sm text-muted-foreground",
+                    "Merging moves {moved_references} references to the kept copy and deletes {duplicate_count} files."
+                }
+                Button {
+                    disabled: merging || duplicate_count == 0,
+                    onclick: move |_| confirm_open.set(true),
+                    "Merge"
+                }
+            }
+
+            ConfirmDialog {
+                is_open: confirm_open,
+                title: "Merge duplicate files?".to_string(),
+                description: format!(
+                    "{} references will point to the kept copy and {} files will be deleted. This cannot be undone.",
+                    moved_references,
+                    duplicate_count,
+                ),
+                confirm_label: "Merge".to_string(),
+                on_confirm: move |_| {
+                    confirm_open.set(false);
+                    let duplicate_ids = duplicate_ids.clone();
+                    spawn(async move {
+                        media_state.merge_duplicates(keep_id(), duplicate_ids).await;
+                    });
+                },
+                on_cancel: move |_| {},
+            }
+        }
+    }
+}
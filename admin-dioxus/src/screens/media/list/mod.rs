@@ -1,15 +1,20 @@
 use dioxus::prelude::*;
+use std::collections::HashMap;
 
 use crate::components::{
-    DataTableScreen, HeaderColumn, ListEmptyState, ListToolbarProps, LoadingOverlay,
-    MediaUsageDialog, PageHeaderProps, SkeletonCellConfig, SkeletonTableRows,
+    BulkOperationsBar, DataTableScreen, HeaderColumn, ListEmptyState, ListToolbarProps,
+    LoadingOverlay, MediaFolderNav, MediaTagsDialog, MediaUsageDialog, PageHeaderProps,
+    SimpleInput, SkeletonCellConfig, SkeletonTableRows,
 };
 use crate::hooks::{
-    use_list_screen_with_handlers, use_state_frame_map_toast, ListScreenConfig,
-    StateFrameToastConfig,
+    use_list_screen_with_handlers, use_state_frame_map_toast, use_state_frame_toast,
+    ListScreenConfig, StateFrameToastConfig,
 };
 use crate::router::Route;
-use crate::store::{use_media, ListQuery, ListStore, Media, MediaListQuery, MediaReference};
+use crate::store::{
+    tags_filter, use_media, BulkOperation, ListQuery, ListStore, Media, MediaListQuery,
+    MediaReference, ResponsiveImage, THUMBNAIL_IMAGE_WIDTH,
+};
 use crate::types::Order;
 use crate::ui::shadcn::{
     Badge, Button, ButtonVariant, Checkbox, DropdownMenu, DropdownMenuContent, DropdownMenuItem,
@@ -28,6 +33,11 @@ pub fn MediaListScreen() -> Element {
     let selected_ids = use_signal(|| Vec::<i32>::new());
     let usage_dialog_open = use_signal(|| false);
     let usage_dialog_media = use_signal(|| None::<Media>);
+    let tags_dialog_open = use_signal(|| false);
+    let tags_dialog_media = use_signal(|| None::<Media>);
+    let mut tag_input = use_signal(String::new);
+    // Media being dragged onto a folder
+    let mut dragging = use_signal(|| Vec::<i32>::new());
 
     // Use the enhanced hook that creates handlers for us
     let (list_state, handlers) = use_list_screen_with_handlers(
@@ -114,6 +124,52 @@ pub fn MediaListScreen() -> Element {
         HeaderColumn::new("", false, "w-12 py-2 px-3", None),
     ];
 
+    use_state_frame_toast(
+        &media_state.move_media,
+        StateFrameToastConfig {
+            loading_title: "Moving media...".to_string(),
+            success_title: Some("Media moved".to_string()),
+            error_title: Some("Failed to move media".to_string()),
+            ..Default::default()
+        },
+    );
+
+    let handle_folder_open = {
+        let mut filters = filters;
+        move |folder_id: Option<i32>| {
+            let mut q = filters.peek().clone();
+            q.set_page(1);
+            q.folder_id = folder_id;
+            filters.set(q);
+        }
+    };
+
+    let handle_folder_drop = {
+        let mut selected_ids = selected_ids;
+        move |folder_id: Option<i32>| {
+            let media_ids = dragging.peek().clone();
+            dragging.set(Vec::new());
+            selected_ids.set(Vec::new());
+            spawn(async move {
+                media_state.move_to_folder(media_ids, folder_id).await;
+            });
+        }
+    };
+
+    let handle_tag_input = {
+        let mut filters = filters;
+        move |value: String| {
+            let tags = tags_filter(&value);
+            tag_input.set(value);
+            let mut q = filters.peek().clone();
+            if q.tags != tags {
+                q.set_page(1);
+                q.tags = tags;
+                filters.set(q);
+            }
+        }
+    };
+
     // Reference type filter handler
     let handle_reference_select = {
         let mut filters = filters;
@@ -137,9 +193,16 @@ pub fn MediaListScreen() -> Element {
                 title: "Media".to_string(),
                 description: "Manage your media files".to_string(),
                 actions: Some(rsx!{
-                    Button {
-                        onclick: move |_| { nav.push(Route::MediaUploadScreen {}); },
-                        "Upload Media"
+                    div { class: "flex items-center gap-2",
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            onclick: move |_| { nav.push(Route::MediaDuplicatesScreen {}); },
+                            "Find Duplicates"
+                        }
+                        Button {
+                            onclick: move |_| { nav.push(Route::MediaUploadScreen {}); },
+                            "Upload Media"
+                        }
                     }
                 }),
                 class: None,
@@ -163,36 +226,39 @@ pub fn MediaListScreen() -> Element {
                     None => "All".to_string(),
                 },
                 on_status_select: EventHandler::new(handle_reference_select),
+                show_status: true,
             }),
             on_prev: move |_| { handlers.handle_prev.call(current_page); },
             on_next: move |_| { handlers.handle_next.call(current_page); },
-            // Bulk actions
-            below_toolbar: if !selected_ids.read().is_empty() {
-                Some(rsx! {
-                    div { class: "w-full flex items-center justify-between bg-transparent border border-zinc-200 dark:border-zinc-800 rounded-md px-4 py-3 shadow-sm",
-                        span { class: "text-sm text-muted-foreground", "{selected_ids.read().len()} selected" }
-                        div { class: "flex items-center gap-2",
-                            Button { variant: ButtonVariant::Outline, class: "h-8 text-red-600 border-red-200 dark:border-red-800 dark:hover:bg-red-950/20 hover:bg-red-50",
-                                onclick: {
-                                    let selected_ids_clone = selected_ids.read().clone();
-                                    let mut selected_ids = selected_ids;
-                                    move |_| {
-                                        for id in &selected_ids_clone {
-                                            let id = *id;
-                                            spawn(async move {
-                                                let media_state = use_media();
-                                                media_state.remove(id).await;
-                                            });
-                                        }
-                                        selected_ids.set(Vec::new());
-                                    }
-                                },
-                                "Delete"
-                            }
+            // Bulk actions for the selection, plus progress of a running bulk job
+            below_toolbar: Some(rsx! {
+                div { class: "flex flex-col gap-3 md:flex-row md:items-start",
+                    div { class: "flex-1",
+                        MediaFolderNav {
+                            current: filters.read().folder_id,
+                            on_navigate: handle_folder_open,
+                            on_drop: Some(EventHandler::new(handle_folder_drop)),
+                            editable: true,
                         }
                     }
-                })
-            } else { None },
+                    div { class: "w-full md:w-72",
+                        SimpleInput {
+                            value: tag_input(),
+                            placeholder: Some("Filter by tags, e.g. hero, logo".to_string()),
+                            class: Some("h-9 px-3".to_string()),
+                            oninput: Some(EventHandler::new(handle_tag_input)),
+                        }
+                    }
+                }
+                BulkOperationsBar {
+                    selected_ids,
+                    operations: vec![
+                        BulkOperation::MediaDelete,
+                    ],
+                    names: media_items.iter().map(|media| (media.id, media.object_key.clone())).collect::<HashMap<_, _>>(),
+                    on_complete: move |_| list_state.trigger_reload(),
+                }
+            }),
             // Table body
             if media_items.is_empty() {
                 if list_loading && !has_data {
@@ -244,6 +310,16 @@ pub fn MediaListScreen() -> Element {
                         ..Default::default()
                     };
                     use_state_frame_map_toast(&media_state.remove, media_id, toast_config);
+                    use_state_frame_map_toast(
+                        &media_state.variants,
+                        media_id,
+                        StateFrameToastConfig {
+                            loading_title: "Generating image sizes...".to_string(),
+                            success_title: Some("Image sizes generated".to_string()),
+                            error_title: Some("Failed to generate image sizes".to_string()),
+                            ..Default::default()
+                        },
+                    );
 
                     // Check if this item is being deleted
                     let is_deleting = media_state.remove.read().get(&media_id).map_or(false, |frame| frame.is_loading());
@@ -252,6 +328,18 @@ pub fn MediaListScreen() -> Element {
                         tr {
                             key: "{media_id}",
                             class: "relative border-b border-zinc-200 dark:border-zinc-800 hover:bg-muted/30 transition-colors",
+                            draggable: true,
+                            // Dragging a selected row takes the whole selection along
+                            ondragstart: move |e| {
+                                let _ = e.data_transfer().set_data("text/plain", &media_id.to_string());
+                                let selection = selected_ids.peek().clone();
+                                if selection.contains(&media_id) {
+                                    dragging.set(selection);
+                                } else {
+                                    dragging.set(vec![media_id]);
+                                }
+                            },
+                            ondragend: move |_| dragging.set(Vec::new()),
                             // Selection checkbox cell
                             td {
                                 class: "py-2 px-3 w-12 text-xs md:text-sm",
@@ -278,7 +366,7 @@ pub fn MediaListScreen() -> Element {
                             td { class: "py-2 px-3 w-16",
                                 if is_image(&mime_type) {
                                     img {
-                                        src: "{media.file_url}",
+                                        src: "{ResponsiveImage::from_media(&media, THUMBNAIL_IMAGE_WIDTH).src}",
                                         alt: "{filename}",
                                         class: "w-12 h-12 object-cover rounded border border-zinc-200 dark:border-zinc-700",
                                     }
@@ -291,6 +379,13 @@ pub fn MediaListScreen() -> Element {
                             // Filename cell
                             td { class: "py-2 px-3 text-xs md:text-sm whitespace-nowrap",
                                 span { class: "font-medium leading-none truncate max-w-xs block", "{filename}" }
+                                if !media.tags.is_empty() {
+                                    div { class: "mt-1 flex flex-wrap gap-1",
+                                        for tag in media.tags.clone() {
+                                            span { key: "{tag}", class: "rounded bg-muted px-1.5 py-0.5 text-[10px] text-muted-foreground", "{tag}" }
+                                        }
+                                    }
+                                }
                             }
                             // Type cell
                             td { class: "py-2 px-3 text-xs md:text-sm",
@@ -362,6 +457,18 @@ pub fn MediaListScreen() -> Element {
                                             },
                                             "View Usage"
                                         }
+                                        DropdownMenuItem {
+                                            onclick: {
+                                                let media_clone = media.clone();
+                                                let mut tags_dialog_open = tags_dialog_open;
+                                                let mut tags_dialog_media = tags_dialog_media;
+                                                move |_| {
+                                                    tags_dialog_open.set(true);
+                                                    tags_dialog_media.set(Some(media_clone.clone()));
+                                                }
+                                            },
+                                            "Edit Tags"
+                                        }
                                         DropdownMenuItem {
                                             onclick: {
                                                 let url = media.file_url.clone();
@@ -376,6 +483,16 @@ pub fn MediaListScreen() -> Element {
                                             },
                                             "Copy URL"
                                         }
+                                        if is_image(&mime_type) {
+                                            DropdownMenuItem {
+                                                onclick: move |_| {
+                                                    spawn(async move {
+                                                        media_state.generate_variants(media_id).await;
+                                                    });
+                                                },
+                                                if media.variants.is_empty() { "Generate Sizes" } else { "Regenerate Sizes" }
+                                            }
+                                        }
                                         DropdownMenuItem { class: "text-red-600", onclick: move |_| {
                                                 let id = media_id;
                                                 spawn({  async move {
@@ -399,5 +516,12 @@ pub fn MediaListScreen() -> Element {
                 media: media.clone(),
             }
         }
+
+        if let Some(media) = &*tags_dialog_media.read() {
+            MediaTagsDialog {
+                is_open: tags_dialog_open,
+                media: media.clone(),
+            }
+        }
     }
 }
@@ -1,5 +1,7 @@
+pub mod duplicates;
 pub mod list;
 pub mod upload;
 
+pub use duplicates::MediaDuplicatesScreen;
 pub use list::MediaListScreen;
 pub use upload::MediaUploadScreen;
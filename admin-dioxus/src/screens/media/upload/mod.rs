@@ -4,6 +4,10 @@ use crate::components::{MediaUploadList, MediaUploadZone, PageHeader};
 use crate::router::Route;
 use crate::ui::shadcn::{Button, ButtonVariant};
 
+mod optimize_panel;
+
+use optimize_panel::OptimizePanel;
+
 #[component]
 pub fn MediaUploadScreen() -> Element {
     let nav = use_navigator();
@@ -46,7 +50,7 @@ pub fn MediaUploadScreen() -> Element {
             }
 
             div { class: "container mx-auto px-4 py-10 md:py-12 space-y-8",
-                div { class: "max-w-3xl mx-auto",
+                div { class: "max-w-3xl mx-auto space-y-6",
                     MediaUploadZone {
                         on_upload: handle_upload,
                         reference_type: None,
@@ -59,6 +63,7 @@ pub fn MediaUploadScreen() -> Element {
                         description: "Drag and drop files here, or click to select. Supports images and videos.".to_string(),
                         multiple: true,
                     }
+                    OptimizePanel {}
                 }
                 if has_uploads {
                     div { class: "max-w-5xl mx-auto space-y-4",
@@ -0,0 +1,93 @@
+use dioxus::prelude::*;
+
+use crate::store::{ImageOptimizeSettings, MAX_DIMENSION_CHOICES};
+use crate::ui::shadcn::Checkbox;
+
+const SELECT_CLASS: &str =
+    "h-8 rounded-md border border-border/70 bg-transparent px-2 text-xs text-foreground";
+const NUMBER_CLASS: &str =
+    "h-8 w-20 rounded-md border border-border/70 bg-transparent px-2 text-xs text-foreground";
+
+fn dimension_label(max_dimension: u32) -> String {
+    if max_dimension == 0 {
+        "Original size".to_string()
+    } else {
+        format!("{} px", max_dimension)
+    }
+}
+
+/// Settings of the pre-upload image pipeline, saved on change and used by every upload.
+#[component]
+pub fn OptimizePanel() -> Element {
+    let mut settings = use_signal(ImageOptimizeSettings::load);
+    let current = settings.read().clone();
+    let mut update = move |change: &dyn Fn(&mut ImageOptimizeSettings)| {
+        let mut current = settings.write();
+        change(&mut current);
+        current.save();
+    };
+
+    rsx! {
+        div { class: "rounded-xl border border-border/70 bg-transparent",
+            div { class: "px-6 pt-6 flex items-start justify-between gap-4",
+                div {
+                    h2 { class: "text-lg font-semibold", "Image Optimization" }
+                    p { class: "text-sm text-muted-foreground",
+                        "JPEG, PNG and WebP images are turned upright, stripped of EXIF and GPS data and downscaled before upload."
+                    }
+                }
+                label { class: "flex items-center gap-2 cursor-pointer text-sm",
+                    Checkbox {
+                        checked: current.enabled,
+                        onchange: move |checked: bool| update(&|settings| settings.enabled = checked),
+                    }
+                    "Enabled"
+                }
+            }
+            if current.enabled {
+                div { class: "px-6 py-6 space-y-3",
+                    div { class: "flex items-center justify-between gap-4",
+                        span { class: "text-sm", "Maximum width or height" }
+                        select {
+                            class: SELECT_CLASS,
+                            onchange: move |event| {
+                                if let Ok(max_dimension) = event.value().parse::<u32>() {
+                                    update(&|settings| settings.max_dimension = max_dimension);
+                                }
+                            },
+                            for max_dimension in MAX_DIMENSION_CHOICES {
+                                option {
+                                    value: "{max_dimension}",
+                                    selected: current.max_dimension == max_dimension,
+                                    {dimension_label(max_dimension)}
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center justify-between gap-4",
+                        span { class: "text-sm", "Quality" }
+                        input {
+                            class: NUMBER_CLASS,
+                            r#type: "number",
+                            min: "1",
+                            max: "100",
+                            value: "{current.quality}",
+                            onchange: move |event| {
+                                if let Ok(quality) = event.value().parse::<u8>() {
+                                    update(&|settings| settings.quality = quality.clamp(1, 100));
+                                }
+                            },
+                        }
+                    }
+                    label { class: "flex items-center justify-between gap-4 cursor-pointer",
+                        span { class: "text-sm", "Convert to WebP or AVIF when smaller" }
+                        Checkbox {
+                            checked: current.modern_formats,
+                            onchange: move |checked: bool| update(&|settings| settings.modern_formats = checked),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
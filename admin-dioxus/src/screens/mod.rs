@@ -3,7 +3,9 @@ mod categories;
 mod home;
 mod login;
 mod media;
+mod outbox;
 mod posts;
+mod series;
 mod sonner_demo;
 mod tags;
 mod users;
@@ -13,7 +15,9 @@ pub use categories::*;
 pub use home::*;
 pub use login::*;
 pub use media::*;
+pub use outbox::*;
 pub use posts::*;
+pub use series::*;
 pub use sonner_demo::*;
 pub use tags::*;
 pub use users::*;
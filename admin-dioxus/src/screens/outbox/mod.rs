@@ -0,0 +1,100 @@
+use dioxus::prelude::*;
+
+use crate::components::PageHeader;
+use crate::store::{use_outbox, OutboxStatus};
+use crate::ui::shadcn::{Badge, BadgeVariant, Button, ButtonSize, ButtonVariant};
+use crate::utils::dates::format_short_date_dt;
+
+/// Mutations made while offline, waiting to be replayed or parked after a failed replay.
+#[component]
+pub fn OutboxScreen() -> Element {
+    let outbox = use_outbox();
+    let entries = outbox.entries.read().clone();
+    let replay = outbox.replay.read().clone();
+    let pending = outbox.pending_count();
+
+    let sync_now = move |_| {
+        spawn(async move { use_outbox().replay().await });
+    };
+
+    rsx! {
+        div { class: "min-h-screen bg-transparent text-foreground",
+            PageHeader {
+                title: "Outbox".to_string(),
+                description: "Changes made while offline. Queued items sync in order when you reconnect.".to_string(),
+                actions: rsx! {
+                    Button {
+                        disabled: pending == 0 || replay.is_loading(),
+                        onclick: sync_now,
+                        if replay.is_loading() { "Syncing..." } else { "Sync now" }
+                    }
+                },
+            }
+
+            div { class: "container mx-auto px-4 pb-10 space-y-3",
+                if let Some(message) = replay.error_message() {
+                    div { class: "rounded-md border border-amber-200 bg-amber-50 px-4 py-3 text-sm text-amber-800 dark:border-amber-900/40 dark:bg-amber-900/20 dark:text-amber-300",
+                        "Last sync stopped: {message}"
+                    }
+                }
+
+                if entries.is_empty() {
+                    div { class: "rounded-md border border-dashed border-border py-12 text-center text-sm text-muted-foreground",
+                        "Nothing waiting to sync."
+                    }
+                }
+
+                for entry in entries {
+                    div {
+                        key: "{entry.id}",
+                        class: "flex flex-col gap-3 rounded-md border border-border px-4 py-3 sm:flex-row sm:items-center",
+                        div { class: "flex-1 space-y-1",
+                            div { class: "flex items-center gap-2",
+                                span { class: "font-medium", "{entry.mutation.label()}" }
+                                Badge {
+                                    variant: match entry.status {
+                                        OutboxStatus::Queued => BadgeVariant::Secondary,
+                                        OutboxStatus::Failed(_) => BadgeVariant::Destructive,
+                                        OutboxStatus::Conflict(_) => BadgeVariant::Outline,
+                                    },
+                                    "{entry.status.label()}"
+                                }
+                            }
+                            p { class: "text-xs text-muted-foreground",
+                                "Queued {format_short_date_dt(&entry.queued_at)}"
+                                if entry.attempts > 0 {
+                                    " · {entry.attempts} failed attempt(s)"
+                                }
+                            }
+                            if let Some(message) = entry.status.message() {
+                                p { class: "text-sm text-red-600 dark:text-red-400", "{message}" }
+                            }
+                        }
+                        div { class: "flex items-center gap-2",
+                            if entry.status != OutboxStatus::Queued {
+                                Button {
+                                    variant: ButtonVariant::Outline,
+                                    size: ButtonSize::Sm,
+                                    onclick: {
+                                        let id = entry.id.clone();
+                                        move |_| use_outbox().requeue(&id)
+                                    },
+                                    if matches!(entry.status, OutboxStatus::Conflict(_)) { "Overwrite" } else { "Retry" }
+                                }
+                            }
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                size: ButtonSize::Sm,
+                                onclick: {
+                                    let id = entry.id.clone();
+                                    move |_| use_outbox().discard(&id)
+                                },
+                                "Discard"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
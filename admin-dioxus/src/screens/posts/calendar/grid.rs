@@ -0,0 +1,145 @@
+//! Date math for the editorial calendar. Days are in the browser's time zone.
+
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveTime, TimeZone, Utc};
+
+use crate::store::{CalendarRange, Post};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarView {
+    Month,
+    Week,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Draft,
+    Scheduled,
+    Published,
+}
+
+impl EntryKind {
+    pub fn of(post: &Post) -> Self {
+        if post.is_published() {
+            EntryKind::Published
+        } else if post.is_scheduled() {
+            EntryKind::Scheduled
+        } else {
+            EntryKind::Draft
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntryKind::Draft => "Draft",
+            EntryKind::Scheduled => "Scheduled",
+            EntryKind::Published => "Published",
+        }
+    }
+}
+
+/// Drafts without a publish time are not yet placed when first dragged.
+const DEFAULT_PUBLISH_TIME: NaiveTime = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+/// The instant a post is placed at: its publish time, or its last update for undated drafts.
+pub fn entry_time(post: &Post) -> DateTime<Utc> {
+    post.published_at.unwrap_or(post.updated_at)
+}
+
+pub fn entry_day(post: &Post) -> NaiveDate {
+    entry_time(post).with_timezone(&Local).date_naive()
+}
+
+fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Days::new(day.weekday().num_days_from_monday() as u64)
+}
+
+/// Days shown for `view` around `anchor`: whole Monday-first weeks covering the month,
+/// or the single week containing `anchor`.
+pub fn visible_days(view: CalendarView, anchor: NaiveDate) -> Vec<NaiveDate> {
+    let (start, count) = match view {
+        CalendarView::Week => (week_start(anchor), 7),
+        CalendarView::Month => {
+            let first = anchor.with_day(1).unwrap_or(anchor);
+            let last = first + Months::new(1) - Days::new(1);
+            let start = week_start(first);
+            let end = week_start(last) + Days::new(6);
+            (start, (end - start).num_days() as u64 + 1)
+        }
+    };
+    (0..count).map(|offset| start + Days::new(offset)).collect()
+}
+
+/// Move `anchor` one month or week forwards (`step > 0`) or backwards.
+pub fn shift(view: CalendarView, anchor: NaiveDate, step: i32) -> NaiveDate {
+    let magnitude = step.unsigned_abs();
+    match (view, step >= 0) {
+        (CalendarView::Month, true) => anchor + Months::new(magnitude),
+        (CalendarView::Month, false) => anchor - Months::new(magnitude),
+        (CalendarView::Week, true) => anchor + Days::new(7 * magnitude as u64),
+        (CalendarView::Week, false) => anchor - Days::new(7 * magnitude as u64),
+    }
+}
+
+fn local_midnight(day: NaiveDate) -> DateTime<Utc> {
+    Local
+        .from_local_datetime(&day.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&day.and_time(NaiveTime::MIN)))
+}
+
+/// Store range covering `days`, from the first local midnight to the one after the last day.
+pub fn range_for(days: &[NaiveDate]) -> Option<CalendarRange> {
+    let (first, last) = (days.first()?, days.last()?);
+    Some(CalendarRange {
+        from: local_midnight(*first),
+        to: local_midnight(*last + Days::new(1)),
+    })
+}
+
+/// New publish time when `post` is dropped on `day`: same local time of day, or 09:00
+/// for drafts that had no publish time yet.
+pub fn reschedule_time(post: &Post, day: NaiveDate) -> Option<DateTime<Utc>> {
+    let time = post
+        .published_at
+        .map(|at| at.with_timezone(&Local).time())
+        .unwrap_or(DEFAULT_PUBLISH_TIME);
+    Local
+        .from_local_datetime(&day.and_time(time))
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn visible_days_cover_whole_weeks() {
+        // March 2025 starts on a Saturday and ends on a Monday
+        let month = visible_days(CalendarView::Month, date(2025, 3, 14));
+        assert_eq!(month.first(), Some(&date(2025, 2, 24)));
+        assert_eq!(month.last(), Some(&date(2025, 4, 6)));
+        assert_eq!(month.len(), 42);
+
+        let week = visible_days(CalendarView::Week, date(2025, 3, 14));
+        assert_eq!(week.first(), Some(&date(2025, 3, 10)));
+        assert_eq!(week.len(), 7);
+
+        assert_eq!(
+            shift(CalendarView::Month, date(2025, 1, 31), 1),
+            date(2025, 2, 28)
+        );
+        assert_eq!(
+            shift(CalendarView::Week, date(2025, 3, 14), -1),
+            date(2025, 3, 7)
+        );
+
+        let range = range_for(&week).unwrap();
+        assert_eq!(range.to - range.from, chrono::Duration::days(7));
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::{Datelike, Local, NaiveDate, Utc};
+use dioxus::prelude::*;
+
+use crate::components::sonner::{use_sonner, ToastOptions};
+use crate::components::PageHeader;
+use crate::hooks::{use_state_frame_map_toast, StateFrameToastConfig};
+use crate::router::Route;
+use crate::store::{use_post, Post};
+use crate::ui::shadcn::{Button, ButtonSize, ButtonVariant};
+
+mod grid;
+
+use grid::{
+    entry_day, entry_time, range_for, reschedule_time, shift, visible_days, CalendarView, EntryKind,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+fn kind_class(kind: EntryKind) -> &'static str {
+    match kind {
+        EntryKind::Draft => "border-zinc-300 bg-zinc-100 text-zinc-700 dark:border-zinc-700 dark:bg-zinc-800/60 dark:text-zinc-300",
+        EntryKind::Scheduled => "border-amber-300 bg-amber-50 text-amber-800 dark:border-amber-900/50 dark:bg-amber-900/20 dark:text-amber-300",
+        EntryKind::Published => "border-emerald-300 bg-emerald-50 text-emerald-800 dark:border-emerald-900/50 dark:bg-emerald-900/20 dark:text-emerald-300",
+    }
+}
+
+/// Editorial calendar: drafts, scheduled and published posts by date, in month or week
+/// view. Dragging a draft or scheduled post onto another day reschedules it.
+#[component]
+pub fn PostsCalendarScreen() -> Element {
+    let posts = use_post();
+    let nav = use_navigator();
+    let sonner = use_sonner();
+    let mut view = use_signal(|| CalendarView::Month);
+    let mut anchor = use_signal(|| Local::now().date_naive());
+    let mut dragging = use_signal(|| None::<i32>);
+    let mut drop_target = use_signal(|| None::<NaiveDate>);
+    let mut moved = use_signal(|| None::<i32>);
+
+    let days = visible_days(view(), anchor());
+    let range = range_for(&days);
+
+    use_effect(use_reactive!(|(range,)| {
+        if let Some(range) = range {
+            spawn(async move {
+                posts.calendar_range(range).await;
+            });
+        }
+    }));
+
+    use_state_frame_map_toast(
+        &posts.schedule,
+        moved().unwrap_or_default(),
+        StateFrameToastConfig {
+            loading_title: "Rescheduling post...".into(),
+            success_title: Some("Post rescheduled".into()),
+            error_title: Some("Failed to reschedule post".into()),
+            ..Default::default()
+        },
+    );
+
+    let frame = posts.calendar.read();
+    let loading = frame.is_loading();
+    let error = frame.error_message();
+    let mut entries: Vec<Post> = frame.data.clone().unwrap_or_default();
+    drop(frame);
+    entries.sort_by_key(entry_time);
+
+    let today = Local::now().date_naive();
+    let current_month = anchor().month();
+    let title = match view() {
+        CalendarView::Month => anchor().format("%B %Y").to_string(),
+        CalendarView::Week => {
+            let (first, last) = (days[0], days[days.len() - 1]);
+            format!("{} – {}", first.format("%b %-d"), last.format("%b %-d, %Y"))
+        }
+    };
+
+    let mut drop_on = move |day: NaiveDate| {
+        drop_target.set(None);
+        let Some(post_id) = dragging.take() else {
+            return;
+        };
+        let post = posts
+            .calendar
+            .peek()
+            .data
+            .as_ref()
+            .and_then(|list| list.iter().find(|p| p.id == post_id).cloned());
+        let Some(post) = post else {
+            return;
+        };
+        if entry_day(&post) == day && post.published_at.is_some() {
+            return;
+        }
+        match reschedule_time(&post, day) {
+            Some(at) if at > Utc::now() => {
+                moved.set(Some(post_id));
+                spawn(async move {
+                    posts.reschedule(post_id, at).await;
+                });
+            }
+            _ => {
+                sonner.error(
+                    "Posts can only be scheduled in the future".to_string(),
+                    ToastOptions::default(),
+                );
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "min-h-screen bg-transparent text-foreground",
+            PageHeader {
+                title: "Editorial calendar".to_string(),
+                description: "Drafts, scheduled and published posts by date. Drag a draft or scheduled post to another day to reschedule it.".to_string(),
+                actions: Some(rsx! {
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        onclick: move |_| { nav.push(Route::PostsListScreen {}); },
+                        "Back to Posts"
+                    }
+                    Button {
+                        onclick: move |_| { nav.push(Route::PostsAddScreen {}); },
+                        "Create Post"
+                    }
+                }),
+            }
+
+            div { class: "container mx-auto space-y-4 px-4 pb-10",
+                div { class: "flex flex-wrap items-center gap-2",
+                    Button { variant: ButtonVariant::Outline, size: ButtonSize::Sm,
+                        onclick: move |_| anchor.set(shift(view(), anchor(), -1)),
+                        "Previous"
+                    }
+                    Button { variant: ButtonVariant::Outline, size: ButtonSize::Sm,
+                        onclick: move |_| anchor.set(Local::now().date_naive()),
+                        "Today"
+                    }
+                    Button { variant: ButtonVariant::Outline, size: ButtonSize::Sm,
+                        onclick: move |_| anchor.set(shift(view(), anchor(), 1)),
+                        "Next"
+                    }
+                    h2 { class: "ml-2 text-lg font-semibold", "{title}" }
+                    if loading {
+                        span { class: "text-xs text-muted-foreground", "Loading..." }
+                    }
+                    div { class: "ml-auto flex items-center gap-1 rounded-md border border-border/70 p-1",
+                        for (option, label) in [(CalendarView::Month, "Month"), (CalendarView::Week, "Week")] {
+                            button {
+                                key: "{label}",
+                                r#type: "button",
+                                class: if view() == option { "rounded px-3 py-1 text-xs font-medium bg-primary text-primary-foreground" } else { "rounded px-3 py-1 text-xs font-medium text-muted-foreground hover:bg-muted/50" },
+                                onclick: move |_| view.set(option),
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+
+                div { class: "flex flex-wrap gap-3 text-xs",
+                    for kind in [EntryKind::Draft, EntryKind::Scheduled, EntryKind::Published] {
+                        span { key: "{kind.label()}", class: "rounded border px-2 py-0.5 {kind_class(kind)}", "{kind.label()}" }
+                    }
+                }
+
+                if let Some(message) = error {
+                    div { class: "rounded-md border border-red-200 bg-red-50 p-3 text-sm text-red-700 dark:border-red-900/40 dark:bg-red-900/20 dark:text-red-300",
+                        "Failed to load posts: {message}"
+                    }
+                }
+
+                div { class: "grid grid-cols-7 overflow-hidden rounded-lg border border-border/70",
+                    for weekday in WEEKDAYS {
+                        div { key: "{weekday}", class: "border-b border-border/70 bg-muted/30 px-2 py-1.5 text-xs font-medium text-muted-foreground", "{weekday}" }
+                    }
+                    for day in days.iter().copied() {
+                        div {
+                            key: "{day}",
+                            class: {
+                                let height = if view() == CalendarView::Week { "min-h-72" } else { "min-h-28" };
+                                let tone = if drop_target() == Some(day) {
+                                    "bg-primary/10"
+                                } else if view() == CalendarView::Month && day.month() != current_month {
+                                    "bg-muted/20 text-muted-foreground"
+                                } else {
+                                    ""
+                                };
+                                format!("{height} space-y-1 border-b border-r border-border/50 p-1.5 {tone}")
+                            },
+                            ondragover: move |e| {
+                                e.prevent_default();
+                                if dragging.peek().is_some() && *drop_target.peek() != Some(day) {
+                                    drop_target.set(Some(day));
+                                }
+                            },
+                            ondrop: move |e| {
+                                e.prevent_default();
+                                drop_on(day);
+                            },
+                            div { class: if day == today { "inline-flex h-6 w-6 items-center justify-center rounded-full bg-primary text-xs font-semibold text-primary-foreground" } else { "px-1 text-xs font-medium" },
+                                "{day.day()}"
+                            }
+                            for post in entries.iter().filter(|p| entry_day(p) == day).cloned() {
+                                CalendarEntry {
+                                    key: "{post.id}",
+                                    post,
+                                    on_drag_start: move |id| dragging.set(Some(id)),
+                                    on_drag_end: move |_| {
+                                        dragging.set(None);
+                                        drop_target.set(None);
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn CalendarEntry(
+    post: Post,
+    on_drag_start: EventHandler<i32>,
+    on_drag_end: EventHandler<()>,
+) -> Element {
+    let nav = use_navigator();
+    let kind = EntryKind::of(&post);
+    let movable = kind != EntryKind::Published;
+    let id = post.id;
+    let time = if post.published_at.is_some() {
+        entry_time(&post)
+            .with_timezone(&Local)
+            .format("%H:%M")
+            .to_string()
+    } else {
+        String::new()
+    };
+
+    rsx! {
+        div {
+            class: "cursor-pointer truncate rounded border px-1.5 py-0.5 text-xs {kind_class(kind)}",
+            title: "{kind.label()}: {post.title}",
+            draggable: movable,
+            ondragstart: move |e| {
+                let _ = e.data_transfer().set_data("text/plain", &id.to_string());
+                on_drag_start.call(id);
+            },
+            ondragend: move |_| on_drag_end.call(()),
+            onclick: move |_| { nav.push(Route::PostsEditScreen { id }); },
+            if !time.is_empty() {
+                span { class: "mr-1 font-mono opacity-70", "{time}" }
+            }
+            "{post.title}"
+        }
+    }
+}
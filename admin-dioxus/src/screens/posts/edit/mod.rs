@@ -55,6 +55,11 @@ pub fn PostsEditScreen(id: i32) -> Element {
                 title: "Edit Post".to_string(),
                 description: "Update your post content, metadata, and publishing settings.".to_string(),
                 actions: Some(rsx! {
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        onclick: move |_| { nav.push(Route::PostsRevisionsScreen { id }); },
+                        "History"
+                    }
                     Button {
                         variant: ButtonVariant::Outline,
                         onclick: move |_| { nav.push(Route::PostsListScreen {}); },
@@ -0,0 +1,231 @@
+use dioxus::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+
+use crate::store::{use_archive, Post};
+use crate::ui::shadcn::{Button, ButtonSize, ButtonVariant};
+
+use super::super::context::use_post_list_context;
+
+const IMPORT_INPUT_ID: &str = "posts-archive-import";
+
+#[derive(Props, PartialEq, Clone)]
+pub struct ArchiveActionsProps {
+    /// Posts on the current page, to export the selected ones.
+    pub posts: Vec<Post>,
+    /// Called after an import, to reload the list.
+    pub on_import: EventHandler<()>,
+}
+
+/// Export and Import buttons for the page header. Export takes the selected posts, or every
+/// post matching the current filters when nothing is selected.
+#[component]
+pub fn ArchiveActions(props: ArchiveActionsProps) -> Element {
+    let ctx = use_post_list_context();
+    let archive = use_archive();
+    let selected_count = ctx.selected_ids.read().len();
+    let running = archive.is_running();
+
+    let export = {
+        let posts = props.posts.clone();
+        move |_| {
+            let selected = ctx.selected_ids.read().clone();
+            let query = ctx.filters.read().clone();
+            let posts: Vec<Post> = posts
+                .iter()
+                .filter(|post| selected.contains(&post.id))
+                .cloned()
+                .collect();
+            spawn(async move {
+                if selected.is_empty() {
+                    archive.export_query(query).await;
+                } else {
+                    archive.export_posts(posts).await;
+                }
+            });
+        }
+    };
+
+    let open_picker = move |_| {
+        if let Some(el) = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|doc| doc.get_element_by_id(IMPORT_INPUT_ID))
+        {
+            if let Ok(input) = el.dyn_into::<HtmlInputElement>() {
+                input.set_value("");
+                input.click();
+            }
+        }
+    };
+
+    let on_import = props.on_import;
+    let handle_file = move |_evt: Event<FormData>| {
+        let file = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|doc| doc.get_element_by_id(IMPORT_INPUT_ID))
+            .and_then(|el| el.dyn_into::<HtmlInputElement>().ok())
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0));
+        if let Some(file) = file {
+            spawn(async move {
+                archive.import_file(file).await;
+                on_import.call(());
+            });
+        }
+    };
+
+    rsx! {
+        input {
+            id: IMPORT_INPUT_ID,
+            r#type: "file",
+            accept: ".json,application/json",
+            class: "hidden",
+            onchange: handle_file,
+        }
+        Button {
+            variant: ButtonVariant::Outline,
+            disabled: running,
+            onclick: export,
+            if selected_count > 0 {
+                "Export {selected_count}"
+            } else {
+                "Export"
+            }
+        }
+        Button {
+            variant: ButtonVariant::Outline,
+            disabled: running,
+            onclick: open_picker,
+            "Import"
+        }
+    }
+}
+
+/// Progress of a running export or import, then what it did.
+#[component]
+pub fn ArchiveReport() -> Element {
+    let archive = use_archive();
+    let status = archive
+        .progress
+        .read()
+        .clone()
+        .unwrap_or_else(|| "Working".to_string());
+    let export = archive.export.read().clone();
+    let import = archive.import.read().clone();
+    let running = archive.is_running();
+
+    let export_report = export.data.clone().filter(|_| export.is_success());
+    let import_report = import.data.clone().filter(|_| import.is_success());
+    let errors: Vec<String> = [
+        export
+            .error_message()
+            .map(|e| format!("Export failed: {}", e)),
+        import
+            .error_message()
+            .map(|e| format!("Import failed: {}", e)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if !running && export_report.is_none() && import_report.is_none() && errors.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "w-full bg-transparent border border-zinc-200 dark:border-zinc-800 rounded-md px-4 py-3 shadow-sm space-y-3",
+            div { class: "flex items-center justify-between gap-4",
+                span { class: "text-sm text-muted-foreground",
+                    if running {
+                        "{status}…"
+                    } else {
+                        "Archive"
+                    }
+                }
+                if !running {
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Sm,
+                        onclick: move |_| archive.dismiss(),
+                        "Dismiss"
+                    }
+                }
+            }
+            for error in errors {
+                p { class: "text-sm text-red-600", "{error}" }
+            }
+            if let Some(report) = export_report {
+                div { class: "space-y-1",
+                    p { class: "text-sm",
+                        "Exported {report.posts} posts with {report.media} media files"
+                    }
+                    if !report.skipped_media.is_empty() {
+                        p { class: "text-sm font-medium text-amber-600",
+                            "{report.skipped_media.len()} media files could not be downloaded and are linked by URL only"
+                        }
+                        ul { class: "text-xs text-muted-foreground space-y-1 max-h-40 overflow-y-auto",
+                            for (name , message) in report.skipped_media {
+                                li {
+                                    span { class: "font-medium text-foreground", "{name}" }
+                                    ": {message}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(report) = import_report {
+                div { class: "space-y-2",
+                    p { class: "text-sm", "Imported {report.created.len()} posts" }
+                    if !report.created_terms.is_empty() {
+                        p { class: "text-xs text-muted-foreground",
+                            {format!("Created {}", report.created_terms.join(", "))}
+                        }
+                    }
+                    if !report.conflicts.is_empty() {
+                        div { class: "space-y-1",
+                            p { class: "text-sm font-medium text-amber-600",
+                                "{report.conflicts.len()} skipped because the slug is already taken"
+                            }
+                            ul { class: "text-xs text-muted-foreground space-y-1 max-h-40 overflow-y-auto",
+                                for slug in report.conflicts {
+                                    li { class: "font-mono", "{slug}" }
+                                }
+                            }
+                        }
+                    }
+                    if !report.warnings.is_empty() {
+                        div { class: "space-y-1",
+                            p { class: "text-sm font-medium text-amber-600",
+                                "{report.warnings.len()} imported with problems"
+                            }
+                            ul { class: "text-xs text-muted-foreground space-y-1 max-h-40 overflow-y-auto",
+                                for (label , message) in report.warnings {
+                                    li {
+                                        span { class: "font-medium text-foreground", "{label}" }
+                                        ": {message}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if !report.failures.is_empty() {
+                        div { class: "space-y-1",
+                            p { class: "text-sm font-medium text-red-600",
+                                "{report.failures.len()} failed"
+                            }
+                            ul { class: "text-xs text-muted-foreground space-y-1 max-h-40 overflow-y-auto",
+                                for (label , message) in report.failures {
+                                    li {
+                                        span { class: "font-medium text-foreground", "{label}" }
+                                        ": {message}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,12 @@
 mod active_filters;
+mod archive_actions;
 mod bulk_actions;
 mod filter_popover;
 mod grid_view;
 mod table_view;
 
 pub use active_filters::ActiveFilters;
+pub use archive_actions::{ArchiveActions, ArchiveReport};
 pub use bulk_actions::BulkActionsBar;
 pub use filter_popover::FilterPopover;
 pub use grid_view::GridView;
@@ -122,13 +122,12 @@ pub fn PostsListScreen() -> Element {
         }
     };
 
-    // Bulk actions if items selected
-    let bulk_actions = if !ctx.selected_ids.read().is_empty() {
-        Some(rsx! {
-            components::BulkActionsBar {}
-        })
-    } else {
-        None
+    // Bulk actions for the selection, plus progress of a running bulk job
+    let bulk_actions = rsx! {
+        components::BulkActionsBar {
+            posts: posts.clone(),
+            on_complete: move |_| list_state.trigger_reload(),
+        }
     };
 
     // Define table headers
@@ -197,6 +196,15 @@ pub fn PostsListScreen() -> Element {
                 actions: Some(rsx!{
                     div { class: "flex items-center gap-2",
                         {view_mode_switcher}
+                        components::ArchiveActions {
+                            posts: posts.clone(),
+                            on_import: move |_| list_state.trigger_reload(),
+                        }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            onclick: move |_| { nav.push(Route::PostsCalendarScreen {}); },
+                            "Calendar"
+                        }
                         Button {
                             onclick: move |_| { nav.push(Route::PostsAddScreen {}); },
                             "Create Post"
@@ -232,11 +240,13 @@ pub fn PostsListScreen() -> Element {
                         ctx_clone.set_status_filter(status);
                     }
                 }),
+                show_status: true,
             }),
             below_toolbar: Some(rsx!{
                 div { class: "space-y-3",
                     {below_toolbar_content}
                     {bulk_actions}
+                    components::ArchiveReport {}
                 }
             }),
             on_prev: move |_| { handlers.handle_prev.call(current_page); },
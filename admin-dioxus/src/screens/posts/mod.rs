@@ -1,9 +1,13 @@
 mod add;
+mod calendar;
 mod edit;
 mod list;
+mod revisions;
 mod view;
 
 pub use add::*;
+pub use calendar::*;
 pub use edit::*;
 pub use list::*;
+pub use revisions::*;
 pub use view::*;
@@ -0,0 +1,258 @@
+use dioxus::prelude::*;
+
+use crate::components::{ConfirmDialog, PageHeader};
+use crate::hooks::{use_state_frame_map_toast, StateFrameToastConfig};
+use crate::router::Route;
+use crate::store::{use_post, use_user, PostRevision};
+use crate::ui::shadcn::{Badge, BadgeVariant, Button, ButtonVariant};
+use crate::utils::post_diff::{block_kind, block_preview, BlockDiff, FieldChange, RevisionDiff};
+
+fn author_name(user_id: i32) -> String {
+    use_user()
+        .view
+        .read()
+        .get(&user_id)
+        .and_then(|frame| frame.data.as_ref().map(|user| user.name.clone()))
+        .unwrap_or_else(|| format!("User #{user_id}"))
+}
+
+/// Stored revisions of a post, diffed block by block against the current version.
+#[component]
+pub fn PostsRevisionsScreen(id: i32) -> Element {
+    let posts = use_post();
+    let nav = use_navigator();
+    let mut selected = use_signal(|| None::<i32>);
+    let mut confirm_open = use_signal(|| false);
+    let mut restoring = use_signal(|| None::<i32>);
+
+    use_effect(move || {
+        spawn(async move {
+            posts.revisions_list(id).await;
+        });
+        if !posts.view.peek().contains_key(&id) {
+            spawn(async move {
+                posts.view_by_id(id).await;
+            });
+        }
+    });
+
+    let list_frame = posts.revisions_list.read().get(&id).cloned();
+    let mut revisions: Vec<PostRevision> = list_frame
+        .as_ref()
+        .and_then(|frame| frame.data.clone())
+        .unwrap_or_default();
+    revisions.sort_by_key(|revision| std::cmp::Reverse(revision.created_at));
+
+    // Resolve author names once the list arrives.
+    let mut author_ids: Vec<i32> = revisions.iter().map(|r| r.created_by).collect();
+    author_ids.sort_unstable();
+    author_ids.dedup();
+    use_effect(use_reactive!(|(author_ids,)| {
+        for user_id in author_ids {
+            if !use_user().view.peek().contains_key(&user_id) {
+                spawn(async move { use_user().view(user_id).await });
+            }
+        }
+    }));
+
+    let restore_key = (id, restoring().unwrap_or_default());
+    use_state_frame_map_toast(
+        &posts.revisions_restore,
+        restore_key,
+        StateFrameToastConfig {
+            loading_title: "Restoring revision...".into(),
+            success_title: Some("Revision restored".into()),
+            error_title: Some("Failed to restore revision".into()),
+            ..Default::default()
+        },
+    );
+
+    let current = posts
+        .view
+        .read()
+        .get(&id)
+        .and_then(|frame| frame.data.clone());
+    let active = selected()
+        .and_then(|rid| revisions.iter().find(|r| r.id == rid))
+        .or_else(|| revisions.first())
+        .cloned();
+    let diff = match (&active, &current) {
+        (Some(revision), Some(post)) => Some(RevisionDiff::between(revision, post)),
+        _ => None,
+    };
+    let is_loading = list_frame.as_ref().is_none_or(|frame| frame.is_loading());
+    let list_error = list_frame.as_ref().and_then(|frame| frame.error_message());
+    let restore_busy = restoring().is_some_and(|rid| {
+        posts
+            .revisions_restore
+            .read()
+            .get(&(id, rid))
+            .is_some_and(|frame| frame.is_loading())
+    });
+
+    rsx! {
+        div { class: "min-h-screen bg-transparent text-foreground",
+            PageHeader {
+                title: "Revision history".to_string(),
+                description: current.as_ref().map(|post| post.title.clone()).unwrap_or_default(),
+                actions: Some(rsx! {
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        onclick: move |_| { nav.push(Route::PostsEditScreen { id }); },
+                        "Back to editor"
+                    }
+                }),
+            }
+
+            div { class: "container mx-auto grid grid-cols-1 gap-6 px-4 pb-10 lg:grid-cols-[18rem_1fr]",
+                div { class: "space-y-2",
+                    if let Some(message) = list_error {
+                        div { class: "rounded-md border border-destructive/40 px-3 py-2 text-sm text-destructive", "{message}" }
+                    } else if is_loading && revisions.is_empty() {
+                        div { class: "py-6 text-sm text-muted-foreground", "Loading revisions..." }
+                    } else if revisions.is_empty() {
+                        div { class: "rounded-md border border-dashed border-border py-10 text-center text-sm text-muted-foreground",
+                            "No revisions yet."
+                        }
+                    }
+                    for revision in revisions.iter().cloned() {
+                        button {
+                            key: "{revision.id}",
+                            r#type: "button",
+                            class: if active.as_ref().is_some_and(|a| a.id == revision.id) { "w-full rounded-md border border-ring bg-muted px-3 py-2 text-left" } else { "w-full rounded-md border border-border px-3 py-2 text-left hover:bg-muted/50" },
+                            onclick: move |_| selected.set(Some(revision.id)),
+                            p { class: "truncate text-sm font-medium", "{revision.title}" }
+                            p { class: "text-xs text-muted-foreground",
+                                {format!("{} · {}", author_name(revision.created_by), revision.created_at.format("%b %d, %Y %H:%M"))}
+                            }
+                        }
+                    }
+                }
+
+                div { class: "min-w-0 space-y-4",
+                    match (&active, &diff) {
+                        (Some(revision), Some(diff)) => rsx! {
+                            div { class: "flex flex-wrap items-center gap-3",
+                                h2 { class: "text-lg font-semibold", "Revision #{revision.id}" }
+                                Badge { variant: BadgeVariant::Secondary,
+                                    if diff.change_count() == 0 { "Same as current" } else { "{diff.change_count()} changes" }
+                                }
+                                div { class: "ml-auto",
+                                    Button {
+                                        disabled: restore_busy || diff.change_count() == 0,
+                                        onclick: move |_| confirm_open.set(true),
+                                        "Restore this revision"
+                                    }
+                                }
+                            }
+                            if diff.unreadable_content {
+                                div { class: "rounded-md border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-800 dark:border-amber-900/40 dark:bg-amber-900/20 dark:text-amber-300",
+                                    "This revision's content could not be read; only the title and excerpt are compared."
+                                }
+                            }
+                            div { class: "grid grid-cols-2 gap-3 text-xs font-medium uppercase tracking-wide text-muted-foreground",
+                                span { "Revision" }
+                                span { "Current" }
+                            }
+                            FieldRow { label: "Title", change: diff.title.clone(), unchanged: revision.title.clone() }
+                            FieldRow {
+                                label: "Excerpt",
+                                change: diff.excerpt.clone(),
+                                unchanged: revision.excerpt.clone().unwrap_or_default(),
+                            }
+                            div { class: "space-y-2",
+                                for (index, block) in diff.blocks.iter().cloned().enumerate() {
+                                    BlockRow { key: "{index}", diff: block }
+                                }
+                            }
+                        },
+                        (Some(_), None) => rsx! {
+                            div { class: "py-6 text-sm text-muted-foreground", "Loading current version..." }
+                        },
+                        _ => rsx! {},
+                    }
+                }
+            }
+
+            if let Some(revision) = active.clone() {
+                ConfirmDialog {
+                    is_open: confirm_open,
+                    title: "Restore this revision?".to_string(),
+                    description: format!(
+                        "The post's title, excerpt and content will be replaced with revision #{} from {}.",
+                        revision.id,
+                        revision.created_at.format("%b %d, %Y %H:%M"),
+                    ),
+                    confirm_label: "Restore".to_string(),
+                    on_confirm: move |_| {
+                        confirm_open.set(false);
+                        restoring.set(Some(revision.id));
+                        spawn(async move {
+                            posts.revisions_restore(id, revision.id).await;
+                            posts.revisions_list(id).await;
+                        });
+                    },
+                    on_cancel: move |_| {},
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FieldRow(label: &'static str, change: Option<FieldChange>, unchanged: String) -> Element {
+    let (before, after, class) = match change {
+        Some(change) => (
+            change.before,
+            change.after,
+            "border-amber-300 bg-amber-50/60 dark:border-amber-900/50 dark:bg-amber-900/10",
+        ),
+        None => (unchanged.clone(), unchanged, "border-border"),
+    };
+    rsx! {
+        div { class: "grid grid-cols-2 gap-3",
+            for text in [before, after] {
+                div { class: "rounded-md border px-3 py-2 text-sm {class}",
+                    p { class: "text-xs text-muted-foreground", "{label}" }
+                    p { class: "whitespace-pre-wrap break-words", "{text}" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn BlockRow(diff: BlockDiff) -> Element {
+    const REMOVED: &str = "border-red-300 bg-red-50/60 dark:border-red-900/50 dark:bg-red-900/10";
+    const ADDED: &str =
+        "border-emerald-300 bg-emerald-50/60 dark:border-emerald-900/50 dark:bg-emerald-900/10";
+    const MODIFIED: &str =
+        "border-amber-300 bg-amber-50/60 dark:border-amber-900/50 dark:bg-amber-900/10";
+
+    let (before, after) = match &diff {
+        BlockDiff::Unchanged(block) => (Some((block, "border-border")), Some((block, "border-border"))),
+        BlockDiff::Removed(block) => (Some((block, REMOVED)), None),
+        BlockDiff::Added(block) => (None, Some((block, ADDED))),
+        BlockDiff::Modified { before, after } => {
+            (Some((&**before, MODIFIED)), Some((&**after, MODIFIED)))
+        }
+    };
+    let cell = |side: Option<(&crate::store::EditorJsBlock, &str)>| match side {
+        Some((block, class)) => rsx! {
+            div { class: "min-w-0 rounded-md border px-3 py-2 text-sm {class}",
+                p { class: "text-xs text-muted-foreground", "{block_kind(block)}" }
+                p { class: "whitespace-pre-wrap break-words", "{block_preview(block)}" }
+            }
+        },
+        None => rsx! {
+            div { class: "rounded-md border border-dashed border-border/60" }
+        },
+    };
+
+    rsx! {
+        div { class: "grid grid-cols-2 gap-3",
+            {cell(before)}
+            {cell(after)}
+        }
+    }
+}
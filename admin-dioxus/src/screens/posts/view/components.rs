@@ -1,6 +1,9 @@
 use crate::components::PageHeader;
 use crate::router::Route;
-use crate::store::{use_post, EditorJsBlock, PostContent};
+use crate::store::{
+    use_post, EditorJsBlock, PostContent, ResponsiveImage, CONTENT_IMAGE_SIZES,
+    CONTENT_IMAGE_WIDTH, WEBP_MIME,
+};
 use crate::ui::shadcn::{Button, ButtonVariant};
 use dioxus::prelude::*;
 
@@ -175,15 +178,31 @@ fn render_delimiter_block(block: &EditorJsBlock) -> Element {
 
 fn render_image_block(block: &EditorJsBlock) -> Element {
     if let EditorJsBlock::Image { data, .. } = block {
-        let url = data.file.url.clone();
+        let image = ResponsiveImage::new(
+            &data.file.url,
+            data.file.width,
+            &data.file.variants,
+            CONTENT_IMAGE_WIDTH,
+        );
         let caption = &data.caption;
 
         rsx! {
             div { class: "my-6",
-                img {
-                    src: "{url}",
-                    alt: caption.as_deref().unwrap_or(""),
-                    class: "w-full h-auto rounded-lg"
+                picture { class: "block",
+                    if let Some(webp_srcset) = image.webp_srcset {
+                        source {
+                            r#type: WEBP_MIME,
+                            "srcset": "{webp_srcset}",
+                            "sizes": CONTENT_IMAGE_SIZES,
+                        }
+                    }
+                    img {
+                        src: "{image.src}",
+                        srcset: image.srcset,
+                        sizes: CONTENT_IMAGE_SIZES,
+                        alt: caption.as_deref().unwrap_or(""),
+                        class: "w-full h-auto rounded-lg"
+                    }
                 }
                 if let Some(ref caption) = data.caption {
                     p { class: "mt-2 text-sm text-muted-foreground text-center italic", "{caption}" }
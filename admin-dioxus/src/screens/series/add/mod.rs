@@ -0,0 +1,69 @@
+use dioxus::prelude::*;
+
+use crate::components::sonner::{Action, ToastOptions};
+use crate::components::PageHeader;
+use crate::containers::{SeriesForm, SeriesFormContainer};
+use crate::hooks::{use_state_frame_toast, StateFrameToastConfig};
+use crate::router::Route;
+use crate::store::use_post;
+use crate::ui::shadcn::{Button, ButtonVariant};
+
+#[component]
+pub fn SeriesAddScreen() -> Element {
+    let posts = use_post();
+    let nav = use_navigator();
+
+    let cfg = StateFrameToastConfig {
+        loading_title: "Creating series...".into(),
+        success_title: Some("Series created successfully".into()),
+        success_options: ToastOptions::default().with_action(Some(Action::with_on_click(
+            "View Series".into(),
+            Callback::new(move |_| {
+                nav.push(Route::SeriesListScreen {});
+            }),
+        ))),
+        error_title: Some("Failed to create series".into()),
+        error_options: ToastOptions::default().with_action(Some(Action::with_on_click(
+            "Retry".into(),
+            Callback::new(move |_| {
+                if let Some(payload) = posts.series_add.peek().meta.clone() {
+                    spawn(async move {
+                        posts.series_create(payload).await;
+                    });
+                }
+            }),
+        ))),
+        ..Default::default()
+    };
+    use_state_frame_toast(&posts.series_add, cfg);
+
+    rsx! {
+        div { class: "min-h-screen bg-transparent text-foreground",
+            PageHeader {
+                title: "Create Series".to_string(),
+                description: "Start a multi-part series. Posts are added to it from the post editor.".to_string(),
+                actions: Some(rsx! {
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        onclick: move |_| { nav.push(Route::SeriesListScreen {}); },
+                        "Back to Series"
+                    }
+                }),
+            }
+
+            div { class: "container mx-auto px-4 py-10 md:py-12",
+                SeriesFormContainer {
+                    error: posts.series_add.read().error.clone(),
+                    title: Some("New Series".to_string()),
+                    submit_label: Some("Create Series".to_string()),
+                    on_submit: move |val: SeriesForm| {
+                        let payload = val.to_add_payload();
+                        spawn(async move {
+                            posts.series_create(payload).await;
+                        });
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use dioxus::prelude::*;
+
+use crate::components::sonner::{Action, ToastOptions};
+use crate::components::{FormTwoColumnSkeleton, PageHeader};
+use crate::containers::{SeriesForm, SeriesFormContainer};
+use crate::hooks::{use_state_frame_map_toast, StateFrameToastConfig};
+use crate::router::Route;
+use crate::store::use_post;
+use crate::ui::shadcn::{Button, ButtonVariant};
+
+#[component]
+pub fn SeriesEditScreen(id: i32) -> Element {
+    let nav = use_navigator();
+    let posts = use_post();
+
+    // There is no single-series endpoint; the series comes from the list cache.
+    use_effect(move || {
+        let list = posts.series_list.peek();
+        let cached = list
+            .data
+            .as_ref()
+            .is_some_and(|page| page.data.iter().any(|s| s.id == id));
+        if !cached && !list.is_loading() {
+            drop(list);
+            spawn(async move {
+                posts.series_list().await;
+            });
+        }
+    });
+
+    let toast_cfg = StateFrameToastConfig {
+        loading_title: "Saving series...".into(),
+        success_title: Some("Series updated successfully".into()),
+        success_options: ToastOptions::default().with_action(Some(Action::with_on_click(
+            "View Series".into(),
+            Callback::new(move |_| {
+                nav.push(Route::SeriesListScreen {});
+            }),
+        ))),
+        error_title: Some("Failed to update series".into()),
+        error_options: ToastOptions::default().with_action(Some(Action::with_on_click(
+            "Retry".into(),
+            Callback::new(move |_| {
+                if let Some(payload) = posts
+                    .series_edit
+                    .peek()
+                    .get(&id)
+                    .and_then(|frame| frame.meta.clone())
+                {
+                    spawn(async move {
+                        posts.series_update(id, payload).await;
+                    });
+                }
+            }),
+        ))),
+        ..Default::default()
+    };
+    use_state_frame_map_toast(&posts.series_edit, id, toast_cfg);
+
+    let list = posts.series_list.read();
+    let series = posts
+        .series_view
+        .read()
+        .get(&id)
+        .and_then(|frame| frame.data.clone())
+        .or_else(|| {
+            list.data
+                .as_ref()
+                .and_then(|page| page.data.iter().find(|s| s.id == id).cloned())
+        });
+    let is_loading = list.is_loading() || list.is_init();
+    let is_failed = list.is_failed();
+    let message = list.error_message();
+    drop(list);
+    let initial_form = series.as_ref().map(SeriesForm::from_series);
+
+    rsx! {
+        div { class: "min-h-screen bg-transparent text-foreground",
+            PageHeader {
+                title: "Edit Series".to_string(),
+                description: "Update the series name, slug and description.".to_string(),
+                actions: Some(rsx! {
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        onclick: move |_| { nav.push(Route::SeriesListScreen {}); },
+                        "Back to Series"
+                    }
+                }),
+            }
+
+            div { class: "container mx-auto px-4 py-10 md:py-12 space-y-4",
+                if is_failed {
+                    div { class: "rounded-md border border-red-200 bg-red-50 p-3 text-red-700 dark:border-red-900/40 dark:bg-red-900/20 dark:text-red-300",
+                        span { class: "text-sm", "Failed to load series." }
+                        if let Some(msg) = message { span { class: "ml-1 text-sm opacity-80", "{msg}" } }
+                        Button {
+                            class: "ml-3",
+                            onclick: move |_| {
+                                spawn(async move {
+                                    posts.series_list().await;
+                                });
+                            },
+                            "Retry"
+                        }
+                    }
+                }
+
+                if let Some(initial) = initial_form {
+                    SeriesFormContainer {
+                        error: posts.series_edit.read().get(&id).and_then(|frame| frame.error.clone()),
+                        title: Some("Edit Series".to_string()),
+                        submit_label: Some("Save Changes".to_string()),
+                        initial: Some(initial),
+                        on_submit: move |val: SeriesForm| {
+                            let payload = val.to_edit_payload();
+                            spawn(async move {
+                                posts.series_update(id, payload).await;
+                            });
+                        },
+                    }
+                } else if is_loading {
+                    FormTwoColumnSkeleton {}
+                } else if !is_failed {
+                    div { class: "rounded-md border border-dashed border-border py-10 text-center text-sm text-muted-foreground",
+                        "Series #{id} was not found on the first page of series."
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use dioxus::prelude::*;
+
+use crate::components::{
+    DataTableScreen, HeaderColumn, ListEmptyState, ListToolbarProps, PageHeaderProps,
+    SkeletonCellConfig, SkeletonTableRows,
+};
+use crate::hooks::{use_list_screen_with_handlers, ListScreenConfig};
+use crate::router::Route;
+use crate::store::{use_post, Series, SeriesListQuery};
+use crate::types::Order;
+use crate::ui::shadcn::{
+    Button, ButtonVariant, DropdownMenu, DropdownMenuContent, DropdownMenuItem, DropdownMenuTrigger,
+};
+use crate::utils::dates::format_short_date_dt;
+use hmziq_dioxus_free_icons::{icons::ld_icons::LdEllipsis, Icon};
+
+#[component]
+pub fn SeriesListScreen() -> Element {
+    let nav = use_navigator();
+    let posts = use_post();
+
+    let filters = use_signal(SeriesListQuery::new);
+
+    // The series list endpoint has no sorting, so headers are not sortable
+    let (list_state, handlers) = use_list_screen_with_handlers(
+        Some(ListScreenConfig {
+            default_sort_field: "name".to_string(),
+            default_sort_order: Order::Asc,
+        }),
+        filters,
+    );
+
+    use_effect(move || {
+        let q = filters();
+        let _tick = list_state.reload_tick();
+        spawn(async move {
+            posts.series_list_with_query(q).await;
+        });
+    });
+
+    let list = posts.series_list.read();
+    let list_loading = list.is_loading();
+
+    let (series, current_page) = if let Some(p) = &list.data {
+        (p.data.clone(), p.page)
+    } else {
+        (Vec::<Series>::new(), 1)
+    };
+
+    let has_data = !series.is_empty();
+
+    let headers = vec![
+        HeaderColumn::new(
+            "Name",
+            false,
+            "py-2 px-3 text-left font-medium text-xs md:text-sm whitespace-nowrap",
+            None,
+        ),
+        HeaderColumn::new(
+            "Slug",
+            false,
+            "py-2 px-3 text-left font-medium text-xs md:text-sm whitespace-nowrap",
+            None,
+        ),
+        HeaderColumn::new(
+            "Description",
+            false,
+            "py-2 px-3 text-left font-medium text-xs md:text-sm",
+            None,
+        ),
+        HeaderColumn::new(
+            "Created",
+            false,
+            "py-2 px-3 text-left font-medium text-xs md:text-sm whitespace-nowrap",
+            None,
+        ),
+        HeaderColumn::new(
+            "Updated",
+            false,
+            "py-2 px-3 text-left font-medium text-xs md:text-sm whitespace-nowrap",
+            None,
+        ),
+        HeaderColumn::new("", false, "w-12 py-2 px-3", None),
+    ];
+
+    rsx! {
+        DataTableScreen::<Series> {
+            frame: (posts.series_list)(),
+            header: Some(PageHeaderProps {
+                title: "Series".to_string(),
+                description: "Group posts into ordered, multi-part series".to_string(),
+                actions: Some(rsx!{
+                    Button {
+                        onclick: move |_| { nav.push(Route::SeriesAddScreen {}); },
+                        "New Series"
+                    }
+                }),
+                class: None,
+                embedded: false,
+            }),
+            headers: Some(headers),
+            current_sort_field: Some(list_state.sort_field()),
+            on_sort: Some(handlers.handle_sort),
+            error_title: Some("Failed to load series".to_string()),
+            error_retry_label: Some("Retry".to_string()),
+            on_error_retry: Some(EventHandler::new(move |_| handlers.handle_retry.call(()))),
+            toolbar: Some(ListToolbarProps {
+                search_value: list_state.search_input(),
+                search_placeholder: "Search series by name or slug".to_string(),
+                disabled: list_loading,
+                on_search_input: handlers.handle_search,
+                status_selected: "All".to_string(),
+                on_status_select: EventHandler::new(|_| {}),
+                show_status: false,
+            }),
+            on_prev: move |_| { handlers.handle_prev.call(current_page); },
+            on_next: move |_| { handlers.handle_next.call(current_page); },
+            if series.is_empty() {
+                if list_loading && !has_data {
+                    SkeletonTableRows {
+                        row_count: 6,
+                        cells: vec![
+                            SkeletonCellConfig::custom(crate::components::UICellType::Default, "py-2 px-3"),
+                            SkeletonCellConfig::custom(crate::components::UICellType::Default, "py-2 px-3"),
+                            SkeletonCellConfig::custom(crate::components::UICellType::Default, "py-2 px-3"),
+                            SkeletonCellConfig::custom(crate::components::UICellType::Default, "py-2 px-3"),
+                            SkeletonCellConfig::custom(crate::components::UICellType::Default, "py-2 px-3"),
+                            SkeletonCellConfig::custom(crate::components::UICellType::Action, "py-2 px-3"),
+                        ],
+                    }
+                } else {
+                    tr { class: "border-b border-zinc-200 dark:border-zinc-800",
+                        td { colspan: "6", class: "py-12 px-4 text-center",
+                            ListEmptyState {
+                                title: "No series found".to_string(),
+                                description: "Try adjusting your search or create a series to group multi-part posts.".to_string(),
+                                clear_label: "Clear search".to_string(),
+                                create_label: "Create your first series".to_string(),
+                                on_clear: move |_| { handlers.handle_clear.call(()); },
+                                on_create: move |_| { nav.push(Route::SeriesAddScreen {}); },
+                            }
+                        }
+                    }
+                }
+            } else {
+                {series.into_iter().map(|item| {
+                    let series_id = item.id;
+                    rsx! {
+                        tr { class: "border-b border-zinc-200 dark:border-zinc-800 hover:bg-muted/30 transition-colors",
+                            td { class: "py-2 px-3 text-xs md:text-sm whitespace-nowrap",
+                                span { class: "font-medium leading-none truncate", "{item.name}" }
+                            }
+                            td { class: "py-2 px-3 text-xs md:text-sm text-muted-foreground whitespace-nowrap",
+                                span { class: "truncate font-mono", "{item.slug}" }
+                            }
+                            td { class: "max-w-xs py-2 px-3 text-xs md:text-sm text-muted-foreground",
+                                span { class: "truncate", {item.description.clone().unwrap_or("—".to_string())} }
+                            }
+                            td { class: "py-2 px-3 text-xs md:text-sm text-muted-foreground whitespace-nowrap", "{format_short_date_dt(&item.created_at)}" }
+                            td { class: "py-2 px-3 text-xs md:text-sm text-muted-foreground whitespace-nowrap", "{format_short_date_dt(&item.updated_at)}" }
+                            td { class: "py-2 px-3 text-xs md:text-sm",
+                                DropdownMenu {
+                                    DropdownMenuTrigger {
+                                        Button { variant: ButtonVariant::Ghost, class: "h-8 w-8 p-0 bg-transparent hover:bg-muted/50", div { class: "w-4 h-4", Icon { icon: LdEllipsis {} } } }
+                                    }
+                                    DropdownMenuContent { class: "bg-background border-zinc-200 dark:border-zinc-800",
+                                        DropdownMenuItem { onclick: move |_| { nav.push(Route::SeriesEditScreen { id: series_id }); }, "Edit" }
+                                        DropdownMenuItem { class: "text-red-600", onclick: move |_| {
+                                                spawn(async move {
+                                                    posts.series_delete(series_id).await;
+                                                });
+                                            }, "Delete" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                })}
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod add;
+mod edit;
+mod list;
+
+pub use add::*;
+pub use edit::*;
+pub use list::*;
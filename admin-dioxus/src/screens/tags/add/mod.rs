@@ -56,6 +56,7 @@ pub fn TagsAddScreen() -> Element {
             // Content: render reusable form component; submission handled here
             div { class: "container mx-auto px-4 py-10 md:py-12",
                     TagFormContainer {
+                        error: tags.add.read().error.clone(),
                         title: Some("New Tag".to_string()),
                         submit_label: Some("Create Tag".to_string()),
                         on_submit: move |val: TagForm| {
@@ -98,6 +98,7 @@ pub fn TagsEditScreen(id: i32) -> Element {
                     FormTwoColumnSkeleton {}
                 } else if let Some(initial) = initial_form.clone() {
                     TagFormContainer {
+                        error: tags.edit.read().get(&id).and_then(|frame| frame.error.clone()),
                         title: Some("Edit Tag".to_string()),
                         submit_label: Some("Save Changes".to_string()),
                         initial: Some(initial.clone()),
@@ -56,6 +56,7 @@ pub fn UsersAddScreen() -> Element {
             // Content: render reusable form component; submission handled here
             div { class: "container mx-auto px-4 py-10 md:py-12",
                     UserFormContainer {
+                        error: users.add.read().error.clone(),
                         title: Some("New User".to_string()),
                         submit_label: Some("Create User".to_string()),
                         on_submit: move |val: UserForm| {
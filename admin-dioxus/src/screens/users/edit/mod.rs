@@ -101,6 +101,7 @@ pub fn UsersEditScreen(id: i32) -> Element {
                     FormTwoColumnSkeleton {}
                 } else if let Some(initial) = initial_form.clone() {
                     UserFormContainer {
+                        error: users.edit.read().get(&id).and_then(|frame| frame.error.clone()),
                         title: Some("Edit User".to_string()),
                         submit_label: Some("Save Changes".to_string()),
                         initial: Some(initial.clone()),
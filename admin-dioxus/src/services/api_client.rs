@@ -0,0 +1,427 @@
+//! Backend-agnostic API client.
+//!
+//! Store actions build an [`ApiRequest`] from the typed routes in [`super::endpoints`] (or the
+//! helpers in this module) and send it through the active [`ApiClient`]. The web build talks
+//! to the API via `gloo-net`, native builds use `reqwest`, and tests can swap in an in-memory
+//! fake with [`set_api_client`]. Timeouts, retries and cancellation are layered on top by
+//! [`super::request_policy`].
+
+use super::request_policy::{self, AbortHandle, RequestPolicy};
+use crate::config::active_profile;
+use crate::store::{use_devtools, TransportErrorInfo};
+use futures_util::future::LocalBoxFuture;
+use serde::{de::DeserializeOwned, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+use web_sys::FormData;
+
+/// Base URL of the active API profile (see [`crate::config`]).
+pub fn get_base_url() -> String {
+    active_profile().base_url()
+}
+
+pub fn csrf_token() -> String {
+    active_profile().csrf_token()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl HttpMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
+
+    /// Methods that are safe to retry by definition.
+    pub fn is_idempotent(&self) -> bool {
+        !matches!(self, HttpMethod::Post)
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiBody {
+    Empty,
+    Json(serde_json::Value),
+    /// Browser-only; the browser sets the multipart boundary header itself.
+    Multipart(FormData),
+}
+
+/// Receives `(sent, total)` bytes while a request body uploads.
+#[derive(Clone)]
+pub struct UploadProgress(Rc<dyn Fn(u64, u64)>);
+
+impl UploadProgress {
+    pub fn new(on_progress: impl Fn(u64, u64) + 'static) -> Self {
+        Self(Rc::new(on_progress))
+    }
+
+    pub fn report(&self, sent: u64, total: u64) {
+        (self.0)(sent, total)
+    }
+}
+
+impl PartialEq for UploadProgress {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for UploadProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UploadProgress")
+    }
+}
+
+/// A request that any [`ApiClient`] backend can send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiRequest {
+    pub method: HttpMethod,
+    /// Path relative to the API base URL, e.g. `/tag/v1/list/query`.
+    pub path: String,
+    pub body: ApiBody,
+    /// Whether the request may be retried automatically. Defaults from the method; read-only
+    /// `POST` endpoints (list queries) opt in with [`ApiRequest::idempotent`].
+    pub idempotent: bool,
+    /// Overrides [`request_policy::default_policy`] for this request.
+    pub policy: Option<RequestPolicy>,
+    pub abort: Option<AbortHandle>,
+    /// Upload progress of a multipart body. Only the web client reports it; it is called from
+    /// within the sending future, so it may write signals.
+    pub upload_progress: Option<UploadProgress>,
+}
+
+impl ApiRequest {
+    pub fn new(method: HttpMethod, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: ApiBody::Empty,
+            idempotent: method.is_idempotent(),
+            policy: None,
+            abort: None,
+            upload_progress: None,
+        }
+    }
+
+    pub fn with_json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        let value = serde_json::to_value(body).expect("request body must serialize to JSON");
+        self.body = ApiBody::Json(value);
+        self
+    }
+
+    pub fn with_multipart(mut self, form_data: &FormData) -> Self {
+        self.body = ApiBody::Multipart(form_data.clone());
+        self
+    }
+
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let policy = self.policy.unwrap_or_else(request_policy::default_policy);
+        self.with_policy(policy.with_timeout(Some(timeout)))
+    }
+
+    /// Cancel the request (including pending retries) when `handle` is aborted.
+    pub fn with_abort(mut self, handle: &AbortHandle) -> Self {
+        self.abort = Some(handle.clone());
+        self
+    }
+
+    /// Report upload progress of the body as `(sent, total)` bytes.
+    pub fn with_upload_progress(mut self, on_progress: impl Fn(u64, u64) + 'static) -> Self {
+        self.upload_progress = Some(UploadProgress::new(on_progress));
+        self
+    }
+
+    pub fn url(&self) -> String {
+        format!("{}{}", get_base_url(), self.path)
+    }
+
+    /// Send this request through the active [`ApiClient`], applying its request policy.
+    /// Debug builds also record it in the devtools request log.
+    pub async fn send(self) -> ApiResult {
+        let devtools = use_devtools();
+        if !devtools.is_recording() {
+            return request_policy::execute(api_client(), self).await;
+        }
+        let entry = devtools.start(&self);
+        let result = request_policy::execute(api_client(), self).await;
+        devtools.finish(entry, &result);
+        result
+    }
+}
+
+/// A fully buffered response. Bodies are read eagerly so callers can decode them synchronously
+/// and still show the raw text when decoding fails.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ApiResponse {
+    pub status: u16,
+    /// Header names are lower-cased.
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl ApiResponse {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: HashMap::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(|v| v.as_str())
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.body)
+    }
+
+    pub fn text(&self) -> &str {
+        &self.body
+    }
+}
+
+pub type ApiResult = Result<ApiResponse, TransportErrorInfo>;
+
+/// A transport able to execute [`ApiRequest`]s.
+///
+/// Implementations classify their own transport failures into [`TransportErrorInfo`] so that
+/// store code never sees backend-specific error types.
+pub trait ApiClient {
+    fn send(&self, request: ApiRequest) -> LocalBoxFuture<'static, ApiResult>;
+}
+
+fn default_client() -> Rc<dyn ApiClient> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Rc::new(super::http_client::GlooClient)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Rc::new(super::reqwest_client::ReqwestClient)
+    }
+}
+
+thread_local! {
+    static API_CLIENT: RefCell<Rc<dyn ApiClient>> = RefCell::new(default_client());
+}
+
+/// The client used by [`ApiRequest::send`].
+pub fn api_client() -> Rc<dyn ApiClient> {
+    API_CLIENT.with(|client| client.borrow().clone())
+}
+
+/// Replace the active client, e.g. with [`fake::FakeApiClient`] in tests.
+pub fn set_api_client(client: Rc<dyn ApiClient>) {
+    API_CLIENT.with(|current| *current.borrow_mut() = client);
+}
+
+pub fn get(endpoint: &str) -> ApiRequest {
+    ApiRequest::new(HttpMethod::Get, endpoint)
+}
+
+pub fn post<T: Serialize + ?Sized>(endpoint: &str, body: &T) -> ApiRequest {
+    ApiRequest::new(HttpMethod::Post, endpoint).with_json(body)
+}
+
+pub fn put<T: Serialize + ?Sized>(endpoint: &str, body: &T) -> ApiRequest {
+    ApiRequest::new(HttpMethod::Put, endpoint).with_json(body)
+}
+
+pub fn delete(endpoint: &str) -> ApiRequest {
+    ApiRequest::new(HttpMethod::Delete, endpoint)
+}
+
+pub fn post_multipart(endpoint: &str, form_data: &FormData) -> ApiRequest {
+    ApiRequest::new(HttpMethod::Post, endpoint).with_multipart(form_data)
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use dioxus::prelude::*;
+    use futures_channel::oneshot;
+    use std::collections::{HashSet, VecDeque};
+
+    /// In-memory client that replays canned responses and records every request it receives.
+    #[derive(Default)]
+    pub struct FakeApiClient {
+        routes: RefCell<HashMap<(HttpMethod, String), VecDeque<ApiResult>>>,
+        stalled: RefCell<HashSet<(HttpMethod, String)>>,
+        deferred: RefCell<HashMap<(HttpMethod, String), oneshot::Receiver<ApiResult>>>,
+        pub requests: RefCell<Vec<ApiRequest>>,
+    }
+
+    impl FakeApiClient {
+        pub fn new() -> Rc<Self> {
+            Rc::new(Self::default())
+        }
+
+        /// Queue a response for `method path`. Queued responses are consumed in order; the last
+        /// one keeps answering once the queue is down to a single entry.
+        pub fn respond(&self, method: HttpMethod, path: &str, result: ApiResult) {
+            self.routes
+                .borrow_mut()
+                .entry((method, path.to_string()))
+                .or_default()
+                .push_back(result);
+        }
+
+        pub fn respond_json<T: Serialize>(
+            &self,
+            method: HttpMethod,
+            path: &str,
+            status: u16,
+            body: &T,
+        ) {
+            let body = serde_json::to_string(body).expect("fake body must serialize");
+            self.respond(method, path, Ok(ApiResponse::new(status, body)));
+        }
+
+        /// Make the next request to `method path` hang until it is aborted.
+        pub fn stall(&self, method: HttpMethod, path: &str) {
+            self.stalled.borrow_mut().insert((method, path.to_string()));
+        }
+
+        /// Hold the next request to `method path` until a result is sent through the
+        /// returned channel.
+        pub fn defer(&self, method: HttpMethod, path: &str) -> oneshot::Sender<ApiResult> {
+            let (tx, rx) = oneshot::channel();
+            self.deferred
+                .borrow_mut()
+                .insert((method, path.to_string()), rx);
+            tx
+        }
+
+        /// Install as the active client. Also drops timeouts and backoff delays so tests can
+        /// drive requests with `now_or_never`.
+        pub fn install(self: &Rc<Self>) {
+            set_api_client(self.clone());
+            request_policy::set_default_policy(RequestPolicy::immediate());
+        }
+    }
+
+    /// Run `f` in the root scope of a fresh Dioxus runtime, so store signals and root context
+    /// work, with a fresh [`FakeApiClient`] installed.
+    pub fn with_fake_client(f: impl FnOnce(Rc<FakeApiClient>)) {
+        let mut dom = VirtualDom::new(|| rsx! {});
+        dom.rebuild_in_place();
+        dom.in_scope(ScopeId::ROOT, || {
+            let fake = FakeApiClient::new();
+            fake.install();
+            f(fake);
+        });
+    }
+
+    impl ApiClient for FakeApiClient {
+        fn send(&self, request: ApiRequest) -> LocalBoxFuture<'static, ApiResult> {
+            let key = (request.method, request.path.clone());
+            self.requests.borrow_mut().push(request);
+            if self.stalled.borrow_mut().remove(&key) {
+                return Box::pin(futures_util::future::pending());
+            }
+            if let Some(rx) = self.deferred.borrow_mut().remove(&key) {
+                return Box::pin(async move {
+                    match rx.await {
+                        Ok(result) => result,
+                        Err(_) => futures_util::future::pending().await,
+                    }
+                });
+            }
+
+            let mut routes = self.routes.borrow_mut();
+            let result = match routes.get_mut(&key) {
+                Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+                Some(queue) if !queue.is_empty() => queue[0].clone(),
+                _ => Ok(ApiResponse::new(404, "")),
+            };
+            Box::pin(async move { result })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::FakeApiClient;
+    use super::*;
+    use crate::store::TransportErrorKind;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn builds_json_requests_relative_to_base_url() {
+        let req = post("/tag/v1/create", &serde_json::json!({ "name": "rust" }));
+        assert_eq!(req.method, HttpMethod::Post);
+        assert_eq!(req.url(), format!("{}/tag/v1/create", get_base_url()));
+        assert_eq!(
+            req.body,
+            ApiBody::Json(serde_json::json!({ "name": "rust" }))
+        );
+        assert_eq!(get("/user/v1/get").body, ApiBody::Empty);
+    }
+
+    #[test]
+    fn send_goes_through_installed_client() {
+        let fake = FakeApiClient::new();
+        fake.install();
+        fake.respond_json(
+            HttpMethod::Get,
+            "/ping",
+            200,
+            &serde_json::json!({ "ok": true }),
+        );
+        fake.respond(
+            HttpMethod::Get,
+            "/down",
+            Err(TransportErrorInfo {
+                kind: TransportErrorKind::Offline,
+                message: None,
+            }),
+        );
+
+        let ok = get("/ping").send().now_or_never().unwrap().unwrap();
+        assert!(ok.is_success());
+        assert_eq!(ok.json::<serde_json::Value>().unwrap()["ok"], true);
+
+        let err = get("/down").send().now_or_never().unwrap().unwrap_err();
+        assert_eq!(err.kind, TransportErrorKind::Offline);
+
+        let missing = get("/missing").send().now_or_never().unwrap().unwrap();
+        assert_eq!(missing.status, 404);
+        assert_eq!(fake.requests.borrow().len(), 3);
+    }
+}
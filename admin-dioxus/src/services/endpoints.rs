@@ -0,0 +1,235 @@
+//! Typed registry of backend routes.
+//!
+//! Each endpoint is a struct holding its path parameters and declaring its HTTP method, JSON
+//! body and response type. Store actions build requests from these instead of formatting
+//! paths by hand, so a backend route change is made once here and every caller that no
+//! longer fits fails to compile.
+//!
+//! ```ignore
+//! let request = TagUpdate { id }.request(&payload); // POST /tag/v1/update/{id}
+//! ```
+
+use super::api_client::{ApiRequest, HttpMethod};
+use crate::store::analytics::{
+    AnalyticsEnvelopeResponse, CommentRatePoint, CommentRateRequest, DashboardSummaryData,
+    DashboardSummaryRequest, MediaUploadPoint, MediaUploadRequest, NewsletterGrowthPoint,
+    NewsletterGrowthRequest, PageViewPoint, PageViewsRequest, PublishingTrendPoint,
+    PublishingTrendsRequest, RegistrationTrendPoint, RegistrationTrendsRequest,
+    VerificationRatePoint, VerificationRatesRequest,
+};
+use crate::store::{
+    AuthUser, CategoriesAddPayload, CategoriesEditPayload, CategoriesListQuery, Category,
+    LoginPayload, Media, MediaDuplicateGroup, MediaFolder, MediaFolderPayload, MediaListQuery,
+    MediaMergePayload, MediaMovePayload, MediaTagsPayload, MediaUploadSession,
+    MediaUploadStartPayload, MediaUsageDetailsRequest, MediaUsageDetailsResponse, PaginatedList,
+    Post, PostCreatePayload, PostEditPayload, PostListQuery, PostRevision, PostSchedulePayload,
+    Series, SeriesAddPostPayload, SeriesCreatePayload, SeriesEditPayload, SeriesListQuery, Tag,
+    TagsAddPayload, TagsEditPayload, TagsListQuery, User, UsersAddPayload, UsersEditPayload,
+    UsersListQuery,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use web_sys::FormData;
+
+/// A backend route with its method, path parameters, JSON body and response type.
+pub trait Endpoint {
+    const METHOD: HttpMethod;
+    /// JSON body. `()` sends `null` on `POST` and nothing on `GET`/`DELETE`.
+    type Body: Serialize;
+    type Response: DeserializeOwned;
+
+    /// Path relative to the API base URL with the parameters filled in.
+    fn path(&self) -> String;
+
+    fn request(&self, body: &Self::Body) -> ApiRequest {
+        let request = ApiRequest::new(Self::METHOD, self.path());
+        match Self::METHOD {
+            HttpMethod::Get | HttpMethod::Delete => request,
+            HttpMethod::Post | HttpMethod::Put => request.with_json(body),
+        }
+    }
+
+    /// For upload endpoints, which take form data instead of [`Endpoint::Body`].
+    fn multipart(&self, form_data: &FormData) -> ApiRequest {
+        ApiRequest::new(Self::METHOD, self.path()).with_multipart(form_data)
+    }
+}
+
+/// Response type of an endpoint, e.g. `ResponseOf<TagList>` is `PaginatedList<Tag>`.
+pub type ResponseOf<E> = <E as Endpoint>::Response;
+
+/// Body for endpoints that expect an empty JSON object (`{}`) rather than `null`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct EmptyObject {}
+
+/// `Name { params } => METHOD "path/{param}", Body => Response;`
+macro_rules! endpoints {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident { $($field:ident: $ty:ty),* } => $method:ident $path:literal, $body:ty => $response:ty;
+    )*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            pub struct $name {
+                $(pub $field: $ty),*
+            }
+
+            impl Endpoint for $name {
+                const METHOD: HttpMethod = HttpMethod::$method;
+                type Body = $body;
+                type Response = $response;
+
+                fn path(&self) -> String {
+                    format!($path, $($field = self.$field),*)
+                }
+            }
+        )*
+    };
+}
+
+// Auth
+endpoints! {
+    AuthLogIn {} => Post "/auth/v1/log_in", LoginPayload => AuthUser;
+    AuthLogOut {} => Post "/auth/v1/log_out", () => Value;
+    /// The signed-in user; `401` when there is no session.
+    CurrentUser {} => Get "/user/v1/get", () => AuthUser;
+}
+
+// Posts
+endpoints! {
+    PostCreate {} => Post "/post/v1/create", PostCreatePayload => Post;
+    PostUpdate { id: i32 } => Post "/post/v1/update/{id}", PostEditPayload => Post;
+    PostDelete { id: i32 } => Post "/post/v1/delete/{id}", () => Value;
+    PostList {} => Post "/post/v1/query", PostListQuery => PaginatedList<Post>;
+    PostListPublished {} => Post "/post/v1/list/published", EmptyObject => PaginatedList<Post>;
+    PostView { id: i32 } => Post "/post/v1/view/{id}", () => Post;
+    PostSchedule {} => Post "/post/v1/schedule", PostSchedulePayload => Value;
+    PostRevisionList { post_id: i32 } => Post "/post/v1/revisions/{post_id}/list", EmptyObject => Vec<PostRevision>;
+    PostRevisionRestore { post_id: i32, revision_id: i32 } => Post "/post/v1/revisions/{post_id}/restore/{revision_id}", EmptyObject => Value;
+    PostTrackView { id: i32 } => Post "/post/v1/track_view/{id}", () => Value;
+    PostSitemap {} => Post "/post/v1/sitemap", EmptyObject => Vec<Post>;
+}
+
+/// `/post/v1/view/{id_or_slug}`; the only route keyed by something other than an id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostViewBySlug<'a> {
+    pub id_or_slug: &'a str,
+}
+
+impl Endpoint for PostViewBySlug<'_> {
+    const METHOD: HttpMethod = HttpMethod::Post;
+    type Body = ();
+    type Response = Post;
+
+    fn path(&self) -> String {
+        format!("/post/v1/view/{}", self.id_or_slug)
+    }
+}
+
+// Series
+endpoints! {
+    SeriesCreate {} => Post "/post/v1/series/create", SeriesCreatePayload => Series;
+    SeriesUpdate { id: i32 } => Post "/post/v1/series/update/{id}", SeriesEditPayload => Series;
+    SeriesDelete { id: i32 } => Post "/post/v1/series/delete/{id}", () => Value;
+    SeriesList {} => Post "/post/v1/series/list", SeriesListQuery => PaginatedList<Series>;
+    SeriesAddPost { post_id: i32, series_id: i32 } => Post "/post/v1/series/add/{post_id}/{series_id}", SeriesAddPostPayload => Value;
+    SeriesRemovePost { post_id: i32, series_id: i32 } => Post "/post/v1/series/remove/{post_id}/{series_id}", EmptyObject => Value;
+}
+
+// Tags
+endpoints! {
+    TagCreate {} => Post "/tag/v1/create", TagsAddPayload => Tag;
+    TagUpdate { id: i32 } => Post "/tag/v1/update/{id}", TagsEditPayload => Tag;
+    TagDelete { id: i32 } => Post "/tag/v1/delete/{id}", () => Value;
+    TagList {} => Post "/tag/v1/list/query", TagsListQuery => PaginatedList<Tag>;
+    TagView { id: i32 } => Post "/tag/v1/view/{id}", () => Tag;
+}
+
+// Categories
+endpoints! {
+    CategoryCreate {} => Post "/category/v1/create", CategoriesAddPayload => Category;
+    CategoryUpdate { id: i32 } => Post "/category/v1/update/{id}", CategoriesEditPayload => Category;
+    CategoryDelete { id: i32 } => Post "/category/v1/delete/{id}", () => Value;
+    CategoryList {} => Post "/category/v1/list/query", CategoriesListQuery => PaginatedList<Category>;
+    CategoryView { id: i32 } => Get "/category/v1/view/{id}", () => Category;
+}
+
+// Users (admin)
+endpoints! {
+    UserCreate {} => Post "/user/v1/admin/create", UsersAddPayload => User;
+    UserUpdate { id: i32 } => Post "/user/v1/admin/update/{id}", UsersEditPayload => User;
+    UserDelete { id: i32 } => Post "/user/v1/admin/delete/{id}", () => Value;
+    UserList {} => Post "/user/v1/admin/list", UsersListQuery => PaginatedList<User>;
+    UserView { id: i32 } => Post "/user/v1/admin/view/{id}", () => User;
+}
+
+// Media
+endpoints! {
+    /// Multipart upload; call with [`Endpoint::multipart`].
+    MediaCreate {} => Post "/media/v1/create", () => Media;
+    MediaDelete { id: i32 } => Post "/media/v1/delete/{id}", () => Value;
+    MediaList {} => Post "/media/v1/list/query", MediaListQuery => PaginatedList<Media>;
+    MediaView { id: i32 } => Get "/media/v1/view/{id}", () => Media;
+    MediaUsage {} => Post "/media/v1/usage/details", MediaUsageDetailsRequest => MediaUsageDetailsResponse;
+    /// Opens a chunked upload session for files too large for a single request.
+    MediaUploadStart {} => Post "/media/v1/upload/start", MediaUploadStartPayload => MediaUploadSession;
+    /// Multipart with a `chunk` field; call with [`Endpoint::multipart`].
+    MediaUploadChunk { session_id: i32, index: u32 } => Post "/media/v1/upload/{session_id}/chunk/{index}", () => MediaUploadSession;
+    MediaUploadStatus { session_id: i32 } => Get "/media/v1/upload/{session_id}", () => MediaUploadSession;
+    /// Assembles the received chunks into a media record.
+    MediaUploadComplete { session_id: i32 } => Post "/media/v1/upload/{session_id}/complete", () => Media;
+    /// Groups of media with the same content hash, largest groups first.
+    MediaDuplicates {} => Get "/media/v1/duplicates", () => Vec<MediaDuplicateGroup>;
+    /// Moves the duplicates' references to the kept media and deletes the duplicates.
+    MediaMerge {} => Post "/media/v1/merge", MediaMergePayload => Media;
+    /// Renders the thumbnail, medium, large and WebP renditions of an image.
+    MediaGenerateVariants { id: i32 } => Post "/media/v1/variants/{id}", () => Media;
+    MediaFolderList {} => Get "/media/v1/folders", () => Vec<MediaFolder>;
+    MediaFolderCreate {} => Post "/media/v1/folders/create", MediaFolderPayload => MediaFolder;
+    MediaFolderUpdate { id: i32 } => Post "/media/v1/folders/update/{id}", MediaFolderPayload => MediaFolder;
+    /// Media inside the folder moves to its parent.
+    MediaFolderDelete { id: i32 } => Post "/media/v1/folders/delete/{id}", () => Value;
+    MediaMove {} => Post "/media/v1/move", MediaMovePayload => Value;
+    /// Every tag used in the library.
+    MediaTagList {} => Get "/media/v1/tags", () => Vec<String>;
+    MediaTagsUpdate { id: i32 } => Post "/media/v1/tags/{id}", MediaTagsPayload => Media;
+}
+
+// Analytics
+endpoints! {
+    RegistrationTrends {} => Post "/analytics/v1/user/registration-trends", RegistrationTrendsRequest => AnalyticsEnvelopeResponse<Vec<RegistrationTrendPoint>>;
+    VerificationRates {} => Post "/analytics/v1/user/verification-rates", VerificationRatesRequest => AnalyticsEnvelopeResponse<Vec<VerificationRatePoint>>;
+    PublishingTrends {} => Post "/analytics/v1/content/publishing-trends", PublishingTrendsRequest => AnalyticsEnvelopeResponse<Vec<PublishingTrendPoint>>;
+    PageViews {} => Post "/analytics/v1/engagement/page-views", PageViewsRequest => AnalyticsEnvelopeResponse<Vec<PageViewPoint>>;
+    CommentRate {} => Post "/analytics/v1/engagement/comment-rate", CommentRateRequest => AnalyticsEnvelopeResponse<Vec<CommentRatePoint>>;
+    NewsletterGrowth {} => Post "/analytics/v1/engagement/newsletter-growth", NewsletterGrowthRequest => AnalyticsEnvelopeResponse<Vec<NewsletterGrowthPoint>>;
+    MediaUploadTrends {} => Post "/analytics/v1/media/upload-trends", MediaUploadRequest => AnalyticsEnvelopeResponse<Vec<MediaUploadPoint>>;
+    DashboardSummary {} => Post "/analytics/v1/dashboard/summary", DashboardSummaryRequest => AnalyticsEnvelopeResponse<DashboardSummaryData>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::ApiBody;
+
+    #[test]
+    fn endpoints_fill_path_params_and_pick_bodies() {
+        let update = PostRevisionRestore {
+            post_id: 4,
+            revision_id: 9,
+        };
+        assert_eq!(update.path(), "/post/v1/revisions/4/restore/9");
+
+        let view = CategoryView { id: 3 }.request(&());
+        assert_eq!(view.method, HttpMethod::Get);
+        assert_eq!(view.body, ApiBody::Empty);
+
+        let delete = TagDelete { id: 3 }.request(&());
+        assert_eq!(delete.path, "/tag/v1/delete/3");
+        assert_eq!(delete.body, ApiBody::Json(Value::Null));
+
+        let list = PostRevisionList { post_id: 1 }.request(&EmptyObject {});
+        assert_eq!(list.body, ApiBody::Json(serde_json::json!({})));
+    }
+}
@@ -1,67 +1,268 @@
-use crate::env::{APP_API_URL, APP_CSRF_TOKEN};
-use gloo_net::http::{Request, RequestBuilder, Response};
-use serde::Serialize;
-use web_sys::{FormData, RequestCredentials};
-
-pub type HttpRequest = Request;
-pub type HttpRequestBuilder = RequestBuilder;
-pub type HttpResponse = Response;
+#![cfg(target_arch = "wasm32")]
+//! `gloo-net` backend for [`ApiClient`], used by the web build.
+use crate::services::api_client::{
+    csrf_token, ApiBody, ApiClient, ApiRequest, ApiResponse, ApiResult, HttpMethod, UploadProgress,
+};
+use crate::store::{is_offline, TransportErrorInfo, TransportErrorKind};
+use futures_channel::{mpsc, oneshot};
+use futures_util::future::{self, Either, LocalBoxFuture};
+use futures_util::StreamExt;
+use gloo_net::http::{Request, RequestBuilder};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{FormData, ProgressEvent, RequestCredentials, XmlHttpRequest};
+
 pub type HttpError = gloo_net::Error;
 
-pub fn get_base_url() -> String {
-    format!("http://{}", APP_API_URL)
+fn create_headers(req: RequestBuilder) -> RequestBuilder {
+    req.header("Content-Type", "application/json")
+        .header("csrf-token", &csrf_token())
+        .credentials(RequestCredentials::Include)
 }
 
-fn create_headers(mut req: RequestBuilder) -> RequestBuilder {
-    req = req
-        .header("Content-Type", "application/json")
-        .header("csrf-token", APP_CSRF_TOKEN)
-        .credentials(RequestCredentials::Include);
-    req
+fn create_multipart_headers(req: RequestBuilder) -> RequestBuilder {
+    // Note: Don't set Content-Type for multipart, browser will set it with boundary
+    req.header("csrf-token", &csrf_token())
+        .credentials(RequestCredentials::Include)
 }
 
-pub fn get(endpoint: &str) -> RequestBuilder {
-    let url = format!("{}{}", get_base_url(), endpoint);
-    let req = Request::get(&url);
-    create_headers(req)
+fn build_request(request: &ApiRequest) -> Result<Request, HttpError> {
+    let url = request.url();
+    let builder = match request.method {
+        HttpMethod::Get => Request::get(&url),
+        HttpMethod::Post => Request::post(&url),
+        HttpMethod::Put => Request::put(&url),
+        HttpMethod::Delete => Request::delete(&url),
+    };
+    let signal = request.abort.as_ref().and_then(|handle| handle.signal());
+    let builder = builder.abort_signal(signal.as_ref());
+
+    match &request.body {
+        ApiBody::Empty => create_headers(builder).build(),
+        ApiBody::Json(value) => create_headers(builder).json(value),
+        ApiBody::Multipart(form_data) => create_multipart_headers(builder).body(form_data),
+    }
 }
 
-pub fn post<T: Serialize>(endpoint: &str, body: &T) -> Request {
-    let url = format!("{}{}", get_base_url(), endpoint);
-    let req_pre = Request::post(&url);
-    let req = create_headers(req_pre).json(body).unwrap();
-    req
+fn transport_error(e: &HttpError) -> TransportErrorInfo {
+    let (kind, message) = classify_transport_error(e);
+    TransportErrorInfo {
+        kind,
+        message: Some(message),
+    }
 }
 
-pub fn put<T: Serialize>(endpoint: &str, body: &T) -> Request {
-    let url = format!("{}{}", get_base_url(), endpoint);
-    let req_pre = Request::put(&url);
-    let req = create_headers(req_pre).json(body).unwrap();
-    req
+fn xhr_error(kind: TransportErrorKind, message: String) -> TransportErrorInfo {
+    TransportErrorInfo {
+        kind,
+        message: Some(message),
+    }
 }
 
-pub fn delete(endpoint: &str) -> RequestBuilder {
-    let url = format!("{}{}", get_base_url(), endpoint);
-    let req = Request::delete(&url);
-    create_headers(req)
+/// Aborts the request when the sending future is dropped, e.g. on a policy timeout.
+struct AbortOnDrop(XmlHttpRequest);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if self.0.ready_state() != XmlHttpRequest::DONE {
+            let _ = self.0.abort();
+        }
+    }
 }
 
-fn create_multipart_headers(mut req: RequestBuilder) -> RequestBuilder {
-    req = req
-        .header("csrf-token", APP_CSRF_TOKEN)
-        .credentials(RequestCredentials::Include);
-    // Note: Don't set Content-Type for multipart, browser will set it with boundary
-    req
+/// `name: value` lines of `getAllResponseHeaders`, with lower-cased names.
+fn parse_headers(raw: &str) -> HashMap<String, String> {
+    raw.lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect()
+}
+
+/// Send a multipart body with `XMLHttpRequest`, which unlike `fetch` reports upload progress.
+/// Progress events are queued by the browser callbacks and handed to `progress` from this
+/// future, so `progress` runs inside the task that awaits the request.
+async fn send_with_progress(
+    request: &ApiRequest,
+    form_data: &FormData,
+    progress: &UploadProgress,
+) -> ApiResult {
+    let js_error =
+        |e: wasm_bindgen::JsValue| xhr_error(TransportErrorKind::Unknown, format!("{:?}", e));
+    let xhr = XmlHttpRequest::new().map_err(js_error)?;
+    xhr.open(request.method.as_str(), &request.url())
+        .map_err(js_error)?;
+    xhr.set_with_credentials(true);
+    xhr.set_request_header("csrf-token", &csrf_token())
+        .map_err(js_error)?;
+
+    let (progress_tx, mut progress_rx) = mpsc::unbounded::<(u64, u64)>();
+    let on_progress = Closure::<dyn FnMut(ProgressEvent)>::new(move |event: ProgressEvent| {
+        if event.length_computable() {
+            let _ = progress_tx.unbounded_send((event.loaded() as u64, event.total() as u64));
+        }
+    });
+    xhr.upload()
+        .map_err(js_error)?
+        .set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+
+    let (done_tx, mut done_rx) = oneshot::channel::<Option<TransportErrorKind>>();
+    let done_tx = Rc::new(RefCell::new(Some(done_tx)));
+    let finish = |error: Option<TransportErrorKind>| {
+        let done_tx = done_tx.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            if let Some(tx) = done_tx.borrow_mut().take() {
+                let _ = tx.send(error);
+            }
+        })
+    };
+    let on_load = finish(None);
+    let on_error = finish(Some(TransportErrorKind::Network));
+    let on_abort = finish(Some(TransportErrorKind::Canceled));
+    xhr.set_onload(Some(on_load.as_ref().unchecked_ref()));
+    xhr.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    xhr.set_onabort(Some(on_abort.as_ref().unchecked_ref()));
+
+    let xhr = AbortOnDrop(xhr);
+    xhr.0
+        .send_with_opt_form_data(Some(form_data))
+        .map_err(js_error)?;
+
+    let outcome = loop {
+        match future::select(progress_rx.next(), &mut done_rx).await {
+            Either::Left((Some((sent, total)), _)) => progress.report(sent, total),
+            Either::Left((None, _)) => break (&mut done_rx).await,
+            Either::Right((outcome, _)) => break outcome,
+        }
+    };
+    let error = match outcome {
+        Ok(None) => None,
+        Ok(Some(_)) if is_offline() => {
+            Some((TransportErrorKind::Offline, "You appear to be offline"))
+        }
+        Ok(Some(TransportErrorKind::Canceled)) => {
+            Some((TransportErrorKind::Canceled, "Request canceled"))
+        }
+        Ok(Some(kind)) => Some((kind, "API server is unreachable")),
+        Err(_) => Some((
+            TransportErrorKind::Unknown,
+            "Upload ended without a response",
+        )),
+    };
+    if let Some((kind, message)) = error {
+        return Err(xhr_error(kind, message.to_string()));
+    }
+
+    let status = xhr.0.status().map_err(js_error)?;
+    let headers = parse_headers(&xhr.0.get_all_response_headers().map_err(js_error)?);
+    let body = xhr.0.response_text().map_err(js_error)?.unwrap_or_default();
+    Ok(ApiResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
+/// Sends requests with the browser `fetch` API via `gloo-net`, and multipart bodies that
+/// report upload progress with `XMLHttpRequest`.
+pub struct GlooClient;
+
+impl ApiClient for GlooClient {
+    fn send(&self, request: ApiRequest) -> LocalBoxFuture<'static, ApiResult> {
+        Box::pin(async move {
+            if let (ApiBody::Multipart(form_data), Some(progress)) =
+                (&request.body, &request.upload_progress)
+            {
+                return send_with_progress(&request, form_data, progress).await;
+            }
+            let req = build_request(&request).map_err(|e| transport_error(&e))?;
+            let response = req.send().await.map_err(|e| transport_error(&e))?;
+
+            let status = response.status();
+            let headers = response
+                .headers()
+                .entries()
+                .map(|(k, v)| (k.to_ascii_lowercase(), v))
+                .collect();
+            let body = response.text().await.map_err(|e| TransportErrorInfo {
+                kind: TransportErrorKind::Unknown,
+                message: Some(format!("Failed to read response body: {}", e)),
+            })?;
+
+            Ok(ApiResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
 }
 
-pub fn post_multipart(endpoint: &str, form_data: &FormData) -> Result<Request, String> {
-    let url = format!("{}{}", get_base_url(), endpoint);
-    let req_pre = Request::post(&url);
-    let req_builder = create_multipart_headers(req_pre);
+/// Heuristically classify a transport error and produce a user-facing message.
+pub fn classify_transport_error(e: &HttpError) -> (TransportErrorKind, String) {
+    if is_offline() {
+        return (
+            TransportErrorKind::Offline,
+            "You appear to be offline".to_string(),
+        );
+    }
+
+    match e {
+        HttpError::SerdeError(se) => {
+            return (
+                TransportErrorKind::Unknown,
+                format!("JSON serialization error: {}", se),
+            );
+        }
+        HttpError::JsError(js) => {
+            let name = js.name.to_lowercase();
+            let msg = js.message.to_lowercase();
+
+            if name.contains("abort") || msg.contains("abort") || msg.contains("canceled") {
+                return (TransportErrorKind::Canceled, "Request canceled".to_string());
+            }
+
+            if msg.contains("timeout") || msg.contains("timed out") || msg.contains("etimedout") {
+                return (TransportErrorKind::Timeout, "Request timed out".to_string());
+            }
+
+            if msg.contains("dns") || msg.contains("resolve") || msg.contains("name not resolved") {
+                return (
+                    TransportErrorKind::Network,
+                    "Could not resolve API host".to_string(),
+                );
+            }
+
+            if msg.contains("cors") || msg.contains("blocked by cors") {
+                return (
+                    TransportErrorKind::Network,
+                    "Request blocked by CORS configuration".to_string(),
+                );
+            }
 
-    let req = req_builder
-        .body(form_data)
-        .map_err(|e| format!("Failed to create multipart request: {:?}", e))?;
+            if msg.contains("failed to fetch")
+                || msg.contains("networkerror")
+                || msg.contains("network error")
+                || name.contains("typeerror")
+            {
+                return (
+                    TransportErrorKind::Network,
+                    "API server is unreachable".to_string(),
+                );
+            }
 
-    Ok(req)
+            return (
+                TransportErrorKind::Network,
+                format!("{}: {}", js.name, js.message),
+            );
+        }
+        HttpError::GlooError(s) => {
+            let s_l = s.to_lowercase();
+            if s_l.contains("timeout") {
+                return (TransportErrorKind::Timeout, "Request timed out".to_string());
+            }
+            (TransportErrorKind::Unknown, s.clone())
+        }
+    }
 }
@@ -1,4 +1,8 @@
+pub mod api_client;
+pub mod endpoints;
+#[cfg(target_arch = "wasm32")]
 pub mod http_client;
+pub mod request_policy;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod reqwest_client;
 
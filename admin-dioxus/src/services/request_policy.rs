@@ -0,0 +1,414 @@
+//! Timeouts, retries with backoff and cancellation applied around every [`ApiClient`] send.
+//!
+//! [`ApiRequest::send`] runs each request through [`execute`]: every attempt is raced against
+//! the policy timeout and the caller's [`AbortHandle`], and failed attempts are retried with
+//! exponential backoff when it is safe to do so. `429`/`503` responses honour the server's
+//! `Retry-After` (header or `ApiError.retry_after`).
+
+use crate::services::api_client::{ApiClient, ApiRequest, ApiResponse, ApiResult};
+use crate::store::{ApiError, TransportErrorInfo, TransportErrorKind};
+use futures_util::future::{self, Either};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestPolicy {
+    /// Per-attempt timeout. `None` waits indefinitely.
+    pub timeout: Option<Duration>,
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on every following attempt.
+    pub base_delay: Duration,
+    /// Upper bound for backoff delays. A `Retry-After` longer than this is not waited out.
+    pub max_delay: Duration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(20)),
+            max_retries: 2,
+            base_delay: Duration::from_millis(300),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// No timeout and no waiting between retries. Used by tests and the fake client.
+    pub fn immediate() -> Self {
+        Self {
+            timeout: None,
+            max_retries: 2,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Exponential backoff for the given (zero-based) retry.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay)
+    }
+
+    /// How long to wait before retrying `request`, or `None` when the result is final.
+    ///
+    /// Rate limiting (`429`) is retried for any method since the server rejected the call
+    /// outright. `503` is retried for any method when the server sent a `Retry-After`, and
+    /// otherwise only for idempotent requests, as are `502`/`504`, network errors and timeouts.
+    pub fn retry_delay(
+        &self,
+        request: &ApiRequest,
+        result: &ApiResult,
+        retry: u32,
+    ) -> Option<Duration> {
+        if retry >= self.max_retries {
+            return None;
+        }
+        match result {
+            Ok(response) => match response.status {
+                429 | 503 => match retry_after(response) {
+                    Some(wait) if wait <= self.max_delay.max(self.base_delay) => Some(wait),
+                    Some(_) => None,
+                    None if response.status == 429 || request.idempotent => {
+                        Some(self.backoff(retry))
+                    }
+                    None => None,
+                },
+                502 | 504 if request.idempotent => Some(self.backoff(retry)),
+                _ => None,
+            },
+            Err(e) => match e.kind {
+                TransportErrorKind::Network | TransportErrorKind::Timeout if request.idempotent => {
+                    Some(self.backoff(retry))
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// `Retry-After` in seconds, from the header or the `ApiError` body.
+pub fn retry_after(response: &ApiResponse) -> Option<Duration> {
+    response
+        .header("retry-after")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .or_else(|| response.json::<ApiError>().ok().and_then(|e| e.retry_after))
+        .map(Duration::from_secs)
+}
+
+thread_local! {
+    static DEFAULT_POLICY: Cell<RequestPolicy> = Cell::new(RequestPolicy::default());
+}
+
+/// Policy used for requests that don't set their own.
+pub fn default_policy() -> RequestPolicy {
+    DEFAULT_POLICY.with(|p| p.get())
+}
+
+pub fn set_default_policy(policy: RequestPolicy) {
+    DEFAULT_POLICY.with(|p| p.set(policy));
+}
+
+#[derive(Default)]
+struct AbortInner {
+    reason: Cell<Option<TransportErrorKind>>,
+    wakers: RefCell<Vec<Waker>>,
+    #[cfg(target_arch = "wasm32")]
+    controller: RefCell<Option<web_sys::AbortController>>,
+}
+
+/// Cancels an in-flight request. On the web the underlying `fetch` is aborted through an
+/// `AbortController`; everywhere else the pending future is simply dropped.
+#[derive(Clone, Default)]
+pub struct AbortHandle {
+    inner: Rc<AbortInner>,
+}
+
+impl AbortHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the request. The caller receives a `Canceled` transport error.
+    pub fn abort(&self) {
+        self.abort_with(TransportErrorKind::Canceled);
+    }
+
+    pub(crate) fn abort_with(&self, kind: TransportErrorKind) {
+        if self.is_aborted() {
+            return;
+        }
+        self.inner.reason.set(Some(kind));
+        #[cfg(target_arch = "wasm32")]
+        if let Some(controller) = self.inner.controller.borrow().as_ref() {
+            controller.abort();
+        }
+        for waker in self.inner.wakers.borrow_mut().drain(..) {
+            waker.wake();
+        }
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.inner.reason.get().is_some()
+    }
+
+    pub fn reason(&self) -> Option<TransportErrorKind> {
+        self.inner.reason.get()
+    }
+
+    /// Resolves once the handle is aborted.
+    pub fn aborted(&self) -> Aborted {
+        Aborted {
+            handle: self.clone(),
+        }
+    }
+
+    /// Signal to hand to `fetch`. The controller is created lazily on first use.
+    #[cfg(target_arch = "wasm32")]
+    pub fn signal(&self) -> Option<web_sys::AbortSignal> {
+        let mut controller = self.inner.controller.borrow_mut();
+        if controller.is_none() {
+            *controller = web_sys::AbortController::new().ok();
+        }
+        controller.as_ref().map(|c| c.signal())
+    }
+}
+
+impl PartialEq for AbortHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl fmt::Debug for AbortHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AbortHandle")
+            .field("reason", &self.reason())
+            .finish()
+    }
+}
+
+pub struct Aborted {
+    handle: AbortHandle,
+}
+
+impl Future for Aborted {
+    type Output = TransportErrorKind;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.handle.reason() {
+            Some(kind) => Poll::Ready(kind),
+            None => {
+                self.handle
+                    .inner
+                    .wakers
+                    .borrow_mut()
+                    .push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn interrupted(kind: TransportErrorKind, timeout: Option<Duration>) -> TransportErrorInfo {
+    let message = match (kind, timeout) {
+        (TransportErrorKind::Timeout, Some(t)) => {
+            Some(format!("Request timed out after {}s", t.as_secs_f32()))
+        }
+        _ => None,
+    };
+    TransportErrorInfo { kind, message }
+}
+
+async fn wait_or_abort(
+    duration: Option<Duration>,
+    cancel: Option<AbortHandle>,
+) -> TransportErrorKind {
+    let timer = Box::pin(async move {
+        match duration {
+            Some(d) => {
+                dioxus_time::sleep(d).await;
+                TransportErrorKind::Timeout
+            }
+            None => future::pending().await,
+        }
+    });
+    let canceled = Box::pin(async move {
+        match cancel {
+            Some(handle) => handle.aborted().await,
+            None => future::pending().await,
+        }
+    });
+    future::select(timer, canceled).await.factor_first().0
+}
+
+async fn attempt(
+    client: &Rc<dyn ApiClient>,
+    mut request: ApiRequest,
+    timeout: Option<Duration>,
+) -> ApiResult {
+    let cancel = request.abort.take();
+    let attempt_handle = AbortHandle::new();
+    request.abort = Some(attempt_handle.clone());
+
+    let send = client.send(request);
+    let interrupt = Box::pin(wait_or_abort(timeout, cancel));
+    match future::select(send, interrupt).await {
+        Either::Left((result, _)) => result,
+        Either::Right((kind, _)) => {
+            attempt_handle.abort_with(kind);
+            Err(interrupted(kind, timeout))
+        }
+    }
+}
+
+/// Send `request` through `client`, applying its policy (or the default one).
+pub async fn execute(client: Rc<dyn ApiClient>, request: ApiRequest) -> ApiResult {
+    let policy = request.policy.unwrap_or_else(default_policy);
+    let cancel = request.abort.clone();
+    let mut retry = 0;
+    loop {
+        if let Some(kind) = cancel.as_ref().and_then(|c| c.reason()) {
+            return Err(interrupted(kind, None));
+        }
+
+        let result = attempt(&client, request.clone(), policy.timeout).await;
+        let Some(delay) = policy.retry_delay(&request, &result, retry) else {
+            return result;
+        };
+        if !delay.is_zero() {
+            let cancel = cancel.clone();
+            let sleep = Box::pin(dioxus_time::sleep(delay));
+            let canceled = Box::pin(async move {
+                match cancel {
+                    Some(handle) => handle.aborted().await,
+                    None => future::pending().await,
+                }
+            });
+            if let Either::Right((kind, _)) = future::select(sleep, canceled).await {
+                return Err(interrupted(kind, None));
+            }
+        }
+        retry += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::{self, fake::FakeApiClient, HttpMethod};
+    use futures_util::FutureExt;
+
+    fn response_with_header(status: u16, name: &str, value: &str) -> ApiResult {
+        let mut response = ApiResponse::new(status, "");
+        response.headers.insert(name.to_string(), value.to_string());
+        Ok(response)
+    }
+
+    #[test]
+    fn idempotent_requests_retry_on_network_errors_and_gateway_failures() {
+        let fake = FakeApiClient::new();
+        fake.install();
+        let network = Err(TransportErrorInfo {
+            kind: TransportErrorKind::Network,
+            message: None,
+        });
+        fake.respond(HttpMethod::Get, "/flaky", network.clone());
+        fake.respond(HttpMethod::Get, "/flaky", Ok(ApiResponse::new(502, "")));
+        fake.respond(HttpMethod::Get, "/flaky", Ok(ApiResponse::new(200, "{}")));
+        fake.respond(HttpMethod::Post, "/create", network);
+
+        let ok = api_client::get("/flaky").send().now_or_never().unwrap();
+        assert!(ok.unwrap().is_success());
+
+        let err = api_client::post("/create", &())
+            .send()
+            .now_or_never()
+            .unwrap();
+        assert_eq!(err.unwrap_err().kind, TransportErrorKind::Network);
+        assert_eq!(fake.requests.borrow().len(), 4, "POST is not retried");
+    }
+
+    #[test]
+    fn rate_limits_honour_retry_after() {
+        let policy = RequestPolicy::default();
+        let post = api_client::post("/tag/v1/create", &());
+
+        let limited = response_with_header(429, "retry-after", "2");
+        assert_eq!(
+            policy.retry_delay(&post, &limited, 0),
+            Some(Duration::from_secs(2))
+        );
+
+        let body = r#"{"status":503,"retryAfter":3}"#;
+        let unavailable = Ok(ApiResponse::new(503, body));
+        assert_eq!(
+            policy.retry_delay(&post, &unavailable, 0),
+            Some(Duration::from_secs(3))
+        );
+
+        let too_long = response_with_header(429, "retry-after", "600");
+        assert_eq!(policy.retry_delay(&post, &too_long, 0), None);
+        assert_eq!(policy.retry_delay(&post, &limited, 2), None);
+        assert_eq!(
+            policy.retry_delay(&post, &Ok(ApiResponse::new(503, "")), 0),
+            None
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        let policy = RequestPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(300));
+        assert_eq!(policy.backoff(1), Duration::from_millis(600));
+        assert_eq!(policy.backoff(10), policy.max_delay);
+    }
+
+    #[test]
+    fn aborting_cancels_pending_requests() {
+        let fake = FakeApiClient::new();
+        fake.install();
+        fake.stall(HttpMethod::Get, "/slow");
+
+        let handle = AbortHandle::new();
+        let mut pending = api_client::get("/slow")
+            .with_abort(&handle)
+            .send()
+            .boxed_local();
+        assert!((&mut pending).now_or_never().is_none());
+
+        handle.abort();
+        let err = pending.now_or_never().unwrap().unwrap_err();
+        assert_eq!(err.kind, TransportErrorKind::Canceled);
+
+        let err = api_client::get("/slow")
+            .with_abort(&handle)
+            .send()
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.kind, TransportErrorKind::Canceled);
+        assert_eq!(
+            fake.requests.borrow().len(),
+            1,
+            "aborted handles short-circuit"
+        );
+    }
+}
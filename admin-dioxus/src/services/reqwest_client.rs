@@ -1,30 +1,21 @@
 #![cfg(not(target_arch = "wasm32"))]
-use crate::env::{APP_API_URL, APP_CSRF_TOKEN};
+//! `reqwest` backend for [`ApiClient`], used by desktop/native builds.
+use crate::services::api_client::{
+    csrf_token, ApiBody, ApiClient, ApiRequest, ApiResponse, ApiResult, HttpMethod,
+};
+use crate::store::{TransportErrorInfo, TransportErrorKind};
+use futures_util::future::LocalBoxFuture;
 use once_cell::sync::Lazy;
 use reqwest::{
     header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
-    Client, Method, Response,
+    Client, Method,
 };
-use serde::Serialize;
-
-/// Create the base URL, mirroring `src/services/http_client.rs`.
-pub fn get_base_url() -> String {
-    format!("http://{}", APP_API_URL)
-}
-
-/// Placeholder for auth token retrieval, if needed later.
-pub fn get_auth_token() -> Option<String> {
-    None
-}
 
 /// Global reqwest client with default headers and cookie store enabled.
+/// The CSRF token is added per request because it depends on the active API profile.
 static CLIENT: Lazy<Client> = Lazy::new(|| {
     let mut headers = HeaderMap::new();
     headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-    headers.insert(
-        HeaderName::from_static("csrf-token"),
-        HeaderValue::from_static(APP_CSRF_TOKEN),
-    );
 
     Client::builder()
         .default_headers(headers)
@@ -38,35 +29,79 @@ pub fn client() -> &'static Client {
     &CLIENT
 }
 
-/// Start a request builder with the preconfigured client.
-pub fn req(endpoint: &str, method: Method) -> reqwest::RequestBuilder {
-    let url = format!("{}{}", get_base_url(), endpoint);
-    client().request(method, url)
+/// Map a reqwest error onto the shared transport error kinds.
+pub fn classify_reqwest_error(e: &reqwest::Error) -> TransportErrorInfo {
+    let kind = if e.is_timeout() {
+        TransportErrorKind::Timeout
+    } else if e.is_connect() || e.is_request() {
+        TransportErrorKind::Network
+    } else {
+        TransportErrorKind::Unknown
+    };
+    let message = match kind {
+        TransportErrorKind::Timeout => "Request timed out".to_string(),
+        TransportErrorKind::Network => "API server is unreachable".to_string(),
+        _ => e.to_string(),
+    };
+    TransportErrorInfo {
+        kind,
+        message: Some(message),
+    }
 }
 
-/// Optionally add bearer auth to a request.
-pub fn with_bearer(builder: reqwest::RequestBuilder, token: &str) -> reqwest::RequestBuilder {
-    builder.bearer_auth(token)
-}
+/// Sends requests with the shared `reqwest` client.
+pub struct ReqwestClient;
 
-pub async fn get(endpoint: &str) -> reqwest::Result<Response> {
-    req(endpoint, Method::GET).send().await
-}
+impl ApiClient for ReqwestClient {
+    fn send(&self, request: ApiRequest) -> LocalBoxFuture<'static, ApiResult> {
+        Box::pin(async move {
+            let method = match request.method {
+                HttpMethod::Get => Method::GET,
+                HttpMethod::Post => Method::POST,
+                HttpMethod::Put => Method::PUT,
+                HttpMethod::Delete => Method::DELETE,
+            };
+            let mut builder = client().request(method, request.url());
+            if let Ok(token) = HeaderValue::from_str(&csrf_token()) {
+                builder = builder.header(HeaderName::from_static("csrf-token"), token);
+            }
+            match &request.body {
+                ApiBody::Empty => {}
+                ApiBody::Json(value) => builder = builder.json(value),
+                ApiBody::Multipart(_) => {
+                    return Err(TransportErrorInfo {
+                        kind: TransportErrorKind::Unknown,
+                        message: Some(
+                            "Multipart uploads are only supported in the web build".to_string(),
+                        ),
+                    });
+                }
+            }
 
-pub async fn delete(endpoint: &str) -> reqwest::Result<Response> {
-    req(endpoint, Method::DELETE).send().await
-}
-
-pub async fn post_json<T: Serialize + ?Sized>(
-    endpoint: &str,
-    body: &T,
-) -> reqwest::Result<Response> {
-    req(endpoint, Method::POST).json(body).send().await
-}
+            let response = builder
+                .send()
+                .await
+                .map_err(|e| classify_reqwest_error(&e))?;
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(k, v)| {
+                    v.to_str()
+                        .ok()
+                        .map(|v| (k.as_str().to_ascii_lowercase(), v.to_string()))
+                })
+                .collect();
+            let body = response
+                .text()
+                .await
+                .map_err(|e| classify_reqwest_error(&e))?;
 
-pub async fn put_json<T: Serialize + ?Sized>(
-    endpoint: &str,
-    body: &T,
-) -> reqwest::Result<Response> {
-    req(endpoint, Method::PUT).json(body).send().await
+            Ok(ApiResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
 }
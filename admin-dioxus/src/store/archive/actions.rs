@@ -0,0 +1,660 @@
+use chrono::Utc;
+use dioxus::prelude::*;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use web_sys::File;
+
+use super::{
+    media_refs, remap_media, ArchiveState, ArchivedMedia, ArchivedPost, ArchivedSeries,
+    ArchivedTerm, ExportReport, ImportReport, MediaRef, PostArchive,
+};
+use crate::services::api_client::ApiRequest;
+use crate::services::endpoints::{
+    CategoryCreate, CategoryList, Endpoint, PostCreate, PostList, PostUpdate, PostViewBySlug,
+    SeriesAddPost, SeriesCreate, SeriesList, TagCreate, TagList,
+};
+use crate::store::{
+    api_error_message, invalidate_queries, transport_error_message, use_media,
+    CategoriesAddPayload, CategoriesListQuery, Category, Media, MediaReference, MediaUploadPayload,
+    PaginatedList, Post, PostCreatePayload, PostEditPayload, PostListQuery, PostStatus, Series,
+    SeriesAddPostPayload, SeriesCreatePayload, SeriesListQuery, StateFrame, Tag, TagsAddPayload,
+    TagsListQuery,
+};
+use crate::utils::file_helpers::{
+    decode_base64, download_text, encode_base64, fetch_bytes, file_from_bytes, read_file_text,
+};
+
+/// Upper bound on list pages walked when exporting a query or matching terms by slug.
+const MAX_PAGES: u64 = 50;
+const UPLOAD_TIMEOUT_SECS: u32 = 60;
+
+async fn fetch<T: DeserializeOwned>(request: ApiRequest) -> Result<T, String> {
+    match request.send().await {
+        Ok(response) if response.is_success() => response
+            .json::<T>()
+            .map_err(|e| format!("Unexpected response: {}", e)),
+        Ok(response) => Err(api_error_message(&response)),
+        Err(e) => Err(transport_error_message(e)),
+    }
+}
+
+/// Every item of a paginated list, one request per page.
+async fn fetch_all<T: DeserializeOwned>(
+    page_request: impl Fn(u64) -> ApiRequest,
+) -> Result<Vec<T>, String> {
+    let mut items = Vec::new();
+    for page in 1..=MAX_PAGES {
+        let list: PaginatedList<T> = fetch(page_request(page)).await?;
+        let has_next_page = list.has_next_page();
+        items.extend(list.data);
+        if !has_next_page {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+/// Ids of this site's categories, tags and series by slug, creating missing ones on demand.
+struct Terms {
+    categories: HashMap<String, i32>,
+    tags: HashMap<String, i32>,
+    series: HashMap<String, i32>,
+}
+
+impl Terms {
+    async fn load() -> Result<Self, String> {
+        let categories: Vec<Category> = fetch_all(|page| {
+            CategoryList {}.request(&CategoriesListQuery {
+                page,
+                ..CategoriesListQuery::new()
+            })
+        })
+        .await?;
+        let tags: Vec<Tag> = fetch_all(|page| {
+            TagList {}.request(&TagsListQuery {
+                page,
+                ..TagsListQuery::new()
+            })
+        })
+        .await?;
+        let series: Vec<Series> = fetch_all(|page| {
+            SeriesList {}.request(&SeriesListQuery {
+                page: Some(page),
+                ..SeriesListQuery::new()
+            })
+        })
+        .await?;
+        Ok(Self {
+            categories: categories.into_iter().map(|c| (c.slug, c.id)).collect(),
+            tags: tags.into_iter().map(|t| (t.slug, t.id)).collect(),
+            series: series.into_iter().map(|s| (s.slug, s.id)).collect(),
+        })
+    }
+
+    async fn category(
+        &mut self,
+        term: &ArchivedTerm,
+        report: &mut ImportReport,
+    ) -> Result<i32, String> {
+        if let Some(id) = self.categories.get(&term.slug) {
+            return Ok(*id);
+        }
+        let category: Category = fetch(CategoryCreate {}.request(&CategoriesAddPayload {
+            name: term.name.clone(),
+            slug: term.slug.clone(),
+            color: term.color.clone(),
+            is_active: Some(true),
+            ..CategoriesAddPayload::default()
+        }))
+        .await?;
+        report
+            .created_terms
+            .push(format!("category \"{}\"", term.name));
+        self.categories.insert(term.slug.clone(), category.id);
+        Ok(category.id)
+    }
+
+    async fn tag(&mut self, term: &ArchivedTerm, report: &mut ImportReport) -> Result<i32, String> {
+        if let Some(id) = self.tags.get(&term.slug) {
+            return Ok(*id);
+        }
+        let tag: Tag = fetch(TagCreate {}.request(&TagsAddPayload {
+            name: term.name.clone(),
+            slug: term.slug.clone(),
+            color: Some(term.color.clone()),
+            is_active: Some(true),
+            ..TagsAddPayload::default()
+        }))
+        .await?;
+        report.created_terms.push(format!("tag \"{}\"", term.name));
+        self.tags.insert(term.slug.clone(), tag.id);
+        Ok(tag.id)
+    }
+
+    async fn series(
+        &mut self,
+        series: &ArchivedSeries,
+        report: &mut ImportReport,
+    ) -> Result<i32, String> {
+        if let Some(id) = self.series.get(&series.slug) {
+            return Ok(*id);
+        }
+        let created: Series = fetch(SeriesCreate {}.request(&SeriesCreatePayload {
+            name: series.name.clone(),
+            slug: series.slug.clone(),
+            description: None,
+        }))
+        .await?;
+        report
+            .created_terms
+            .push(format!("series \"{}\"", series.name));
+        self.series.insert(series.slug.clone(), created.id);
+        Ok(created.id)
+    }
+}
+
+impl ArchiveState {
+    pub fn is_running(&self) -> bool {
+        self.export.read().is_loading() || self.import.read().is_loading()
+    }
+
+    fn set_progress(&self, step: impl Into<String>) {
+        *self.progress.write() = Some(step.into());
+    }
+
+    /// Forget the last export and import results.
+    pub fn dismiss(&self) {
+        if !self.is_running() {
+            *self.export.write() = StateFrame::new();
+            *self.import.write() = StateFrame::new();
+        }
+    }
+
+    /// Export every post matching `query`, across all pages.
+    pub async fn export_query(&self, query: PostListQuery) {
+        if self.is_running() {
+            return;
+        }
+        self.export.write().set_loading();
+        self.set_progress("Collecting posts");
+        let posts = fetch_all(|page| {
+            PostList {}.request(&PostListQuery {
+                page: Some(page),
+                ..query.clone()
+            })
+        })
+        .await;
+        match posts {
+            Ok(posts) => self.export_posts(posts).await,
+            Err(message) => {
+                self.export.write().set_failed(message);
+                *self.progress.write() = None;
+            }
+        }
+    }
+
+    /// Bundle `posts` and the media they use into a JSON archive and download it. Media
+    /// that fail to download are reported and left out.
+    pub async fn export_posts(&self, posts: Vec<Post>) {
+        if self.import.read().is_loading() {
+            return;
+        }
+        self.export.write().set_loading();
+        let exported_at = Utc::now();
+        let mut archive = PostArchive::new(&posts, exported_at);
+
+        let mut refs = Vec::new();
+        for post in &posts {
+            for media in media_refs(post) {
+                if !refs
+                    .iter()
+                    .any(|existing: &MediaRef| existing.id == media.id)
+                {
+                    refs.push(media);
+                }
+            }
+        }
+        let mut skipped_media = Vec::new();
+        for (index, media) in refs.iter().enumerate() {
+            self.set_progress(format!("Downloading media {} of {}", index + 1, refs.len()));
+            match fetch_bytes(&media.url).await {
+                Ok((bytes, mime_type)) => archive.media.push(ArchivedMedia {
+                    id: media.id,
+                    file_name: media.file_name.clone(),
+                    mime_type,
+                    width: media.width,
+                    height: media.height,
+                    data: encode_base64(&bytes),
+                }),
+                Err(message) => skipped_media.push((media.file_name.clone(), message)),
+            }
+        }
+
+        let result = serde_json::to_string_pretty(&archive)
+            .map_err(|e| format!("Failed to write archive: {}", e))
+            .and_then(|json| {
+                let filename = format!("posts-{}.json", exported_at.format("%Y%m%d-%H%M%S"));
+                download_text(&filename, "application/json", &json)
+            });
+        match result {
+            Ok(()) => self.export.write().set_success(Some(ExportReport {
+                posts: archive.posts.len(),
+                media: archive.media.len(),
+                skipped_media,
+            })),
+            Err(message) => self.export.write().set_failed(message),
+        }
+        *self.progress.write() = None;
+    }
+
+    /// Import a file written by [`export_posts`](Self::export_posts).
+    pub async fn import_file(&self, file: File) {
+        if self.is_running() {
+            return;
+        }
+        self.import.write().set_loading();
+        let archive = read_file_text(&file)
+            .await
+            .and_then(|json| PostArchive::parse(&json));
+        match archive {
+            Ok(archive) => self.import_archive(archive).await,
+            Err(message) => self.import.write().set_failed(message),
+        }
+    }
+
+    /// Create the archive's posts on this site. Posts whose slug is taken are skipped and
+    /// reported; categories, tags and series are matched by slug and created when missing;
+    /// media are re-uploaded and the posts pointed at the new files.
+    pub async fn import_archive(&self, archive: PostArchive) {
+        self.import.write().set_loading();
+        let mut report = ImportReport::default();
+
+        let total = archive.posts.len();
+        let mut posts = Vec::new();
+        for (index, post) in archive.posts.into_iter().enumerate() {
+            self.set_progress(format!("Checking slug {} of {}", index + 1, total));
+            let request = PostViewBySlug {
+                id_or_slug: &post.slug,
+            }
+            .request(&());
+            match request.send().await {
+                Ok(response) if response.status == 404 => posts.push(post),
+                Ok(response) if response.is_success() => report.conflicts.push(post.slug),
+                Ok(response) => report.failures.push((
+                    post.title,
+                    format!("Could not check slug (status {})", response.status),
+                )),
+                Err(e) => report
+                    .failures
+                    .push((post.title, transport_error_message(e))),
+            }
+        }
+
+        if !posts.is_empty() {
+            if let Err(message) = self.import_posts(posts, &archive.media, &mut report).await {
+                self.import.write().set_failed(message);
+                *self.progress.write() = None;
+                return;
+            }
+        }
+
+        for resource in ["post", "category", "tag", "media"] {
+            invalidate_queries(resource);
+        }
+        self.import.write().set_success(Some(report));
+        *self.progress.write() = None;
+    }
+
+    async fn import_posts(
+        &self,
+        posts: Vec<ArchivedPost>,
+        media: &[ArchivedMedia],
+        report: &mut ImportReport,
+    ) -> Result<(), String> {
+        self.set_progress("Matching categories, tags and series");
+        let mut terms = Terms::load().await?;
+
+        let used: HashSet<i32> = posts.iter().flat_map(ArchivedPost::media_ids).collect();
+        let to_upload: Vec<&ArchivedMedia> =
+            media.iter().filter(|m| used.contains(&m.id)).collect();
+        let mut uploaded: HashMap<i32, Media> = HashMap::new();
+        for (index, file) in to_upload.iter().enumerate() {
+            self.set_progress(format!(
+                "Uploading media {} of {}",
+                index + 1,
+                to_upload.len()
+            ));
+            match upload(file).await {
+                Ok(media) => {
+                    uploaded.insert(file.id, media);
+                }
+                Err(message) => report.failures.push((file.file_name.clone(), message)),
+            }
+        }
+
+        let total = posts.len();
+        for (index, post) in posts.into_iter().enumerate() {
+            self.set_progress(format!("Creating post {} of {}", index + 1, total));
+            let title = post.title.clone();
+            match create_post(post, &mut terms, &uploaded, report).await {
+                Ok(warnings) => {
+                    for warning in warnings {
+                        report.warnings.push((title.clone(), warning));
+                    }
+                    report.created.push(title);
+                }
+                Err(message) => report.failures.push((title, message)),
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn upload(file: &ArchivedMedia) -> Result<Media, String> {
+    let bytes = decode_base64(&file.data)?;
+    let payload = MediaUploadPayload {
+        file: file_from_bytes(&bytes, &file.file_name, &file.mime_type)?,
+        reference_type: Some(MediaReference::Post),
+        width: file.width,
+        height: file.height,
+        optimize: false,
+    };
+    use_media()
+        .upload_and_wait(payload, UPLOAD_TIMEOUT_SECS)
+        .await
+}
+
+/// Create `post`, then archive it and add it to its series. `Err` only when the post was not
+/// created; follow-up steps that fail come back as warnings.
+async fn create_post(
+    mut post: ArchivedPost,
+    terms: &mut Terms,
+    uploaded: &HashMap<i32, Media>,
+    report: &mut ImportReport,
+) -> Result<Vec<String>, String> {
+    let category_id = terms.category(&post.category, report).await?;
+    let mut tag_ids = Vec::new();
+    for tag in &post.tags {
+        tag_ids.push(terms.tag(tag, report).await?);
+    }
+    let series_id = match &post.series {
+        Some(series) => Some(terms.series(series, report).await?),
+        None => None,
+    };
+    remap_media(&mut post.content, uploaded);
+
+    let created: Post = fetch(
+        PostCreate {}.request(&PostCreatePayload {
+            title: post.title,
+            content: post.content,
+            published_at: post.published_at,
+            is_published: post.status == PostStatus::Published,
+            slug: post.slug,
+            excerpt: post.excerpt,
+            featured_image_id: post
+                .featured_image_id
+                .and_then(|id| uploaded.get(&id))
+                .map(|media| media.id),
+            category_id,
+            tag_ids,
+            seo: Some(post.seo),
+        }),
+    )
+    .await?;
+
+    let mut warnings = Vec::new();
+    // Create only distinguishes published from draft.
+    if post.status == PostStatus::Archived {
+        let archived = fetch::<Post>(PostUpdate { id: created.id }.request(&PostEditPayload {
+            status: Some(PostStatus::Archived),
+            ..PostEditPayload::default()
+        }))
+        .await;
+        if let Err(message) = archived {
+            warnings.push(format!("Created as a draft, not archived: {}", message));
+        }
+    }
+    if let (Some(series_id), Some(series)) = (series_id, post.series) {
+        let added = fetch::<serde_json::Value>(
+            SeriesAddPost {
+                post_id: created.id,
+                series_id,
+            }
+            .request(&SeriesAddPostPayload {
+                position: series.position,
+            }),
+        )
+        .await;
+        if let Err(message) = added {
+            warnings.push(format!(
+                "Not added to series \"{}\": {}",
+                series.name, message
+            ));
+        }
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::{fake::with_fake_client, ApiBody, HttpMethod};
+    use crate::store::{use_archive, PostContent, PostSeo};
+    use dioxus::prelude::*;
+    use futures_util::FutureExt;
+    use serde_json::json;
+
+    fn content(blocks: serde_json::Value) -> PostContent {
+        serde_json::from_value(json!({ "time": 1, "blocks": blocks, "version": "2.30.7" })).unwrap()
+    }
+
+    fn media(id: i32, url: &str) -> Media {
+        serde_json::from_value(json!({
+            "id": id,
+            "object_key": format!("media/{}.png", id),
+            "file_url": url,
+            "mime_type": "image/png",
+            "size": 10,
+            "width": 20,
+            "height": 10,
+        }))
+        .unwrap()
+    }
+
+    fn archived_post(slug: &str) -> ArchivedPost {
+        ArchivedPost {
+            title: format!("Post {}", slug),
+            slug: slug.to_string(),
+            excerpt: None,
+            status: PostStatus::Draft,
+            published_at: None,
+            content: content(json!([{ "type": "paragraph", "data": { "text": "Hi" } }])),
+            category: ArchivedTerm {
+                name: "Guides".into(),
+                slug: "guides".into(),
+                color: "#000000".into(),
+            },
+            tags: vec![ArchivedTerm {
+                name: "Rust".into(),
+                slug: "rust".into(),
+                color: "#ff0000".into(),
+            }],
+            series: None,
+            featured_image_id: None,
+            seo: PostSeo::default(),
+        }
+    }
+
+    #[test]
+    fn remap_media_points_blocks_at_uploaded_files() {
+        let mut post_content = content(json!([
+            { "type": "image", "data": {
+                "file": { "url": "https://old/a.png", "width": null, "height": null,
+                          "name": "a.png", "title": null, "media_id": 1 },
+                "caption": null, "stretched": false } },
+            { "type": "attaches", "data": {
+                "file": { "url": "https://old/b.pdf", "size": 3, "name": "b.pdf",
+                          "extension": "pdf", "media_id": 2 } } },
+        ]));
+        let post = ArchivedPost {
+            content: post_content.clone(),
+            featured_image_id: Some(3),
+            ..archived_post("a")
+        };
+        assert_eq!(post.media_ids(), vec![3, 1, 2]);
+
+        let uploaded = HashMap::from([(1, media(41, "https://new/a.png"))]);
+        remap_media(&mut post_content, &uploaded);
+        match &post_content.blocks[0] {
+            crate::store::EditorJsBlock::Image { data, .. } => {
+                assert_eq!(data.file.media_id, Some(41));
+                assert_eq!(data.file.url, "https://new/a.png");
+            }
+            other => panic!("unexpected block {:?}", other),
+        }
+        match &post_content.blocks[1] {
+            crate::store::EditorJsBlock::Attaches { data, .. } => {
+                assert_eq!(data.file.media_id, None);
+                assert_eq!(data.file.url, "https://old/b.pdf");
+            }
+            other => panic!("unexpected block {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_foreign_json() {
+        assert!(PostArchive::parse(r#"{"posts": []}"#).is_err());
+        let mut archive = PostArchive::new(&[], Utc::now());
+        archive.format = "other".into();
+        let json = serde_json::to_string(&archive).unwrap();
+        assert!(PostArchive::parse(&json).is_err());
+
+        archive.format = crate::store::ARCHIVE_FORMAT.into();
+        let json = serde_json::to_string(&archive).unwrap();
+        assert_eq!(PostArchive::parse(&json).unwrap(), archive);
+    }
+
+    #[test]
+    fn import_skips_taken_slugs_and_creates_missing_terms() {
+        with_fake_client(|fake| {
+            let now = Utc::now();
+            let empty = json!({ "data": [], "total": 0, "page": 1, "per_page": 10 });
+            fake.respond_json(HttpMethod::Post, "/post/v1/view/taken", 200, &json!({}));
+            fake.respond_json(HttpMethod::Post, "/category/v1/list/query", 200, &empty);
+            fake.respond_json(HttpMethod::Post, "/post/v1/series/list", 200, &empty);
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/list/query",
+                200,
+                &json!({
+                    "data": [{ "id": 7, "name": "Rust", "slug": "rust", "created_at": now,
+                               "updated_at": now, "description": null, "color": "#ff0000",
+                               "text_color": "#ffffff", "is_active": true }],
+                    "total": 1, "page": 1, "per_page": 10,
+                }),
+            );
+            fake.respond_json(
+                HttpMethod::Post,
+                "/category/v1/create",
+                200,
+                &json!({ "id": 5, "name": "Guides", "slug": "guides", "created_at": now,
+                         "updated_at": now, "color": "#000000", "text_color": "#ffffff",
+                         "is_active": true, "cover": null, "logo": null, "cover_id": null,
+                         "description": null, "logo_id": null, "parent_id": null }),
+            );
+            // Only the status of the create response matters past this point.
+            fake.respond_json(HttpMethod::Post, "/post/v1/create", 400, &json!({}));
+
+            let mut archive = PostArchive::new(&[], now);
+            archive.posts = vec![archived_post("taken"), archived_post("fresh")];
+            let state = use_archive();
+            state.import_archive(archive).now_or_never().unwrap();
+
+            let frame = state.import.read();
+            let report = frame.data.clone().unwrap();
+            assert_eq!(report.conflicts, vec!["taken".to_string()]);
+            assert_eq!(
+                report.created_terms,
+                vec!["category \"Guides\"".to_string()]
+            );
+            assert!(report.created.is_empty());
+            assert_eq!(report.failures.len(), 1);
+            assert_eq!(report.failures[0].0, "Post fresh");
+
+            let requests = fake.requests.borrow();
+            let create = requests
+                .iter()
+                .find(|r| r.path == "/post/v1/create")
+                .unwrap();
+            let ApiBody::Json(body) = &create.body else {
+                panic!("expected a JSON body");
+            };
+            assert_eq!(body["slug"], "fresh");
+            assert_eq!(body["category_id"], 5);
+            assert_eq!(body["tag_ids"], json!([7]));
+        });
+    }
+
+    #[test]
+    fn import_keeps_created_posts_whose_follow_up_fails() {
+        with_fake_client(|fake| {
+            let now = Utc::now();
+            let empty = json!({ "data": [], "total": 0, "page": 1, "per_page": 10 });
+            fake.respond_json(HttpMethod::Post, "/post/v1/view/old", 404, &json!({}));
+            fake.respond_json(HttpMethod::Post, "/category/v1/list/query", 200, &empty);
+            fake.respond_json(HttpMethod::Post, "/post/v1/series/list", 200, &empty);
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/list/query",
+                200,
+                &json!({
+                    "data": [{ "id": 7, "name": "Rust", "slug": "rust", "created_at": now,
+                               "updated_at": now, "description": null, "color": "#ff0000",
+                               "text_color": "#ffffff", "is_active": true }],
+                    "total": 1, "page": 1, "per_page": 10,
+                }),
+            );
+            fake.respond_json(
+                HttpMethod::Post,
+                "/category/v1/create",
+                200,
+                &json!({ "id": 5, "name": "Guides", "slug": "guides", "created_at": now,
+                         "updated_at": now, "color": "#000000", "text_color": "#ffffff",
+                         "is_active": true, "cover": null, "logo": null, "cover_id": null,
+                         "description": null, "logo_id": null, "parent_id": null }),
+            );
+            fake.respond_json(
+                HttpMethod::Post,
+                "/post/v1/create",
+                200,
+                &json!({
+                    "id": 9, "title": "Post old", "slug": "old", "excerpt": null,
+                    "content": { "time": 1, "blocks": [], "version": "2.30.7" },
+                    "featured_image": null, "published_at": null, "created_at": now,
+                    "updated_at": now, "status": "Draft", "likes_count": 0, "view_count": 0,
+                    "comment_count": 0,
+                    "author": { "id": 1, "name": "A", "email": "a@x.test", "avatar": null },
+                    "category": { "id": 5, "name": "Guides", "slug": "guides",
+                                  "color": "#000000" },
+                }),
+            );
+            // No route for /post/v1/update/9: archiving it fails with a 404
+
+            let mut archive = PostArchive::new(&[], now);
+            archive.posts = vec![ArchivedPost {
+                status: PostStatus::Archived,
+                ..archived_post("old")
+            }];
+            let state = use_archive();
+            state.import_archive(archive).now_or_never().unwrap();
+
+            let frame = state.import.read();
+            let report = frame.data.clone().unwrap();
+            assert_eq!(report.created, vec!["Post old".to_string()]);
+            assert!(report.failures.is_empty());
+            assert_eq!(report.warnings.len(), 1);
+            assert_eq!(report.warnings[0].0, "Post old");
+            assert!(report.warnings[0].1.starts_with("Created as a draft"));
+        });
+    }
+}
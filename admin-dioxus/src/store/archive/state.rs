@@ -0,0 +1,285 @@
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::store::{EditorJsBlock, Media, Post, PostContent, PostSeo, PostStatus, StateFrame};
+
+/// Identifies a post archive file, so unrelated JSON is rejected on import.
+pub const ARCHIVE_FORMAT: &str = "ruxlog.posts";
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// A portable bundle of posts and the media they use, as written by export.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostArchive {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub posts: Vec<ArchivedPost>,
+    pub media: Vec<ArchivedMedia>,
+}
+
+/// A post with its relations referenced by slug, since ids do not carry over between sites.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedPost {
+    pub title: String,
+    pub slug: String,
+    pub excerpt: Option<String>,
+    pub status: PostStatus,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Media ids inside refer to [`ArchivedMedia::id`].
+    pub content: PostContent,
+    pub category: ArchivedTerm,
+    #[serde(default)]
+    pub tags: Vec<ArchivedTerm>,
+    #[serde(default)]
+    pub series: Option<ArchivedSeries>,
+    pub featured_image_id: Option<i32>,
+    #[serde(default)]
+    pub seo: PostSeo,
+}
+
+/// A category or tag.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedTerm {
+    pub name: String,
+    pub slug: String,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedSeries {
+    pub name: String,
+    pub slug: String,
+    pub position: Option<i32>,
+}
+
+/// A media file embedded in the archive.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedMedia {
+    /// Id on the exporting site.
+    pub id: i32,
+    pub file_name: String,
+    pub mime_type: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// File contents, base64 encoded.
+    pub data: String,
+}
+
+/// A media file a post uses, found by [`media_refs`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRef {
+    pub id: i32,
+    pub url: String,
+    pub file_name: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+}
+
+impl PostArchive {
+    /// Archive `posts` without media; export adds the files as they download.
+    pub fn new(posts: &[Post], exported_at: DateTime<Utc>) -> Self {
+        Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at,
+            posts: posts.iter().map(ArchivedPost::from_post).collect(),
+            media: Vec::new(),
+        }
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let archive: Self =
+            serde_json::from_str(json).map_err(|e| format!("Not a post archive: {}", e))?;
+        if archive.format != ARCHIVE_FORMAT {
+            return Err(format!(
+                "Not a post archive (format \"{}\")",
+                archive.format
+            ));
+        }
+        if archive.version > ARCHIVE_VERSION {
+            return Err(format!(
+                "Archive version {} is newer than this admin supports",
+                archive.version
+            ));
+        }
+        Ok(archive)
+    }
+}
+
+impl ArchivedPost {
+    pub fn from_post(post: &Post) -> Self {
+        Self {
+            title: post.title.clone(),
+            slug: post.slug.clone(),
+            excerpt: post.excerpt.clone(),
+            status: post.status.clone(),
+            published_at: post.published_at,
+            content: post.content.clone(),
+            category: ArchivedTerm {
+                name: post.category.name.clone(),
+                slug: post.category.slug.clone(),
+                color: post.category.color.clone(),
+            },
+            tags: post
+                .tags
+                .iter()
+                .map(|tag| ArchivedTerm {
+                    name: tag.name.clone(),
+                    slug: tag.slug.clone(),
+                    color: tag.color.clone(),
+                })
+                .collect(),
+            series: post.series.as_ref().map(|series| ArchivedSeries {
+                name: series.name.clone(),
+                slug: series.slug.clone(),
+                position: series.position,
+            }),
+            featured_image_id: post.featured_image.as_ref().map(|media| media.id),
+            seo: post.seo.clone(),
+        }
+    }
+
+    /// Ids of the archived media this post uses.
+    pub fn media_ids(&self) -> Vec<i32> {
+        self.featured_image_id
+            .into_iter()
+            .chain(block_media_ids(&self.content))
+            .collect()
+    }
+}
+
+fn block_media_ids(content: &PostContent) -> impl Iterator<Item = i32> + '_ {
+    content.blocks.iter().filter_map(|block| match block {
+        EditorJsBlock::Image { data, .. } => data.file.media_id,
+        EditorJsBlock::Attaches { data, .. } => data.file.media_id,
+        _ => None,
+    })
+}
+
+fn file_name_from_url(url: &str) -> String {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("file")
+        .to_string()
+}
+
+/// Media files used by `post`: the featured image, and image and attachment blocks that
+/// point at uploaded media. Each id is listed once.
+pub fn media_refs(post: &Post) -> Vec<MediaRef> {
+    let mut refs: Vec<MediaRef> = Vec::new();
+    if let Some(media) = &post.featured_image {
+        refs.push(MediaRef {
+            id: media.id,
+            url: media.file_url.clone(),
+            file_name: file_name_from_url(&media.object_key),
+            width: media.width,
+            height: media.height,
+        });
+    }
+    for block in &post.content.blocks {
+        let media = match block {
+            EditorJsBlock::Image { data, .. } => data.file.media_id.map(|id| MediaRef {
+                id,
+                url: data.file.url.clone(),
+                file_name: data
+                    .file
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| file_name_from_url(&data.file.url)),
+                width: data.file.width,
+                height: data.file.height,
+            }),
+            EditorJsBlock::Attaches { data, .. } => data.file.media_id.map(|id| MediaRef {
+                id,
+                url: data.file.url.clone(),
+                file_name: data.file.name.clone(),
+                width: None,
+                height: None,
+            }),
+            _ => None,
+        };
+        if let Some(media) = media {
+            if !refs.iter().any(|existing| existing.id == media.id) {
+                refs.push(media);
+            }
+        }
+    }
+    refs
+}
+
+/// Point image and attachment blocks at the media re-uploaded on import, keyed by archived
+/// id. Blocks whose file was not re-uploaded keep their URL but lose the id, which would
+/// otherwise name an unrelated file on this site.
+pub fn remap_media(content: &mut PostContent, uploaded: &HashMap<i32, Media>) {
+    for block in &mut content.blocks {
+        let (media_id, url) = match block {
+            EditorJsBlock::Image { data, .. } => (&mut data.file.media_id, &mut data.file.url),
+            EditorJsBlock::Attaches { data, .. } => (&mut data.file.media_id, &mut data.file.url),
+            _ => continue,
+        };
+        match media_id.and_then(|id| uploaded.get(&id)) {
+            Some(media) => {
+                *media_id = Some(media.id);
+                *url = media.file_url.clone();
+            }
+            None => *media_id = None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExportReport {
+    pub posts: usize,
+    pub media: usize,
+    /// Media files that could not be downloaded, with the reason. Posts still reference them
+    /// by URL.
+    pub skipped_media: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportReport {
+    /// Titles of the posts created.
+    pub created: Vec<String>,
+    /// Categories, tags and series that were missing and got created, e.g. `tag "Rust"`.
+    pub created_terms: Vec<String>,
+    /// Slugs skipped because a post with that slug already exists.
+    pub conflicts: Vec<String>,
+    /// Posts and media files that could not be imported, with the reason.
+    pub failures: Vec<(String, String)>,
+    /// Posts that were created but could not be archived or added to their series, with the
+    /// reason. They are also listed in `created`.
+    pub warnings: Vec<(String, String)>,
+}
+
+pub struct ArchiveState {
+    pub export: GlobalSignal<StateFrame<ExportReport>>,
+    pub import: GlobalSignal<StateFrame<ImportReport>>,
+    /// Current step of a running export or import, e.g. "Uploading media 2 of 5".
+    pub progress: GlobalSignal<Option<String>>,
+}
+
+impl ArchiveState {
+    pub fn new() -> Self {
+        Self {
+            export: GlobalSignal::new(StateFrame::new),
+            import: GlobalSignal::new(StateFrame::new),
+            progress: GlobalSignal::new(|| None),
+        }
+    }
+}
+
+impl Default for ArchiveState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static ARCHIVE_STATE: std::sync::OnceLock<ArchiveState> = std::sync::OnceLock::new();
+
+pub fn use_archive() -> &'static ArchiveState {
+    ARCHIVE_STATE.get_or_init(ArchiveState::new)
+}
@@ -1,5 +1,6 @@
 use super::{AuthState, AuthUser, LoginPayload, UserRole};
-use crate::{services::http_client, store::StateFrame};
+use crate::services::endpoints::{AuthLogIn, AuthLogOut, CurrentUser, Endpoint, ResponseOf};
+use crate::store::StateFrame;
 use dioxus::{logger::tracing, prelude::*};
 
 impl AuthUser {
@@ -38,30 +39,33 @@ impl AuthState {
     pub async fn logout(&self) {
         self.logout_status.write().set_loading();
         let empty_body = {};
-        let result = http_client::post("/auth/v1/log_out", &empty_body)
-            .send()
-            .await;
+        let result = AuthLogOut {}.request(&empty_body).send().await;
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
+                if response.is_success() {
                     self.logout_status.write().set_success(None);
                     *self.user.write() = None;
                     self.reset_all_stores();
                 } else {
-                    self.logout_status.write().set_api_error(&response).await;
+                    self.logout_status.write().set_api_error(&response);
                     *self.user.write() = None;
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 self.logout_status
                     .write()
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
                 *self.user.write() = None;
             }
         }
     }
 
+    /// Drop the signed-in session and every cached store, e.g. after switching API profiles.
+    pub fn reset_session(&self) {
+        self.reset();
+        self.reset_all_stores();
+    }
+
     fn reset_all_stores(&self) {
         use crate::store::{
             analytics::use_analytics, categories::use_categories, image_editor::use_image_editor,
@@ -75,17 +79,18 @@ impl AuthState {
         use_post().reset();
         use_analytics().reset();
         use_image_editor().reset();
+        crate::store::clear_query_cache();
     }
 
     pub async fn init(&self) {
         // self.init_status.write().set_success(None, None);
         // *self.user.write() = Some(User::dev());
         self.init_status.write().set_loading();
-        let result = http_client::get("/user/v1/get").send().await;
+        let result = CurrentUser {}.request(&()).send().await;
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
-                    match response.json::<AuthUser>().await {
+                if response.is_success() {
+                    match response.json::<ResponseOf<CurrentUser>>() {
                         Ok(user) => {
                             if !user.is_verified || !user.is_admin() {
                                 self.init_status.write().set_failed(
@@ -97,7 +102,7 @@ impl AuthState {
                             self.init_status.write().set_success(None);
                         }
                         Err(e) => {
-                            let raw = response.text().await.unwrap_or_default();
+                            let raw = response.text().to_string();
                             tracing::error!("Failed to parse user data: {}\nResponse: {}", e, raw);
                             self.init_status.write().set_decode_error(
                                 "user",
@@ -106,18 +111,17 @@ impl AuthState {
                             );
                         }
                     }
-                } else if response.status() == 401 {
+                } else if response.status == 401 {
                     // Unauthorized, no user logged in
                     self.init_status.write().set_success(None);
                 } else {
-                    self.init_status.write().set_api_error(&response).await;
+                    self.init_status.write().set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 self.init_status
                     .write()
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
             }
         }
     }
@@ -125,11 +129,11 @@ impl AuthState {
     pub async fn login(&self, email: String, password: String) {
         self.login_status.write().set_loading();
         let payload = LoginPayload { email, password };
-        let result = http_client::post("/auth/v1/log_in", &payload).send().await;
+        let result = AuthLogIn {}.request(&payload).send().await;
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
-                    match response.json::<AuthUser>().await {
+                if response.is_success() {
+                    match response.json::<ResponseOf<AuthLogIn>>() {
                         Ok(user) => {
                             if !user.is_verified || !user.is_admin() {
                                 self.login_status.write().set_failed(
@@ -141,7 +145,7 @@ impl AuthState {
                             self.login_status.write().set_success(None);
                         }
                         Err(e) => {
-                            let raw = response.text().await.unwrap_or_default();
+                            let raw = response.text().to_string();
                             eprintln!("Failed to parse user data: {}\nResponse: {}", e, raw);
                             self.login_status.write().set_decode_error(
                                 "user",
@@ -151,14 +155,13 @@ impl AuthState {
                         }
                     }
                 } else {
-                    self.login_status.write().set_api_error(&response).await;
+                    self.login_status.write().set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 self.login_status
                     .write()
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
             }
         }
     }
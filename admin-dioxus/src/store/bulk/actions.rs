@@ -0,0 +1,153 @@
+use super::{BulkItem, BulkItemStatus, BulkJob, BulkOperation, BulkState};
+use crate::store::{api_error_message, invalidate_queries, transport_error_message};
+use dioxus::prelude::*;
+
+impl BulkState {
+    pub fn is_running(&self) -> bool {
+        self.job
+            .read()
+            .as_ref()
+            .is_some_and(|job| !job.is_finished())
+    }
+
+    /// Apply `operation` to each `(id, label)` in turn, recording every item's outcome on
+    /// the job. Failures do not stop the run. Ignored while another job is running.
+    pub async fn run(&self, operation: BulkOperation, items: Vec<(i32, String)>) {
+        if self.is_running() || items.is_empty() {
+            return;
+        }
+        *self.job.write() = Some(BulkJob {
+            operation,
+            items: items
+                .into_iter()
+                .map(|(id, label)| BulkItem {
+                    id,
+                    label,
+                    status: BulkItemStatus::Pending,
+                })
+                .collect(),
+        });
+
+        let ids: Vec<i32> = self
+            .job
+            .read()
+            .iter()
+            .flat_map(|job| job.items.iter().map(|item| item.id))
+            .collect();
+        for (index, id) in ids.into_iter().enumerate() {
+            let status = match operation.request(id).send().await {
+                Ok(response) if response.is_success() => BulkItemStatus::Done,
+                Ok(response) => BulkItemStatus::Failed(api_error_message(&response)),
+                Err(e) => BulkItemStatus::Failed(transport_error_message(e)),
+            };
+            if let Some(item) = self
+                .job
+                .write()
+                .as_mut()
+                .and_then(|job| job.items.get_mut(index))
+            {
+                item.status = status;
+            }
+        }
+
+        invalidate_queries(operation.resource());
+    }
+
+    /// Run the job's operation again over the items that failed.
+    pub async fn retry_failed(&self) {
+        let Some((operation, failed)) = self.job.read().as_ref().map(|job| {
+            let failed = job
+                .failures()
+                .into_iter()
+                .map(|item| (item.id, item.label.clone()))
+                .collect::<Vec<_>>();
+            (job.operation, failed)
+        }) else {
+            return;
+        };
+        if self.is_running() {
+            return;
+        }
+        *self.job.write() = None;
+        self.run(operation, failed).await;
+    }
+
+    /// Forget a finished job.
+    pub fn dismiss(&self) {
+        if !self.is_running() {
+            *self.job.write() = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::{fake::with_fake_client, HttpMethod};
+    use crate::store::use_bulk;
+    use dioxus::prelude::*;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn run_reports_partial_failures_and_retries_them() {
+        with_fake_client(|fake| {
+            let ok = serde_json::json!({});
+            fake.respond_json(HttpMethod::Post, "/post/v1/delete/1", 200, &ok);
+            fake.respond_json(
+                HttpMethod::Post,
+                "/post/v1/delete/2",
+                403,
+                &serde_json::json!({ "status": 403, "message": "Not allowed" }),
+            );
+            fake.respond_json(HttpMethod::Post, "/post/v1/delete/2", 200, &ok);
+            fake.respond_json(HttpMethod::Post, "/post/v1/delete/3", 200, &ok);
+
+            let bulk = use_bulk();
+            let items = vec![
+                (1, "First".to_string()),
+                (2, "Second".to_string()),
+                (3, "Third".to_string()),
+            ];
+            bulk.run(BulkOperation::PostDelete, items)
+                .now_or_never()
+                .unwrap();
+
+            {
+                let job = bulk.job.read();
+                let job = job.as_ref().unwrap();
+                assert!(job.is_finished());
+                assert_eq!(job.succeeded(), 2);
+                assert_eq!(job.percent(), 100);
+                let failures = job.failures();
+                assert_eq!(failures.len(), 1);
+                assert_eq!(failures[0].id, 2);
+                assert_eq!(
+                    failures[0].status,
+                    BulkItemStatus::Failed("Not allowed".to_string())
+                );
+            }
+
+            bulk.retry_failed().now_or_never().unwrap();
+
+            let job = bulk.job.read();
+            let job = job.as_ref().unwrap();
+            assert_eq!(job.items.len(), 1);
+            assert_eq!(job.succeeded(), 1);
+            let paths: Vec<String> = fake
+                .requests
+                .borrow()
+                .iter()
+                .map(|r| r.path.clone())
+                .collect();
+            assert_eq!(
+                paths,
+                vec![
+                    "/post/v1/delete/1",
+                    "/post/v1/delete/2",
+                    "/post/v1/delete/3",
+                    "/post/v1/delete/2",
+                ]
+            );
+        });
+    }
+}
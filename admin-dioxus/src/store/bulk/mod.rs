@@ -0,0 +1,4 @@
+mod actions;
+mod state;
+
+pub use state::*;
@@ -0,0 +1,233 @@
+use crate::services::api_client::ApiRequest;
+use crate::services::endpoints::{
+    CategoryDelete, CategoryUpdate, Endpoint, MediaDelete, PostDelete, PostUpdate, TagDelete,
+    TagUpdate, UserDelete, UserUpdate,
+};
+use crate::store::{
+    CategoriesEditPayload, PostEditPayload, PostStatus, TagsEditPayload, UsersEditPayload,
+};
+use dioxus::prelude::*;
+
+/// A mutation applied to every selected row of a list screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkOperation {
+    PostPublish,
+    PostUnpublish,
+    PostArchive,
+    PostDelete,
+    TagActivate,
+    TagDeactivate,
+    TagDelete,
+    CategoryActivate,
+    CategoryDeactivate,
+    CategoryDelete,
+    UserVerify,
+    UserDelete,
+    MediaDelete,
+}
+
+impl BulkOperation {
+    /// Button label in the bulk actions bar.
+    pub fn label(&self) -> &'static str {
+        match self {
+            BulkOperation::PostPublish => "Publish",
+            BulkOperation::PostUnpublish => "Set as Draft",
+            BulkOperation::PostArchive => "Archive",
+            BulkOperation::TagActivate | BulkOperation::CategoryActivate => "Activate",
+            BulkOperation::TagDeactivate | BulkOperation::CategoryDeactivate => "Deactivate",
+            BulkOperation::UserVerify => "Verify",
+            BulkOperation::PostDelete
+            | BulkOperation::TagDelete
+            | BulkOperation::CategoryDelete
+            | BulkOperation::UserDelete
+            | BulkOperation::MediaDelete => "Delete",
+        }
+    }
+
+    /// Completes "3 posts …" in progress and result messages.
+    pub fn past_tense(&self) -> &'static str {
+        match self {
+            BulkOperation::PostPublish => "published",
+            BulkOperation::PostUnpublish => "moved to drafts",
+            BulkOperation::PostArchive => "archived",
+            BulkOperation::TagActivate | BulkOperation::CategoryActivate => "activated",
+            BulkOperation::TagDeactivate | BulkOperation::CategoryDeactivate => "deactivated",
+            BulkOperation::UserVerify => "verified",
+            BulkOperation::PostDelete
+            | BulkOperation::TagDelete
+            | BulkOperation::CategoryDelete
+            | BulkOperation::UserDelete
+            | BulkOperation::MediaDelete => "deleted",
+        }
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            BulkOperation::PostDelete
+                | BulkOperation::TagDelete
+                | BulkOperation::CategoryDelete
+                | BulkOperation::UserDelete
+                | BulkOperation::MediaDelete
+        )
+    }
+
+    /// Query-cache tag invalidated once the operation has run.
+    pub fn resource(&self) -> &'static str {
+        match self {
+            BulkOperation::PostPublish
+            | BulkOperation::PostUnpublish
+            | BulkOperation::PostArchive
+            | BulkOperation::PostDelete => "post",
+            BulkOperation::TagActivate
+            | BulkOperation::TagDeactivate
+            | BulkOperation::TagDelete => "tag",
+            BulkOperation::CategoryActivate
+            | BulkOperation::CategoryDeactivate
+            | BulkOperation::CategoryDelete => "category",
+            BulkOperation::UserVerify | BulkOperation::UserDelete => "user",
+            BulkOperation::MediaDelete => "media",
+        }
+    }
+
+    /// `"post"`/`"posts"` depending on `count`.
+    pub fn noun(&self, count: usize) -> &'static str {
+        let singular = count == 1;
+        match (self.resource(), singular) {
+            ("post", true) => "post",
+            ("post", false) => "posts",
+            ("tag", true) => "tag",
+            ("tag", false) => "tags",
+            ("category", true) => "category",
+            ("category", false) => "categories",
+            ("user", true) => "user",
+            ("user", false) => "users",
+            (_, true) => "media file",
+            (_, false) => "media files",
+        }
+    }
+
+    pub fn request(&self, id: i32) -> ApiRequest {
+        match self {
+            BulkOperation::PostPublish => {
+                PostUpdate { id }.request(&post_status(PostStatus::Published))
+            }
+            BulkOperation::PostUnpublish => {
+                PostUpdate { id }.request(&post_status(PostStatus::Draft))
+            }
+            BulkOperation::PostArchive => {
+                PostUpdate { id }.request(&post_status(PostStatus::Archived))
+            }
+            BulkOperation::PostDelete => PostDelete { id }.request(&()),
+            BulkOperation::TagActivate | BulkOperation::TagDeactivate => {
+                TagUpdate { id }.request(&TagsEditPayload {
+                    is_active: Some(*self == BulkOperation::TagActivate),
+                    ..TagsEditPayload::default()
+                })
+            }
+            BulkOperation::TagDelete => TagDelete { id }.request(&()),
+            BulkOperation::CategoryActivate | BulkOperation::CategoryDeactivate => {
+                CategoryUpdate { id }.request(&CategoriesEditPayload {
+                    is_active: Some(*self == BulkOperation::CategoryActivate),
+                    ..CategoriesEditPayload::default()
+                })
+            }
+            BulkOperation::CategoryDelete => CategoryDelete { id }.request(&()),
+            BulkOperation::UserVerify => UserUpdate { id }.request(&UsersEditPayload {
+                is_verified: Some(true),
+                ..UsersEditPayload::default()
+            }),
+            BulkOperation::UserDelete => UserDelete { id }.request(&()),
+            BulkOperation::MediaDelete => MediaDelete { id }.request(&()),
+        }
+    }
+}
+
+fn post_status(status: PostStatus) -> PostEditPayload {
+    PostEditPayload {
+        status: Some(status),
+        ..PostEditPayload::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkItemStatus {
+    Pending,
+    Done,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkItem {
+    pub id: i32,
+    /// Row name shown in the failure report, e.g. the post title.
+    pub label: String,
+    pub status: BulkItemStatus,
+}
+
+/// One run of a [`BulkOperation`] over a selection, in selection order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkJob {
+    pub operation: BulkOperation,
+    pub items: Vec<BulkItem>,
+}
+
+impl BulkJob {
+    pub fn processed(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status != BulkItemStatus::Pending)
+            .count()
+    }
+
+    pub fn succeeded(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status == BulkItemStatus::Done)
+            .count()
+    }
+
+    pub fn failures(&self) -> Vec<&BulkItem> {
+        self.items
+            .iter()
+            .filter(|item| matches!(item.status, BulkItemStatus::Failed(_)))
+            .collect()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.processed() == self.items.len()
+    }
+
+    /// 0-100, for the progress bar.
+    pub fn percent(&self) -> i32 {
+        if self.items.is_empty() {
+            return 100;
+        }
+        (self.processed() * 100 / self.items.len()) as i32
+    }
+}
+
+pub struct BulkState {
+    /// The running or last finished job, until dismissed.
+    pub job: GlobalSignal<Option<BulkJob>>,
+}
+
+impl BulkState {
+    pub fn new() -> Self {
+        Self {
+            job: GlobalSignal::new(|| None),
+        }
+    }
+}
+
+impl Default for BulkState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static BULK_STATE: std::sync::OnceLock<BulkState> = std::sync::OnceLock::new();
+
+pub fn use_bulk() -> &'static BulkState {
+    BULK_STATE.get_or_init(BulkState::new)
+}
@@ -1,9 +1,7 @@
-use crate::store::{ListQuery, ListStore, Media, PaginatedList, StateFrame};
+use crate::store::{ListQuery, Media, ResourceStore};
 use crate::types::SortParam;
 use chrono::{DateTime, Utc};
-use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Category {
@@ -122,42 +120,39 @@ pub struct CategoriesEditPayload {
     pub is_active: Option<bool>,
 }
 
-pub struct CategoriesState {
-    pub add: GlobalSignal<StateFrame<(), CategoriesAddPayload>>,
-    pub edit: GlobalSignal<HashMap<i32, StateFrame<(), CategoriesEditPayload>>>,
-    pub remove: GlobalSignal<HashMap<i32, StateFrame>>,
-    pub list: GlobalSignal<StateFrame<PaginatedList<Category>>>,
-    pub view: GlobalSignal<HashMap<i32, StateFrame<Category>>>,
-}
-
-impl CategoriesState {
-    pub fn new() -> Self {
-        Self {
-            add: GlobalSignal::new(|| StateFrame::new()),
-            edit: GlobalSignal::new(|| HashMap::new()),
-            remove: GlobalSignal::new(|| HashMap::new()),
-            list: GlobalSignal::new(|| StateFrame::new()),
-            view: GlobalSignal::new(|| HashMap::new()),
+impl CategoriesEditPayload {
+    /// Apply the changed fields to a cached category (used for optimistic updates).
+    /// Cover and logo media are left alone until the server returns them.
+    pub fn apply_to(&self, category: &mut Category) {
+        if let Some(name) = &self.name {
+            category.name = name.clone();
+        }
+        if let Some(slug) = &self.slug {
+            category.slug = slug.clone();
+        }
+        if let Some(parent_id) = self.parent_id {
+            category.parent_id = parent_id;
+        }
+        if let Some(description) = &self.description {
+            category.description = description.clone();
+        }
+        if let Some(color) = &self.color {
+            category.color = color.clone();
+        }
+        if let Some(text_color) = &self.text_color {
+            category.text_color = text_color.clone();
+        }
+        if let Some(is_active) = self.is_active {
+            category.is_active = is_active;
         }
     }
 }
 
-static CATEGORIES_STATE: std::sync::OnceLock<CategoriesState> = std::sync::OnceLock::new();
+pub type CategoriesState =
+    ResourceStore<Category, CategoriesListQuery, CategoriesAddPayload, CategoriesEditPayload>;
 
-impl ListStore<Category, CategoriesListQuery> for CategoriesState {
-    fn list_frame(&self) -> &GlobalSignal<StateFrame<PaginatedList<Category>>> {
-        &self.list
-    }
-
-    async fn fetch_list(&self) {
-        self.list().await;
-    }
-
-    async fn fetch_list_with_query(&self, query: CategoriesListQuery) {
-        self.list_with_query(query).await;
-    }
-}
+static CATEGORIES_STATE: std::sync::OnceLock<CategoriesState> = std::sync::OnceLock::new();
 
 pub fn use_categories() -> &'static CategoriesState {
-    CATEGORIES_STATE.get_or_init(|| CategoriesState::new())
+    CATEGORIES_STATE.get_or_init(|| CategoriesState::new(super::actions::config()))
 }
@@ -0,0 +1,250 @@
+use super::{DevtoolsState, RequestLogEntry, RequestLogFilter, REQUEST_LOG_CAPACITY};
+use crate::services::api_client::{ApiBody, ApiRequest, ApiResponse, ApiResult};
+use crate::store::{devtools_enabled, ApiError};
+use chrono::Utc;
+use dioxus::core::Runtime;
+use dioxus::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ENTRY_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Payload keys whose values are never logged, matched case-insensitively within the key
+/// (`password`, `new_password`, `two_fa_secret`, …).
+const CREDENTIAL_KEYS: [&str; 4] = ["password", "secret", "token", "otp"];
+
+/// Mask credential fields anywhere in `value`. Returns whether any were found.
+fn redact_credentials(value: &mut serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let mut redacted = false;
+            for (key, field) in fields.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if CREDENTIAL_KEYS
+                    .iter()
+                    .any(|credential| key.contains(credential))
+                {
+                    *field = serde_json::Value::String("[redacted]".to_string());
+                    redacted = true;
+                } else {
+                    redacted |= redact_credentials(field);
+                }
+            }
+            redacted
+        }
+        serde_json::Value::Array(items) => items
+            .iter_mut()
+            .map(redact_credentials)
+            .fold(false, |any, redacted| any || redacted),
+        _ => false,
+    }
+}
+
+fn response_request_id(response: &ApiResponse) -> Option<String> {
+    response
+        .header("x-request-id")
+        .map(str::to_string)
+        .or_else(|| response.json::<ApiError>().ok()?.request_id)
+}
+
+impl DevtoolsState {
+    /// Requests are only logged in debug builds and inside the Dioxus runtime.
+    pub fn is_recording(&self) -> bool {
+        devtools_enabled() && Runtime::try_current().is_some()
+    }
+
+    /// Log `request` as in flight and return the entry id for [`DevtoolsState::finish`].
+    pub fn start(&self, request: &ApiRequest) -> u64 {
+        let id = NEXT_ENTRY_ID.fetch_add(1, Ordering::Relaxed);
+        let (mut payload, multipart) = match &request.body {
+            ApiBody::Empty => (None, false),
+            ApiBody::Json(value) => (Some(value.clone()), false),
+            ApiBody::Multipart(_) => (None, true),
+        };
+        let redacted = payload.as_mut().is_some_and(redact_credentials);
+        let mut log = self.log.write();
+        log.push_back(RequestLogEntry {
+            id,
+            method: request.method,
+            path: request.path.clone(),
+            url: request.url(),
+            payload,
+            redacted,
+            multipart,
+            started_at: Utc::now(),
+            duration_ms: 0,
+            status: None,
+            transport_error: None,
+            request_id: None,
+            response_body: None,
+            decode_error: None,
+        });
+        while log.len() > REQUEST_LOG_CAPACITY {
+            log.pop_front();
+        }
+        id
+    }
+
+    pub fn finish(&self, id: u64, result: &ApiResult) {
+        let mut log = self.log.write();
+        let Some(entry) = log.iter_mut().rev().find(|entry| entry.id == id) else {
+            return;
+        };
+        entry.duration_ms = (Utc::now() - entry.started_at).num_milliseconds();
+        match result {
+            Ok(response) => {
+                entry.status = Some(response.status);
+                entry.request_id = response_request_id(response);
+                entry.response_body = Some(response.text().to_string());
+            }
+            Err(error) => {
+                entry.transport_error = Some(match &error.message {
+                    Some(message) => format!("{}: {}", error.kind.label(), message),
+                    None => error.kind.label().to_string(),
+                });
+            }
+        }
+    }
+
+    /// Attach a decode failure to the newest entry whose response body is `raw`.
+    pub fn note_decode_error(&self, raw: &str, error: &str) {
+        if !self.is_recording() {
+            return;
+        }
+        let mut log = self.log.write();
+        if let Some(entry) = log.iter_mut().rev().find(|entry| {
+            entry.decode_error.is_none() && entry.response_body.as_deref() == Some(raw)
+        }) {
+            entry.decode_error = Some(error.to_string());
+        }
+    }
+
+    /// Matching entries, newest first.
+    pub fn filtered(&self, filter: &RequestLogFilter) -> Vec<RequestLogEntry> {
+        self.log
+            .read()
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+            .cloned()
+            .collect()
+    }
+
+    /// Send the logged request again; the new attempt is logged as its own entry.
+    pub async fn resend(&self, id: u64) {
+        let Some(entry) = self.log.read().iter().find(|entry| entry.id == id).cloned() else {
+            return;
+        };
+        if !entry.can_resend() {
+            return;
+        }
+        let mut request = ApiRequest::new(entry.method, entry.path);
+        if let Some(payload) = &entry.payload {
+            request = request.with_json(payload);
+        }
+        let _ = request.send().await;
+    }
+
+    pub fn clear(&self) {
+        self.log.write().clear();
+    }
+
+    pub fn toggle(&self) {
+        let open = *self.open.peek();
+        *self.open.write() = !open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::api_client::{fake::with_fake_client, get, post, ApiResponse, HttpMethod};
+    use crate::store::{use_devtools, RequestLogFilter, StateFrame, TransportErrorKind};
+    use dioxus::prelude::*;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn logs_requests_decode_errors_and_resends() {
+        with_fake_client(|fake| {
+            let devtools = use_devtools();
+            devtools.clear();
+            let failed = ApiResponse::new(
+                500,
+                r#"{"type":"SYSTEM_001","status":500,"requestId":"req-1"}"#,
+            );
+            fake.respond(HttpMethod::Post, "/tag/v1/create", Ok(failed));
+            fake.respond(
+                HttpMethod::Get,
+                "/ping",
+                Ok(ApiResponse::new(200, "not json")),
+            );
+
+            let _ = post("/tag/v1/create", &serde_json::json!({ "name": "it's" }))
+                .send()
+                .now_or_never()
+                .unwrap();
+            let _ = get("/ping").send().now_or_never().unwrap();
+            StateFrame::<serde_json::Value>::new().set_decode_error(
+                "ping",
+                "expected value",
+                Some("not json".to_string()),
+            );
+
+            let errors = devtools.filtered(&RequestLogFilter {
+                errors_only: true,
+                ..RequestLogFilter::default()
+            });
+            assert_eq!(errors.len(), 2);
+            assert_eq!(errors[0].path, "/ping");
+            assert_eq!(errors[0].decode_error.as_deref(), Some("expected value"));
+            assert_eq!(errors[1].status, Some(500));
+            assert_eq!(errors[1].request_id.as_deref(), Some("req-1"));
+            assert!(errors[1]
+                .to_curl()
+                .contains(r#"--data-raw '{"name":"it'\''s"}'"#));
+
+            let posts = devtools.filtered(&RequestLogFilter {
+                method: Some(HttpMethod::Post),
+                ..RequestLogFilter::default()
+            });
+            devtools.resend(posts[0].id).now_or_never().unwrap();
+            assert_eq!(devtools.log.read().len(), 3);
+            assert_eq!(fake.requests.borrow().len(), 3);
+
+            fake.respond(
+                HttpMethod::Get,
+                "/down",
+                Err(crate::store::TransportErrorInfo {
+                    kind: TransportErrorKind::Timeout,
+                    message: None,
+                }),
+            );
+            let _ = get("/down").send().now_or_never().unwrap();
+            let last = devtools.log.read().back().cloned().unwrap();
+            assert_eq!(last.status_label(), "ERR");
+            assert_eq!(last.transport_error.as_deref(), Some("Timeout"));
+        });
+    }
+
+    #[test]
+    fn credentials_never_reach_the_log() {
+        with_fake_client(|fake| {
+            let devtools = use_devtools();
+            devtools.clear();
+            let body = serde_json::json!({
+                "email": "a@example.com",
+                "password": "hunter2",
+                "change": [{ "newPassword": "hunter3" }],
+            });
+            let _ = post("/auth/v1/log_in", &body)
+                .send()
+                .now_or_never()
+                .unwrap();
+
+            let entry = devtools.log.read().back().cloned().unwrap();
+            let curl = entry.to_curl();
+            assert!(curl.contains("a@example.com"));
+            assert!(!curl.contains("hunter2") && !curl.contains("hunter3"));
+            assert!(entry.redacted && !entry.can_resend());
+            assert_eq!(fake.requests.borrow().len(), 1);
+        });
+    }
+}
@@ -0,0 +1,132 @@
+use crate::services::api_client::{csrf_token, HttpMethod};
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use std::collections::VecDeque;
+
+/// Most recent requests kept in the log; older entries are dropped.
+pub const REQUEST_LOG_CAPACITY: usize = 200;
+
+/// The devtools drawer and request log only exist in debug builds.
+pub fn devtools_enabled() -> bool {
+    cfg!(debug_assertions)
+}
+
+/// One request sent through `ApiRequest::send`, recorded for the devtools drawer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestLogEntry {
+    pub id: u64,
+    pub method: HttpMethod,
+    /// Path relative to the API base URL.
+    pub path: String,
+    pub url: String,
+    /// JSON body; `None` for bodiless and multipart requests.
+    pub payload: Option<serde_json::Value>,
+    /// Credential fields of `payload` were masked, so it cannot be re-sent as logged.
+    pub redacted: bool,
+    /// Multipart uploads are logged but cannot be re-sent or copied with their files.
+    pub multipart: bool,
+    pub started_at: DateTime<Utc>,
+    /// Wall time including retries.
+    pub duration_ms: i64,
+    /// `None` when no response arrived.
+    pub status: Option<u16>,
+    /// Transport failure, e.g. `Timeout: Request timed out`.
+    pub transport_error: Option<String>,
+    /// From the `x-request-id` header or the `request_id` of an error body.
+    pub request_id: Option<String>,
+    pub response_body: Option<String>,
+    /// Set when a store failed to decode the response body.
+    pub decode_error: Option<String>,
+}
+
+impl RequestLogEntry {
+    pub fn is_error(&self) -> bool {
+        self.decode_error.is_some() || !matches!(self.status, Some(200..=299))
+    }
+
+    pub fn status_label(&self) -> String {
+        match (self.status, &self.transport_error) {
+            (Some(status), _) => status.to_string(),
+            (None, Some(_)) => "ERR".to_string(),
+            (None, None) => "pending".to_string(),
+        }
+    }
+
+    pub fn can_resend(&self) -> bool {
+        !self.multipart && !self.redacted
+    }
+
+    /// The request as a shell `curl` command (without cookies).
+    pub fn to_curl(&self) -> String {
+        let mut parts = vec![
+            "curl".to_string(),
+            "-X".to_string(),
+            self.method.as_str().to_string(),
+            shell_quote(&self.url),
+            "-H".to_string(),
+            shell_quote(&format!("csrf-token: {}", csrf_token())),
+        ];
+        if let Some(payload) = &self.payload {
+            parts.push("-H".to_string());
+            parts.push(shell_quote("Content-Type: application/json"));
+            parts.push("--data-raw".to_string());
+            parts.push(shell_quote(&payload.to_string()));
+        } else if self.multipart {
+            parts.push("-F".to_string());
+            parts.push(shell_quote("file=@<path>"));
+        }
+        parts.join(" ")
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Filters applied to the request log in the drawer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestLogFilter {
+    /// Case-insensitive match against path and request id.
+    pub search: String,
+    pub method: Option<HttpMethod>,
+    pub errors_only: bool,
+}
+
+impl RequestLogFilter {
+    pub fn matches(&self, entry: &RequestLogEntry) -> bool {
+        if self.method.is_some_and(|method| method != entry.method) {
+            return false;
+        }
+        if self.errors_only && !entry.is_error() {
+            return false;
+        }
+        let search = self.search.trim().to_lowercase();
+        search.is_empty()
+            || entry.path.to_lowercase().contains(&search)
+            || entry
+                .request_id
+                .as_deref()
+                .is_some_and(|id| id.to_lowercase().contains(&search))
+    }
+}
+
+pub struct DevtoolsState {
+    /// Newest entry last.
+    pub log: GlobalSignal<VecDeque<RequestLogEntry>>,
+    pub open: GlobalSignal<bool>,
+}
+
+impl Default for DevtoolsState {
+    fn default() -> Self {
+        Self {
+            log: GlobalSignal::new(VecDeque::new),
+            open: GlobalSignal::new(|| false),
+        }
+    }
+}
+
+static DEVTOOLS_STATE: std::sync::OnceLock<DevtoolsState> = std::sync::OnceLock::new();
+
+pub fn use_devtools() -> &'static DevtoolsState {
+    DEVTOOLS_STATE.get_or_init(DevtoolsState::default)
+}
@@ -1,5 +1,7 @@
-use crate::services::http_client::HttpError;
+use crate::services::api_client::ApiResponse;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
 /// Unified error carried by StateFrame
 #[derive(Debug, Clone, PartialEq)]
@@ -17,6 +19,38 @@ pub enum AppError {
 }
 
 impl AppError {
+    /// The error a failed (non-2xx) response carries: its [`ApiError`] body, or a decode
+    /// error when the body is not one.
+    pub fn from_response(response: &ApiResponse) -> Self {
+        let status = response.status;
+        let body = response.text();
+        match serde_json::from_str::<ApiError>(body) {
+            Ok(mut api_error) => {
+                if api_error.message.is_none() {
+                    let ty = api_error.r#type.clone().unwrap_or_default();
+                    api_error.message = Some(if ty.is_empty() {
+                        format!("Request failed (status {})", api_error.status)
+                    } else {
+                        format!(
+                            "Request failed with type {} (status {})",
+                            ty, api_error.status
+                        )
+                    });
+                }
+                AppError::Api(api_error)
+            }
+            Err(e) => AppError::Decode {
+                label: "api_error".to_string(),
+                error: format!("Failed to parse API error (status {}): {}", status, e),
+                raw: if body.is_empty() {
+                    None
+                } else {
+                    Some(body.to_string())
+                },
+            },
+        }
+    }
+
     pub fn message(&self) -> String {
         match self {
             AppError::Api(api) => api.message(),
@@ -45,6 +79,14 @@ impl AppError {
             AppError::Other { message } => message.clone(),
         }
     }
+
+    /// Per-field validation messages; empty unless this is an API error that carries them.
+    pub fn field_errors(&self) -> HashMap<String, String> {
+        match self {
+            AppError::Api(api) => api.field_errors(),
+            _ => HashMap::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -83,6 +125,44 @@ impl ApiError {
             format!("Request failed with type {} (status {})", ty, self.status)
         }
     }
+
+    /// Per-field validation messages from `context`, keyed by payload field name.
+    ///
+    /// Accepted shapes (the first message wins when a field has several):
+    /// - `{"fields": {"slug": "Slug already taken"}}`, where a value may also be a list of
+    ///   strings or of `{"message": ..}` objects (the `validator` crate's shape);
+    /// - `{"errors": [{"field": "slug", "message": "Slug already taken"}]}`.
+    pub fn field_errors(&self) -> HashMap<String, String> {
+        let mut errors = HashMap::new();
+        let Some(context) = &self.context else {
+            return errors;
+        };
+        if let Some(fields) = context.get("fields").and_then(Value::as_object) {
+            for (field, value) in fields {
+                if let Some(message) = first_message(value) {
+                    errors.insert(field.clone(), message);
+                }
+            }
+        }
+        if let Some(list) = context.get("errors").and_then(Value::as_array) {
+            for item in list {
+                let field = item.get("field").and_then(Value::as_str);
+                if let (Some(field), Some(message)) = (field, first_message(item)) {
+                    errors.entry(field.to_string()).or_insert(message);
+                }
+            }
+        }
+        errors
+    }
+}
+
+fn first_message(value: &Value) -> Option<String> {
+    match value {
+        Value::String(message) => Some(message.clone()),
+        Value::Array(items) => items.iter().find_map(first_message),
+        Value::Object(object) => object.get("message").and_then(first_message),
+        _ => None,
+    }
 }
 
 /// Transport-layer error information
@@ -129,6 +209,16 @@ pub struct TransportErrorInfo {
     pub message: Option<String>,
 }
 
+/// Message for a failed response of a request whose outcome is not kept in a `StateFrame`.
+pub fn api_error_message(response: &ApiResponse) -> String {
+    AppError::from_response(response).message()
+}
+
+/// Message for a transport failure of a request whose outcome is not kept in a `StateFrame`.
+pub fn transport_error_message(error: TransportErrorInfo) -> String {
+    AppError::Transport(error).message()
+}
+
 /// Best-effort offline detection (wasm only). Returns false on non-wasm targets.
 pub fn is_offline() -> bool {
     #[cfg(target_arch = "wasm32")]
@@ -144,70 +234,45 @@ pub fn is_offline() -> bool {
     }
 }
 
-/// Heuristically classify a transport error and produce a user-facing message.
-pub fn classify_transport_error(e: &HttpError) -> (TransportErrorKind, String) {
-    if is_offline() {
-        return (
-            TransportErrorKind::Offline,
-            "You appear to be offline".to_string(),
-        );
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    match e {
-        HttpError::SerdeError(se) => {
-            return (
-                TransportErrorKind::Unknown,
-                format!("JSON serialization error: {}", se),
-            );
+    fn api_error(context: Value) -> ApiError {
+        ApiError {
+            r#type: Some("VALIDATION".into()),
+            message: None,
+            status: 422,
+            details: None,
+            context: Some(context),
+            retry_after: None,
+            request_id: None,
         }
-        HttpError::JsError(js) => {
-            let name = js.name.to_lowercase();
-            let msg = js.message.to_lowercase();
-
-            if name.contains("abort") || msg.contains("abort") || msg.contains("canceled") {
-                return (TransportErrorKind::Canceled, "Request canceled".to_string());
-            }
-
-            if msg.contains("timeout") || msg.contains("timed out") || msg.contains("etimedout") {
-                return (TransportErrorKind::Timeout, "Request timed out".to_string());
-            }
-
-            if msg.contains("dns") || msg.contains("resolve") || msg.contains("name not resolved") {
-                return (
-                    TransportErrorKind::Network,
-                    "Could not resolve API host".to_string(),
-                );
-            }
-
-            if msg.contains("cors") || msg.contains("blocked by cors") {
-                return (
-                    TransportErrorKind::Network,
-                    "Request blocked by CORS configuration".to_string(),
-                );
-            }
+    }
 
-            if msg.contains("failed to fetch")
-                || msg.contains("networkerror")
-                || msg.contains("network error")
-                || name.contains("typeerror")
-            {
-                return (
-                    TransportErrorKind::Network,
-                    "API server is unreachable".to_string(),
-                );
-            }
+    #[test]
+    fn field_errors_accept_fields_map_and_errors_list() {
+        let errors = api_error(serde_json::json!({
+            "fields": {
+                "slug": "Slug already taken",
+                "name": [{ "code": "length", "message": "Name is too long" }],
+                "color": 3,
+            },
+            "errors": [
+                { "field": "email", "message": "Email is in use" },
+                { "field": "slug", "message": "ignored duplicate" },
+            ],
+        }))
+        .field_errors();
 
-            return (
-                TransportErrorKind::Network,
-                format!("{}: {}", js.name, js.message),
-            );
-        }
-        HttpError::GlooError(s) => {
-            let s_l = s.to_lowercase();
-            if s_l.contains("timeout") {
-                return (TransportErrorKind::Timeout, "Request timed out".to_string());
-            }
-            (TransportErrorKind::Unknown, s.clone())
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors["slug"], "Slug already taken");
+        assert_eq!(errors["name"], "Name is too long");
+        assert_eq!(errors["email"], "Email is in use");
+        assert!(AppError::Other {
+            message: "x".into()
         }
+        .field_errors()
+        .is_empty());
     }
 }
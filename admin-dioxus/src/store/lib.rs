@@ -1,14 +1,18 @@
-use crate::services::http_client::{HttpError, HttpRequest, HttpResponse};
-use crate::store::error::{
-    classify_transport_error, ApiError, AppError, TransportErrorInfo, TransportErrorKind,
-};
+use crate::services::api_client::{ApiBody, ApiRequest, ApiResponse, ApiResult};
+use crate::services::request_policy::AbortHandle;
+use crate::store::error::{AppError, TransportErrorInfo, TransportErrorKind};
+use crate::store::use_devtools;
+use chrono::{DateTime, Utc};
 use dioxus::logger::tracing;
-use dioxus::prelude::GlobalSignal;
+use dioxus::prelude::{GlobalSignal, Signal};
 use serde::{de::DeserializeOwned, Deserialize};
-use serde_json;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::future::Future;
 use std::hash::Hash;
+use std::rc::Rc;
+use std::time::Duration;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum StateFrameStatus {
@@ -149,45 +153,9 @@ impl<T: Clone, Q: Clone> StateFrame<T, Q> {
         self.meta = meta;
     }
 
-    pub async fn set_api_error(&mut self, response: &HttpResponse) {
+    pub fn set_api_error(&mut self, response: &ApiResponse) {
         self.status = StateFrameStatus::Failed;
-        let status = response.status();
-        let body = match response.text().await {
-            Ok(text) => text,
-            Err(e) => {
-                self.error = Some(AppError::Decode {
-                    label: "api_error".to_string(),
-                    error: format!("Failed to read API error body: {}", e),
-                    raw: None,
-                });
-                return;
-            }
-        };
-
-        match serde_json::from_str::<ApiError>(&body) {
-            Ok(mut api_error) => {
-                if api_error.message.is_none() {
-                    let ty = api_error.r#type.clone().unwrap_or_default();
-                    api_error.message = Some(if ty.is_empty() {
-                        format!("Request failed (status {})", api_error.status)
-                    } else {
-                        format!(
-                            "Request failed with type {} (status {})",
-                            ty, api_error.status
-                        )
-                    });
-                }
-
-                self.error = Some(AppError::Api(api_error));
-            }
-            Err(e) => {
-                self.error = Some(AppError::Decode {
-                    label: "api_error".to_string(),
-                    error: format!("Failed to parse API error (status {}): {}", status, e),
-                    raw: if body.is_empty() { None } else { Some(body) },
-                });
-            }
-        }
+        self.error = Some(AppError::from_response(response));
     }
 
     /// Mark this frame as a transport-layer failure (network/offline/etc.)
@@ -206,6 +174,9 @@ impl<T: Clone, Q: Clone> StateFrame<T, Q> {
         self.status = StateFrameStatus::Failed;
         let label_s = label.into();
         let err_s = err.into();
+        if let Some(raw) = &raw {
+            use_devtools().note_decode_error(raw, &err_s);
+        }
         self.error = Some(AppError::Decode {
             label: label_s,
             error: err_s,
@@ -260,28 +231,213 @@ impl<T: Clone, Q: Clone> StateFrame<T, Q> {
     }
 }
 
+thread_local! {
+    /// In-flight list request per list signal, keyed by the signal's address.
+    static LIST_IN_FLIGHT: RefCell<HashMap<usize, AbortHandle>> = RefCell::new(HashMap::new());
+    static QUERY_CACHE: RefCell<HashMap<String, QueryEntry>> = RefCell::new(HashMap::new());
+    static STALE_TIMES: RefCell<HashMap<String, Duration>> = RefCell::new(HashMap::new());
+}
+
+fn signal_key<T>(state: &GlobalSignal<T>) -> usize {
+    state as *const GlobalSignal<T> as usize
+}
+
+/// Abort the pending list request for `state`, if any. The frame keeps its current data.
+pub fn cancel_list_request<T>(state: &GlobalSignal<T>) {
+    if let Some(handle) = LIST_IN_FLIGHT.with(|m| m.borrow_mut().remove(&signal_key(state))) {
+        handle.abort();
+    }
+}
+
+// ============================================================================
+// Query cache
+// ============================================================================
+
+/// How long a cached query is served without revalidating, unless overridden per tag.
+pub const DEFAULT_STALE_TIME: Duration = Duration::from_secs(30);
+
+/// Most queries cached at once. Every search term and page is its own entry, so the least
+/// recently fetched ones are dropped past this.
+const QUERY_CACHE_CAPACITY: usize = 64;
+
+/// A successful list/view response remembered by [`list_state_abstraction`] and
+/// [`view_state_abstraction`].
+struct QueryEntry {
+    value: Rc<dyn Any>,
+    fetched_at: DateTime<Utc>,
+    stale_time: Duration,
+    invalidated: bool,
+    /// Address of the signal the entry hydrates; edits and removals invalidate by owner.
+    owner: usize,
+    tags: Vec<String>,
+}
+
+impl QueryEntry {
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        !self.invalidated
+            && (now - self.fetched_at)
+                .to_std()
+                .map_or(true, |age| age < self.stale_time)
+    }
+}
+
+/// Cache key: method, endpoint and the serialized body (the `ListQuery` for list endpoints).
+pub fn query_key(req: &ApiRequest) -> String {
+    match &req.body {
+        ApiBody::Json(body) => format!("{} {} {}", req.method, req.path, body),
+        _ => format!("{} {}", req.method, req.path),
+    }
+}
+
+/// Invalidation tag for an endpoint: its resource segment, e.g. `/tag/v1/list/query` → `tag`.
+pub fn query_tag(path: &str) -> String {
+    path.split('/')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Override [`DEFAULT_STALE_TIME`] for every query tagged `tag`.
+pub fn set_query_stale_time(tag: &str, stale_time: Duration) {
+    STALE_TIMES.with(|t| t.borrow_mut().insert(tag.to_string(), stale_time));
+}
+
+fn stale_time_for(tag: &str) -> Duration {
+    STALE_TIMES.with(|t| t.borrow().get(tag).copied().unwrap_or(DEFAULT_STALE_TIME))
+}
+
+/// Mark every query tagged `tag` as stale. Cached data is still shown, but the next
+/// fetch revalidates it.
+pub fn invalidate_queries(tag: &str) {
+    QUERY_CACHE.with(|cache| {
+        for entry in cache.borrow_mut().values_mut() {
+            if entry.tags.iter().any(|t| t == tag) {
+                entry.invalidated = true;
+            }
+        }
+    });
+}
+
+/// Invalidate the queries hydrating `state` along with everything sharing their tags.
+pub fn invalidate_signal_queries<T>(state: &GlobalSignal<T>) {
+    let owner = signal_key(state);
+    let tags: Vec<String> = QUERY_CACHE.with(|cache| {
+        cache
+            .borrow()
+            .values()
+            .filter(|entry| entry.owner == owner)
+            .flat_map(|entry| entry.tags.clone())
+            .collect()
+    });
+    QUERY_CACHE.with(|cache| {
+        for entry in cache.borrow_mut().values_mut() {
+            if entry.owner == owner || entry.tags.iter().any(|t| tags.contains(t)) {
+                entry.invalidated = true;
+            }
+        }
+    });
+}
+
+/// Drop every cached query, e.g. when the session or API profile changes.
+pub fn clear_query_cache() {
+    QUERY_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// Cached value for `key` and whether it is still fresh.
+fn cached_query<T: Clone + 'static>(key: &str) -> Option<(T, bool)> {
+    let now = Utc::now();
+    QUERY_CACHE.with(|cache| {
+        let cache = cache.borrow();
+        let entry = cache.get(key)?;
+        let value = entry.value.downcast_ref::<T>()?.clone();
+        Some((value, entry.is_fresh(now)))
+    })
+}
+
+fn store_query<T: 'static>(key: String, owner: usize, path: &str, value: T) {
+    let tag = query_tag(path);
+    let entry = QueryEntry {
+        value: Rc::new(value),
+        fetched_at: Utc::now(),
+        stale_time: stale_time_for(&tag),
+        invalidated: false,
+        owner,
+        tags: vec![tag],
+    };
+    QUERY_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.insert(key, entry);
+        if cache.len() > QUERY_CACHE_CAPACITY {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.fetched_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+    });
+}
+
 /// Send a request, parse JSON into `T`, and update the provided `StateFrame<T>`.
 /// Returns `Some(T)` on success to allow callers to perform cache-sync logic if needed.
+///
+/// Responses are cached per endpoint and query (stale-while-revalidate): a fresh entry is
+/// served without a request, a stale one is shown immediately while it is refetched, so
+/// the frame only enters `Loading` on a cache miss.
+///
+/// List queries are treated as idempotent so they are retried on transient failures, and a
+/// newer query for the same `state` cancels the previous one (e.g. while typing a search).
+/// A superseded request returns `None` without touching the frame.
 pub async fn list_state_abstraction<T>(
     state: &GlobalSignal<StateFrame<T>>,
-    req: HttpRequest,
+    req: ApiRequest,
     parse_label: &str,
 ) -> Option<T>
 where
     T: DeserializeOwned + Clone + 'static,
 {
-    state.write().set_loading();
-    match req.send().await {
+    cancel_list_request(state);
+
+    let owner = signal_key(state);
+    let cache_key = query_key(&req);
+    let cached = cached_query::<T>(&cache_key);
+    match &cached {
+        Some((data, true)) => {
+            state.write().set_success(Some(data.clone()));
+            return Some(data.clone());
+        }
+        Some((data, false)) => state.write().set_success(Some(data.clone())),
+        None => state.write().set_loading(),
+    }
+
+    let handle = AbortHandle::new();
+    LIST_IN_FLIGHT.with(|m| m.borrow_mut().insert(owner, handle.clone()));
+    let path = req.path.clone();
+    let result = req.idempotent().with_abort(&handle).send().await;
+
+    if handle.is_aborted() {
+        return None;
+    }
+    LIST_IN_FLIGHT.with(|m| {
+        let mut map = m.borrow_mut();
+        if map.get(&owner) == Some(&handle) {
+            map.remove(&owner);
+        }
+    });
+
+    match result {
         Ok(response) => {
-            if (200..300).contains(&response.status()) {
-                match response.json::<T>().await {
+            if response.is_success() {
+                match response.json::<T>() {
                     Ok(data) => {
+                        store_query(cache_key, owner, &path, data.clone());
                         state.write().set_success(Some(data.clone()));
                         Some(data)
                     }
                     Err(e) => {
                         // Try to get the raw response text for better debugging
-                        let response_text = response.text().await.unwrap_or_default();
+                        let response_text = response.text().to_string();
                         tracing::error!(
                             "Failed to parse {}: {:?}\nResponse: {}",
                             parse_label,
@@ -297,13 +453,17 @@ where
                     }
                 }
             } else {
-                state.write().set_api_error(&response).await;
+                state.write().set_api_error(&response);
                 None
             }
         }
+        Err(e) if cached.is_some() => {
+            // Keep showing the cached data; the next fetch revalidates again.
+            tracing::warn!("Failed to revalidate {}: {:?}", parse_label, e);
+            None
+        }
         Err(e) => {
-            let (kind, msg) = classify_transport_error(&e);
-            state.write().set_transport_error(kind, Some(msg));
+            state.write().set_transport_error(e.kind, e.message);
             None
         }
     }
@@ -322,7 +482,7 @@ where
     Data: Clone + 'static,
     Meta: Clone + 'static,
     Parsed: DeserializeOwned + Clone + 'static,
-    F: Future<Output = Result<HttpResponse, HttpError>>,
+    F: Future<Output = ApiResult>,
     OnSuccess: Fn(&Parsed) -> (Option<Data>, Option<String>),
 {
     {
@@ -332,15 +492,15 @@ where
 
     match send_future.await {
         Ok(response) => {
-            if (200..300).contains(&response.status()) {
-                match response.json::<Parsed>().await {
+            if response.is_success() {
+                match response.json::<Parsed>() {
                     Ok(parsed) => {
                         let (data, _message) = on_success(&parsed);
                         state.write().set_success(data);
                         Some(parsed)
                     }
                     Err(e) => {
-                        let response_text = response.text().await.unwrap_or_default();
+                        let response_text = response.text().to_string();
                         state.write().set_decode_error(
                             parse_label,
                             format!("{}", e),
@@ -350,13 +510,12 @@ where
                     }
                 }
             } else {
-                state.write().set_api_error(&response).await;
+                state.write().set_api_error(&response);
                 None
             }
         }
         Err(e) => {
-            let (kind, msg) = classify_transport_error(&e);
-            state.write().set_transport_error(kind, Some(msg));
+            state.write().set_transport_error(e.kind, e.message);
             None
         }
     }
@@ -364,10 +523,10 @@ where
 
 /// Shared helper to fetch a single record and hydrate a keyed `StateFrame` map.
 /// Returns `Some(Parsed)` on success so callers can optionally sync additional caches.
-pub async fn view_state_abstraction<K, StoreData, Parsed, F, MapFn>(
+pub async fn view_state_abstraction<K, StoreData, Parsed, MapFn>(
     state: &GlobalSignal<HashMap<K, StateFrame<StoreData>>>,
     id: K,
-    send_future: F,
+    req: ApiRequest,
     parse_label: &str,
     map_to_store: MapFn,
 ) -> Option<Parsed>
@@ -375,19 +534,30 @@ where
     K: Eq + Hash + Copy + 'static,
     StoreData: Clone + 'static,
     Parsed: DeserializeOwned + Clone + 'static,
-    F: Future<Output = Result<HttpResponse, HttpError>>,
     MapFn: Fn(&Parsed) -> StoreData,
 {
+    let owner = signal_key(state);
+    let cache_key = query_key(&req);
+    let cached = cached_query::<Parsed>(&cache_key);
     {
         let mut map = state.write();
-        map.entry(id).or_insert_with(StateFrame::new).set_loading();
+        let frame = map.entry(id).or_insert_with(StateFrame::new);
+        match &cached {
+            Some((parsed, _)) => frame.set_success(Some(map_to_store(parsed))),
+            None => frame.set_loading(),
+        }
+    }
+    if let Some((parsed, true)) = cached {
+        return Some(parsed);
     }
 
-    match send_future.await {
+    let path = req.path.clone();
+    match req.idempotent().send().await {
         Ok(response) => {
-            if (200..300).contains(&response.status()) {
-                match response.json::<Parsed>().await {
+            if response.is_success() {
+                match response.json::<Parsed>() {
                     Ok(parsed) => {
+                        store_query(cache_key, owner, &path, parsed.clone());
                         let store_value = map_to_store(&parsed);
                         let mut map = state.write();
                         map.entry(id)
@@ -396,7 +566,7 @@ where
                         Some(parsed)
                     }
                     Err(e) => {
-                        let response_text = response.text().await.unwrap_or_default();
+                        let response_text = response.text().to_string();
                         tracing::error!(
                             "Failed to parse {}: {}\nResponse: {}",
                             parse_label,
@@ -414,24 +584,121 @@ where
                 let mut map = state.write();
                 map.entry(id)
                     .or_insert_with(StateFrame::new)
-                    .set_api_error(&response)
-                    .await;
+                    .set_api_error(&response);
                 None
             }
         }
+        Err(e) if cached.is_some() => {
+            tracing::warn!("Failed to revalidate {}: {:?}", parse_label, e);
+            None
+        }
         Err(e) => {
-            let (kind, msg) = classify_transport_error(&e);
             let mut map = state.write();
             map.entry(id)
                 .or_insert_with(StateFrame::new)
-                .set_transport_error(kind, Some(msg));
+                .set_transport_error(e.kind, e.message);
             None
         }
     }
 }
 
+// ============================================================================
+// Optimistic mutations
+// ============================================================================
+
+/// Opt-in optimistic mode for [`edit_state_abstraction`] and [`remove_state_abstraction`]:
+/// caches are patched before the request is sent and restored when it fails.
+pub struct Optimistic<Apply = ()> {
+    apply: Apply,
+    retry: Option<Rc<dyn Fn()>>,
+}
+
+impl Optimistic {
+    /// Drop the item from the list and view caches immediately.
+    pub fn remove() -> Self {
+        Self {
+            apply: (),
+            retry: None,
+        }
+    }
+}
+
+impl<Apply> Optimistic<Apply> {
+    /// Patch the cached item with the payload immediately.
+    pub fn edit(apply: Apply) -> Self {
+        Self { apply, retry: None }
+    }
+
+    /// Action behind the "Retry" button of the rollback toast.
+    pub fn with_retry(mut self, retry: impl Fn() + 'static) -> Self {
+        self.retry = Some(Rc::new(retry));
+        self
+    }
+}
+
+struct EditSnapshot<T: Clone> {
+    list_item: Option<T>,
+    view_frame: Option<StateFrame<T>>,
+}
+
+struct RemoveSnapshot<T: Clone> {
+    list_item: Option<(usize, T)>,
+    /// Whether removing `list_item` also decremented the list's `total`.
+    total_decremented: bool,
+    view_frame: Option<StateFrame<T>>,
+}
+
+/// An optimistic mutation that failed and was rolled back. `RollbackToasts` turns these
+/// into toasts with a retry action.
+#[derive(Clone)]
+pub struct MutationRollback {
+    pub id: u64,
+    pub label: String,
+    pub error: AppError,
+    pub retry: Option<Rc<dyn Fn()>>,
+}
+
+impl PartialEq for MutationRollback {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+pub static MUTATION_ROLLBACKS: GlobalSignal<Vec<MutationRollback>> = Signal::global(Vec::new);
+
+thread_local! {
+    static NEXT_ROLLBACK_ID: Cell<u64> = const { Cell::new(1) };
+}
+
+fn report_rollback(label: &str, error: AppError, retry: Option<Rc<dyn Fn()>>) {
+    let id = NEXT_ROLLBACK_ID.with(|next| next.replace(next.get() + 1));
+    MUTATION_ROLLBACKS.write().push(MutationRollback {
+        id,
+        label: label.to_string(),
+        error,
+        retry,
+    });
+}
+
+/// Mutations fire the invalidation tags of the caches they patch so other pages and
+/// filters refetch on their next visit.
+fn invalidate_synced_queries<L, V>(
+    list_cache: Option<&GlobalSignal<L>>,
+    view_cache: Option<&GlobalSignal<V>>,
+) {
+    if let Some(list_cache) = list_cache {
+        invalidate_signal_queries(list_cache);
+    }
+    if let Some(view_cache) = view_cache {
+        invalidate_signal_queries(view_cache);
+    }
+}
+
 /// Specialized version for updating items in a PaginatedList cache
-pub async fn edit_state_abstraction<K, T, Payload, F, GetId, OnSuccess>(
+///
+/// With `optimistic`, the cached item is patched through `Optimistic::edit` before the
+/// request is sent and restored if it fails with an API or transport error.
+pub async fn edit_state_abstraction<K, T, Payload, F, GetId, OnSuccess, Apply>(
     state: &GlobalSignal<HashMap<K, StateFrame<(), Payload>>>,
     id: K,
     payload: Payload,
@@ -441,15 +708,61 @@ pub async fn edit_state_abstraction<K, T, Payload, F, GetId, OnSuccess>(
     sync_view_cache: Option<&GlobalSignal<HashMap<K, StateFrame<T>>>>,
     get_id: GetId,
     on_success: Option<OnSuccess>,
+    optimistic: Option<Optimistic<Apply>>,
 ) -> Option<T>
 where
     K: Eq + Hash + Copy + 'static,
     T: DeserializeOwned + Clone + PartialEq + 'static,
     Payload: Clone + 'static,
-    F: Future<Output = Result<HttpResponse, HttpError>>,
+    F: Future<Output = ApiResult>,
     GetId: Fn(&T) -> K,
     OnSuccess: FnOnce(&T),
+    Apply: Fn(&mut T, &Payload),
 {
+    let snapshot = optimistic.as_ref().map(|opt| {
+        let mut snapshot = EditSnapshot {
+            list_item: None,
+            view_frame: None,
+        };
+        if let Some(list_cache) = sync_list_cache {
+            let mut list_frame = list_cache.write();
+            if let Some(list) = &mut list_frame.data {
+                if let Some(item) = list.data.iter_mut().find(|i| get_id(i) == id) {
+                    snapshot.list_item = Some(item.clone());
+                    (opt.apply)(item, &payload);
+                }
+            }
+        }
+        if let Some(view_cache) = sync_view_cache {
+            let mut view_map = view_cache.write();
+            if let Some(frame) = view_map.get_mut(&id) {
+                if frame.data.is_some() {
+                    snapshot.view_frame = Some(frame.clone());
+                }
+                if let Some(data) = &mut frame.data {
+                    (opt.apply)(data, &payload);
+                }
+            }
+        }
+        snapshot
+    });
+    let rollback = |error: AppError| {
+        if let (Some(snapshot), Some(opt)) = (&snapshot, &optimistic) {
+            if let (Some(list_cache), Some(original)) = (sync_list_cache, &snapshot.list_item) {
+                let mut list_frame = list_cache.write();
+                if let Some(list) = &mut list_frame.data {
+                    if let Some(item) = list.data.iter_mut().find(|i| get_id(i) == id) {
+                        *item = original.clone();
+                    }
+                }
+            }
+            if let (Some(view_cache), Some(frame)) = (sync_view_cache, &snapshot.view_frame) {
+                view_cache.write().insert(id, frame.clone());
+            }
+            report_rollback(parse_label, error, opt.retry.clone());
+        }
+    };
+
     {
         let mut map = state.write();
         map.entry(id)
@@ -459,8 +772,8 @@ where
 
     match send_future.await {
         Ok(response) => {
-            if (200..300).contains(&response.status()) {
-                match response.json::<T>().await {
+            if response.is_success() {
+                match response.json::<T>() {
                     Ok(parsed) => {
                         {
                             let mut map = state.write();
@@ -488,6 +801,8 @@ where
                                 .set_success(Some(parsed.clone()));
                         }
 
+                        invalidate_synced_queries(sync_list_cache, sync_view_cache);
+
                         // Call optional success callback for custom logic
                         if let Some(callback) = on_success {
                             callback(&parsed);
@@ -496,7 +811,7 @@ where
                         Some(parsed)
                     }
                     Err(e) => {
-                        let response_text = response.text().await.unwrap_or_default();
+                        let response_text = response.text().to_string();
                         let mut map = state.write();
                         map.entry(id)
                             .or_insert_with(StateFrame::new)
@@ -505,20 +820,24 @@ where
                     }
                 }
             } else {
-                let mut map = state.write();
-                map.entry(id)
-                    .or_insert_with(StateFrame::new)
-                    .set_api_error(&response)
-                    .await;
+                let error = {
+                    let mut map = state.write();
+                    let frame = map.entry(id).or_insert_with(StateFrame::new);
+                    frame.set_api_error(&response);
+                    frame.error_or_message("Request failed")
+                };
+                rollback(error);
                 None
             }
         }
         Err(e) => {
-            let (kind, msg) = classify_transport_error(&e);
-            let mut map = state.write();
-            map.entry(id)
-                .or_insert_with(StateFrame::new)
-                .set_transport_error(kind, Some(msg));
+            {
+                let mut map = state.write();
+                map.entry(id)
+                    .or_insert_with(StateFrame::new)
+                    .set_transport_error(e.kind, e.message.clone());
+            }
+            rollback(AppError::Transport(e));
             None
         }
     }
@@ -526,23 +845,79 @@ where
 
 /// Specialized version for removing items and syncing caches
 /// Use this when sync_view_cache stores `StateFrame<T>` (not wrapped in Option)
+///
+/// With `optimistic` (`Optimistic::remove()`), the item leaves the list and view caches
+/// before the request is sent and is put back if it fails.
 pub async fn remove_state_abstraction<K, T, F, GetId, OnSuccess>(
     state: &GlobalSignal<HashMap<K, StateFrame>>,
     id: K,
     send_future: F,
-    _parse_label: &str,
+    parse_label: &str,
     sync_list_cache: Option<&GlobalSignal<StateFrame<PaginatedList<T>>>>,
     sync_view_cache: Option<&GlobalSignal<HashMap<K, StateFrame<T>>>>,
     get_id: GetId,
     on_success: Option<OnSuccess>,
+    optimistic: Option<Optimistic>,
 ) -> bool
 where
     K: Eq + Hash + Copy + 'static,
     T: Clone + PartialEq + 'static,
-    F: Future<Output = Result<HttpResponse, HttpError>>,
+    F: Future<Output = ApiResult>,
     GetId: Fn(&T) -> K,
     OnSuccess: FnOnce(),
 {
+    let remove_from_caches = || {
+        let mut snapshot = RemoveSnapshot {
+            list_item: None,
+            total_decremented: false,
+            view_frame: None,
+        };
+        // Sync list cache if provided - remove the item and count it out of the total
+        if let Some(list_cache) = sync_list_cache {
+            let mut list_frame = list_cache.write();
+            if let Some(list) = &mut list_frame.data {
+                if let Some(index) = list.data.iter().position(|item| get_id(item) == id) {
+                    snapshot.list_item = Some((index, list.data.remove(index)));
+                    if list.total > 0 {
+                        list.total -= 1;
+                        snapshot.total_decremented = true;
+                    }
+                }
+            }
+        }
+
+        // Sync view cache if provided - remove the entry
+        if let Some(view_cache) = sync_view_cache {
+            let mut view_map = view_cache.write();
+            snapshot.view_frame = view_map.remove(&id);
+        }
+        snapshot
+    };
+    let snapshot = optimistic.as_ref().map(|_| remove_from_caches());
+    let rollback = |error: AppError| {
+        if let (Some(snapshot), Some(opt)) = (&snapshot, &optimistic) {
+            if let Some(list_cache) = sync_list_cache {
+                let mut list_frame = list_cache.write();
+                if let Some(list) = &mut list_frame.data {
+                    // A list refreshed in the meantime already holds the item and counts it
+                    if let Some((index, item)) = &snapshot.list_item {
+                        if !list.data.iter().any(|i| get_id(i) == id) {
+                            list.data
+                                .insert((*index).min(list.data.len()), item.clone());
+                            if snapshot.total_decremented {
+                                list.total += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            if let (Some(view_cache), Some(frame)) = (sync_view_cache, &snapshot.view_frame) {
+                view_cache.write().insert(id, frame.clone());
+            }
+            report_rollback(parse_label, error, opt.retry.clone());
+        }
+    };
+
     {
         let mut map = state.write();
         map.entry(id).or_insert_with(StateFrame::new).set_loading();
@@ -550,7 +925,7 @@ where
 
     match send_future.await {
         Ok(response) => {
-            if (200..300).contains(&response.status()) {
+            if response.is_success() {
                 {
                     let mut map = state.write();
                     map.entry(id)
@@ -558,23 +933,11 @@ where
                         .set_success(None);
                 }
 
-                // Sync list cache if provided - remove the item
-                if let Some(list_cache) = sync_list_cache {
-                    let mut list_frame = list_cache.write();
-                    if let Some(list) = &mut list_frame.data {
-                        list.data.retain(|item| get_id(item) != id);
-                        // Update total count
-                        if list.total > 0 {
-                            list.total -= 1;
-                        }
-                    }
+                if snapshot.is_none() {
+                    remove_from_caches();
                 }
 
-                // Sync view cache if provided - remove the entry
-                if let Some(view_cache) = sync_view_cache {
-                    let mut view_map = view_cache.write();
-                    view_map.remove(&id);
-                }
+                invalidate_synced_queries(sync_list_cache, sync_view_cache);
 
                 // Call optional success callback for custom logic
                 if let Some(callback) = on_success {
@@ -583,20 +946,24 @@ where
 
                 true
             } else {
-                let mut map = state.write();
-                map.entry(id)
-                    .or_insert_with(StateFrame::new)
-                    .set_api_error(&response)
-                    .await;
+                let error = {
+                    let mut map = state.write();
+                    let frame = map.entry(id).or_insert_with(StateFrame::new);
+                    frame.set_api_error(&response);
+                    frame.error_or_message("Request failed")
+                };
+                rollback(error);
                 false
             }
         }
         Err(e) => {
-            let (kind, msg) = classify_transport_error(&e);
-            let mut map = state.write();
-            map.entry(id)
-                .or_insert_with(StateFrame::new)
-                .set_transport_error(kind, Some(msg));
+            {
+                let mut map = state.write();
+                map.entry(id)
+                    .or_insert_with(StateFrame::new)
+                    .set_transport_error(e.kind, e.message.clone());
+            }
+            rollback(AppError::Transport(e));
             false
         }
     }
@@ -617,7 +984,7 @@ pub async fn remove_state_abstraction_vec<K, T, F, GetId, OnSuccess>(
 where
     K: Eq + Hash + Copy + 'static,
     T: Clone + PartialEq + 'static,
-    F: Future<Output = Result<HttpResponse, HttpError>>,
+    F: Future<Output = ApiResult>,
     GetId: Fn(&T) -> K,
     OnSuccess: FnOnce(),
 {
@@ -628,7 +995,7 @@ where
 
     match send_future.await {
         Ok(response) => {
-            if (200..300).contains(&response.status()) {
+            if response.is_success() {
                 {
                     let mut map = state.write();
                     map.entry(id)
@@ -650,6 +1017,8 @@ where
                     view_map.remove(&id);
                 }
 
+                invalidate_synced_queries(sync_list_cache, sync_view_cache);
+
                 // Call optional success callback for custom logic
                 if let Some(callback) = on_success {
                     callback();
@@ -660,18 +1029,356 @@ where
                 let mut map = state.write();
                 map.entry(id)
                     .or_insert_with(StateFrame::new)
-                    .set_api_error(&response)
-                    .await;
+                    .set_api_error(&response);
                 false
             }
         }
         Err(e) => {
-            let (kind, msg) = classify_transport_error(&e);
             let mut map = state.write();
             map.entry(id)
                 .or_insert_with(StateFrame::new)
-                .set_transport_error(kind, Some(msg));
+                .set_transport_error(e.kind, e.message);
             false
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::fake::{with_fake_client, FakeApiClient};
+    use crate::services::api_client::HttpMethod;
+    use crate::store::{use_tag, Tag, TagsListQuery};
+    use dioxus::prelude::*;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn list_abstraction_hydrates_frame_from_fake_client() {
+        with_fake_client(|fake| {
+            let tag = Tag {
+                id: 7,
+                name: "Rust".into(),
+                ..Tag::default()
+            };
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/list/query",
+                200,
+                &serde_json::json!({
+                    "data": [tag],
+                    "total": 1,
+                    "page": 1,
+                    "per_page": 10,
+                }),
+            );
+
+            let tags = use_tag();
+            tags.list_with_query(TagsListQuery::new())
+                .now_or_never()
+                .unwrap();
+
+            let frame = tags.list.read();
+            assert!(frame.is_success());
+            assert_eq!(frame.data.as_ref().unwrap().data[0].id, 7);
+            assert_eq!(fake.requests.borrow()[0].path, "/tag/v1/list/query");
+        });
+    }
+
+    #[test]
+    fn newer_list_query_cancels_the_stale_one() {
+        with_fake_client(|fake| {
+            fake.stall(HttpMethod::Post, "/tag/v1/list/query");
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/list/query",
+                200,
+                &serde_json::json!({ "data": [], "total": 0, "page": 1, "per_page": 10 }),
+            );
+
+            let tags = use_tag();
+            let mut stale = tags.list_with_query(TagsListQuery::new()).boxed_local();
+            assert!((&mut stale).now_or_never().is_none());
+
+            tags.list_with_query(TagsListQuery::new())
+                .now_or_never()
+                .unwrap();
+            stale.now_or_never().unwrap();
+
+            let frame = tags.list.read();
+            assert!(frame.is_success(), "stale cancel must not fail the frame");
+            assert_eq!(frame.data.as_ref().unwrap().total, 0);
+            assert_eq!(fake.requests.borrow().len(), 2);
+            assert!(fake.requests.borrow()[0]
+                .abort
+                .as_ref()
+                .unwrap()
+                .is_aborted());
+        });
+    }
+
+    fn respond_with_tags(fake: &FakeApiClient, ids: &[i32]) {
+        let data: Vec<Tag> = ids
+            .iter()
+            .map(|&id| Tag {
+                id,
+                ..Tag::default()
+            })
+            .collect();
+        fake.respond_json(
+            HttpMethod::Post,
+            "/tag/v1/list/query",
+            200,
+            &serde_json::json!({ "data": data, "total": ids.len(), "page": 1, "per_page": 10 }),
+        );
+    }
+
+    #[test]
+    fn fresh_queries_are_served_from_cache_until_invalidated() {
+        with_fake_client(|fake| {
+            respond_with_tags(&fake, &[1, 2]);
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/update/1",
+                200,
+                &Tag {
+                    id: 1,
+                    name: "Renamed".into(),
+                    ..Tag::default()
+                },
+            );
+
+            let tags = use_tag();
+            let query = TagsListQuery::new();
+            tags.list_with_query(query.clone()).now_or_never().unwrap();
+            tags.list_with_query(query.clone()).now_or_never().unwrap();
+            assert_eq!(
+                fake.requests.borrow().len(),
+                1,
+                "second fetch is a cache hit"
+            );
+
+            let mut page_two = query.clone();
+            page_two.page = 2;
+            tags.list_with_query(page_two).now_or_never().unwrap();
+            assert_eq!(fake.requests.borrow().len(), 2, "queries are keyed by body");
+
+            tags.edit(1, Default::default()).now_or_never().unwrap();
+            tags.list_with_query(query).now_or_never().unwrap();
+            let paths: Vec<String> = fake
+                .requests
+                .borrow()
+                .iter()
+                .map(|r| r.path.clone())
+                .collect();
+            assert_eq!(
+                paths,
+                [
+                    "/tag/v1/list/query",
+                    "/tag/v1/list/query",
+                    "/tag/v1/update/1",
+                    "/tag/v1/list/query"
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn stale_queries_show_cached_data_while_revalidating() {
+        with_fake_client(|fake| {
+            set_query_stale_time("tag", Duration::ZERO);
+            respond_with_tags(&fake, &[1]);
+            respond_with_tags(&fake, &[1, 2]);
+
+            let tags = use_tag();
+            tags.list_with_query(TagsListQuery::new())
+                .now_or_never()
+                .unwrap();
+
+            fake.stall(HttpMethod::Post, "/tag/v1/list/query");
+            let mut revalidate = tags.list_with_query(TagsListQuery::new()).boxed_local();
+            assert!((&mut revalidate).now_or_never().is_none());
+            {
+                let frame = tags.list.read();
+                assert!(frame.is_success(), "no loading flash for cached queries");
+                assert_eq!(frame.data.as_ref().unwrap().data[0].id, 1);
+            }
+            drop(revalidate);
+
+            tags.list_with_query(TagsListQuery::new())
+                .now_or_never()
+                .unwrap();
+            assert_eq!(tags.list.read().data.as_ref().unwrap().total, 2);
+        });
+    }
+
+    #[test]
+    fn optimistic_remove_rolls_back_on_failure() {
+        with_fake_client(|fake| {
+            respond_with_tags(&fake, &[1, 2, 3]);
+            let reply = fake.defer(HttpMethod::Post, "/tag/v1/delete/2");
+
+            let tags = use_tag();
+            tags.list().now_or_never().unwrap();
+            let ids = || -> Vec<i32> {
+                let frame = tags.list.read();
+                frame
+                    .data
+                    .as_ref()
+                    .unwrap()
+                    .data
+                    .iter()
+                    .map(|t| t.id)
+                    .collect()
+            };
+
+            let mut pending = tags.remove(2).boxed_local();
+            assert!((&mut pending).now_or_never().is_none());
+            assert_eq!(ids(), [1, 3], "removed before the server answers");
+            assert_eq!(tags.list.read().data.as_ref().unwrap().total, 2);
+
+            let body = r#"{"type":"INTERNAL","status":500}"#;
+            reply.send(Ok(ApiResponse::new(500, body))).unwrap();
+            pending.now_or_never().unwrap();
+
+            assert_eq!(ids(), [1, 2, 3]);
+            assert_eq!(tags.list.read().data.as_ref().unwrap().total, 3);
+            assert_eq!(tags.remove.read()[&2].error_status(), Some(500));
+            let rollbacks = MUTATION_ROLLBACKS.read();
+            assert_eq!(rollbacks.len(), 1);
+            assert_eq!(rollbacks[0].label, "tag");
+            assert!(rollbacks[0].retry.is_some());
+        });
+    }
+
+    #[test]
+    fn optimistic_remove_rollback_only_restores_what_it_took() {
+        with_fake_client(|fake| {
+            respond_with_tags(&fake, &[1, 2, 3]);
+            let body = r#"{"type":"INTERNAL","status":500}"#;
+            fake.respond(
+                HttpMethod::Post,
+                "/tag/v1/delete/9",
+                Ok(ApiResponse::new(500, body)),
+            );
+            let reply = fake.defer(HttpMethod::Post, "/tag/v1/delete/2");
+
+            let tags = use_tag();
+            tags.list().now_or_never().unwrap();
+            let total = || tags.list.read().data.as_ref().unwrap().total;
+
+            // Not on the loaded page: the total is left alone both ways
+            tags.remove(9).now_or_never().unwrap();
+            assert_eq!(total(), 3);
+
+            // A refresh before the failure already counts the item again
+            let mut pending = tags.remove(2).boxed_local();
+            assert!((&mut pending).now_or_never().is_none());
+            assert_eq!(total(), 2);
+            invalidate_queries("tag");
+            tags.list().now_or_never().unwrap();
+            assert_eq!(total(), 3);
+            reply.send(Ok(ApiResponse::new(500, body))).unwrap();
+            pending.now_or_never().unwrap();
+            assert_eq!(total(), 3);
+        });
+    }
+
+    #[test]
+    fn optimistic_edit_applies_immediately_and_restores_on_transport_error() {
+        with_fake_client(|fake| {
+            respond_with_tags(&fake, &[1]);
+            let reply = fake.defer(HttpMethod::Post, "/tag/v1/update/1");
+
+            let tags = use_tag();
+            tags.list().now_or_never().unwrap();
+            let is_active = || tags.list.read().data.as_ref().unwrap().data[0].is_active;
+            let payload = crate::store::TagsEditPayload {
+                is_active: Some(false),
+                ..Default::default()
+            };
+
+            let mut pending = tags.edit(1, payload).boxed_local();
+            assert!((&mut pending).now_or_never().is_none());
+            assert!(!is_active());
+
+            reply
+                .send(Err(TransportErrorInfo {
+                    kind: TransportErrorKind::Offline,
+                    message: None,
+                }))
+                .unwrap();
+            pending.now_or_never().unwrap();
+
+            assert!(is_active(), "rolled back to the snapshot");
+            assert_eq!(
+                tags.edit.read()[&1].transport_error_kind(),
+                Some(TransportErrorKind::Offline)
+            );
+        });
+    }
+
+    #[test]
+    fn query_keys_and_tags_follow_the_endpoint() {
+        use crate::services::endpoints::{Endpoint, TagList};
+        let req = TagList {}.request(&TagsListQuery::new());
+        assert!(query_key(&req).starts_with("POST /tag/v1/list/query {"));
+        assert_eq!(query_tag(&req.path), "tag");
+        assert_eq!(query_tag("/post/v1/series/list"), "post");
+    }
+
+    #[test]
+    fn query_cache_drops_the_oldest_entry_past_capacity() {
+        clear_query_cache();
+        for i in 0..QUERY_CACHE_CAPACITY {
+            store_query(format!("q{}", i), 0, "/tag/v1/list/query", i);
+        }
+        QUERY_CACHE.with(|cache| {
+            cache.borrow_mut().get_mut("q1").unwrap().fetched_at -= chrono::Duration::hours(1);
+        });
+
+        store_query("new".to_string(), 0, "/tag/v1/list/query", 0usize);
+        assert_eq!(
+            QUERY_CACHE.with(|cache| cache.borrow().len()),
+            QUERY_CACHE_CAPACITY
+        );
+        assert!(cached_query::<usize>("q1").is_none());
+        assert!(cached_query::<usize>("q0").is_some());
+        assert!(cached_query::<usize>("new").is_some());
+    }
+
+    #[test]
+    fn api_errors_and_transport_errors_land_in_the_frame() {
+        with_fake_client(|fake| {
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/delete/3",
+                409,
+                &serde_json::json!({
+                    "type": "TAG_IN_USE",
+                    "status": 409,
+                }),
+            );
+            fake.respond(
+                HttpMethod::Post,
+                "/tag/v1/view/4",
+                Err(TransportErrorInfo {
+                    kind: TransportErrorKind::Network,
+                    message: None,
+                }),
+            );
+
+            let tags = use_tag();
+            tags.remove(3).now_or_never().unwrap();
+            tags.view(4).now_or_never().unwrap();
+
+            let removed = tags.remove.read();
+            assert_eq!(removed[&3].error_status(), Some(409));
+            assert_eq!(removed[&3].error_type(), Some("TAG_IN_USE"));
+            let viewed = tags.view.read();
+            assert_eq!(
+                viewed[&4].transport_error_kind(),
+                Some(TransportErrorKind::Network)
+            );
+        });
+    }
+}
@@ -1,12 +1,13 @@
+use super::uploads::progress_reporter;
 use super::{
-    Media, MediaListQuery, MediaState, MediaUploadPayload, MediaUsageDetails,
-    MediaUsageDetailsRequest, MediaUsageDetailsResponse, UploadStatus,
+    content_hash, prepare_upload, Media, MediaListQuery, MediaReference, MediaState,
+    MediaUploadPayload, MediaUploadStartPayload, MediaUsageDetails, MediaUsageDetailsRequest,
+    MediaUsageDetailsResponse, PreparedUpload, UploadStatus, CHUNK_SIZE,
 };
-use crate::services::http_client;
-use crate::store::{
-    list_state_abstraction, remove_state_abstraction, view_state_abstraction, PaginatedList,
-    StateFrame,
+use crate::services::endpoints::{
+    Endpoint, MediaCreate, MediaDelete, MediaList, MediaUsage, MediaView, ResponseOf,
 };
+use crate::store::{invalidate_queries, view_state_abstraction, ResourceConfig, StateFrame};
 use std::collections::HashMap;
 use web_sys::{Blob, FormData, Url};
 
@@ -45,7 +46,7 @@ impl MediaState {
         gloo_console::log!("[MediaState::upload] Initializing tracking state");
         {
             let mut status_map = self.upload_status.write();
-            status_map.insert(blob_url.clone(), UploadStatus::Uploading);
+            status_map.insert(blob_url.clone(), UploadStatus::Queued);
         }
         {
             let mut progress_map = self.upload_progress.write();
@@ -67,233 +68,188 @@ impl MediaState {
         }
         gloo_console::log!("[MediaState::upload] Tracking state initialized");
 
-        // 3. Prepare multipart form data
-        gloo_console::log!("[MediaState::upload] Preparing form data");
-        let form_data = FormData::new().map_err(|e| {
-            let err_msg = format!("Failed to create FormData: {:?}", e);
-            gloo_console::error!("[MediaState::upload]", &err_msg);
-            err_msg
-        })?;
-
-        form_data
-            .append_with_blob("file", &payload.file)
-            .map_err(|e| {
-                let err_msg = format!("Failed to append file: {:?}", e);
-                gloo_console::error!("[MediaState::upload]", &err_msg);
-                err_msg
-            })?;
-
-        if let Some(ref_type) = &payload.reference_type {
-            gloo_console::log!(
-                "[MediaState::upload] Adding reference_type:",
-                ref_type.to_string()
-            );
-            form_data
-                .append_with_str("reference_type", &ref_type.to_string())
-                .map_err(|e| {
-                    let err_msg = format!("Failed to append reference_type: {:?}", e);
-                    gloo_console::error!("[MediaState::upload]", &err_msg);
-                    err_msg
-                })?;
-        }
-
-        if let Some(width) = payload.width {
-            gloo_console::log!("[MediaState::upload] Adding width: ", width.to_string());
-            form_data
-                .append_with_str("width", &width.to_string())
-                .map_err(|e| {
-                    let err_msg = format!("Failed to append width: {:?}", e);
-                    gloo_console::error!("[MediaState::upload]", &err_msg);
-                    err_msg
-                })?;
-        }
-
-        if let Some(height) = payload.height {
-            gloo_console::log!("[MediaState::upload] Adding height: ", height.to_string());
-            form_data
-                .append_with_str("height", &height.to_string())
-                .map_err(|e| {
-                    let err_msg = format!("Failed to append height: {:?}", e);
-                    gloo_console::error!("[MediaState::upload]", &err_msg);
-                    err_msg
-                })?;
-        }
-
-        gloo_console::log!("[MediaState::upload] Form data prepared successfully");
-
-        // 4. Upload in background
-        let blob_url_clone = blob_url.clone();
+        // 3. Check, optimize and upload in background once a slot is free
         gloo_console::log!(
             "[MediaState::upload] Spawning background upload task for:",
             &filename
         );
+        self.spawn_upload(blob_url.clone(), payload, true);
 
+        // 4. Return blob URL immediately
+        gloo_console::log!(
+            "[MediaState::upload] Upload function complete, returning blob URL:",
+            &blob_url
+        );
+        Ok(blob_url)
+    }
+
+    /// Upload `payload` in the background once a slot is free: hash the file, hold it when
+    /// `check_duplicates` finds the same file in the library, optimize it, then send it.
+    pub(super) fn spawn_upload(
+        &self,
+        blob_url: String,
+        payload: MediaUploadPayload,
+        check_duplicates: bool,
+    ) {
         wasm_bindgen_futures::spawn_local(async move {
             use super::use_media;
             let media_state = use_media();
 
-            gloo_console::log!("[MediaState::upload background] Creating HTTP request");
-
-            match http_client::post_multipart("/media/v1/create", &form_data) {
-                Ok(request) => {
+            let Some(slot) = media_state.acquire_upload_slot(&blob_url).await else {
+                gloo_console::log!(
+                    "[MediaState::upload background] Removed while queued:",
+                    &blob_url
+                );
+                return;
+            };
+            media_state
+                .upload_status
+                .write()
+                .insert(blob_url.clone(), UploadStatus::Optimizing);
+
+            let source_hash = content_hash(&payload.file).await.ok();
+            if check_duplicates
+                && media_state
+                    .hold_if_duplicate(&blob_url, &payload, source_hash.as_deref())
+                    .await
+            {
+                return;
+            }
+            let prepared = prepare_upload(&payload, source_hash.clone()).await;
+            // The optimized file may match an earlier optimized upload of the same image
+            if check_duplicates
+                && prepared.content_hash != source_hash
+                && media_state
+                    .hold_if_duplicate(&blob_url, &payload, prepared.content_hash.as_deref())
+                    .await
+            {
+                return;
+            }
+            media_state.blob_file_info.write().insert(
+                blob_url.clone(),
+                super::FileInfo {
+                    filename: prepared.file.name(),
+                    size: prepared.file.size() as i64,
+                },
+            );
+            media_state
+                .upload_status
+                .write()
+                .insert(blob_url.clone(), UploadStatus::Uploading);
+
+            let result = media_state
+                .send_upload(&prepared, payload.reference_type.clone(), &blob_url)
+                .await;
+            drop(slot);
+
+            match result {
+                Ok(media) => {
                     gloo_console::log!(
-                        "[MediaState::upload background] Request created, sending..."
+                        "[MediaState::upload background] Upload successful! Media ID: ",
+                        media.id.to_string(),
+                        " URL: ",
+                        &media.file_url
                     );
 
-                    match request.send().await {
-                        Ok(response) => {
-                            let status = response.status();
-                            gloo_console::log!(
-                                "[MediaState::upload background] Response received - Status: ",
-                                status.to_string(),
-                                " OK: ",
-                                response.ok().to_string()
-                            );
-
-                            if response.ok() {
-                                gloo_console::log!(
-                                    "[MediaState::upload background] Parsing JSON response"
-                                );
-
-                                match response.json::<Media>().await {
-                                    Ok(media) => {
-                                        gloo_console::log!(
-                                            "[MediaState::upload background] Upload successful! Media ID: ",
-                                            media.id.to_string(),
-                                            " URL: ",
-                                            &media.file_url
-                                        );
-
-                                        // Success: update tracking
-                                        {
-                                            let mut status_map = media_state.upload_status.write();
-                                            status_map.insert(
-                                                blob_url_clone.clone(),
-                                                UploadStatus::Success,
-                                            );
-                                        }
-                                        {
-                                            let mut progress_map =
-                                                media_state.upload_progress.write();
-                                            progress_map.insert(blob_url_clone.clone(), 100.0);
-                                        }
-                                        {
-                                            let mut blob_map = media_state.blob_to_media.write();
-                                            blob_map.insert(blob_url_clone.clone(), Some(media));
-                                        }
-
-                                        gloo_console::log!("[MediaState::upload background] Status updated to Success");
-
-                                        // Refresh list
-                                        gloo_console::log!(
-                                            "[MediaState::upload background] Refreshing media list"
-                                        );
-                                        media_state.list().await;
-                                    }
-                                    Err(e) => {
-                                        let err_msg = format!("Failed to parse response: {:?}", e);
-                                        gloo_console::error!(
-                                            "[MediaState::upload background]",
-                                            &err_msg
-                                        );
-
-                                        let mut status_map = media_state.upload_status.write();
-                                        status_map
-                                            .insert(blob_url_clone, UploadStatus::Error(err_msg));
-                                    }
-                                }
-                            } else {
-                                let err_msg =
-                                    format!("Upload failed with status: {}", status.to_string());
-                                gloo_console::error!("[MediaState::upload background] ", &err_msg);
-
-                                let mut status_map = media_state.upload_status.write();
-                                status_map.insert(blob_url_clone, UploadStatus::Error(err_msg));
-                            }
-                        }
-                        Err(e) => {
-                            let err_msg = format!("Request failed: {:?}", e);
-                            gloo_console::error!("[MediaState::upload background]", &err_msg);
-
-                            let mut status_map = media_state.upload_status.write();
-                            status_map.insert(blob_url_clone, UploadStatus::Error(err_msg));
-                        }
+                    // Success: update tracking
+                    {
+                        let mut status_map = media_state.upload_status.write();
+                        status_map.insert(blob_url.clone(), UploadStatus::Success);
+                    }
+                    {
+                        let mut progress_map = media_state.upload_progress.write();
+                        progress_map.insert(blob_url.clone(), 100.0);
                     }
+                    {
+                        let mut blob_map = media_state.blob_to_media.write();
+                        blob_map.insert(blob_url.clone(), Some(media));
+                    }
+
+                    // Refresh list
+                    gloo_console::log!("[MediaState::upload background] Refreshing media list");
+                    invalidate_queries("media");
+                    media_state.list().await;
                 }
-                Err(e) => {
-                    gloo_console::error!(
-                        "[MediaState::upload background] Failed to create request:",
-                        &e
-                    );
+                Err(err_msg) => {
+                    gloo_console::error!("[MediaState::upload background]", &err_msg);
 
                     let mut status_map = media_state.upload_status.write();
-                    status_map.insert(blob_url_clone, UploadStatus::Error(e));
+                    status_map.insert(blob_url, UploadStatus::Error(err_msg));
                 }
             }
         });
-
-        // 5. Return blob URL immediately
-        gloo_console::log!(
-            "[MediaState::upload] Upload function complete, returning blob URL:",
-            &blob_url
-        );
-        Ok(blob_url)
     }
 
-    pub async fn remove(&self, id: i32) {
-        let _ = remove_state_abstraction(
-            &self.remove,
-            id,
-            http_client::post(&format!("/media/v1/delete/{}", id), &()).send(),
-            "media",
-            Some(&self.list),
-            Some(&self.view),
-            |media: &Media| media.id,
-            None::<fn()>,
-        )
-        .await;
-    }
-
-    pub async fn list(&self) {
-        let _ = list_state_abstraction::<PaginatedList<Media>>(
-            &self.list,
-            http_client::post("/media/v1/list/query", &serde_json::json!({})),
-            "media",
-        )
-        .await;
-    }
-
-    pub async fn list_with_query(&self, query: MediaListQuery) {
-        let _ = list_state_abstraction::<PaginatedList<Media>>(
-            &self.list,
-            http_client::post("/media/v1/list/query", &query),
-            "media",
-        )
-        .await;
+    /// [`upload`](Self::upload) and wait for the server's media record, for callers that
+    /// need the final id and URL rather than a preview. Gives up after `timeout_secs`. A file
+    /// the library already has resolves to the existing media, as there is no one to ask.
+    pub async fn upload_and_wait(
+        &self,
+        payload: MediaUploadPayload,
+        timeout_secs: u32,
+    ) -> Result<Media, String> {
+        let blob_url = self.upload(payload).await?;
+        let mut waited_ms = 0;
+        loop {
+            if let Some(media) = self.get_uploaded_media(&blob_url) {
+                self.cleanup_blob(&blob_url);
+                return Ok(media);
+            }
+            match self.get_upload_status(&blob_url) {
+                Some(UploadStatus::Error(message)) => {
+                    self.cleanup_blob(&blob_url);
+                    return Err(message);
+                }
+                Some(UploadStatus::Duplicate(_)) => {
+                    self.use_existing_upload(&blob_url);
+                    continue;
+                }
+                _ => {}
+            }
+            if waited_ms >= timeout_secs * 1000 {
+                return Err(format!("Upload timeout after {} seconds", timeout_secs));
+            }
+            gloo_timers::future::TimeoutFuture::new(500).await;
+            waited_ms += 500;
+        }
     }
 
-    pub async fn view(&self, id: i32) {
-        let _ = view_state_abstraction(
-            &self.view,
-            id,
-            http_client::get(&format!("/media/v1/view/{}", id)).send(),
-            "media",
-            |media: &Media| media.clone(),
-        )
-        .await;
+    /// Send a prepared file, in chunks when it is larger than [`CHUNK_SIZE`].
+    async fn send_upload(
+        &self,
+        prepared: &PreparedUpload,
+        reference_type: Option<MediaReference>,
+        blob_url: &str,
+    ) -> Result<Media, String> {
+        let total_size = prepared.file.size() as u64;
+        if total_size <= CHUNK_SIZE {
+            let form_data = upload_form(prepared, reference_type.as_ref())?;
+            let progress = progress_reporter(blob_url, total_size, 0, total_size);
+            return upload_single(&form_data, progress).await;
+        }
+        gloo_console::log!("[MediaState::send_upload] Uploading in chunks");
+        let start_payload = MediaUploadStartPayload {
+            file_name: prepared.file.name(),
+            mime_type: prepared.file.type_(),
+            total_size,
+            chunk_size: CHUNK_SIZE,
+            reference_type,
+            width: prepared.width,
+            height: prepared.height,
+            content_hash: prepared.content_hash.clone(),
+            is_optimized: prepared.is_optimized,
+        };
+        self.upload_chunked(&prepared.file, start_payload, blob_url)
+            .await
     }
 
     pub async fn usage_details(&self, id: i32) {
         let _ = view_state_abstraction(
             &self.usage_details,
             id,
-            http_client::post(
-                "/media/v1/usage/details",
-                &MediaUsageDetailsRequest {
-                    media_ids: vec![id],
-                },
-            )
-            .send(),
+            MediaUsage {}.request(&MediaUsageDetailsRequest {
+                media_ids: vec![id],
+            }),
             "media usage details",
             |response: &MediaUsageDetailsResponse| {
                 response
@@ -313,15 +269,17 @@ impl MediaState {
     }
 
     pub fn reset(&self) {
+        self.resource.reset();
         *self.upload.write() = StateFrame::new();
-        *self.remove.write() = HashMap::new();
-        *self.list.write() = StateFrame::new();
-        *self.view.write() = HashMap::new();
         *self.usage_details.write() = HashMap::new();
         *self.upload_progress.write() = HashMap::new();
         *self.upload_status.write() = HashMap::new();
         *self.blob_to_media.write() = HashMap::new();
         *self.blob_file_info.write() = HashMap::new();
+        self.upload_queue.write().clear();
+        *self.duplicate_uploads.write() = HashMap::new();
+        *self.duplicates.write() = StateFrame::new();
+        *self.merge.write() = HashMap::new();
     }
 
     // Helper methods for upload tracking
@@ -365,8 +323,85 @@ impl MediaState {
         self.upload_status.write().remove(blob_url);
         self.blob_to_media.write().remove(blob_url);
         self.blob_file_info.write().remove(blob_url);
+        self.duplicate_uploads.write().remove(blob_url);
+        // A queued upload gives up once it is no longer in the queue
+        self.upload_queue.write().retain(|url| url != blob_url);
 
         // Revoke the blob URL to free memory
         Url::revoke_object_url(blob_url).ok();
     }
 }
+
+/// Multipart body for [`upload_single`].
+fn upload_form(
+    prepared: &PreparedUpload,
+    reference_type: Option<&MediaReference>,
+) -> Result<FormData, String> {
+    let form_data = FormData::new().map_err(|e| format!("Failed to create FormData: {:?}", e))?;
+    form_data
+        .append_with_blob("file", &prepared.file)
+        .map_err(|e| format!("Failed to append file: {:?}", e))?;
+
+    let mut fields = vec![("is_optimized", prepared.is_optimized.to_string())];
+    if let Some(ref_type) = reference_type {
+        fields.push(("reference_type", ref_type.to_string()));
+    }
+    if let Some(width) = prepared.width {
+        fields.push(("width", width.to_string()));
+    }
+    if let Some(height) = prepared.height {
+        fields.push(("height", height.to_string()));
+    }
+    if let Some(hash) = &prepared.content_hash {
+        fields.push(("content_hash", hash.clone()));
+    }
+    for (name, value) in fields {
+        form_data
+            .append_with_str(name, &value)
+            .map_err(|e| format!("Failed to append {}: {:?}", name, e))?;
+    }
+    Ok(form_data)
+}
+
+/// Upload a file in one multipart request, passing the bytes sent to `progress`.
+async fn upload_single(
+    form_data: &FormData,
+    progress: impl Fn(u64, u64) + 'static,
+) -> Result<Media, String> {
+    gloo_console::log!("[upload_single] Sending multipart request");
+    let response = MediaCreate {}
+        .multipart(form_data)
+        .with_upload_progress(progress)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+    gloo_console::log!(
+        "[upload_single] Response received - Status: ",
+        response.status.to_string()
+    );
+    if !response.is_success() {
+        return Err(format!("Upload failed with status: {}", response.status));
+    }
+    response
+        .json::<ResponseOf<MediaCreate>>()
+        .map_err(|e| format!("Failed to parse response: {:?}", e))
+}
+
+/// Media is created through [`MediaState::upload`] and never edited, so the generic store only
+/// covers listing, viewing and (non-optimistic) removal.
+pub(super) fn config() -> ResourceConfig<Media, MediaListQuery, (), ()> {
+    ResourceConfig {
+        label: "media",
+        list_label: "media",
+        cache_tag: "media",
+        create: None,
+        update: None,
+        delete: |id| MediaDelete { id }.request(&()),
+        list: |query| MediaList {}.request(query),
+        view: |id| MediaView { id }.request(&()),
+        id: |media| media.id,
+        apply_update: None,
+        optimistic_remove: false,
+        offline_create: None,
+    }
+}
@@ -0,0 +1,190 @@
+//! Finding media that holds the same bytes, both before an upload and across the library.
+//!
+//! Files are matched by the SHA-256 `content_hash` the client computes before uploading (see
+//! [`content_hash`](super::content_hash)). An upload whose hash is already in the library is
+//! held as [`UploadStatus::Duplicate`] until the user picks the existing media or uploads
+//! anyway. Copies already in the library are grouped by hash and can be merged into one.
+
+use super::{
+    Media, MediaDuplicateGroup, MediaListQuery, MediaMergePayload, MediaState, MediaUploadPayload,
+    UploadStatus,
+};
+use crate::services::endpoints::{Endpoint, MediaDuplicates, MediaList, MediaMerge, ResponseOf};
+use crate::store::{
+    edit_state_abstraction, invalidate_queries, list_state_abstraction, Optimistic,
+};
+
+/// The copy a merge keeps by default: the most referenced one, then the oldest.
+pub fn keep_candidate(group: &MediaDuplicateGroup) -> Option<&Media> {
+    group.media.iter().min_by(|a, b| {
+        b.usage_count
+            .cmp(&a.usage_count)
+            .then(a.created_at.cmp(&b.created_at))
+            .then(a.id.cmp(&b.id))
+    })
+}
+
+/// Bytes freed by keeping a single copy.
+pub fn wasted_bytes(group: &MediaDuplicateGroup) -> i64 {
+    let total: i64 = group.media.iter().map(|media| media.size).sum();
+    let largest = group
+        .media
+        .iter()
+        .map(|media| media.size)
+        .max()
+        .unwrap_or(0);
+    total - largest
+}
+
+impl MediaState {
+    /// Existing media with `content_hash`, if any. Lookup failures count as no match so an
+    /// upload is never blocked by them.
+    pub async fn find_by_hash(&self, content_hash: &str) -> Option<Media> {
+        let query = MediaListQuery {
+            content_hash: Some(content_hash.to_string()),
+            ..MediaListQuery::new()
+        };
+        let response = MediaList {}.request(&query).send().await.ok()?;
+        if !response.is_success() {
+            return None;
+        }
+        // Servers that ignore the filter return unrelated media
+        response
+            .json::<ResponseOf<MediaList>>()
+            .ok()?
+            .data
+            .into_iter()
+            .find(|media| media.content_hash.as_deref() == Some(content_hash))
+    }
+
+    /// Hold the upload of `payload` when media with `content_hash` already exists. Returns
+    /// whether it was held.
+    pub(super) async fn hold_if_duplicate(
+        &self,
+        blob_url: &str,
+        payload: &MediaUploadPayload,
+        content_hash: Option<&str>,
+    ) -> bool {
+        let Some(content_hash) = content_hash else {
+            return false;
+        };
+        let Some(existing) = self.find_by_hash(content_hash).await else {
+            return false;
+        };
+        gloo_console::log!(
+            "[MediaState::hold_if_duplicate] Same file as media",
+            existing.id.to_string()
+        );
+        self.duplicate_uploads
+            .write()
+            .insert(blob_url.to_string(), payload.clone());
+        self.upload_status
+            .write()
+            .insert(blob_url.to_string(), UploadStatus::Duplicate(existing));
+        true
+    }
+
+    /// Finish a held upload with the existing media instead of uploading the file.
+    pub fn use_existing_upload(&self, blob_url: &str) {
+        let Some(UploadStatus::Duplicate(existing)) = self.get_upload_status(blob_url) else {
+            return;
+        };
+        self.duplicate_uploads.write().remove(blob_url);
+        self.blob_to_media
+            .write()
+            .insert(blob_url.to_string(), Some(existing));
+        self.upload_progress
+            .write()
+            .insert(blob_url.to_string(), 100.0);
+        self.upload_status
+            .write()
+            .insert(blob_url.to_string(), UploadStatus::Success);
+    }
+
+    /// Upload a held file even though the library already has it.
+    pub fn upload_anyway(&self, blob_url: &str) {
+        let Some(payload) = self.duplicate_uploads.write().remove(blob_url) else {
+            return;
+        };
+        self.upload_status
+            .write()
+            .insert(blob_url.to_string(), UploadStatus::Queued);
+        self.spawn_upload(blob_url.to_string(), payload, false);
+    }
+
+    /// Load every group of media sharing a content hash.
+    pub async fn duplicates(&self) {
+        let _ = list_state_abstraction(
+            &self.duplicates,
+            MediaDuplicates {}.request(&()),
+            "media duplicates",
+        )
+        .await;
+    }
+
+    /// Merge `duplicate_ids` into `keep_id`: their references move to the kept media and the
+    /// duplicates are deleted.
+    pub async fn merge_duplicates(&self, keep_id: i32, duplicate_ids: Vec<i32>) {
+        let payload = MediaMergePayload {
+            keep_id,
+            duplicate_ids,
+        };
+        let merged = edit_state_abstraction(
+            &self.merge,
+            keep_id,
+            payload.clone(),
+            MediaMerge {}.request(&payload).send(),
+            "merged media",
+            Some(&self.list),
+            Some(&self.view),
+            |media: &Media| media.id,
+            None::<fn(&Media)>,
+            None::<Optimistic<fn(&mut Media, &MediaMergePayload)>>,
+        )
+        .await;
+        if merged.is_some() {
+            // The duplicates are gone, so their list rows and usage counts are stale
+            invalidate_queries("media");
+            self.usage_details.write().clear();
+            self.duplicates().await;
+            self.list().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn media(id: i32, size: i64, usage_count: i32, day: u32) -> Media {
+        Media {
+            id,
+            size,
+            usage_count,
+            created_at: Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap(),
+            ..Media::default()
+        }
+    }
+
+    #[test]
+    fn keeps_the_most_used_then_oldest_copy() {
+        let group = MediaDuplicateGroup {
+            content_hash: "abc".into(),
+            media: vec![
+                media(1, 100, 0, 1),
+                media(2, 100, 3, 5),
+                media(3, 100, 3, 2),
+            ],
+        };
+        assert_eq!(keep_candidate(&group).map(|media| media.id), Some(3));
+        assert_eq!(wasted_bytes(&group), 200);
+
+        let unused = MediaDuplicateGroup {
+            content_hash: "abc".into(),
+            media: vec![media(4, 50, 0, 9), media(5, 80, 0, 3)],
+        };
+        assert_eq!(keep_candidate(&unused).map(|media| media.id), Some(5));
+        assert_eq!(wasted_bytes(&unused), 50);
+    }
+}
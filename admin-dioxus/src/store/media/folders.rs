@@ -0,0 +1,182 @@
+//! Organizing the library into folders: loading the folder tree, creating, renaming and
+//! deleting folders, and moving media between them.
+//!
+//! Folders nest through [`MediaFolder::parent_id`]; media outside any folder sits at the
+//! library root. The list screen and the picker browse one folder at a time through
+//! [`MediaListQuery::folder_id`](super::MediaListQuery::folder_id).
+
+use super::{MediaFolder, MediaFolderPayload, MediaMovePayload, MediaState};
+use crate::services::endpoints::{
+    Endpoint, MediaFolderCreate, MediaFolderDelete, MediaFolderList, MediaFolderUpdate, MediaMove,
+};
+use crate::store::{
+    invalidate_queries, list_state_abstraction, remove_state_abstraction_vec,
+    state_request_abstraction,
+};
+
+/// Direct subfolders of `parent` (the root when `None`), by name.
+pub fn child_folders(folders: &[MediaFolder], parent: Option<i32>) -> Vec<&MediaFolder> {
+    let mut children: Vec<&MediaFolder> = folders
+        .iter()
+        .filter(|folder| folder.parent_id == parent)
+        .collect();
+    children.sort_by_key(|folder| folder.name.to_lowercase());
+    children
+}
+
+/// Folders from the root down to `id`, for breadcrumbs. Stops at a missing parent or a cycle.
+pub fn folder_path(folders: &[MediaFolder], id: i32) -> Vec<&MediaFolder> {
+    let mut path = Vec::new();
+    let mut next = Some(id);
+    while let Some(id) = next {
+        let Some(folder) = folders.iter().find(|folder| folder.id == id) else {
+            break;
+        };
+        if path.iter().any(|seen: &&MediaFolder| seen.id == id) {
+            break;
+        }
+        path.push(folder);
+        next = folder.parent_id;
+    }
+    path.reverse();
+    path
+}
+
+impl MediaState {
+    /// Load every folder of the library.
+    pub async fn folders(&self) {
+        let _ = list_state_abstraction(
+            &self.folders,
+            MediaFolderList {}.request(&()),
+            "media folders",
+        )
+        .await;
+    }
+
+    pub async fn create_folder(&self, payload: MediaFolderPayload) {
+        let created = state_request_abstraction(
+            &self.folder_save,
+            Some(payload.clone()),
+            MediaFolderCreate {}.request(&payload).send(),
+            "media folder",
+            |folder: &MediaFolder| (Some(folder.clone()), None),
+        )
+        .await;
+        if created.is_some() {
+            invalidate_queries("media");
+            self.folders().await;
+        }
+    }
+
+    pub async fn rename_folder(&self, id: i32, payload: MediaFolderPayload) {
+        let renamed = state_request_abstraction(
+            &self.folder_save,
+            Some(payload.clone()),
+            MediaFolderUpdate { id }.request(&payload).send(),
+            "media folder",
+            |folder: &MediaFolder| (Some(folder.clone()), None),
+        )
+        .await;
+        if renamed.is_some() {
+            invalidate_queries("media");
+            self.folders().await;
+        }
+    }
+
+    /// Delete folder `id`. Its media and subfolders move up to its parent.
+    pub async fn delete_folder(&self, id: i32) {
+        let removed = remove_state_abstraction_vec(
+            &self.folder_remove,
+            id,
+            MediaFolderDelete { id }.request(&()).send(),
+            "media folder",
+            Some(&self.folders),
+            None,
+            |folder: &MediaFolder| folder.id,
+            None::<fn()>,
+        )
+        .await;
+        if removed {
+            invalidate_queries("media");
+            self.folders().await;
+            self.list().await;
+        }
+    }
+
+    /// Move `media_ids` into `folder_id`, or to the library root when it is `None`.
+    pub async fn move_to_folder(&self, media_ids: Vec<i32>, folder_id: Option<i32>) {
+        if media_ids.is_empty() {
+            return;
+        }
+        let payload = MediaMovePayload {
+            media_ids,
+            folder_id,
+        };
+        let moved = state_request_abstraction(
+            &self.move_media,
+            Some(payload.clone()),
+            MediaMove {}.request(&payload).send(),
+            "media move",
+            |_: &serde_json::Value| (None, None),
+        )
+        .await;
+        if moved.is_some() {
+            invalidate_queries("media");
+            self.folders().await;
+            self.list().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(id: i32, name: &str, parent_id: Option<i32>) -> MediaFolder {
+        MediaFolder {
+            id,
+            name: name.into(),
+            parent_id,
+            media_count: 0,
+        }
+    }
+
+    #[test]
+    fn lists_children_by_name() {
+        let folders = vec![
+            folder(1, "photos", None),
+            folder(2, "Brand", None),
+            folder(3, "2024", Some(1)),
+        ];
+        let names = |parent| {
+            child_folders(&folders, parent)
+                .into_iter()
+                .map(|folder| folder.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(None), vec!["Brand", "photos"]);
+        assert_eq!(names(Some(1)), vec!["2024"]);
+        assert!(names(Some(3)).is_empty());
+    }
+
+    #[test]
+    fn builds_the_path_from_the_root() {
+        let folders = vec![
+            folder(1, "photos", None),
+            folder(2, "2024", Some(1)),
+            folder(3, "june", Some(2)),
+            folder(4, "a", Some(5)),
+            folder(5, "b", Some(4)),
+        ];
+        let ids = |id| {
+            folder_path(&folders, id)
+                .into_iter()
+                .map(|folder| folder.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(3), vec![1, 2, 3]);
+        assert_eq!(ids(1), vec![1]);
+        assert!(ids(9).is_empty());
+        assert_eq!(ids(4), vec![5, 4]);
+    }
+}
@@ -1,5 +1,17 @@
 mod actions;
+mod duplicates;
+mod folders;
+mod optimize;
 mod state;
+mod tags;
+mod uploads;
+mod variants;
 
 // pub use actions::*;
+pub use duplicates::*;
+pub use folders::*;
+pub use optimize::*;
 pub use state::*;
+pub use tags::*;
+pub use uploads::*;
+pub use variants::*;
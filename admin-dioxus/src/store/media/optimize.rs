@@ -0,0 +1,534 @@
+//! Automatic image optimization before upload.
+//!
+//! JPEG, PNG and WebP files are decoded with photon, turned upright according to their EXIF
+//! orientation, downscaled to [`ImageOptimizeSettings::max_dimension`] and re-encoded through
+//! a canvas, which drops EXIF, GPS and other embedded metadata. When enabled, WebP and AVIF
+//! are tried as well and the smallest result is uploaded. Every file gets a SHA-256
+//! `content_hash` of the bytes that are sent.
+
+use super::MediaUploadPayload;
+use crate::utils::persist;
+use photon_rs::native::open_image_from_bytes;
+use photon_rs::transform::{resize, SamplingFilter};
+use photon_rs::PhotonImage;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, File, FilePropertyBag, HtmlCanvasElement};
+
+/// Types the pipeline re-encodes. GIFs may be animated and SVGs are not raster images, so
+/// both are uploaded as they are.
+pub const OPTIMIZABLE_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+/// Choices offered for the longest side; 0 keeps the original size.
+pub const MAX_DIMENSION_CHOICES: [u32; 5] = [1280, 1920, 2560, 3840, 0];
+/// Bytes hashed at a time, so large videos are never read into memory whole.
+const HASH_SLICE_SIZE: f64 = 8.0 * 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageOptimizeSettings {
+    pub enabled: bool,
+    /// Longest side in pixels after downscaling; 0 keeps the original size.
+    pub max_dimension: u32,
+    /// Encoder quality for lossy formats, 1–100.
+    pub quality: u8,
+    /// Also try WebP and AVIF and keep whichever file is smallest.
+    pub modern_formats: bool,
+}
+
+impl Default for ImageOptimizeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_dimension: 2560,
+            quality: 82,
+            modern_formats: true,
+        }
+    }
+}
+
+impl ImageOptimizeSettings {
+    pub fn load() -> Self {
+        persist::get_image_optimize()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            persist::set_image_optimize(&json);
+        }
+    }
+}
+
+/// The file that is actually sent, with what the pipeline learned about it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedUpload {
+    pub file: File,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub content_hash: Option<String>,
+    pub is_optimized: bool,
+}
+
+fn read_u16(bytes: &[u8], at: usize, little_endian: bool) -> Option<u16> {
+    let pair: [u8; 2] = bytes.get(at..at + 2)?.try_into().ok()?;
+    Some(if little_endian {
+        u16::from_le_bytes(pair)
+    } else {
+        u16::from_be_bytes(pair)
+    })
+}
+
+fn read_u32(bytes: &[u8], at: usize, little_endian: bool) -> Option<u32> {
+    let quad: [u8; 4] = bytes.get(at..at + 4)?.try_into().ok()?;
+    Some(if little_endian {
+        u32::from_le_bytes(quad)
+    } else {
+        u32::from_be_bytes(quad)
+    })
+}
+
+/// APP segments of a JPEG as `(marker, payload)`, up to the start of the image data.
+fn jpeg_segments(bytes: &[u8]) -> Vec<(u8, &[u8])> {
+    let mut segments = Vec::new();
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return segments;
+    }
+    let mut at = 2;
+    while let (Some(0xFF), Some(&marker)) = (bytes.get(at), bytes.get(at + 1)) {
+        // Start of scan: compressed data follows
+        if marker == 0xDA {
+            break;
+        }
+        let Some(length) = read_u16(bytes, at + 2, false) else {
+            break;
+        };
+        let end = at + 2 + length as usize;
+        let Some(payload) = bytes.get(at + 4..end) else {
+            break;
+        };
+        segments.push((marker, payload));
+        at = end;
+    }
+    segments
+}
+
+/// The EXIF orientation (1–8) of a JPEG, if it has one.
+pub fn exif_orientation(bytes: &[u8]) -> Option<u16> {
+    let tiff = jpeg_segments(bytes)
+        .into_iter()
+        .filter(|(marker, _)| *marker == 0xE1)
+        .find_map(|(_, payload)| payload.strip_prefix(b"Exif\0\0"))?;
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let ifd = read_u32(tiff, 4, little_endian)? as usize;
+    let entries = read_u16(tiff, ifd, little_endian)?;
+    (0..entries as usize)
+        .map(|index| ifd + 2 + index * 12)
+        .find(|entry| read_u16(tiff, *entry, little_endian) == Some(0x0112))
+        .and_then(|entry| read_u16(tiff, entry + 8, little_endian))
+        .filter(|orientation| (1..=8).contains(orientation))
+}
+
+/// Whether the file carries EXIF, XMP or text metadata that re-encoding would drop.
+pub fn has_metadata(bytes: &[u8]) -> bool {
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        return jpeg_segments(bytes)
+            .iter()
+            .any(|(marker, _)| *marker == 0xE1);
+    }
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let mut at = 8;
+        while let (Some(length), Some(kind)) =
+            (read_u32(bytes, at, false), bytes.get(at + 4..at + 8))
+        {
+            if matches!(kind, b"eXIf" | b"tEXt" | b"iTXt" | b"zTXt") {
+                return true;
+            }
+            at += 12 + length as usize;
+        }
+        return false;
+    }
+    if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        let mut at = 12;
+        while let (Some(kind), Some(length)) =
+            (bytes.get(at..at + 4), read_u32(bytes, at + 4, true))
+        {
+            if matches!(kind, b"EXIF" | b"XMP ") {
+                return true;
+            }
+            // Chunks are padded to an even length
+            at += 8 + (length as usize).div_ceil(2) * 2;
+        }
+    }
+    false
+}
+
+/// Size of the image once `orientation` is applied; 5–8 swap width and height.
+pub fn oriented_size(width: u32, height: u32, orientation: u16) -> (u32, u32) {
+    if (5..=8).contains(&orientation) {
+        (height, width)
+    } else {
+        (width, height)
+    }
+}
+
+/// RGBA `pixels` turned upright according to EXIF `orientation`.
+pub fn orient_pixels(pixels: &[u8], width: u32, height: u32, orientation: u16) -> Vec<u8> {
+    if !(2..=8).contains(&orientation) {
+        return pixels.to_vec();
+    }
+    let (w, h) = (width as usize, height as usize);
+    let (out_width, out_height) = oriented_size(width, height, orientation);
+    let mut out = Vec::with_capacity(pixels.len());
+    for y in 0..out_height as usize {
+        for x in 0..out_width as usize {
+            let (src_x, src_y) = match orientation {
+                2 => (w - 1 - x, y),
+                3 => (w - 1 - x, h - 1 - y),
+                4 => (x, h - 1 - y),
+                5 => (y, x),
+                6 => (y, h - 1 - x),
+                7 => (w - 1 - y, h - 1 - x),
+                _ => (w - 1 - y, x),
+            };
+            let at = (src_y * w + src_x) * 4;
+            out.extend_from_slice(&pixels[at..at + 4]);
+        }
+    }
+    out
+}
+
+/// Scale `width`×`height` down so neither side exceeds `max`, keeping the aspect ratio.
+pub fn fit_within(width: u32, height: u32, max: u32) -> (u32, u32) {
+    if max == 0 || (width <= max && height <= max) {
+        return (width, height);
+    }
+    let scale = max as f64 / width.max(height) as f64;
+    (
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    )
+}
+
+/// `name` with the extension of `mime_type`.
+pub fn renamed(name: &str, mime_type: &str) -> String {
+    let extension = match mime_type {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        _ => return name.to_string(),
+    };
+    let stem = match name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => name,
+    };
+    format!("{}.{}", stem, extension)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+async fn read_bytes(blob: &Blob) -> Result<Vec<u8>, String> {
+    let buffer = JsFuture::from(blob.array_buffer())
+        .await
+        .map_err(|e| format!("Failed to read file: {:?}", e))?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+/// Hex SHA-256 of `blob`, read a slice at a time.
+pub async fn content_hash(blob: &Blob) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    let size = blob.size();
+    let mut start = 0.0;
+    while start < size {
+        let end = (start + HASH_SLICE_SIZE).min(size);
+        let slice = blob
+            .slice_with_f64_and_f64(start, end)
+            .map_err(|e| format!("Failed to slice file: {:?}", e))?;
+        hasher.update(read_bytes(&slice).await?);
+        start = end;
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Encode the canvas as `mime_type`. `None` when the browser cannot encode that type and
+/// falls back to PNG instead.
+async fn encode(
+    canvas: &HtmlCanvasElement,
+    mime_type: &str,
+    quality: u8,
+) -> Result<Option<Blob>, String> {
+    let (sender, receiver) = futures_channel::oneshot::channel();
+    let mut sender = Some(sender);
+    let callback = wasm_bindgen::closure::Closure::wrap(Box::new(move |blob: Option<Blob>| {
+        if let Some(sender) = sender.take() {
+            let _ = sender.send(blob);
+        }
+    }) as Box<dyn FnMut(Option<Blob>)>);
+
+    canvas
+        .to_blob_with_type_and_encoder_options(
+            callback.as_ref().unchecked_ref(),
+            mime_type,
+            &wasm_bindgen::JsValue::from_f64(quality.clamp(1, 100) as f64 / 100.0),
+        )
+        .map_err(|e| format!("Failed to encode image: {:?}", e))?;
+    callback.forget();
+
+    let blob = receiver
+        .await
+        .map_err(|_| "Failed to receive encoded image".to_string())?;
+    Ok(blob.filter(|blob| blob.type_() == mime_type))
+}
+
+fn canvas_with(pixels: &[u8], width: u32, height: u32) -> Result<HtmlCanvasElement, String> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("No document available")?;
+    let canvas = document
+        .create_element("canvas")
+        .map_err(|e| format!("Failed to create canvas: {:?}", e))?
+        .dyn_into::<HtmlCanvasElement>()
+        .map_err(|e| format!("Failed to cast to canvas: {:?}", e))?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+
+    let context = canvas
+        .get_context("2d")
+        .map_err(|e| format!("Failed to get context: {:?}", e))?
+        .ok_or("No context")?
+        .dyn_into::<web_sys::CanvasRenderingContext2d>()
+        .map_err(|e| format!("Failed to cast context: {:?}", e))?;
+    let image_data = web_sys::ImageData::new_with_u8_clamped_array_and_sh(
+        wasm_bindgen::Clamped(pixels),
+        width,
+        height,
+    )
+    .map_err(|e| format!("Failed to create ImageData: {:?}", e))?;
+    context
+        .put_image_data(&image_data, 0.0, 0.0)
+        .map_err(|e| format!("Failed to put image data: {:?}", e))?;
+    Ok(canvas)
+}
+
+/// Run the pipeline on an image `file`. The original is kept when it needed no changes and
+/// no re-encoding came out smaller.
+pub async fn optimize_image(
+    file: &File,
+    settings: &ImageOptimizeSettings,
+) -> Result<PreparedUpload, String> {
+    let bytes = read_bytes(file).await?;
+    let orientation = exif_orientation(&bytes).unwrap_or(1);
+    let image =
+        open_image_from_bytes(&bytes).map_err(|e| format!("Failed to open image: {:?}", e))?;
+    let (width, height) = (image.get_width(), image.get_height());
+    let (upright_width, upright_height) = oriented_size(width, height, orientation);
+    let (target_width, target_height) =
+        fit_within(upright_width, upright_height, settings.max_dimension);
+
+    let needs_changes = orientation != 1
+        || (target_width, target_height) != (upright_width, upright_height)
+        || has_metadata(&bytes);
+
+    let mut image = if orientation != 1 {
+        let pixels = orient_pixels(&image.get_raw_pixels(), width, height, orientation);
+        PhotonImage::new(pixels, upright_width, upright_height)
+    } else {
+        image
+    };
+    if (target_width, target_height) != (upright_width, upright_height) {
+        image = resize(
+            &image,
+            target_width,
+            target_height,
+            SamplingFilter::Lanczos3,
+        );
+    }
+
+    let original_type = file.type_();
+    let mut types = vec![original_type.as_str()];
+    if settings.modern_formats {
+        types.extend(
+            ["image/webp", "image/avif"]
+                .into_iter()
+                .filter(|t| *t != original_type),
+        );
+    }
+    let canvas = canvas_with(&image.get_raw_pixels(), target_width, target_height)?;
+    let mut best: Option<Blob> = None;
+    for mime_type in types {
+        if let Some(blob) = encode(&canvas, mime_type, settings.quality).await? {
+            if best.as_ref().is_none_or(|best| blob.size() < best.size()) {
+                best = Some(blob);
+            }
+        }
+    }
+
+    let dimensions = (Some(target_width as i32), Some(target_height as i32));
+    let best = best.filter(|best| needs_changes || best.size() < file.size());
+    let Some(best) = best else {
+        return Ok(PreparedUpload {
+            file: file.clone(),
+            width: dimensions.0,
+            height: dimensions.1,
+            content_hash: Some(to_hex(&Sha256::digest(&bytes))),
+            is_optimized: false,
+        });
+    };
+
+    let options = FilePropertyBag::new();
+    options.set_type(&best.type_());
+    options.set_last_modified(file.last_modified());
+    let parts = js_sys::Array::of1(&best);
+    let optimized = File::new_with_blob_sequence_and_options(
+        &parts,
+        &renamed(&file.name(), &best.type_()),
+        &options,
+    )
+    .map_err(|e| format!("Failed to create file: {:?}", e))?;
+    gloo_console::log!(
+        "[optimize_image]",
+        file.name(),
+        format!("{} → {} bytes", file.size(), optimized.size())
+    );
+    Ok(PreparedUpload {
+        content_hash: Some(content_hash(&optimized).await?),
+        file: optimized,
+        width: dimensions.0,
+        height: dimensions.1,
+        is_optimized: true,
+    })
+}
+
+/// The file to send for `payload`: optimized when it asks for it and the saved settings
+/// allow, otherwise the original. `source_hash` is the original file's hash when already
+/// known. Never fails; a file that cannot be optimized or hashed is sent as it is.
+pub async fn prepare_upload(
+    payload: &MediaUploadPayload,
+    source_hash: Option<String>,
+) -> PreparedUpload {
+    let settings = ImageOptimizeSettings::load();
+    let file = &payload.file;
+    if payload.optimize && settings.enabled && OPTIMIZABLE_TYPES.contains(&file.type_().as_str()) {
+        match optimize_image(file, &settings).await {
+            Ok(prepared) => return prepared,
+            Err(message) => gloo_console::warn!("[prepare_upload] Not optimized:", message),
+        }
+    }
+    let content_hash = match source_hash {
+        Some(hash) => Some(hash),
+        None => match content_hash(file).await {
+            Ok(hash) => Some(hash),
+            Err(message) => {
+                gloo_console::warn!("[prepare_upload] Not hashed:", message);
+                None
+            }
+        },
+    };
+    PreparedUpload {
+        file: file.clone(),
+        width: payload.width,
+        height: payload.height,
+        content_hash,
+        is_optimized: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A JPEG with one EXIF IFD entry holding `orientation`.
+    fn jpeg_with_orientation(orientation: u16, little_endian: bool) -> Vec<u8> {
+        let u16_bytes = |value: u16| {
+            if little_endian {
+                value.to_le_bytes()
+            } else {
+                value.to_be_bytes()
+            }
+        };
+        let u32_bytes = |value: u32| {
+            if little_endian {
+                value.to_le_bytes()
+            } else {
+                value.to_be_bytes()
+            }
+        };
+        let mut tiff = Vec::new();
+        tiff.extend(if little_endian { b"II" } else { b"MM" });
+        tiff.extend(u16_bytes(42));
+        tiff.extend(u32_bytes(8));
+        tiff.extend(u16_bytes(1));
+        tiff.extend(u16_bytes(0x0112));
+        tiff.extend(u16_bytes(3));
+        tiff.extend(u32_bytes(1));
+        tiff.extend(u16_bytes(orientation));
+        tiff.extend([0, 0]);
+        tiff.extend(u32_bytes(0));
+
+        let mut payload = b"Exif\0\0".to_vec();
+        payload.extend(tiff);
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xE1];
+        jpeg.extend((payload.len() as u16 + 2).to_be_bytes());
+        jpeg.extend(payload);
+        jpeg.extend([0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34]);
+        jpeg
+    }
+
+    #[test]
+    fn reads_exif_orientation_and_metadata() {
+        assert_eq!(exif_orientation(&jpeg_with_orientation(6, true)), Some(6));
+        assert_eq!(exif_orientation(&jpeg_with_orientation(3, false)), Some(3));
+        assert_eq!(exif_orientation(&jpeg_with_orientation(9, true)), None);
+        assert!(has_metadata(&jpeg_with_orientation(1, true)));
+
+        let plain_jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x02, 0xFF, 0xDA];
+        assert_eq!(exif_orientation(&plain_jpeg), None);
+        assert!(!has_metadata(&plain_jpeg));
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend([0, 0, 0, 0]);
+        png.extend(b"IHDR");
+        png.extend([0; 4]);
+        assert!(!has_metadata(&png));
+        png.extend([0, 0, 0, 1]);
+        png.extend(b"tEXt");
+        png.extend([b'a', 0, 0, 0, 0]);
+        assert!(has_metadata(&png));
+    }
+
+    #[test]
+    fn orients_pixels() {
+        // 2×1: A then B
+        let pixels = [1, 1, 1, 1, 2, 2, 2, 2];
+        assert_eq!(orient_pixels(&pixels, 2, 1, 1), pixels);
+        assert_eq!(orient_pixels(&pixels, 2, 1, 2), [2, 2, 2, 2, 1, 1, 1, 1]);
+        // 90° clockwise: A on top of B
+        assert_eq!(orient_pixels(&pixels, 2, 1, 6), pixels);
+        assert_eq!(oriented_size(2, 1, 6), (1, 2));
+        // 90° counter-clockwise: B on top of A
+        assert_eq!(orient_pixels(&pixels, 2, 1, 8), [2, 2, 2, 2, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn fits_and_renames() {
+        assert_eq!(fit_within(4000, 3000, 2560), (2560, 1920));
+        assert_eq!(fit_within(1000, 3000, 1500), (500, 1500));
+        assert_eq!(fit_within(800, 600, 2560), (800, 600));
+        assert_eq!(fit_within(4000, 3000, 0), (4000, 3000));
+
+        assert_eq!(renamed("photo.JPG", "image/webp"), "photo.webp");
+        assert_eq!(renamed("archive.tar.png", "image/avif"), "archive.tar.avif");
+        assert_eq!(renamed("noext", "image/jpeg"), "noext.jpg");
+        assert_eq!(renamed(".hidden", "image/png"), ".hidden.png");
+        assert_eq!(to_hex(&Sha256::digest(b"abc"))[..8], *"ba7816bf");
+    }
+}
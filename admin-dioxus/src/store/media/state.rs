@@ -1,9 +1,9 @@
-use crate::store::{ListQuery, ListStore, PaginatedList, StateFrame};
+use crate::store::{ListQuery, ListStore, PaginatedList, ResourceStore, StateFrame};
 use crate::types::SortParam;
 use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use web_sys::File;
 
@@ -28,6 +28,13 @@ impl fmt::Display for MediaReference {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum UploadStatus {
+    /// Waiting for one of the [`MAX_CONCURRENT_UPLOADS`](super::MAX_CONCURRENT_UPLOADS) slots.
+    Queued,
+    /// Going through the pre-upload image pipeline (see [`prepare_upload`](super::prepare_upload)).
+    Optimizing,
+    /// The library already holds the same file; waiting for the user to use it or upload
+    /// anyway.
+    Duplicate(Media),
     Uploading,
     Success,
     Error(String),
@@ -56,6 +63,13 @@ pub struct Media {
     pub optimized_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub usage_count: i32,
+    /// Resized and WebP renditions, see [`ResponsiveImage`](super::ResponsiveImage).
+    #[serde(default)]
+    pub variants: Vec<MediaVariant>,
+    #[serde(default)]
+    pub folder_id: Option<i32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
     #[serde(default = "default_datetime")]
     pub created_at: DateTime<Utc>,
     #[serde(default = "default_datetime")]
@@ -83,12 +97,28 @@ impl Default for Media {
             is_optimized: false,
             optimized_at: None,
             usage_count: 0,
+            variants: Vec::new(),
+            folder_id: None,
+            tags: Vec::new(),
             created_at: DateTime::<Utc>::from_timestamp(0, 0).unwrap_or_else(|| Utc::now()),
             updated_at: DateTime::<Utc>::from_timestamp(0, 0).unwrap_or_else(|| Utc::now()),
         }
     }
 }
 
+/// A resized or re-encoded copy of an image, e.g. the WebP `medium` rendition.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaVariant {
+    /// `thumbnail`, `medium` or `large`.
+    pub name: String,
+    pub url: String,
+    pub width: i32,
+    pub height: i32,
+    pub mime_type: String,
+    #[serde(default)]
+    pub size: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct MediaListQuery {
     pub page: u64,
@@ -102,6 +132,12 @@ pub struct MediaListQuery {
     pub created_at_lt: Option<DateTime<Utc>>,
     pub updated_at_gt: Option<DateTime<Utc>>,
     pub updated_at_lt: Option<DateTime<Utc>>,
+    /// Only media whose stored bytes have this SHA-256.
+    pub content_hash: Option<String>,
+    /// Only media directly inside this folder.
+    pub folder_id: Option<i32>,
+    /// Only media carrying every one of these tags.
+    pub tags: Option<Vec<String>>,
 }
 
 impl MediaListQuery {
@@ -149,6 +185,47 @@ pub struct MediaUploadPayload {
     pub reference_type: Option<MediaReference>,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    /// Run the file through the pre-upload image pipeline. Off where the exact bytes must be
+    /// kept, e.g. restoring an archive.
+    pub optimize: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaUploadStartPayload {
+    pub file_name: String,
+    pub mime_type: String,
+    pub total_size: u64,
+    pub chunk_size: u64,
+    pub reference_type: Option<MediaReference>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub content_hash: Option<String>,
+    pub is_optimized: bool,
+}
+
+/// A chunked upload on the server. Chunks may arrive in any order; the file is assembled
+/// once every chunk is received.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaUploadSession {
+    pub id: i32,
+    pub chunk_size: u64,
+    pub total_size: u64,
+    /// Indexes of the chunks stored so far.
+    #[serde(default)]
+    pub received_chunks: Vec<u32>,
+}
+
+/// A chunked upload saved locally so it can resume after a reload, once the same file is
+/// selected again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InterruptedUpload {
+    /// See [`upload_fingerprint`](super::upload_fingerprint).
+    pub fingerprint: String,
+    pub session_id: i32,
+    pub file_name: String,
+    pub total_size: u64,
+    pub uploaded_bytes: u64,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -225,48 +302,127 @@ pub struct MediaUsageDetailsResponse {
     pub data: Vec<MediaUsageDetails>,
 }
 
+/// Media records sharing one content hash.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaDuplicateGroup {
+    pub content_hash: String,
+    pub media: Vec<Media>,
+}
+
+/// Points every reference to `duplicate_ids` at `keep_id`, then deletes the duplicates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaMergePayload {
+    pub keep_id: i32,
+    pub duplicate_ids: Vec<i32>,
+}
+
+/// A user-defined folder in the media library. Folders nest through `parent_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaFolder {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+    #[serde(default)]
+    pub media_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaFolderPayload {
+    pub name: String,
+    pub parent_id: Option<i32>,
+}
+
+/// Moves `media_ids` into `folder_id`, or out of any folder when it is `None`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaMovePayload {
+    pub media_ids: Vec<i32>,
+    pub folder_id: Option<i32>,
+}
+
+/// Replaces the tags of a media record.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediaTagsPayload {
+    pub tags: Vec<String>,
+}
+
 pub struct MediaState {
+    /// `remove`, `list` and `view` frames and actions, reachable directly through `Deref`.
+    pub resource: ResourceStore<Media, MediaListQuery, (), ()>,
     pub upload: GlobalSignal<StateFrame<(), MediaUploadPayload>>,
-    pub remove: GlobalSignal<HashMap<i32, StateFrame>>,
-    pub list: GlobalSignal<StateFrame<PaginatedList<Media>>>,
-    pub view: GlobalSignal<HashMap<i32, StateFrame<Media>>>,
     pub usage_details: GlobalSignal<HashMap<i32, StateFrame<MediaUsageDetails>>>,
     // Upload tracking
     pub upload_progress: GlobalSignal<HashMap<String, f64>>, // blob URL -> progress %
     pub upload_status: GlobalSignal<HashMap<String, UploadStatus>>, // blob URL -> status
     pub blob_to_media: GlobalSignal<HashMap<String, Option<Media>>>, // blob URL -> uploaded Media
     pub blob_file_info: GlobalSignal<HashMap<String, FileInfo>>, // blob URL -> file info
+    pub upload_queue: GlobalSignal<VecDeque<String>>, // blob URLs waiting for a slot, in order
+    pub active_uploads: GlobalSignal<usize>,
+    /// Chunked uploads that stopped before completing, in this or an earlier session.
+    pub interrupted_uploads: GlobalSignal<Vec<InterruptedUpload>>,
+    /// Uploads held as [`UploadStatus::Duplicate`], kept to upload anyway.
+    pub duplicate_uploads: GlobalSignal<HashMap<String, MediaUploadPayload>>, // blob URL -> payload
+    pub duplicates: GlobalSignal<StateFrame<Vec<MediaDuplicateGroup>>>,
+    pub merge: GlobalSignal<HashMap<i32, StateFrame<(), MediaMergePayload>>>, // kept media id -> merge
+    pub variants: GlobalSignal<HashMap<i32, StateFrame>>, // media id -> rendition generation
+    // Folders and tags
+    pub folders: GlobalSignal<StateFrame<Vec<MediaFolder>>>,
+    pub folder_save: GlobalSignal<StateFrame<MediaFolder, MediaFolderPayload>>,
+    pub folder_remove: GlobalSignal<HashMap<i32, StateFrame>>,
+    pub move_media: GlobalSignal<StateFrame<(), MediaMovePayload>>,
+    /// Every tag in use, for suggestions.
+    pub tags: GlobalSignal<StateFrame<Vec<String>>>,
+    pub tags_update: GlobalSignal<HashMap<i32, StateFrame<(), MediaTagsPayload>>>,
 }
 
 impl MediaState {
     pub fn new() -> Self {
         Self {
+            resource: ResourceStore::new(super::actions::config()),
             upload: GlobalSignal::new(|| StateFrame::new()),
-            remove: GlobalSignal::new(|| HashMap::new()),
-            list: GlobalSignal::new(|| StateFrame::new()),
-            view: GlobalSignal::new(|| HashMap::new()),
             usage_details: GlobalSignal::new(|| HashMap::new()),
             upload_progress: GlobalSignal::new(|| HashMap::new()),
             upload_status: GlobalSignal::new(|| HashMap::new()),
             blob_to_media: GlobalSignal::new(|| HashMap::new()),
             blob_file_info: GlobalSignal::new(|| HashMap::new()),
+            upload_queue: GlobalSignal::new(|| VecDeque::new()),
+            active_uploads: GlobalSignal::new(|| 0),
+            interrupted_uploads: GlobalSignal::new(|| Vec::new()),
+            duplicate_uploads: GlobalSignal::new(|| HashMap::new()),
+            duplicates: GlobalSignal::new(|| StateFrame::new()),
+            merge: GlobalSignal::new(|| HashMap::new()),
+            variants: GlobalSignal::new(|| HashMap::new()),
+            folders: GlobalSignal::new(|| StateFrame::new()),
+            folder_save: GlobalSignal::new(|| StateFrame::new()),
+            folder_remove: GlobalSignal::new(|| HashMap::new()),
+            move_media: GlobalSignal::new(|| StateFrame::new()),
+            tags: GlobalSignal::new(|| StateFrame::new()),
+            tags_update: GlobalSignal::new(|| HashMap::new()),
         }
     }
 }
 
+impl std::ops::Deref for MediaState {
+    type Target = ResourceStore<Media, MediaListQuery, (), ()>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.resource
+    }
+}
+
 static MEDIA_STATE: std::sync::OnceLock<MediaState> = std::sync::OnceLock::new();
 
 impl ListStore<Media, MediaListQuery> for MediaState {
     fn list_frame(&self) -> &GlobalSignal<StateFrame<PaginatedList<Media>>> {
-        &self.list
+        &self.resource.list
     }
 
     async fn fetch_list(&self) {
-        self.list().await;
+        self.resource.list().await;
     }
 
     async fn fetch_list_with_query(&self, query: MediaListQuery) {
-        self.list_with_query(query).await;
+        self.resource.list_with_query(query).await;
     }
 }
 
@@ -0,0 +1,79 @@
+//! Free-form media tags: normalizing what editors type, loading the tags in use and replacing
+//! the tags of a media record.
+
+use super::{Media, MediaState, MediaTagsPayload};
+use crate::services::endpoints::{Endpoint, MediaTagList, MediaTagsUpdate};
+use crate::store::{edit_state_abstraction, list_state_abstraction, Optimistic};
+
+/// Longest tag kept, in characters.
+pub const MAX_TAG_LENGTH: usize = 40;
+
+/// Comma-separated `input` as tags: trimmed, lowercased, inner whitespace collapsed, without
+/// blanks or repeats.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',') {
+        let tag: String = tag
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+            .chars()
+            .take(MAX_TAG_LENGTH)
+            .collect();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// The `tags` filter for `input`, `None` when it names no tag.
+pub fn tags_filter(input: &str) -> Option<Vec<String>> {
+    Some(parse_tags(input)).filter(|tags| !tags.is_empty())
+}
+
+impl MediaState {
+    /// Load every tag used in the library.
+    pub async fn tags(&self) {
+        let _ =
+            list_state_abstraction(&self.tags, MediaTagList {}.request(&()), "media tags").await;
+    }
+
+    /// Replace the tags of media `id`.
+    pub async fn set_tags(&self, id: i32, tags: Vec<String>) {
+        let payload = MediaTagsPayload { tags };
+        let updated = edit_state_abstraction(
+            &self.tags_update,
+            id,
+            payload.clone(),
+            MediaTagsUpdate { id }.request(&payload).send(),
+            "media tags",
+            Some(&self.list),
+            Some(&self.view),
+            |media: &Media| media.id,
+            None::<fn(&Media)>,
+            None::<Optimistic<fn(&mut Media, &MediaTagsPayload)>>,
+        )
+        .await;
+        if updated.is_some() {
+            self.tags().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_tags() {
+        assert_eq!(
+            parse_tags(" Hero ,hero, product  shots,,  , Q3"),
+            vec!["hero", "product shots", "q3"]
+        );
+        assert_eq!(parse_tags(&"x".repeat(60))[0].len(), MAX_TAG_LENGTH);
+        assert_eq!(tags_filter(" , "), None);
+        assert_eq!(tags_filter("Logo"), Some(vec!["logo".to_string()]));
+    }
+}
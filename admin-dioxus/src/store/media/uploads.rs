@@ -0,0 +1,397 @@
+//! Chunked, resumable uploads and the limit on uploads running at once.
+//!
+//! Files larger than [`CHUNK_SIZE`] go through an upload session: the server is told the
+//! file's size, receives it chunk by chunk and assembles it on completion. Failed chunks are
+//! retried by the request policy. A session that still fails is saved locally and resumed
+//! when the same file is selected again, skipping the chunks the server already has.
+
+use super::{
+    use_media, InterruptedUpload, Media, MediaState, MediaUploadSession, MediaUploadStartPayload,
+};
+use crate::services::endpoints::{
+    Endpoint, MediaUploadChunk, MediaUploadComplete, MediaUploadStart, MediaUploadStatus,
+};
+use crate::services::request_policy::default_policy;
+use crate::store::{api_error_message, transport_error_message};
+use crate::utils::persist;
+use chrono::Utc;
+use dioxus::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use web_sys::{File, FormData};
+
+/// Size of each chunk, and the size above which uploads are chunked. Matches the smallest
+/// part object stores accept for multipart uploads.
+pub const CHUNK_SIZE: u64 = 5 * 1024 * 1024;
+/// Uploads running at once across every upload zone; the rest wait in order.
+pub const MAX_CONCURRENT_UPLOADS: usize = 3;
+/// Retries per chunk on network errors, timeouts and gateway errors.
+const CHUNK_RETRIES: u32 = 4;
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(120);
+
+static RESTORED: AtomicBool = AtomicBool::new(false);
+
+/// Identifies a file across reloads, when the `File` object itself is gone.
+pub fn upload_fingerprint(file: &File) -> String {
+    format!(
+        "{}:{}:{}",
+        file.name(),
+        file.size() as u64,
+        file.last_modified() as i64
+    )
+}
+
+pub fn chunk_count(total_size: u64, chunk_size: u64) -> u32 {
+    total_size.div_ceil(chunk_size.max(1)).max(1) as u32
+}
+
+/// Byte range `start..end` of chunk `index`.
+pub fn chunk_range(index: u32, chunk_size: u64, total_size: u64) -> (u64, u64) {
+    let start = (index as u64 * chunk_size).min(total_size);
+    (start, (start + chunk_size).min(total_size))
+}
+
+/// Chunks the server does not have yet, in order.
+pub fn missing_chunks(session: &MediaUploadSession) -> Vec<u32> {
+    (0..chunk_count(session.total_size, session.chunk_size))
+        .filter(|index| !session.received_chunks.contains(index))
+        .collect()
+}
+
+/// Bytes of the chunks the server has.
+pub fn received_bytes(session: &MediaUploadSession) -> u64 {
+    let count = chunk_count(session.total_size, session.chunk_size);
+    let mut indexes = session.received_chunks.clone();
+    indexes.sort_unstable();
+    indexes.dedup();
+    indexes
+        .into_iter()
+        .filter(|index| *index < count)
+        .map(|index| {
+            let (start, end) = chunk_range(index, session.chunk_size, session.total_size);
+            end - start
+        })
+        .sum()
+}
+
+/// Percent of a `total_size`-byte upload done with `confirmed` bytes on the server and `sent`
+/// of the `total` bytes of the request carrying the next `sending` bytes on their way. Capped
+/// at 99; 100 is left for the media record.
+pub fn upload_percent(total_size: u64, confirmed: u64, sending: u64, sent: u64, total: u64) -> f64 {
+    if total_size == 0 {
+        return 0.0;
+    }
+    // The request also carries multipart framing, so count its share of `sending`
+    let in_flight = if total == 0 {
+        0.0
+    } else {
+        sending as f64 * sent.min(total) as f64 / total as f64
+    };
+    ((confirmed as f64 + in_flight) / total_size as f64 * 100.0).min(99.0)
+}
+
+/// Upload progress callback for the request carrying `sending` bytes of `blob_url`'s upload,
+/// after `confirmed` bytes the server already has.
+pub(super) fn progress_reporter(
+    blob_url: &str,
+    total_size: u64,
+    confirmed: u64,
+    sending: u64,
+) -> impl Fn(u64, u64) + 'static {
+    let blob_url = blob_url.to_string();
+    move |sent, total| {
+        let percent = upload_percent(total_size, confirmed, sending, sent, total);
+        use_media()
+            .upload_progress
+            .write()
+            .insert(blob_url.clone(), percent);
+    }
+}
+
+/// Every chunked upload that has not completed, including running ones.
+fn saved_uploads() -> Vec<InterruptedUpload> {
+    persist::get_upload_sessions()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_uploads(uploads: &[InterruptedUpload]) {
+    if let Ok(json) = serde_json::to_string(uploads) {
+        persist::set_upload_sessions(&json);
+    }
+}
+
+fn remember_upload(upload: &InterruptedUpload) {
+    let mut uploads = saved_uploads();
+    uploads.retain(|saved| saved.fingerprint != upload.fingerprint);
+    uploads.push(upload.clone());
+    save_uploads(&uploads);
+}
+
+fn forget_upload(fingerprint: &str) {
+    let mut uploads = saved_uploads();
+    uploads.retain(|saved| saved.fingerprint != fingerprint);
+    save_uploads(&uploads);
+}
+
+fn interrupted(message: String) -> String {
+    format!(
+        "Upload interrupted: {}. Select the file again to resume.",
+        message
+    )
+}
+
+/// Holds one of the [`MAX_CONCURRENT_UPLOADS`] slots and frees it when dropped.
+pub(super) struct UploadSlot;
+
+impl Drop for UploadSlot {
+    fn drop(&mut self) {
+        let mut active = use_media().active_uploads.write();
+        *active = active.saturating_sub(1);
+    }
+}
+
+impl MediaState {
+    /// Wait until `blob_url` is first in the queue and a slot is free. `None` when the upload
+    /// was removed from the queue while waiting.
+    pub(super) async fn acquire_upload_slot(&self, blob_url: &str) -> Option<UploadSlot> {
+        self.upload_queue.write().push_back(blob_url.to_string());
+        loop {
+            let (queued, first) = {
+                let queue = self.upload_queue.peek();
+                (
+                    queue.iter().any(|url| url == blob_url),
+                    queue.front().is_some_and(|url| url == blob_url),
+                )
+            };
+            if !queued {
+                return None;
+            }
+            if first && *self.active_uploads.peek() < MAX_CONCURRENT_UPLOADS {
+                self.upload_queue.write().pop_front();
+                *self.active_uploads.write() += 1;
+                return Some(UploadSlot);
+            }
+            gloo_timers::future::TimeoutFuture::new(100).await;
+        }
+    }
+
+    /// Load uploads interrupted before the page was loaded. Only the first call reads them;
+    /// later on the saved list also holds uploads that are running.
+    pub fn restore_interrupted_uploads(&self) {
+        if !RESTORED.swap(true, Ordering::Relaxed) {
+            self.interrupted_uploads.write().extend(saved_uploads());
+        }
+    }
+
+    /// Forget an interrupted upload, e.g. when the user no longer has the file.
+    pub fn discard_interrupted_upload(&self, fingerprint: &str) {
+        self.interrupted_uploads
+            .write()
+            .retain(|saved| saved.fingerprint != fingerprint);
+        forget_upload(fingerprint);
+    }
+
+    fn set_chunk_progress(&self, blob_url: &str, session: &MediaUploadSession) {
+        let percent = upload_percent(session.total_size, received_bytes(session), 0, 0, 0);
+        self.upload_progress
+            .write()
+            .insert(blob_url.to_string(), percent);
+    }
+
+    /// The saved session for `fingerprint`, if the server still has it.
+    async fn resumable_session(&self, fingerprint: &str) -> Option<MediaUploadSession> {
+        let session_id = saved_uploads()
+            .into_iter()
+            .find(|saved| saved.fingerprint == fingerprint)
+            .map(|saved| saved.session_id)?;
+        let response = MediaUploadStatus { session_id }
+            .request(&())
+            .send()
+            .await
+            .ok()?;
+        if !response.is_success() {
+            self.discard_interrupted_upload(fingerprint);
+            return None;
+        }
+        response.json::<MediaUploadSession>().ok()
+    }
+
+    /// Upload `file` in chunks, reporting the bytes sent as progress of `blob_url`. Resumes
+    /// the saved session for the same file when there is one.
+    pub(super) async fn upload_chunked(
+        &self,
+        file: &File,
+        payload: MediaUploadStartPayload,
+        blob_url: &str,
+    ) -> Result<Media, String> {
+        let fingerprint = upload_fingerprint(file);
+        let session = match self.resumable_session(&fingerprint).await {
+            Some(session) => {
+                gloo_console::log!(
+                    "[MediaState::upload_chunked] Resuming session",
+                    session.id.to_string()
+                );
+                session
+            }
+            None => {
+                let response = MediaUploadStart {}
+                    .request(&payload)
+                    .send()
+                    .await
+                    .map_err(transport_error_message)?;
+                if !response.is_success() {
+                    return Err(api_error_message(&response));
+                }
+                response
+                    .json::<MediaUploadSession>()
+                    .map_err(|e| format!("Failed to parse upload session: {}", e))?
+            }
+        };
+
+        // Resuming: no longer shown as interrupted while it runs
+        self.interrupted_uploads
+            .write()
+            .retain(|saved| saved.fingerprint != fingerprint);
+        let mut saved = InterruptedUpload {
+            fingerprint: fingerprint.clone(),
+            session_id: session.id,
+            file_name: payload.file_name.clone(),
+            total_size: session.total_size,
+            uploaded_bytes: received_bytes(&session),
+            updated_at: Utc::now(),
+        };
+        remember_upload(&saved);
+        self.set_chunk_progress(blob_url, &session);
+
+        match self.send_chunks(file, session, &mut saved, blob_url).await {
+            Ok(media) => {
+                forget_upload(&fingerprint);
+                Ok(media)
+            }
+            Err(message) => {
+                self.interrupted_uploads.write().push(saved);
+                Err(message)
+            }
+        }
+    }
+
+    /// Send the chunks `session` is missing, then assemble the file.
+    async fn send_chunks(
+        &self,
+        file: &File,
+        mut session: MediaUploadSession,
+        saved: &mut InterruptedUpload,
+        blob_url: &str,
+    ) -> Result<Media, String> {
+        let policy = default_policy()
+            .with_max_retries(CHUNK_RETRIES)
+            .with_timeout(Some(CHUNK_TIMEOUT));
+        for index in missing_chunks(&session) {
+            let (start, end) = chunk_range(index, session.chunk_size, session.total_size);
+            let chunk = file
+                .slice_with_f64_and_f64(start as f64, end as f64)
+                .map_err(|e| format!("Failed to read chunk {}: {:?}", index, e))?;
+            let form_data =
+                FormData::new().map_err(|e| format!("Failed to create FormData: {:?}", e))?;
+            form_data
+                .append_with_blob("chunk", &chunk)
+                .map_err(|e| format!("Failed to append chunk {}: {:?}", index, e))?;
+
+            // Re-sending a chunk index overwrites it, so chunks are safe to retry
+            let response = MediaUploadChunk {
+                session_id: session.id,
+                index,
+            }
+            .multipart(&form_data)
+            .idempotent()
+            .with_policy(policy)
+            .with_upload_progress(progress_reporter(
+                blob_url,
+                session.total_size,
+                received_bytes(&session),
+                end - start,
+            ))
+            .send()
+            .await
+            .map_err(|e| interrupted(transport_error_message(e)))?;
+            if !response.is_success() {
+                return Err(interrupted(api_error_message(&response)));
+            }
+            match response.json::<MediaUploadSession>() {
+                Ok(updated) => session = updated,
+                Err(_) => session.received_chunks.push(index),
+            }
+
+            saved.uploaded_bytes = received_bytes(&session);
+            saved.updated_at = Utc::now();
+            remember_upload(saved);
+            self.set_chunk_progress(blob_url, &session);
+        }
+
+        let response = MediaUploadComplete {
+            session_id: session.id,
+        }
+        .request(&())
+        .send()
+        .await
+        .map_err(|e| interrupted(transport_error_message(e)))?;
+        if !response.is_success() {
+            return Err(interrupted(api_error_message(&response)));
+        }
+        response
+            .json::<Media>()
+            .map_err(|e| format!("Failed to parse response: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(total_size: u64, received_chunks: Vec<u32>) -> MediaUploadSession {
+        MediaUploadSession {
+            id: 1,
+            chunk_size: 10,
+            total_size,
+            received_chunks,
+        }
+    }
+
+    #[test]
+    fn chunks_cover_the_file_and_skip_received_ones() {
+        assert_eq!(chunk_count(25, 10), 3);
+        assert_eq!(chunk_count(20, 10), 2);
+        assert_eq!(chunk_count(0, 10), 1);
+        assert_eq!(chunk_range(0, 10, 25), (0, 10));
+        assert_eq!(chunk_range(2, 10, 25), (20, 25));
+
+        assert_eq!(missing_chunks(&session(25, vec![])), vec![0, 1, 2]);
+        assert_eq!(missing_chunks(&session(25, vec![1])), vec![0, 2]);
+        assert!(missing_chunks(&session(25, vec![0, 1, 2])).is_empty());
+    }
+
+    #[test]
+    fn progress_moves_within_a_request() {
+        // A single-request upload of 1000 bytes, sent as a 1100-byte multipart body
+        let single: Vec<f64> = [0, 275, 550, 1100]
+            .into_iter()
+            .map(|sent| upload_percent(1000, 0, 1000, sent, 1100))
+            .collect();
+        assert_eq!(single, vec![0.0, 25.0, 50.0, 99.0]);
+
+        // The second of four 10-byte chunks: started, halfway and confirmed
+        assert_eq!(upload_percent(40, 10, 10, 0, 20), 25.0);
+        assert_eq!(upload_percent(40, 10, 10, 10, 20), 37.5);
+        assert_eq!(upload_percent(40, 20, 0, 0, 0), 50.0);
+        assert_eq!(upload_percent(0, 0, 0, 0, 0), 0.0);
+    }
+
+    #[test]
+    fn received_bytes_counts_each_chunk_once() {
+        assert_eq!(received_bytes(&session(25, vec![])), 0);
+        assert_eq!(received_bytes(&session(25, vec![2])), 5);
+        assert_eq!(received_bytes(&session(25, vec![0, 2, 2, 7])), 15);
+        assert_eq!(received_bytes(&session(25, vec![0, 1, 2])), 25);
+    }
+}
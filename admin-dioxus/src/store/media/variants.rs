@@ -0,0 +1,197 @@
+//! Responsive renditions of an image: `thumbnail`, `medium` and `large` copies in the original
+//! format and as WebP, rendered by the backend and recorded on [`Media::variants`].
+//!
+//! Renderers show the narrowest rendition that still covers the width an image is displayed
+//! at, and list the rest in `srcset` so the browser can pick one for the screen's density.
+
+use super::{Media, MediaState, MediaVariant};
+use crate::services::endpoints::{Endpoint, MediaGenerateVariants};
+use crate::store::{edit_state_abstraction, Optimistic};
+
+pub const WEBP_MIME: &str = "image/webp";
+
+/// Width of the post column, in CSS pixels.
+pub const CONTENT_IMAGE_WIDTH: i32 = 768;
+/// `sizes` for images spanning the post column.
+pub const CONTENT_IMAGE_SIZES: &str = "(max-width: 768px) 100vw, 768px";
+/// Width of a preview card.
+pub const PREVIEW_IMAGE_WIDTH: i32 = 320;
+/// `sizes` for preview cards.
+pub const PREVIEW_IMAGE_SIZES: &str = "(max-width: 640px) 100vw, 320px";
+/// Width of a list thumbnail.
+pub const THUMBNAIL_IMAGE_WIDTH: i32 = 48;
+
+/// An image's `src`, with `srcset`s when it has renditions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponsiveImage {
+    pub src: String,
+    /// Renditions in the original format, plus the original when its width is known.
+    pub srcset: Option<String>,
+    /// WebP renditions, for a `<source type="image/webp">`.
+    pub webp_srcset: Option<String>,
+}
+
+impl ResponsiveImage {
+    /// The image at `url` (`width` pixels wide, if known) shown `display_width` pixels wide.
+    pub fn new(
+        url: &str,
+        width: Option<i32>,
+        variants: &[MediaVariant],
+        display_width: i32,
+    ) -> Self {
+        let src = pick_variant(variants, display_width)
+            .filter(|variant| width.is_none_or(|width| variant.width < width))
+            .map_or_else(|| url.to_string(), |variant| variant.url.clone());
+
+        let mut candidates: Vec<(&str, i32)> = renditions(variants, false)
+            .into_iter()
+            .map(|variant| (variant.url.as_str(), variant.width))
+            .collect();
+        if let Some(width) = width {
+            if !candidates.is_empty() && candidates.iter().all(|(_, w)| *w < width) {
+                candidates.push((url, width));
+            }
+        }
+        let webp: Vec<(&str, i32)> = renditions(variants, true)
+            .into_iter()
+            .map(|variant| (variant.url.as_str(), variant.width))
+            .collect();
+
+        Self {
+            src,
+            srcset: srcset(&candidates),
+            webp_srcset: srcset(&webp),
+        }
+    }
+
+    pub fn from_media(media: &Media, display_width: i32) -> Self {
+        Self::new(&media.file_url, media.width, &media.variants, display_width)
+    }
+}
+
+/// Renditions that are (or are not) WebP, narrowest first.
+fn renditions(variants: &[MediaVariant], webp: bool) -> Vec<&MediaVariant> {
+    let mut matching: Vec<&MediaVariant> = variants
+        .iter()
+        .filter(|variant| (variant.mime_type == WEBP_MIME) == webp)
+        .collect();
+    matching.sort_by_key(|variant| variant.width);
+    matching
+}
+
+/// The narrowest non-WebP rendition at least `display_width` pixels wide, or the widest one
+/// when none is.
+pub fn pick_variant(variants: &[MediaVariant], display_width: i32) -> Option<&MediaVariant> {
+    let candidates = renditions(variants, false);
+    candidates
+        .iter()
+        .find(|variant| variant.width >= display_width)
+        .or(candidates.last())
+        .copied()
+}
+
+/// `url 320w, url 768w`, or `None` without candidates.
+fn srcset(candidates: &[(&str, i32)]) -> Option<String> {
+    if candidates.is_empty() {
+        return None;
+    }
+    let entries: Vec<String> = candidates
+        .iter()
+        .map(|(url, width)| format!("{} {}w", url.replace(' ', "%20"), width))
+        .collect();
+    Some(entries.join(", "))
+}
+
+impl MediaState {
+    /// Have the backend (re)render the renditions of image `id`, returning the updated media.
+    pub async fn generate_variants(&self, id: i32) -> Option<Media> {
+        edit_state_abstraction(
+            &self.variants,
+            id,
+            (),
+            MediaGenerateVariants { id }.request(&()).send(),
+            "media variants",
+            Some(&self.list),
+            Some(&self.view),
+            |media: &Media| media.id,
+            None::<fn(&Media)>,
+            None::<Optimistic<fn(&mut Media, &())>>,
+        )
+        .await
+    }
+
+    /// `media` with renditions, generating them first when it has none. Falls back to
+    /// `media` as is when generation fails, so callers can still use the original.
+    pub async fn with_variants(&self, media: Media) -> Media {
+        if !media.variants.is_empty() || !media.mime_type.starts_with("image/") {
+            return media;
+        }
+        self.generate_variants(media.id).await.unwrap_or(media)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(name: &str, width: i32, mime_type: &str) -> MediaVariant {
+        MediaVariant {
+            name: name.into(),
+            url: format!("https://cdn.test/{}-{}.{}", name, width, &mime_type[6..]),
+            width,
+            height: width / 2,
+            mime_type: mime_type.into(),
+            size: 0,
+        }
+    }
+
+    fn variants() -> Vec<MediaVariant> {
+        vec![
+            variant("large", 1536, "image/jpeg"),
+            variant("thumbnail", 320, "image/jpeg"),
+            variant("medium", 768, "image/jpeg"),
+            variant("medium", 768, WEBP_MIME),
+            variant("thumbnail", 320, WEBP_MIME),
+        ]
+    }
+
+    #[test]
+    fn picks_the_narrowest_rendition_that_fits() {
+        let variants = variants();
+        let name = |width| pick_variant(&variants, width).map(|variant| variant.width);
+        assert_eq!(name(48), Some(320));
+        assert_eq!(name(320), Some(320));
+        assert_eq!(name(700), Some(768));
+        assert_eq!(name(4000), Some(1536));
+        assert_eq!(pick_variant(&[], 320), None);
+    }
+
+    #[test]
+    fn builds_srcsets_per_format() {
+        let image = ResponsiveImage::new("https://cdn.test/full.jpg", Some(3000), &variants(), 768);
+        assert_eq!(image.src, "https://cdn.test/medium-768.jpeg");
+        assert_eq!(
+            image.srcset.as_deref(),
+            Some(
+                "https://cdn.test/thumbnail-320.jpeg 320w, https://cdn.test/medium-768.jpeg 768w, \
+                 https://cdn.test/large-1536.jpeg 1536w, https://cdn.test/full.jpg 3000w"
+            )
+        );
+        assert_eq!(
+            image.webp_srcset.as_deref(),
+            Some("https://cdn.test/thumbnail-320.webp 320w, https://cdn.test/medium-768.webp 768w")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_original() {
+        let plain = ResponsiveImage::new("https://cdn.test/a b.png", Some(800), &[], 768);
+        assert_eq!(plain.src, "https://cdn.test/a b.png");
+        assert_eq!(plain.srcset, None);
+        assert_eq!(plain.webp_srcset, None);
+
+        // A rendition wider than the original is never preferred over it
+        let small = ResponsiveImage::new("https://cdn.test/small.jpg", Some(600), &variants(), 768);
+        assert_eq!(small.src, "https://cdn.test/small.jpg");
+    }
+}
@@ -1,23 +1,33 @@
 pub mod analytics;
+mod archive;
 mod auth;
+mod bulk;
 mod categories;
+mod devtools;
 mod error;
 mod image_editor;
 mod lib;
 mod media;
+mod outbox;
 mod posts;
+mod resource;
 mod tags;
 mod traits;
 mod users;
 
 pub use analytics::*;
+pub use archive::*;
 pub use auth::*;
+pub use bulk::*;
 pub use categories::*;
+pub use devtools::*;
 pub use error::*;
 pub use image_editor::*;
 pub use lib::*;
 pub use media::*;
+pub use outbox::*;
 pub use posts::*;
+pub use resource::*;
 pub use tags::*;
 pub use traits::*;
 pub use users::*;
@@ -0,0 +1,330 @@
+use super::{OutboxEntry, OutboxMutation, OutboxState, OutboxStatus};
+use crate::services::endpoints::{Endpoint, PostView};
+use crate::store::{api_error_message, invalidate_queries, is_offline, TransportErrorInfo};
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use dioxus_storage::StorageBacking;
+use serde::Deserialize;
+
+/// Where the outbox outlives a reload: localStorage on the web, the app's data directory on
+/// native. Tests keep it in memory, scoped to their own VirtualDom.
+#[cfg(not(test))]
+type OutboxStorage = dioxus_storage::LocalStorage;
+#[cfg(test)]
+type OutboxStorage = dioxus_storage::SessionStorage;
+
+const OUTBOX_STORAGE_KEY: &str = "outbox";
+
+/// Why a replayed entry was not applied.
+enum ReplayError {
+    /// The API answered; the entry is parked with this status until the user acts on it.
+    Rejected(OutboxStatus),
+    /// No usable answer; the entry stays queued and replay stops to preserve ordering.
+    Transport(TransportErrorInfo),
+}
+
+/// The only field the conflict check needs from `/post/v1/view/{id}`.
+#[derive(Deserialize)]
+struct ServerVersion {
+    updated_at: DateTime<Utc>,
+}
+
+async fn apply(mutation: &OutboxMutation) -> Result<(), ReplayError> {
+    if let OutboxMutation::PostEdit {
+        post_id,
+        base_updated_at: Some(base),
+        ..
+    } = mutation
+    {
+        let response = PostView { id: *post_id }
+            .request(&())
+            .send()
+            .await
+            .map_err(ReplayError::Transport)?;
+        if !response.is_success() {
+            return Err(ReplayError::Rejected(OutboxStatus::Failed(
+                api_error_message(&response),
+            )));
+        }
+        match response.json::<ServerVersion>() {
+            Ok(server) if server.updated_at > *base => {
+                return Err(ReplayError::Rejected(OutboxStatus::Conflict(format!(
+                    "The post was updated on the server at {} after this edit was queued",
+                    server.updated_at.format("%Y-%m-%d %H:%M UTC")
+                ))));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Err(ReplayError::Rejected(OutboxStatus::Failed(format!(
+                    "Unexpected response format for 'post': {}",
+                    e
+                ))));
+            }
+        }
+    }
+
+    let response = mutation
+        .request()
+        .send()
+        .await
+        .map_err(ReplayError::Transport)?;
+    if response.is_success() {
+        Ok(())
+    } else {
+        Err(ReplayError::Rejected(OutboxStatus::Failed(
+            api_error_message(&response),
+        )))
+    }
+}
+
+impl OutboxState {
+    /// Load entries saved by a previous session. Entries already in memory win.
+    pub fn restore(&self) {
+        if !self.entries.read().is_empty() {
+            return;
+        }
+        if let Some(entries) =
+            OutboxStorage::get::<Vec<OutboxEntry>>(&OUTBOX_STORAGE_KEY.to_string())
+        {
+            *self.entries.write() = entries;
+        }
+    }
+
+    fn save(&self) {
+        OutboxStorage::set(OUTBOX_STORAGE_KEY.to_string(), &*self.entries.read());
+    }
+
+    /// Append `mutation` to the outbox and return its entry id.
+    pub fn enqueue(&self, mutation: OutboxMutation) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.entries.write().push(OutboxEntry {
+            id: id.clone(),
+            queued_at: Utc::now(),
+            mutation,
+            status: OutboxStatus::Queued,
+            attempts: 0,
+        });
+        self.save();
+        id
+    }
+
+    /// Queue the mutation instead of sending it while the browser is offline.
+    /// Returns `true` when it was queued and the caller should not send the request.
+    pub fn defer_if_offline(&self, mutation: impl FnOnce() -> OutboxMutation) -> bool {
+        if !is_offline() {
+            return false;
+        }
+        self.enqueue(mutation());
+        true
+    }
+
+    pub fn discard(&self, id: &str) {
+        self.entries.write().retain(|entry| entry.id != id);
+        self.save();
+    }
+
+    /// Put a failed or conflicting entry back in the queue. A conflicting post edit is
+    /// requeued without its `updated_at` check, so the next replay overwrites the server copy.
+    pub fn requeue(&self, id: &str) {
+        {
+            let mut entries = self.entries.write();
+            let Some(entry) = entries.iter_mut().find(|entry| entry.id == id) else {
+                return;
+            };
+            if let (
+                OutboxStatus::Conflict(_),
+                OutboxMutation::PostEdit {
+                    base_updated_at, ..
+                },
+            ) = (&entry.status, &mut entry.mutation)
+            {
+                *base_updated_at = None;
+            }
+            entry.status = OutboxStatus::Queued;
+        }
+        self.save();
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.entries
+            .read()
+            .iter()
+            .filter(|entry| entry.status == OutboxStatus::Queued)
+            .count()
+    }
+
+    /// Send queued entries in the order they were made. API rejections and conflicts are
+    /// parked for the user; the first transport failure stops the replay so later entries
+    /// never overtake earlier ones.
+    pub async fn replay(&self) {
+        if self.replay.read().is_loading() {
+            return;
+        }
+        self.replay.write().set_loading();
+
+        let queued: Vec<(String, OutboxMutation)> = self
+            .entries
+            .read()
+            .iter()
+            .filter(|entry| entry.status == OutboxStatus::Queued)
+            .map(|entry| (entry.id.clone(), entry.mutation.clone()))
+            .collect();
+
+        for (id, mutation) in queued {
+            // The user may have discarded the entry while an earlier one was in flight.
+            if !self.entries.read().iter().any(|entry| entry.id == id) {
+                continue;
+            }
+            match apply(&mutation).await {
+                Ok(()) => {
+                    self.entries.write().retain(|entry| entry.id != id);
+                    invalidate_queries(mutation.resource());
+                }
+                Err(ReplayError::Rejected(status)) => {
+                    if let Some(entry) = self.entries.write().iter_mut().find(|e| e.id == id) {
+                        entry.status = status;
+                    }
+                }
+                Err(ReplayError::Transport(error)) => {
+                    if let Some(entry) = self.entries.write().iter_mut().find(|e| e.id == id) {
+                        entry.attempts += 1;
+                    }
+                    self.save();
+                    self.replay
+                        .write()
+                        .set_transport_error(error.kind, error.message);
+                    return;
+                }
+            }
+            self.save();
+        }
+
+        self.replay.write().set_success(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::{fake::with_fake_client, HttpMethod};
+    use crate::store::{
+        use_outbox, CategoriesAddPayload, PostEditPayload, TagsAddPayload, TransportErrorKind,
+    };
+    use futures_util::FutureExt;
+
+    fn tag_create(name: &str) -> OutboxMutation {
+        OutboxMutation::TagCreate {
+            payload: TagsAddPayload {
+                name: name.into(),
+                ..TagsAddPayload::default()
+            },
+        }
+    }
+
+    #[test]
+    fn replay_sends_entries_in_order_and_clears_them() {
+        with_fake_client(|fake| {
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/create",
+                201,
+                &serde_json::json!({}),
+            );
+            fake.respond_json(
+                HttpMethod::Post,
+                "/category/v1/create",
+                201,
+                &serde_json::json!({}),
+            );
+
+            let outbox = use_outbox();
+            outbox.enqueue(tag_create("rust"));
+            outbox.enqueue(OutboxMutation::CategoryCreate {
+                payload: CategoriesAddPayload {
+                    name: "News".into(),
+                    ..CategoriesAddPayload::default()
+                },
+            });
+            assert_eq!(outbox.pending_count(), 2);
+
+            outbox.replay().now_or_never().unwrap();
+
+            assert!(outbox.entries.read().is_empty());
+            assert!(outbox.replay.read().is_success());
+            let paths: Vec<String> = fake
+                .requests
+                .borrow()
+                .iter()
+                .map(|r| r.path.clone())
+                .collect();
+            assert_eq!(paths, vec!["/tag/v1/create", "/category/v1/create"]);
+        });
+    }
+
+    #[test]
+    fn replay_parks_conflicts_and_stops_on_transport_errors() {
+        with_fake_client(|fake| {
+            let base = Utc::now() - chrono::Duration::hours(1);
+            fake.respond_json(
+                HttpMethod::Post,
+                "/post/v1/view/3",
+                200,
+                &serde_json::json!({ "id": 3, "updated_at": Utc::now() }),
+            );
+            fake.respond(
+                HttpMethod::Post,
+                "/tag/v1/create",
+                Err(TransportErrorInfo {
+                    kind: TransportErrorKind::Network,
+                    message: None,
+                }),
+            );
+
+            let outbox = use_outbox();
+            let edit = outbox.enqueue(OutboxMutation::PostEdit {
+                post_id: 3,
+                payload: PostEditPayload {
+                    title: Some("Offline title".into()),
+                    ..PostEditPayload::default()
+                },
+                base_updated_at: Some(base),
+            });
+            outbox.enqueue(tag_create("rust"));
+            outbox.enqueue(tag_create("wasm"));
+
+            outbox.replay().now_or_never().unwrap();
+
+            let entries = outbox.entries.read().clone();
+            assert!(matches!(entries[0].status, OutboxStatus::Conflict(_)));
+            assert_eq!(entries[1].status, OutboxStatus::Queued);
+            assert_eq!(entries[1].attempts, 1);
+            assert_eq!(
+                entries[2].attempts, 0,
+                "replay stops at the first transport error"
+            );
+            assert!(outbox.replay.read().is_failed());
+            assert!(
+                !fake
+                    .requests
+                    .borrow()
+                    .iter()
+                    .any(|r| r.path == "/post/v1/update/3"),
+                "conflicting edits are not sent"
+            );
+
+            outbox.requeue(&edit);
+            assert!(matches!(
+                &outbox.entries.read()[0].mutation,
+                OutboxMutation::PostEdit {
+                    base_updated_at: None,
+                    ..
+                }
+            ));
+
+            // The outbox survives a reload.
+            outbox.entries.write().clear();
+            outbox.restore();
+            assert_eq!(outbox.entries.read().len(), 3);
+        });
+    }
+}
@@ -0,0 +1,129 @@
+use crate::services::api_client::ApiRequest;
+use crate::services::endpoints::{CategoryCreate, Endpoint, PostUpdate, TagCreate};
+use crate::store::{CategoriesAddPayload, PostEditPayload, StateFrame, TagsAddPayload};
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Shown on the originating form when a mutation is queued instead of sent.
+pub const OUTBOX_QUEUED_MESSAGE: &str =
+    "You're offline. The change was queued and will sync when you reconnect.";
+
+/// A mutation made while offline, replayed in order once the browser reconnects.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutboxMutation {
+    PostEdit {
+        post_id: i32,
+        payload: PostEditPayload,
+        /// `updated_at` of the post the edit was made against. Replay refuses to overwrite a
+        /// post that changed on the server since; `None` overwrites unconditionally.
+        base_updated_at: Option<DateTime<Utc>>,
+    },
+    TagCreate {
+        payload: TagsAddPayload,
+    },
+    CategoryCreate {
+        payload: CategoriesAddPayload,
+    },
+}
+
+impl OutboxMutation {
+    pub fn label(&self) -> String {
+        match self {
+            OutboxMutation::PostEdit {
+                post_id, payload, ..
+            } => match &payload.title {
+                Some(title) => format!("Edit post \"{}\"", title),
+                None => format!("Edit post #{}", post_id),
+            },
+            OutboxMutation::TagCreate { payload } => format!("Create tag \"{}\"", payload.name),
+            OutboxMutation::CategoryCreate { payload } => {
+                format!("Create category \"{}\"", payload.name)
+            }
+        }
+    }
+
+    /// Query-cache tag invalidated once the mutation is applied.
+    pub fn resource(&self) -> &'static str {
+        match self {
+            OutboxMutation::PostEdit { .. } => "post",
+            OutboxMutation::TagCreate { .. } => "tag",
+            OutboxMutation::CategoryCreate { .. } => "category",
+        }
+    }
+
+    pub fn request(&self) -> ApiRequest {
+        match self {
+            OutboxMutation::PostEdit {
+                post_id, payload, ..
+            } => PostUpdate { id: *post_id }.request(payload),
+            OutboxMutation::TagCreate { payload } => TagCreate {}.request(payload),
+            OutboxMutation::CategoryCreate { payload } => CategoryCreate {}.request(payload),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "status", content = "message", rename_all = "snake_case")]
+pub enum OutboxStatus {
+    Queued,
+    /// Rejected by the API; kept until the user retries or discards it.
+    Failed(String),
+    /// The target changed on the server after the mutation was queued.
+    Conflict(String),
+}
+
+impl OutboxStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutboxStatus::Queued => "Queued",
+            OutboxStatus::Failed(_) => "Failed",
+            OutboxStatus::Conflict(_) => "Conflict",
+        }
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            OutboxStatus::Queued => None,
+            OutboxStatus::Failed(message) | OutboxStatus::Conflict(message) => Some(message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub queued_at: DateTime<Utc>,
+    pub mutation: OutboxMutation,
+    pub status: OutboxStatus,
+    /// Replay attempts that ended in a transport error.
+    pub attempts: u32,
+}
+
+pub struct OutboxState {
+    /// Pending mutations in the order they were made.
+    pub entries: GlobalSignal<Vec<OutboxEntry>>,
+    pub replay: GlobalSignal<StateFrame>,
+}
+
+impl OutboxState {
+    pub fn new() -> Self {
+        Self {
+            entries: GlobalSignal::new(Vec::new),
+            replay: GlobalSignal::new(StateFrame::new),
+        }
+    }
+}
+
+impl Default for OutboxState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static OUTBOX_STATE: std::sync::OnceLock<OutboxState> = std::sync::OnceLock::new();
+
+pub fn use_outbox() -> &'static OutboxState {
+    OUTBOX_STATE.get_or_init(OutboxState::new)
+}
@@ -1,14 +1,26 @@
 use super::{
-    Post, PostCreatePayload, PostEditPayload, PostListQuery, PostRevision, PostSchedulePayload,
-    PostState, Series, SeriesCreatePayload, SeriesEditPayload, SeriesListQuery,
+    use_post, CalendarRange, Post, PostCreatePayload, PostEditPayload, PostListQuery,
+    PostSchedulePayload, PostState, PostStatus, Series, SeriesAddPostPayload, SeriesCreatePayload,
+    SeriesEditPayload, SeriesListQuery,
+};
+use crate::services::endpoints::{
+    EmptyObject, Endpoint, PostCreate, PostDelete, PostList, PostListPublished, PostRevisionList,
+    PostRevisionRestore, PostSchedule, PostSitemap, PostTrackView, PostUpdate, PostView,
+    PostViewBySlug, ResponseOf, SeriesAddPost, SeriesCreate, SeriesDelete, SeriesList,
+    SeriesRemovePost, SeriesUpdate,
 };
-use crate::services::http_client;
 use crate::store::{
-    edit_state_abstraction, list_state_abstraction, remove_state_abstraction,
-    state_request_abstraction, view_state_abstraction, PaginatedList, StateFrame,
+    edit_state_abstraction, invalidate_queries, list_state_abstraction, remove_state_abstraction,
+    state_request_abstraction, use_outbox, view_state_abstraction, Optimistic, OutboxMutation,
+    PaginatedList, StateFrame, TransportErrorKind, OUTBOX_QUEUED_MESSAGE,
 };
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
 use std::collections::HashMap;
 
+/// Upper bound on list pages fetched for one calendar range.
+const CALENDAR_MAX_PAGES: u64 = 10;
+
 // ============================================================================
 // Core Post CRUD Operations
 // ============================================================================
@@ -17,7 +29,7 @@ impl PostState {
     /// Create a new post
     pub async fn add(&self, payload: PostCreatePayload) {
         let meta_payload = payload.clone();
-        let request = http_client::post("/post/v1/create", &payload);
+        let request = PostCreate {}.request(&payload);
         let created = state_request_abstraction(
             &self.add,
             Some(meta_payload),
@@ -28,46 +40,113 @@ impl PostState {
         .await;
 
         if created.is_some() {
+            invalidate_queries("post");
             self.list().await;
         }
     }
 
     /// Update an existing post
     pub async fn edit(&self, post_id: i32, payload: PostEditPayload) {
+        if use_outbox().defer_if_offline(|| OutboxMutation::PostEdit {
+            post_id,
+            payload: payload.clone(),
+            base_updated_at: self.cached_updated_at(post_id),
+        }) {
+            self.edit
+                .write()
+                .entry(post_id)
+                .or_default()
+                .set_transport_error(
+                    TransportErrorKind::Offline,
+                    Some(OUTBOX_QUEUED_MESSAGE.to_string()),
+                );
+            return;
+        }
+
         let _post = edit_state_abstraction(
             &self.edit,
             post_id,
             payload.clone(),
-            http_client::post(&format!("/post/v1/update/{}", post_id), &payload).send(),
+            PostUpdate { id: post_id }.request(&payload).send(),
             "post",
             Some(&self.list),
             Some(&self.view),
             |post: &Post| post.id,
             None::<fn(&Post)>,
+            Some(
+                Optimistic::edit(|post: &mut Post, payload: &PostEditPayload| {
+                    payload.apply_to(post)
+                })
+                .with_retry(move || {
+                    let payload = payload.clone();
+                    spawn(async move { use_post().edit(post_id, payload).await });
+                }),
+            ),
         )
         .await;
     }
 
+    /// Save editor content to a post in the background. Unlike `edit`, this leaves the
+    /// `edit` frame and the cached post alone so the open form does not react to it.
+    pub async fn autosave(&self, post_id: i32, payload: PostEditPayload) {
+        // Keep the last save time while the next save is in flight
+        self.autosave
+            .write()
+            .entry(post_id)
+            .or_default()
+            .set_loading();
+
+        let result = PostUpdate { id: post_id }.request(&payload).send().await;
+
+        let mut autosave_map = self.autosave.write();
+        let frame = autosave_map.entry(post_id).or_default();
+        match result {
+            Ok(response) if response.is_success() => {
+                frame.set_success(Some(Utc::now()));
+                drop(autosave_map);
+                invalidate_queries("post");
+            }
+            Ok(response) => frame.set_api_error(&response),
+            Err(e) => frame.set_transport_error(e.kind, e.message),
+        }
+    }
+
+    /// `updated_at` of the cached copy of a post, used as the base for offline edits.
+    fn cached_updated_at(&self, post_id: i32) -> Option<DateTime<Utc>> {
+        if let Some(post) = self.view.read().get(&post_id).and_then(|f| f.data.as_ref()) {
+            return Some(post.updated_at);
+        }
+        self.list
+            .read()
+            .data
+            .as_ref()
+            .and_then(|list| list.data.iter().find(|p| p.id == post_id))
+            .map(|post| post.updated_at)
+    }
+
     /// Delete a post
     pub async fn remove(&self, post_id: i32) {
         let _ = remove_state_abstraction(
             &self.remove,
             post_id,
-            http_client::post(&format!("/post/v1/delete/{}", post_id), &()).send(),
+            PostDelete { id: post_id }.request(&()).send(),
             "post",
             Some(&self.list),
             Some(&self.view),
             |post: &Post| post.id,
             None::<fn()>,
+            Some(Optimistic::remove().with_retry(move || {
+                spawn(async move { use_post().remove(post_id).await });
+            })),
         )
         .await;
     }
 
     /// List posts with default query
     pub async fn list(&self) {
-        let _ = list_state_abstraction::<PaginatedList<Post>>(
+        let _ = list_state_abstraction::<ResponseOf<PostList>>(
             &self.list,
-            http_client::post("/post/v1/query", &serde_json::json!({})),
+            PostList {}.request(&PostListQuery::new()),
             "posts",
         )
         .await;
@@ -75,9 +154,9 @@ impl PostState {
 
     /// List posts with custom query parameters
     pub async fn list_with_query(&self, query: PostListQuery) {
-        let _ = list_state_abstraction::<PaginatedList<Post>>(
+        let _ = list_state_abstraction::<ResponseOf<PostList>>(
             &self.list,
-            http_client::post("/post/v1/query", &query),
+            PostList {}.request(&query),
             "posts",
         )
         .await;
@@ -87,14 +166,12 @@ impl PostState {
     /// Note: This method fetches by id_or_slug but caches by post.id
     pub async fn view(&self, id_or_slug: &str) {
         // We need to handle this manually since the key might be a slug but we cache by id
-        let result = http_client::post(&format!("/post/v1/view/{}", id_or_slug), &())
-            .send()
-            .await;
+        let result = PostViewBySlug { id_or_slug }.request(&()).send().await;
 
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
-                    match response.json::<Post>().await {
+                if response.is_success() {
+                    match response.json::<ResponseOf<PostViewBySlug>>() {
                         Ok(post) => {
                             let post_id = post.id;
                             let mut view_map = self.view.write();
@@ -111,12 +188,12 @@ impl PostState {
                 } else {
                     dioxus::logger::tracing::error!(
                         "Failed to fetch post: status {}",
-                        response.status()
+                        response.status
                     );
                 }
             }
             Err(e) => {
-                dioxus::logger::tracing::error!("Network error fetching post: {}", e);
+                dioxus::logger::tracing::error!("Network error fetching post: {:?}", e);
             }
         }
     }
@@ -126,7 +203,7 @@ impl PostState {
         let _ = view_state_abstraction(
             &self.view,
             post_id,
-            http_client::post(&format!("/post/v1/view/{}", post_id), &()).send(),
+            PostView { id: post_id }.request(&()),
             "post",
             |post: &Post| post.clone(),
         )
@@ -135,9 +212,9 @@ impl PostState {
 
     /// List published posts (public endpoint)
     pub async fn list_published(&self) {
-        let _ = list_state_abstraction::<PaginatedList<Post>>(
+        let _ = list_state_abstraction::<ResponseOf<PostListPublished>>(
             &self.list,
-            http_client::post("/post/v1/list/published", &serde_json::json!({})),
+            PostListPublished {}.request(&EmptyObject {}),
             "published posts",
         )
         .await;
@@ -157,14 +234,12 @@ impl PostState {
             .set_loading();
         drop(schedule_map);
 
-        let result = http_client::post("/post/v1/schedule", &payload)
-            .send()
-            .await;
+        let result = PostSchedule {}.request(&payload).send().await;
 
         let mut schedule_map = self.schedule.write();
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
+                if response.is_success() {
                     schedule_map
                         .entry(post_id)
                         .or_insert_with(StateFrame::new)
@@ -172,24 +247,145 @@ impl PostState {
 
                     // Refresh the post view and list
                     drop(schedule_map);
+                    invalidate_queries("post");
                     self.view_by_id(post_id).await;
                     self.list().await;
                 } else {
                     schedule_map
                         .entry(post_id)
                         .or_insert_with(StateFrame::new)
-                        .set_api_error(&response)
-                        .await;
+                        .set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 schedule_map
                     .entry(post_id)
                     .or_insert_with(StateFrame::new)
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
+            }
+        }
+    }
+
+    /// Move a post to a new publish time from the calendar. The calendar entry is
+    /// updated right away and reverted if the schedule call fails.
+    pub async fn reschedule(&self, post_id: i32, publish_at: DateTime<Utc>) {
+        let previous = {
+            let mut calendar = self.calendar.write();
+            calendar
+                .data
+                .as_mut()
+                .and_then(|posts| posts.iter_mut().find(|p| p.id == post_id))
+                .map(|post| std::mem::replace(&mut post.published_at, Some(publish_at)))
+        };
+
+        self.schedule(PostSchedulePayload {
+            post_id,
+            publish_at,
+        })
+        .await;
+
+        let failed = self
+            .schedule
+            .peek()
+            .get(&post_id)
+            .is_some_and(|frame| frame.is_failed());
+        if let (true, Some(previous)) = (failed, previous) {
+            if let Some(post) = self
+                .calendar
+                .write()
+                .data
+                .as_mut()
+                .and_then(|posts| posts.iter_mut().find(|p| p.id == post_id))
+            {
+                post.published_at = previous;
+            }
+        }
+    }
+
+    // ============================================================================
+    // Editorial Calendar
+    // ============================================================================
+
+    /// Load every post that lands in `range`: scheduled and published posts by their
+    /// publish time, drafts without one by their last update.
+    pub async fn calendar_range(&self, range: CalendarRange) {
+        self.calendar.write().set_loading_meta(Some(range));
+
+        let dated = PostListQuery {
+            published_at_gt: Some(range.from),
+            published_at_lt: Some(range.to),
+            ..PostListQuery::new()
+        };
+        let drafts = PostListQuery {
+            status: Some(PostStatus::Draft),
+            updated_at_gt: Some(range.from),
+            updated_at_lt: Some(range.to),
+            ..PostListQuery::new()
+        };
+
+        let Some(mut posts) = self.calendar_pages(dated, range).await else {
+            return;
+        };
+        let Some(drafts) = self.calendar_pages(drafts, range).await else {
+            return;
+        };
+        // Drafts with a publish time are placed by it, so only undated ones come from here
+        for draft in drafts {
+            if draft.published_at.is_none() && !posts.iter().any(|p| p.id == draft.id) {
+                posts.push(draft);
+            }
+        }
+
+        let mut calendar = self.calendar.write();
+        if calendar.meta == Some(range) {
+            calendar.set_success(Some(posts));
+        }
+    }
+
+    /// Fetch all pages of `query`, up to `CALENDAR_MAX_PAGES`. Errors are written to the
+    /// calendar frame; `None` is also returned when a newer range replaced this one.
+    async fn calendar_pages(
+        &self,
+        mut query: PostListQuery,
+        range: CalendarRange,
+    ) -> Option<Vec<Post>> {
+        let mut posts = Vec::new();
+        for page in 1..=CALENDAR_MAX_PAGES {
+            query.page = Some(page);
+            let result = PostList {}.request(&query).send().await;
+            if self.calendar.peek().meta != Some(range) {
+                return None;
+            }
+            let response = match result {
+                Ok(response) if response.is_success() => response,
+                Ok(response) => {
+                    self.calendar.write().set_api_error(&response);
+                    return None;
+                }
+                Err(e) => {
+                    self.calendar.write().set_transport_error(e.kind, e.message);
+                    return None;
+                }
+            };
+            match response.json::<ResponseOf<PostList>>() {
+                Ok(list) => {
+                    let more = list.has_next_page();
+                    posts.extend(list.data);
+                    if !more {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    self.calendar.write().set_decode_error(
+                        "calendar posts",
+                        e.to_string(),
+                        Some(response.text().to_string()),
+                    );
+                    return None;
+                }
             }
         }
+        Some(posts)
     }
 
     // ============================================================================
@@ -205,18 +401,16 @@ impl PostState {
             .set_loading();
         drop(revisions_map);
 
-        let result = http_client::post(
-            &format!("/post/v1/revisions/{}/list", post_id),
-            &serde_json::json!({}),
-        )
-        .send()
-        .await;
+        let result = PostRevisionList { post_id }
+            .request(&EmptyObject {})
+            .send()
+            .await;
 
         let mut revisions_map = self.revisions_list.write();
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
-                    match response.json::<Vec<PostRevision>>().await {
+                if response.is_success() {
+                    match response.json::<ResponseOf<PostRevisionList>>() {
                         Ok(revisions) => {
                             revisions_map
                                 .entry(post_id)
@@ -224,7 +418,7 @@ impl PostState {
                                 .set_success(Some(revisions));
                         }
                         Err(e) => {
-                            let raw = response.text().await.unwrap_or_default();
+                            let raw = response.text().to_string();
                             revisions_map
                                 .entry(post_id)
                                 .or_insert_with(StateFrame::new)
@@ -235,16 +429,14 @@ impl PostState {
                     revisions_map
                         .entry(post_id)
                         .or_insert_with(StateFrame::new)
-                        .set_api_error(&response)
-                        .await;
+                        .set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 revisions_map
                     .entry(post_id)
                     .or_insert_with(StateFrame::new)
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
             }
         }
     }
@@ -259,17 +451,18 @@ impl PostState {
             .set_loading();
         drop(restore_map);
 
-        let result = http_client::post(
-            &format!("/post/v1/revisions/{}/restore/{}", post_id, revision_id),
-            &serde_json::json!({}),
-        )
+        let result = PostRevisionRestore {
+            post_id,
+            revision_id,
+        }
+        .request(&EmptyObject {})
         .send()
         .await;
 
         let mut restore_map = self.revisions_restore.write();
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
+                if response.is_success() {
                     restore_map
                         .entry(key)
                         .or_insert_with(StateFrame::new)
@@ -277,22 +470,21 @@ impl PostState {
 
                     // Refresh the post view
                     drop(restore_map);
+                    invalidate_queries("post");
                     self.view_by_id(post_id).await;
                     self.list().await;
                 } else {
                     restore_map
                         .entry(key)
                         .or_insert_with(StateFrame::new)
-                        .set_api_error(&response)
-                        .await;
+                        .set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 restore_map
                     .entry(key)
                     .or_insert_with(StateFrame::new)
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
             }
         }
     }
@@ -310,14 +502,12 @@ impl PostState {
             .set_loading();
         drop(track_map);
 
-        let result = http_client::post(&format!("/post/v1/track_view/{}", post_id), &())
-            .send()
-            .await;
+        let result = PostTrackView { id: post_id }.request(&()).send().await;
 
         let mut track_map = self.track_view.write();
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
+                if response.is_success() {
                     track_map
                         .entry(post_id)
                         .or_insert_with(StateFrame::new)
@@ -326,16 +516,14 @@ impl PostState {
                     track_map
                         .entry(post_id)
                         .or_insert_with(StateFrame::new)
-                        .set_api_error(&response)
-                        .await;
+                        .set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 track_map
                     .entry(post_id)
                     .or_insert_with(StateFrame::new)
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
             }
         }
     }
@@ -347,7 +535,7 @@ impl PostState {
     /// Create a new series
     pub async fn series_create(&self, payload: SeriesCreatePayload) {
         let meta_payload = payload.clone();
-        let request = http_client::post("/post/v1/series/create", &payload);
+        let request = SeriesCreate {}.request(&payload);
         let created = state_request_abstraction(
             &self.series_add,
             Some(meta_payload),
@@ -358,6 +546,7 @@ impl PostState {
         .await;
 
         if created.is_some() {
+            invalidate_queries("post");
             self.series_list().await;
         }
     }
@@ -368,12 +557,13 @@ impl PostState {
             &self.series_edit,
             series_id,
             payload.clone(),
-            http_client::post(&format!("/post/v1/series/update/{}", series_id), &payload).send(),
+            SeriesUpdate { id: series_id }.request(&payload).send(),
             "series",
             Some(&self.series_list),
             Some(&self.series_view),
             |series: &Series| series.id,
             None::<fn(&Series)>,
+            None::<Optimistic<fn(&mut Series, &SeriesEditPayload)>>,
         )
         .await;
     }
@@ -383,21 +573,22 @@ impl PostState {
         let _ = remove_state_abstraction(
             &self.series_remove,
             series_id,
-            http_client::post(&format!("/post/v1/series/delete/{}", series_id), &()).send(),
+            SeriesDelete { id: series_id }.request(&()).send(),
             "series",
             Some(&self.series_list),
             Some(&self.series_view),
             |series: &Series| series.id,
             None::<fn()>,
+            None,
         )
         .await;
     }
 
     /// List all series
     pub async fn series_list(&self) {
-        let _ = list_state_abstraction::<PaginatedList<Series>>(
+        let _ = list_state_abstraction::<ResponseOf<SeriesList>>(
             &self.series_list,
-            http_client::post("/post/v1/series/list", &serde_json::json!({})),
+            SeriesList {}.request(&SeriesListQuery::new()),
             "series",
         )
         .await;
@@ -405,16 +596,16 @@ impl PostState {
 
     /// List series with query parameters
     pub async fn series_list_with_query(&self, query: SeriesListQuery) {
-        let _ = list_state_abstraction::<PaginatedList<Series>>(
+        let _ = list_state_abstraction::<ResponseOf<SeriesList>>(
             &self.series_list,
-            http_client::post("/post/v1/series/list", &query),
+            SeriesList {}.request(&query),
             "series",
         )
         .await;
     }
 
-    /// Add a post to a series
-    pub async fn series_add_post(&self, post_id: i32, series_id: i32) {
+    /// Add a post to a series; `position` is its 1-based part number (appended when `None`).
+    pub async fn series_add_post(&self, post_id: i32, series_id: i32, position: Option<i32>) {
         let key = (post_id, series_id);
         let mut add_map = self.series_add_post.write();
         add_map
@@ -423,17 +614,15 @@ impl PostState {
             .set_loading();
         drop(add_map);
 
-        let result = http_client::post(
-            &format!("/post/v1/series/add/{}/{}", post_id, series_id),
-            &serde_json::json!({}),
-        )
-        .send()
-        .await;
+        let result = SeriesAddPost { post_id, series_id }
+            .request(&SeriesAddPostPayload { position })
+            .send()
+            .await;
 
         let mut add_map = self.series_add_post.write();
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
+                if response.is_success() {
                     add_map
                         .entry(key)
                         .or_insert_with(StateFrame::new)
@@ -446,16 +635,14 @@ impl PostState {
                     add_map
                         .entry(key)
                         .or_insert_with(StateFrame::new)
-                        .set_api_error(&response)
-                        .await;
+                        .set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 add_map
                     .entry(key)
                     .or_insert_with(StateFrame::new)
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
             }
         }
     }
@@ -470,17 +657,15 @@ impl PostState {
             .set_loading();
         drop(remove_map);
 
-        let result = http_client::post(
-            &format!("/post/v1/series/remove/{}/{}", post_id, series_id),
-            &serde_json::json!({}),
-        )
-        .send()
-        .await;
+        let result = SeriesRemovePost { post_id, series_id }
+            .request(&EmptyObject {})
+            .send()
+            .await;
 
         let mut remove_map = self.series_remove_post.write();
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
+                if response.is_success() {
                     remove_map
                         .entry(key)
                         .or_insert_with(StateFrame::new)
@@ -493,18 +678,54 @@ impl PostState {
                     remove_map
                         .entry(key)
                         .or_insert_with(StateFrame::new)
-                        .set_api_error(&response)
-                        .await;
+                        .set_api_error(&response);
                 }
             }
             Err(e) => {
-                let (kind, msg) = crate::store::classify_transport_error(&e);
                 remove_map
                     .entry(key)
                     .or_insert_with(StateFrame::new)
-                    .set_transport_error(kind, Some(msg));
+                    .set_transport_error(e.kind, e.message);
+            }
+        }
+    }
+
+    /// Move a post from its `previous` series placement to `next`, each a
+    /// `(series_id, position)` pair. Re-adding to the same series updates the position.
+    ///
+    /// Returns whether the post now sits at `next`. A failed removal skips the add, so the post
+    /// never ends up in two series.
+    pub async fn series_assign(
+        &self,
+        post_id: i32,
+        previous: Option<(i32, Option<i32>)>,
+        next: Option<(i32, Option<i32>)>,
+    ) -> bool {
+        if previous == next {
+            return true;
+        }
+        if let Some((old_series, _)) = previous {
+            if next.map(|(series_id, _)| series_id) != Some(old_series) {
+                self.series_remove_post(post_id, old_series).await;
+                let removed = self
+                    .series_remove_post
+                    .peek()
+                    .get(&(post_id, old_series))
+                    .is_some_and(|frame| frame.is_success());
+                if !removed {
+                    return false;
+                }
             }
         }
+        if let Some((series_id, position)) = next {
+            self.series_add_post(post_id, series_id, position).await;
+            return self
+                .series_add_post
+                .peek()
+                .get(&(post_id, series_id))
+                .is_some_and(|frame| frame.is_success());
+        }
+        true
     }
 
     // ============================================================================
@@ -513,14 +734,12 @@ impl PostState {
 
     /// Get sitemap data for published posts
     pub async fn sitemap(&self) -> Option<Vec<Post>> {
-        let result = http_client::post("/post/v1/sitemap", &serde_json::json!({}))
-            .send()
-            .await;
+        let result = PostSitemap {}.request(&EmptyObject {}).send().await;
 
         match result {
             Ok(response) => {
-                if (200..300).contains(&response.status()) {
-                    response.json::<Vec<Post>>().await.ok()
+                if response.is_success() {
+                    response.json::<ResponseOf<PostSitemap>>().ok()
                 } else {
                     None
                 }
@@ -540,7 +759,9 @@ impl PostState {
         *self.add.write() = StateFrame::new();
         *self.edit.write() = HashMap::new();
         *self.remove.write() = HashMap::new();
+        *self.autosave.write() = HashMap::new();
         *self.schedule.write() = HashMap::new();
+        *self.calendar.write() = StateFrame::new();
         *self.revisions_list.write() = HashMap::new();
         *self.revisions_restore.write() = HashMap::new();
         *self.track_view.write() = HashMap::new();
@@ -574,3 +795,66 @@ impl ListStore<Post, PostListQuery> for PostState {
         self.list_with_query(query).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::{fake::with_fake_client, ApiBody, HttpMethod};
+    use futures_util::FutureExt;
+
+    #[test]
+    fn series_assign_moves_post_between_series() {
+        with_fake_client(|fake| {
+            let ok = serde_json::json!({});
+            fake.respond_json(HttpMethod::Post, "/post/v1/series/remove/5/1", 200, &ok);
+            fake.respond_json(HttpMethod::Post, "/post/v1/series/add/5/2", 200, &ok);
+            let posts = use_post();
+
+            assert!(posts
+                .series_assign(5, Some((2, Some(3))), Some((2, Some(3))))
+                .now_or_never()
+                .unwrap());
+            assert!(fake.requests.borrow().is_empty());
+
+            assert!(posts
+                .series_assign(5, Some((1, Some(2))), Some((2, Some(4))))
+                .now_or_never()
+                .unwrap());
+            let sent: Vec<(String, ApiBody)> = fake
+                .requests
+                .borrow()
+                .iter()
+                .filter(|r| r.path.contains("/series/"))
+                .map(|r| (r.path.clone(), r.body.clone()))
+                .collect();
+            assert_eq!(
+                sent,
+                vec![
+                    (
+                        "/post/v1/series/remove/5/1".to_string(),
+                        ApiBody::Json(serde_json::json!({}))
+                    ),
+                    (
+                        "/post/v1/series/add/5/2".to_string(),
+                        ApiBody::Json(serde_json::json!({ "position": 4 }))
+                    ),
+                ]
+            );
+            assert!(posts.series_add_post.read()[&(5, 2)].is_success());
+
+            // A failed removal reports failure and leaves the post where it was
+            fake.requests.borrow_mut().clear();
+            assert!(!posts
+                .series_assign(5, Some((3, None)), Some((2, None)))
+                .now_or_never()
+                .unwrap());
+            let paths: Vec<String> = fake
+                .requests
+                .borrow()
+                .iter()
+                .map(|r| r.path.clone())
+                .collect();
+            assert_eq!(paths, vec!["/post/v1/series/remove/5/3".to_string()]);
+        });
+    }
+}
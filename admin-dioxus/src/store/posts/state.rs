@@ -1,4 +1,4 @@
-use crate::store::{ListQuery, Media, PaginatedList, StateFrame};
+use crate::store::{ListQuery, Media, MediaVariant, PaginatedList, StateFrame};
 use crate::types::SortParam;
 use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
@@ -38,6 +38,16 @@ pub struct PostTag {
     pub color: String,
 }
 
+/// Series a post belongs to, with its part number inside the series.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PostSeries {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub position: Option<i32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "PascalCase")]
 pub enum PostStatus {
@@ -240,6 +250,9 @@ pub struct ImageFile {
     pub title: Option<String>,
     #[serde(default)]
     pub media_id: Option<i32>,
+    /// Renditions of the uploaded media, for `srcset`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<MediaVariant>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -303,6 +316,40 @@ pub struct ButtonBlock {
     pub style: Option<String>,
 }
 
+/// Search and social metadata. Empty fields fall back to the post's title, excerpt and
+/// featured image wherever the post is rendered.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct PostSeo {
+    #[serde(default)]
+    pub meta_title: Option<String>,
+    #[serde(default)]
+    pub meta_description: Option<String>,
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    #[serde(default)]
+    pub noindex: bool,
+    #[serde(default)]
+    pub nofollow: bool,
+    #[serde(default)]
+    pub og_title: Option<String>,
+    #[serde(default)]
+    pub og_description: Option<String>,
+    /// Phrase the post should rank for; only used for the editor's checks.
+    #[serde(default)]
+    pub focus_keyword: Option<String>,
+}
+
+impl PostSeo {
+    /// Value of the robots meta tag, e.g. `"noindex, follow"`.
+    pub fn robots(&self) -> String {
+        format!(
+            "{}, {}",
+            if self.noindex { "noindex" } else { "index" },
+            if self.nofollow { "nofollow" } else { "follow" }
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Post {
     pub id: i32,
@@ -318,10 +365,14 @@ pub struct Post {
     pub category: PostCategory,
     #[serde(default)]
     pub tags: Vec<PostTag>,
+    #[serde(default)]
+    pub series: Option<PostSeries>,
     pub likes_count: i32,
     pub view_count: i32,
     pub comment_count: i64,
     pub status: PostStatus,
+    #[serde(default)]
+    pub seo: PostSeo,
 }
 
 impl Post {
@@ -336,6 +387,11 @@ impl Post {
     pub fn is_archived(&self) -> bool {
         self.status == PostStatus::Archived
     }
+
+    /// A draft with a publish time in the future.
+    pub fn is_scheduled(&self) -> bool {
+        self.is_draft() && self.published_at.is_some_and(|at| at > Utc::now())
+    }
 }
 
 // ============================================================================
@@ -355,19 +411,53 @@ pub struct PostCreatePayload {
     pub category_id: i32,
     #[serde(default)]
     pub tag_ids: Vec<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seo: Option<PostSeo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct PostEditPayload {
     pub title: Option<String>,
     pub content: Option<PostContent>,
-    pub published_at: Option<DateTime<Utc>>,
+    /// `Some(None)` clears the publish time, e.g. to unschedule a post; `None` leaves it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<Option<DateTime<Utc>>>,
     pub status: Option<PostStatus>,
     pub slug: Option<String>,
     pub excerpt: Option<String>,
     pub featured_image_id: Option<i32>,
     pub category_id: Option<i32>,
     pub tag_ids: Option<Vec<i32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seo: Option<PostSeo>,
+}
+
+impl PostEditPayload {
+    /// Apply the changed scalar fields to a cached post (used for optimistic updates).
+    /// Relations (category, tags, featured image) are left alone until the server returns them.
+    pub fn apply_to(&self, post: &mut Post) {
+        if let Some(title) = &self.title {
+            post.title = title.clone();
+        }
+        if let Some(content) = &self.content {
+            post.content = content.clone();
+        }
+        if let Some(published_at) = self.published_at {
+            post.published_at = published_at;
+        }
+        if let Some(status) = &self.status {
+            post.status = status.clone();
+        }
+        if let Some(slug) = &self.slug {
+            post.slug = slug.clone();
+        }
+        if let Some(excerpt) = &self.excerpt {
+            post.excerpt = Some(excerpt.clone());
+        }
+        if let Some(seo) = &self.seo {
+            post.seo = seo.clone();
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -433,6 +523,13 @@ pub struct PostSchedulePayload {
     pub publish_at: DateTime<Utc>,
 }
 
+/// Half-open `[from, to)` window loaded into the editorial calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
 // ============================================================================
 // Post Revisions
 // ============================================================================
@@ -476,6 +573,13 @@ pub struct SeriesEditPayload {
     pub description: Option<String>,
 }
 
+/// Body of the add-to-series call; `position` is the 1-based part number, appended when `None`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct SeriesAddPostPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<i32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct SeriesListQuery {
     pub page: Option<u64>,
@@ -532,9 +636,13 @@ pub struct PostState {
     pub add: GlobalSignal<StateFrame<Post, PostCreatePayload>>,
     pub edit: GlobalSignal<HashMap<i32, StateFrame<(), PostEditPayload>>>,
     pub remove: GlobalSignal<HashMap<i32, StateFrame>>,
+    // Background draft saves from the editor; data is the time of the last successful save
+    pub autosave: GlobalSignal<HashMap<i32, StateFrame<DateTime<Utc>>>>,
 
     // Scheduling
     pub schedule: GlobalSignal<HashMap<i32, StateFrame>>,
+    // Posts shown in the editorial calendar, for the range in `meta`
+    pub calendar: GlobalSignal<StateFrame<Vec<Post>, CalendarRange>>,
 
     // Revisions
     pub revisions_list: GlobalSignal<HashMap<i32, StateFrame<Vec<PostRevision>>>>,
@@ -561,7 +669,9 @@ impl PostState {
             add: GlobalSignal::new(|| StateFrame::new()),
             edit: GlobalSignal::new(|| HashMap::new()),
             remove: GlobalSignal::new(|| HashMap::new()),
+            autosave: GlobalSignal::new(|| HashMap::new()),
             schedule: GlobalSignal::new(|| HashMap::new()),
+            calendar: GlobalSignal::new(|| StateFrame::new()),
             revisions_list: GlobalSignal::new(|| HashMap::new()),
             revisions_restore: GlobalSignal::new(|| HashMap::new()),
             track_view: GlobalSignal::new(|| HashMap::new()),
@@ -0,0 +1,282 @@
+//! Generic CRUD store shared by the standard entities (tags, categories, users, media).
+//!
+//! A [`ResourceStore`] owns the usual `add/edit/remove/list/view` frames and implements the
+//! matching actions on top of the state abstractions in [`super::lib`]. Everything that
+//! differs per entity lives in a [`ResourceConfig`]: labels, routes, how to read an id and
+//! whether edits and removals are optimistic. Adding an entity means declaring its types and
+//! one config.
+
+use crate::services::api_client::ApiRequest;
+use crate::store::{
+    edit_state_abstraction, invalidate_queries, list_state_abstraction, remove_state_abstraction,
+    state_request_abstraction, use_outbox, view_state_abstraction, ListQuery, ListStore,
+    Optimistic, OutboxMutation, PaginatedList, StateFrame, TransportErrorKind,
+    OUTBOX_QUEUED_MESSAGE,
+};
+use dioxus::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+
+/// Per-entity wiring for a [`ResourceStore`].
+pub struct ResourceConfig<T, Q, Create, Update> {
+    /// Singular label for decode errors and rollback toasts, e.g. `"tag"`.
+    pub label: &'static str,
+    /// Plural label for list decode errors, e.g. `"tags"`.
+    pub list_label: &'static str,
+    /// Query-cache tag invalidated after a create (see [`invalidate_queries`]).
+    pub cache_tag: &'static str,
+    /// `None` when the entity is created elsewhere (e.g. media uploads).
+    pub create: Option<fn(&Create) -> ApiRequest>,
+    /// `None` for read-only entities.
+    pub update: Option<fn(i32, &Update) -> ApiRequest>,
+    pub delete: fn(i32) -> ApiRequest,
+    pub list: fn(&Q) -> ApiRequest,
+    pub view: fn(i32) -> ApiRequest,
+    pub id: fn(&T) -> i32,
+    /// Patches a cached item so edits show before the server answers.
+    pub apply_update: Option<fn(&mut T, &Update)>,
+    /// Drop removed items from the caches before the server answers.
+    pub optimistic_remove: bool,
+    /// Queue creates in the offline outbox instead of sending them while offline.
+    pub offline_create: Option<fn(&Create) -> OutboxMutation>,
+}
+
+pub struct ResourceStore<T: Clone + 'static, Q, Create: Clone + 'static, Update: Clone + 'static> {
+    pub add: GlobalSignal<StateFrame<(), Create>>,
+    pub edit: GlobalSignal<HashMap<i32, StateFrame<(), Update>>>,
+    pub remove: GlobalSignal<HashMap<i32, StateFrame>>,
+    pub list: GlobalSignal<StateFrame<PaginatedList<T>>>,
+    pub view: GlobalSignal<HashMap<i32, StateFrame<T>>>,
+    pub config: ResourceConfig<T, Q, Create, Update>,
+}
+
+impl<T, Q, Create, Update> ResourceStore<T, Q, Create, Update>
+where
+    T: DeserializeOwned + Clone + PartialEq + 'static,
+    Q: ListQuery,
+    Create: Serialize + Clone + 'static,
+    Update: Serialize + Clone + 'static,
+{
+    /// Global signals are keyed by source location, so each store's signals are keyed by
+    /// where `new` is called from; create every store from its own call site.
+    #[track_caller]
+    pub fn new(config: ResourceConfig<T, Q, Create, Update>) -> Self {
+        let caller = std::panic::Location::caller();
+        let (file, line, column) = (caller.file(), caller.line(), caller.column());
+        Self {
+            add: GlobalSignal::with_location(StateFrame::new, file, line, column, 0),
+            edit: GlobalSignal::with_location(HashMap::new, file, line, column, 1),
+            remove: GlobalSignal::with_location(HashMap::new, file, line, column, 2),
+            list: GlobalSignal::with_location(StateFrame::new, file, line, column, 3),
+            view: GlobalSignal::with_location(HashMap::new, file, line, column, 4),
+            config,
+        }
+    }
+
+    pub async fn add(&self, payload: Create) {
+        let Some(create) = self.config.create else {
+            self.add
+                .write()
+                .set_failed(format!("Creating a {} is not supported", self.config.label));
+            return;
+        };
+        if let Some(to_mutation) = self.config.offline_create {
+            if use_outbox().defer_if_offline(|| to_mutation(&payload)) {
+                self.add.write().set_transport_error(
+                    TransportErrorKind::Offline,
+                    Some(OUTBOX_QUEUED_MESSAGE.to_string()),
+                );
+                return;
+            }
+        }
+
+        let created = state_request_abstraction(
+            &self.add,
+            Some(payload.clone()),
+            create(&payload).send(),
+            self.config.label,
+            |_item: &T| (None, None),
+        )
+        .await;
+
+        if created.is_some() {
+            invalidate_queries(self.config.cache_tag);
+            self.list().await;
+        }
+    }
+
+    /// Takes `&'static self` so the rollback toast's "Retry" can call back into the store.
+    pub async fn edit(&'static self, id: i32, payload: Update) {
+        let Some(update) = self.config.update else {
+            self.edit
+                .write()
+                .entry(id)
+                .or_default()
+                .set_failed(format!("Editing a {} is not supported", self.config.label));
+            return;
+        };
+        let optimistic = self.config.apply_update.map(|apply| {
+            let payload = payload.clone();
+            Optimistic::edit(apply).with_retry(move || {
+                let payload = payload.clone();
+                spawn(async move { self.edit(id, payload).await });
+            })
+        });
+
+        let _ = edit_state_abstraction(
+            &self.edit,
+            id,
+            payload.clone(),
+            update(id, &payload).send(),
+            self.config.label,
+            Some(&self.list),
+            Some(&self.view),
+            self.config.id,
+            None::<fn(&T)>,
+            optimistic,
+        )
+        .await;
+    }
+
+    pub async fn remove(&'static self, id: i32) {
+        let optimistic = self.config.optimistic_remove.then(|| {
+            Optimistic::remove().with_retry(move || {
+                spawn(async move { self.remove(id).await });
+            })
+        });
+
+        let _ = remove_state_abstraction(
+            &self.remove,
+            id,
+            (self.config.delete)(id).send(),
+            self.config.label,
+            Some(&self.list),
+            Some(&self.view),
+            self.config.id,
+            None::<fn()>,
+            optimistic,
+        )
+        .await;
+    }
+
+    pub async fn list(&self) {
+        self.list_with_query(Q::new()).await;
+    }
+
+    pub async fn list_with_query(&self, query: Q) {
+        let _ = list_state_abstraction::<PaginatedList<T>>(
+            &self.list,
+            (self.config.list)(&query),
+            self.config.list_label,
+        )
+        .await;
+    }
+
+    pub async fn view(&self, id: i32) {
+        let _ = view_state_abstraction(
+            &self.view,
+            id,
+            (self.config.view)(id),
+            self.config.label,
+            |item: &T| item.clone(),
+        )
+        .await;
+    }
+
+    pub fn reset(&self) {
+        *self.add.write() = StateFrame::new();
+        *self.edit.write() = HashMap::new();
+        *self.remove.write() = HashMap::new();
+        *self.list.write() = StateFrame::new();
+        *self.view.write() = HashMap::new();
+    }
+}
+
+impl<T, Q, Create, Update> ListStore<T, Q> for ResourceStore<T, Q, Create, Update>
+where
+    T: DeserializeOwned + Clone + PartialEq + 'static,
+    Q: ListQuery,
+    Create: Serialize + Clone + 'static,
+    Update: Serialize + Clone + 'static,
+{
+    fn list_frame(&self) -> &GlobalSignal<StateFrame<PaginatedList<T>>> {
+        &self.list
+    }
+
+    async fn fetch_list(&self) {
+        self.list().await;
+    }
+
+    async fn fetch_list_with_query(&self, query: Q) {
+        self.list_with_query(query).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_client::{fake::with_fake_client, HttpMethod};
+    use crate::store::{use_categories, use_tag, Category, Tag};
+    use futures_util::FutureExt;
+
+    fn page(items: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({ "data": items, "total": 1, "page": 1, "per_page": 10 })
+    }
+
+    #[test]
+    fn stores_keep_separate_frames_and_sync_removals() {
+        with_fake_client(|fake| {
+            let tag = Tag {
+                id: 7,
+                name: "rust".into(),
+                ..Tag::default()
+            };
+            let category = Category {
+                id: 7,
+                name: "News".into(),
+                ..Category::default()
+            };
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/list/query",
+                200,
+                &page(serde_json::json!([tag])),
+            );
+            fake.respond_json(
+                HttpMethod::Post,
+                "/category/v1/list/query",
+                200,
+                &page(serde_json::json!([category])),
+            );
+            fake.respond_json(
+                HttpMethod::Post,
+                "/tag/v1/delete/7",
+                200,
+                &serde_json::json!({}),
+            );
+
+            use_tag().list().now_or_never().unwrap();
+            use_categories().list().now_or_never().unwrap();
+            assert_eq!(use_tag().list.read().data.as_ref().unwrap().data, vec![tag]);
+            assert_eq!(
+                use_categories().list.read().data.as_ref().unwrap().data,
+                vec![category]
+            );
+
+            use_tag().remove(7).now_or_never().unwrap();
+            assert!(use_tag().remove.read()[&7].is_success());
+            assert!(use_tag().list.read().data.as_ref().unwrap().data.is_empty());
+            assert_eq!(
+                use_categories()
+                    .list
+                    .read()
+                    .data
+                    .as_ref()
+                    .unwrap()
+                    .data
+                    .len(),
+                1
+            );
+        });
+    }
+}
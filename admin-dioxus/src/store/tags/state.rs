@@ -1,9 +1,7 @@
-use crate::store::{ListQuery, ListStore, PaginatedList, StateFrame};
+use crate::store::{ListQuery, ResourceStore};
 use crate::types::SortParam;
 use chrono::{DateTime, Utc};
-use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Tag {
@@ -105,42 +103,34 @@ pub struct TagsEditPayload {
     pub is_active: Option<bool>,
 }
 
-pub struct TagsState {
-    pub add: GlobalSignal<StateFrame<(), TagsAddPayload>>,
-    pub edit: GlobalSignal<HashMap<i32, StateFrame<(), TagsEditPayload>>>,
-    pub remove: GlobalSignal<HashMap<i32, StateFrame>>,
-    pub list: GlobalSignal<StateFrame<PaginatedList<Tag>>>,
-    pub view: GlobalSignal<HashMap<i32, StateFrame<Tag>>>,
-}
-
-impl TagsState {
-    pub fn new() -> Self {
-        Self {
-            add: GlobalSignal::new(|| StateFrame::new()),
-            edit: GlobalSignal::new(|| HashMap::new()),
-            remove: GlobalSignal::new(|| HashMap::new()),
-            list: GlobalSignal::new(|| StateFrame::new()),
-            view: GlobalSignal::new(|| HashMap::new()),
+impl TagsEditPayload {
+    /// Apply the changed fields to a cached tag (used for optimistic updates).
+    pub fn apply_to(&self, tag: &mut Tag) {
+        if let Some(name) = &self.name {
+            tag.name = name.clone();
+        }
+        if let Some(slug) = &self.slug {
+            tag.slug = slug.clone();
+        }
+        if let Some(description) = &self.description {
+            tag.description = Some(description.clone());
+        }
+        if let Some(color) = &self.color {
+            tag.color = color.clone();
+        }
+        if let Some(text_color) = &self.text_color {
+            tag.text_color = text_color.clone();
+        }
+        if let Some(is_active) = self.is_active {
+            tag.is_active = is_active;
         }
     }
 }
 
-static TAG_STATE: std::sync::OnceLock<TagsState> = std::sync::OnceLock::new();
-
-impl ListStore<Tag, TagsListQuery> for TagsState {
-    fn list_frame(&self) -> &GlobalSignal<StateFrame<PaginatedList<Tag>>> {
-        &self.list
-    }
-
-    async fn fetch_list(&self) {
-        self.list().await;
-    }
+pub type TagsState = ResourceStore<Tag, TagsListQuery, TagsAddPayload, TagsEditPayload>;
 
-    async fn fetch_list_with_query(&self, query: TagsListQuery) {
-        self.list_with_query(query).await;
-    }
-}
+static TAG_STATE: std::sync::OnceLock<TagsState> = std::sync::OnceLock::new();
 
 pub fn use_tag() -> &'static TagsState {
-    TAG_STATE.get_or_init(|| TagsState::new())
+    TAG_STATE.get_or_init(|| TagsState::new(super::actions::config()))
 }
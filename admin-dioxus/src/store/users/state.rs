@@ -1,12 +1,10 @@
 use crate::store::auth::UserRole;
 use crate::store::media::Media;
-use crate::store::traits::{ListQuery, ListStore};
-use crate::store::{PaginatedList, StateFrame};
+use crate::store::traits::ListQuery;
+use crate::store::ResourceStore;
 use crate::types::SortParam;
 use chrono::{DateTime, Utc};
-use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct User {
@@ -57,6 +55,21 @@ pub struct UsersEditPayload {
     pub role: Option<String>,
 }
 
+impl UsersEditPayload {
+    /// Apply the changed fields to a cached user (used for optimistic updates).
+    pub fn apply_to(&self, user: &mut User) {
+        if let Some(name) = &self.name {
+            user.name = name.clone();
+        }
+        if let Some(email) = &self.email {
+            user.email = email.clone();
+        }
+        if let Some(is_verified) = self.is_verified {
+            user.is_verified = is_verified;
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct UsersListQuery {
     pub page: Option<u64>,
@@ -135,42 +148,10 @@ impl ListQuery for UsersListQuery {
     }
 }
 
-pub struct UsersState {
-    pub add: GlobalSignal<StateFrame<(), UsersAddPayload>>,
-    pub edit: GlobalSignal<HashMap<i32, StateFrame<(), UsersEditPayload>>>,
-    pub remove: GlobalSignal<HashMap<i32, StateFrame>>,
-    pub list: GlobalSignal<StateFrame<PaginatedList<User>>>,
-    pub view: GlobalSignal<HashMap<i32, StateFrame<User>>>,
-}
-
-impl UsersState {
-    pub fn new() -> Self {
-        UsersState {
-            add: GlobalSignal::new(|| StateFrame::new()),
-            edit: GlobalSignal::new(|| HashMap::new()),
-            remove: GlobalSignal::new(|| HashMap::new()),
-            list: GlobalSignal::new(|| StateFrame::new()),
-            view: GlobalSignal::new(|| HashMap::new()),
-        }
-    }
-}
+pub type UsersState = ResourceStore<User, UsersListQuery, UsersAddPayload, UsersEditPayload>;
 
 static USER_STATE: std::sync::OnceLock<UsersState> = std::sync::OnceLock::new();
 
 pub fn use_user() -> &'static UsersState {
-    USER_STATE.get_or_init(|| UsersState::new())
-}
-
-impl ListStore<User, UsersListQuery> for UsersState {
-    fn list_frame(&self) -> &GlobalSignal<StateFrame<PaginatedList<User>>> {
-        &self.list
-    }
-
-    async fn fetch_list(&self) {
-        self.list().await;
-    }
-
-    async fn fetch_list_with_query(&self, query: UsersListQuery) {
-        self.list_with_query(query).await;
-    }
+    USER_STATE.get_or_init(|| UsersState::new(super::actions::config()))
 }
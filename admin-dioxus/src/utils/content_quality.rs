@@ -0,0 +1,577 @@
+//! Pre-publish checks on a post body: length, readability, structure, images and links.
+//! Each rule can be switched off, reported as a warning, or made to block publishing.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::store::{EditorJsBlock, PostContent};
+use crate::utils::persist;
+use crate::utils::post_render::{inline_links, inline_text};
+use crate::utils::urls::sanitize_url;
+
+const WORDS_PER_MINUTE: usize = 200;
+/// Readability scores on less text than this are too noisy to act on.
+const MIN_WORDS_FOR_READABILITY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleAction {
+    Off,
+    Warn,
+    Block,
+}
+
+impl RuleAction {
+    pub const ALL: [RuleAction; 3] = [RuleAction::Off, RuleAction::Warn, RuleAction::Block];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RuleAction::Off => "Off",
+            RuleAction::Warn => "Warn",
+            RuleAction::Block => "Block",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.label() == label)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QualityRule {
+    ShortContent,
+    Readability,
+    HeadingGap,
+    DuplicateHeading,
+    MissingAltText,
+    EmptyBlock,
+    BadLink,
+}
+
+impl QualityRule {
+    pub const ALL: [QualityRule; 7] = [
+        QualityRule::ShortContent,
+        QualityRule::Readability,
+        QualityRule::HeadingGap,
+        QualityRule::DuplicateHeading,
+        QualityRule::MissingAltText,
+        QualityRule::EmptyBlock,
+        QualityRule::BadLink,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            QualityRule::ShortContent => "Minimum length",
+            QualityRule::Readability => "Readability",
+            QualityRule::HeadingGap => "Heading levels",
+            QualityRule::DuplicateHeading => "Duplicate headings",
+            QualityRule::MissingAltText => "Image captions",
+            QualityRule::EmptyBlock => "Empty blocks",
+            QualityRule::BadLink => "Links",
+        }
+    }
+}
+
+/// How each rule is enforced, plus the thresholds the length and readability rules use.
+/// Saved per browser.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityRules {
+    pub min_words: usize,
+    /// Flesch reading ease below which the readability rule fires; 0–100, higher is easier.
+    pub min_readability: f64,
+    pub short_content: RuleAction,
+    pub readability: RuleAction,
+    pub heading_gap: RuleAction,
+    pub duplicate_heading: RuleAction,
+    pub missing_alt_text: RuleAction,
+    pub empty_block: RuleAction,
+    pub bad_link: RuleAction,
+}
+
+impl Default for QualityRules {
+    fn default() -> Self {
+        Self {
+            min_words: 300,
+            min_readability: 30.0,
+            short_content: RuleAction::Warn,
+            readability: RuleAction::Warn,
+            heading_gap: RuleAction::Warn,
+            duplicate_heading: RuleAction::Warn,
+            missing_alt_text: RuleAction::Warn,
+            empty_block: RuleAction::Warn,
+            bad_link: RuleAction::Block,
+        }
+    }
+}
+
+impl QualityRules {
+    pub fn load() -> Self {
+        persist::get_quality_rules()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            persist::set_quality_rules(&json);
+        }
+    }
+
+    pub fn action(&self, rule: QualityRule) -> RuleAction {
+        match rule {
+            QualityRule::ShortContent => self.short_content,
+            QualityRule::Readability => self.readability,
+            QualityRule::HeadingGap => self.heading_gap,
+            QualityRule::DuplicateHeading => self.duplicate_heading,
+            QualityRule::MissingAltText => self.missing_alt_text,
+            QualityRule::EmptyBlock => self.empty_block,
+            QualityRule::BadLink => self.bad_link,
+        }
+    }
+
+    pub fn set_action(&mut self, rule: QualityRule, action: RuleAction) {
+        let slot = match rule {
+            QualityRule::ShortContent => &mut self.short_content,
+            QualityRule::Readability => &mut self.readability,
+            QualityRule::HeadingGap => &mut self.heading_gap,
+            QualityRule::DuplicateHeading => &mut self.duplicate_heading,
+            QualityRule::MissingAltText => &mut self.missing_alt_text,
+            QualityRule::EmptyBlock => &mut self.empty_block,
+            QualityRule::BadLink => &mut self.bad_link,
+        };
+        *slot = action;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContentStats {
+    pub words: usize,
+    pub reading_minutes: usize,
+    /// Flesch reading ease of the prose, `None` when there is none.
+    pub readability: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityIssue {
+    pub rule: QualityRule,
+    pub action: RuleAction,
+    /// 1-based block number, when the issue belongs to one block.
+    pub block: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QualityReport {
+    pub stats: ContentStats,
+    /// Issues of rules that are not switched off, blocking ones first.
+    pub issues: Vec<QualityIssue>,
+}
+
+impl QualityReport {
+    pub fn blocking(&self) -> impl Iterator<Item = &QualityIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.action == RuleAction::Block)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &QualityIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.action == RuleAction::Warn)
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        self.blocking().next().is_some()
+    }
+}
+
+/// Label used when reading-ease scores are shown, e.g. "fairly difficult".
+pub fn readability_label(score: f64) -> &'static str {
+    match score {
+        s if s >= 80.0 => "easy",
+        s if s >= 60.0 => "plain English",
+        s if s >= 50.0 => "fairly difficult",
+        s if s >= 30.0 => "difficult",
+        _ => "very difficult",
+    }
+}
+
+/// Prose of a block, for word counts and readability. Code, embeds and raw HTML are not
+/// prose.
+fn block_prose(block: &EditorJsBlock) -> Vec<String> {
+    match block {
+        EditorJsBlock::Header { data, .. } => vec![inline_text(&data.text)],
+        EditorJsBlock::Paragraph { data, .. } => vec![inline_text(&data.text)],
+        EditorJsBlock::List { data, .. } => data.items.iter().map(|i| inline_text(i)).collect(),
+        EditorJsBlock::Quote { data, .. } => vec![inline_text(&data.text)],
+        EditorJsBlock::Checklist { data, .. } => data
+            .items
+            .iter()
+            .map(|item| inline_text(&item.text))
+            .collect(),
+        EditorJsBlock::Warning { data, .. } => {
+            vec![inline_text(&data.title), inline_text(&data.message)]
+        }
+        EditorJsBlock::Alert { data, .. } => vec![inline_text(&data.message)],
+        EditorJsBlock::Table { data, .. } => data
+            .content
+            .iter()
+            .flatten()
+            .map(|cell| inline_text(cell))
+            .collect(),
+        EditorJsBlock::Image { data, .. } => data.caption.iter().map(|c| inline_text(c)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Inline HTML of a block that may hold links.
+fn block_html(block: &EditorJsBlock) -> Vec<&str> {
+    match block {
+        EditorJsBlock::Header { data, .. } => vec![data.text.as_str()],
+        EditorJsBlock::Paragraph { data, .. } => vec![data.text.as_str()],
+        EditorJsBlock::List { data, .. } => data.items.iter().map(String::as_str).collect(),
+        EditorJsBlock::Quote { data, .. } => vec![data.text.as_str()],
+        EditorJsBlock::Checklist { data, .. } => {
+            data.items.iter().map(|item| item.text.as_str()).collect()
+        }
+        EditorJsBlock::Warning { data, .. } => vec![data.message.as_str()],
+        EditorJsBlock::Alert { data, .. } => vec![data.message.as_str()],
+        EditorJsBlock::Table { data, .. } => {
+            data.content.iter().flatten().map(String::as_str).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn is_empty_block(block: &EditorJsBlock) -> bool {
+    let blank = |html: &str| inline_text(html).trim().is_empty();
+    match block {
+        EditorJsBlock::Header { data, .. } => blank(&data.text),
+        EditorJsBlock::Paragraph { data, .. } => blank(&data.text),
+        EditorJsBlock::List { data, .. } => data.items.iter().all(|item| blank(item)),
+        EditorJsBlock::Quote { data, .. } => blank(&data.text),
+        EditorJsBlock::Checklist { data, .. } => data.items.iter().all(|item| blank(&item.text)),
+        EditorJsBlock::Code { data, .. } => data.code.trim().is_empty(),
+        EditorJsBlock::Raw { data, .. } => data.html.trim().is_empty(),
+        EditorJsBlock::Table { data, .. } => data.content.iter().flatten().all(|c| blank(c)),
+        EditorJsBlock::Image { data, .. } => data.file.url.trim().is_empty(),
+        EditorJsBlock::Embed { data, .. } => data.embed.trim().is_empty(),
+        EditorJsBlock::LinkTool { data, .. } => data.link.trim().is_empty(),
+        EditorJsBlock::Warning { data, .. } => blank(&data.title) && blank(&data.message),
+        EditorJsBlock::Alert { data, .. } => blank(&data.message),
+        EditorJsBlock::Button { data, .. } => data.text.trim().is_empty(),
+        EditorJsBlock::Attaches { .. } | EditorJsBlock::Delimiter { .. } => false,
+        EditorJsBlock::Unknown => false,
+    }
+}
+
+/// Why `url` is not a usable link, if it is not. Uses the same rules as rendering, so a
+/// link flagged here is one readers would not be able to follow.
+pub fn link_problem(url: &str) -> Option<String> {
+    let trimmed = url.trim();
+    if trimmed.is_empty() {
+        return Some("Link has no address".to_string());
+    }
+    let Some(sanitized) = sanitize_url(trimmed) else {
+        return Some(format!("Link “{}” uses a blocked scheme", trimmed));
+    };
+    if sanitized != trimmed {
+        return Some(format!("Link “{}” has no http(s):// prefix", trimmed));
+    }
+    let lower = sanitized.to_ascii_lowercase();
+    let host = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))
+        .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+    match host {
+        Some(host) if host.is_empty() || host.contains(' ') => {
+            Some(format!("Link “{}” has no valid host", trimmed))
+        }
+        Some(host) if !host.contains('.') && !host.starts_with("localhost") => {
+            Some(format!("Link “{}” has no valid host", trimmed))
+        }
+        _ => None,
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+}
+
+/// Vowel groups, less a silent trailing "e"; at least one per word.
+fn syllables(word: &str) -> usize {
+    let word: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let is_vowel = |c: char| "aeiouy".contains(c);
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for &c in &word {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    if word.len() > 2 && word.ends_with(&['e']) && !word.ends_with(&['l', 'e']) && count > 1 {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+/// Flesch reading ease of `text`, `None` without words.
+pub fn flesch_reading_ease(text: &str) -> Option<f64> {
+    let word_list: Vec<&str> = words(text).collect();
+    if word_list.is_empty() {
+        return None;
+    }
+    let sentences = text
+        .split(['.', '!', '?', '\n'])
+        .filter(|sentence| words(sentence).next().is_some())
+        .count()
+        .max(1);
+    let syllable_count: usize = word_list.iter().map(|word| syllables(word)).sum();
+    let word_count = word_list.len() as f64;
+    Some(
+        206.835
+            - 1.015 * (word_count / sentences as f64)
+            - 84.6 * (syllable_count as f64 / word_count),
+    )
+}
+
+pub fn check_content(content: &PostContent, rules: &QualityRules) -> QualityReport {
+    let mut issues = Vec::new();
+    let mut push = |rule: QualityRule, block: Option<usize>, message: String| {
+        let action = rules.action(rule);
+        if action != RuleAction::Off {
+            issues.push(QualityIssue {
+                rule,
+                action,
+                block,
+                message,
+            });
+        }
+    };
+
+    let mut prose = Vec::new();
+    let mut previous_level = 1;
+    let mut headings = HashSet::new();
+    for (index, block) in content.blocks.iter().enumerate() {
+        let number = Some(index + 1);
+        prose.extend(block_prose(block));
+
+        if is_empty_block(block) {
+            push(
+                QualityRule::EmptyBlock,
+                number,
+                "Block is empty".to_string(),
+            );
+            continue;
+        }
+
+        match block {
+            EditorJsBlock::Header { data, .. } => {
+                if data.level > previous_level + 1 {
+                    push(
+                        QualityRule::HeadingGap,
+                        number,
+                        format!(
+                            "Heading level {} follows level {}; levels should not be skipped",
+                            data.level, previous_level
+                        ),
+                    );
+                }
+                previous_level = data.level;
+                let text = inline_text(&data.text).trim().to_lowercase();
+                if !headings.insert(text) {
+                    push(
+                        QualityRule::DuplicateHeading,
+                        number,
+                        format!(
+                            "Heading “{}” is used more than once",
+                            inline_text(&data.text).trim()
+                        ),
+                    );
+                }
+            }
+            EditorJsBlock::Image { data, .. } => {
+                let described = [data.caption.as_deref(), data.file.title.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .any(|text| !inline_text(text).trim().is_empty());
+                if !described {
+                    push(
+                        QualityRule::MissingAltText,
+                        number,
+                        "Image has no caption or alt text".to_string(),
+                    );
+                }
+            }
+            EditorJsBlock::LinkTool { data, .. } => {
+                if let Some(problem) = link_problem(&data.link) {
+                    push(QualityRule::BadLink, number, problem);
+                }
+            }
+            EditorJsBlock::Button { data, .. } => {
+                if let Some(problem) = data.link.as_deref().and_then(link_problem) {
+                    push(QualityRule::BadLink, number, problem);
+                }
+            }
+            _ => {}
+        }
+
+        for html in block_html(block) {
+            for href in inline_links(html) {
+                if let Some(problem) = link_problem(&href) {
+                    push(QualityRule::BadLink, number, problem);
+                }
+            }
+        }
+    }
+
+    let text = prose.join("\n");
+    let word_count = words(&text).count();
+    let stats = ContentStats {
+        words: word_count,
+        reading_minutes: word_count.div_ceil(WORDS_PER_MINUTE),
+        readability: flesch_reading_ease(&text),
+    };
+
+    if word_count < rules.min_words {
+        push(
+            QualityRule::ShortContent,
+            None,
+            format!(
+                "{} words; at least {} are expected",
+                word_count, rules.min_words
+            ),
+        );
+    }
+    if let Some(score) = stats.readability {
+        if word_count >= MIN_WORDS_FOR_READABILITY && score < rules.min_readability {
+            push(
+                QualityRule::Readability,
+                None,
+                format!(
+                    "Reading ease is {:.0} ({}); aim for {:.0} or more with shorter sentences and words",
+                    score,
+                    readability_label(score),
+                    rules.min_readability
+                ),
+            );
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.action != RuleAction::Block);
+    QualityReport { stats, issues }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn content(blocks: serde_json::Value) -> PostContent {
+        serde_json::from_value(json!({ "time": 1, "blocks": blocks, "version": "2.30.7" })).unwrap()
+    }
+
+    #[test]
+    fn stats_count_prose_only() {
+        let post = content(json!([
+            { "type": "header", "data": { "text": "Hello world", "level": 2 } },
+            { "type": "paragraph", "data": { "text": "The cat sat on the <b>mat</b>." } },
+            { "type": "code", "data": { "code": "fn main() { println!(\"ignored words here\"); }" } },
+        ]));
+        let report = check_content(&post, &QualityRules::default());
+        assert_eq!(report.stats.words, 8);
+        assert_eq!(report.stats.reading_minutes, 1);
+        assert!(report.stats.readability.unwrap() > 80.0);
+        assert_eq!(report.issues[0].rule, QualityRule::ShortContent);
+    }
+
+    #[test]
+    fn structure_and_image_issues_point_at_blocks() {
+        let post = content(json!([
+            { "type": "header", "data": { "text": "Intro", "level": 2 } },
+            { "type": "header", "data": { "text": "Details", "level": 4 } },
+            { "type": "paragraph", "data": { "text": "<br>" } },
+            { "type": "header", "data": { "text": "intro", "level": 2 } },
+            { "type": "image", "data": {
+                "file": { "url": "https://cdn.example.com/a.png", "width": null, "height": null,
+                          "name": null, "title": null },
+                "caption": "", "stretched": false } },
+        ]));
+        let rules = QualityRules {
+            short_content: RuleAction::Off,
+            ..QualityRules::default()
+        };
+        let report = check_content(&post, &rules);
+        let found: Vec<(QualityRule, Option<usize>)> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.rule, issue.block))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (QualityRule::HeadingGap, Some(2)),
+                (QualityRule::EmptyBlock, Some(3)),
+                (QualityRule::DuplicateHeading, Some(4)),
+                (QualityRule::MissingAltText, Some(5)),
+            ]
+        );
+        assert!(!report.is_blocked());
+    }
+
+    #[test]
+    fn bad_links_block_by_default() {
+        let post = content(json!([
+            { "type": "paragraph", "data": {
+                "text": "See <a href=\"javascript:alert(1)\">this</a>, <a href=\"example.com\">that</a> and <a href=\"https://ok.example.com/x\">fine</a>." } },
+            { "type": "linktool", "data": { "link": "https://", "meta": {
+                "url": "https://", "title": null, "description": null, "image": null } } },
+        ]));
+        let rules = QualityRules {
+            short_content: RuleAction::Off,
+            ..QualityRules::default()
+        };
+        let report = check_content(&post, &rules);
+        assert!(report.is_blocked());
+        let messages: Vec<&str> = report.blocking().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].contains("blocked scheme"));
+        assert!(messages[1].contains("no http(s)://"));
+        assert!(messages[2].contains("has no valid host"));
+    }
+
+    #[test]
+    fn anchors_and_relative_links_are_fine() {
+        for url in [
+            "#intro",
+            "?page=2",
+            "/about",
+            "./notes",
+            "tel:+15550100",
+            "mailto:a@b.c",
+        ] {
+            assert_eq!(link_problem(url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn readability_scores_follow_word_and_sentence_length() {
+        let easy = flesch_reading_ease("The cat sat. The dog ran. We had fun.").unwrap();
+        let hard = flesch_reading_ease(
+            "Institutional considerations notwithstanding, comprehensive organizational restructuring necessitates extraordinary administrative deliberation.",
+        )
+        .unwrap();
+        assert!(easy > 90.0);
+        assert!(hard < 0.0);
+        assert_eq!(flesch_reading_ease("  "), None);
+        assert_eq!(syllables("table"), 2);
+        assert_eq!(syllables("make"), 1);
+    }
+}
@@ -1,4 +1,5 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use std::str::FromStr;
 
 /// Format a backend timestamp string (e.g., "2024-05-06T12:34:56.789")
 /// into a short human-readable date like "May 6, 2024".
@@ -14,3 +15,121 @@ pub fn format_short_date(date_str: &str) -> String {
 pub fn format_short_date_dt(dt: &DateTime<Utc>) -> String {
     dt.format("%b %-d, %Y").to_string()
 }
+
+/// Value format of `<input type="datetime-local">`.
+const DATETIME_LOCAL_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// UTC offsets, in minutes, offered next to the browser zone and UTC when scheduling.
+pub const SCHEDULE_OFFSETS: [i32; 16] = [
+    -600, -480, -420, -360, -300, -240, -180, 60, 120, 180, 240, 330, 480, 540, 600, 720,
+];
+
+/// Time zone a publish time is entered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleZone {
+    /// The browser's zone, daylight saving included.
+    Local,
+    Utc,
+    /// Fixed offset in minutes east of UTC.
+    Offset(i32),
+}
+
+impl ScheduleZone {
+    /// Stable value for `<select>` options, e.g. `local`, `utc` or `+05:30`.
+    pub fn key(&self) -> String {
+        match self {
+            ScheduleZone::Local => "local".into(),
+            ScheduleZone::Utc => "utc".into(),
+            ScheduleZone::Offset(minutes) => format_offset(*minutes),
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "local" => Some(ScheduleZone::Local),
+            "utc" => Some(ScheduleZone::Utc),
+            other => FixedOffset::from_str(other)
+                .ok()
+                .map(|offset| ScheduleZone::Offset(offset.local_minus_utc() / 60)),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            ScheduleZone::Local => {
+                let minutes = Local::now().offset().local_minus_utc() / 60;
+                format!("Browser time (UTC{})", format_offset(minutes))
+            }
+            ScheduleZone::Utc => "UTC".into(),
+            ScheduleZone::Offset(minutes) => format!("UTC{}", format_offset(*minutes)),
+        }
+    }
+
+    /// Wall-clock time in this zone to an instant. `None` for local times skipped by a
+    /// daylight-saving jump; ambiguous times resolve to the earlier instant.
+    pub fn to_utc(&self, wall: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            ScheduleZone::Local => Local
+                .from_local_datetime(&wall)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+            ScheduleZone::Utc => Some(Utc.from_utc_datetime(&wall)),
+            ScheduleZone::Offset(minutes) => FixedOffset::east_opt(minutes * 60)?
+                .from_local_datetime(&wall)
+                .single()
+                .map(|at| at.with_timezone(&Utc)),
+        }
+    }
+
+    /// Wall-clock time of an instant in this zone.
+    pub fn from_utc(&self, at: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            ScheduleZone::Local => at.with_timezone(&Local).naive_local(),
+            ScheduleZone::Utc => at.naive_utc(),
+            ScheduleZone::Offset(minutes) => FixedOffset::east_opt(minutes * 60)
+                .map(|offset| at.with_timezone(&offset).naive_local())
+                .unwrap_or_else(|| at.naive_utc()),
+        }
+    }
+}
+
+fn format_offset(minutes: i32) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let minutes = minutes.abs();
+    format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+/// Parse the value of a `datetime-local` input, e.g. "2025-03-01T09:30".
+pub fn parse_datetime_local(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, DATETIME_LOCAL_FORMAT).ok()
+}
+
+/// Format a wall-clock time as a `datetime-local` input value.
+pub fn format_datetime_local(wall: &NaiveDateTime) -> String {
+    wall.format(DATETIME_LOCAL_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_zone_round_trips_offsets() {
+        let wall = parse_datetime_local("2025-03-01T09:30").unwrap();
+        let india = ScheduleZone::from_key("+05:30").unwrap();
+        assert_eq!(india, ScheduleZone::Offset(330));
+
+        let at = india.to_utc(wall).unwrap();
+        assert_eq!(at.to_rfc3339(), "2025-03-01T04:00:00+00:00");
+        assert_eq!(
+            format_datetime_local(&india.from_utc(at)),
+            "2025-03-01T09:30"
+        );
+        assert_eq!(
+            ScheduleZone::Utc.to_utc(wall).unwrap().to_rfc3339(),
+            "2025-03-01T09:30:00+00:00"
+        );
+        assert_eq!(ScheduleZone::Offset(-300).key(), "-05:00");
+        assert_eq!(parse_datetime_local("tomorrow"), None);
+    }
+}
@@ -1,4 +1,6 @@
-use web_sys::File;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, File, FilePropertyBag, HtmlElement, Url};
 
 /// Format file size in bytes to human-readable format
 /// Examples: 1024 -> "1.0 KB", 1536000 -> "1.5 MB"
@@ -117,6 +119,129 @@ pub fn truncate_filename(filename: &str, max_length: usize) -> String {
     }
 }
 
+/// Download `url` into memory, e.g. to bundle media into an export. Returns the bytes and
+/// the response's content type.
+pub async fn fetch_bytes(url: &str) -> Result<(Vec<u8>, String), String> {
+    let window = web_sys::window().ok_or("No window available")?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|e| format!("Failed to fetch: {:?}", e))?
+        .dyn_into()
+        .map_err(|e| format!("Failed to cast response: {:?}", e))?;
+    if !response.ok() {
+        return Err(format!("Download failed with status {}", response.status()));
+    }
+    let mime_type = response
+        .headers()
+        .get("content-type")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let buffer = JsFuture::from(
+        response
+            .array_buffer()
+            .map_err(|e| format!("Failed to read body: {:?}", e))?,
+    )
+    .await
+    .map_err(|e| format!("Failed to read body: {:?}", e))?;
+    Ok((js_sys::Uint8Array::new(&buffer).to_vec(), mime_type))
+}
+
+/// Read a picked file as UTF-8 text.
+pub async fn read_file_text(file: &File) -> Result<String, String> {
+    JsFuture::from(file.text())
+        .await
+        .map_err(|e| format!("Failed to read file: {:?}", e))?
+        .as_string()
+        .ok_or_else(|| "File is not text".to_string())
+}
+
+/// Build a `File` from bytes, e.g. to re-upload media from an import.
+pub fn file_from_bytes(bytes: &[u8], filename: &str, mime_type: &str) -> Result<File, String> {
+    let array = js_sys::Uint8Array::from(bytes);
+    let options = FilePropertyBag::new();
+    options.set_type(mime_type);
+    File::new_with_u8_array_sequence_and_options(&js_sys::Array::of1(&array), filename, &options)
+        .map_err(|e| format!("Failed to create file: {:?}", e))
+}
+
+/// Offer `contents` to the user as a file download.
+pub fn download_text(filename: &str, mime_type: &str, contents: &str) -> Result<(), String> {
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob =
+        Blob::new_with_str_sequence_and_options(&js_sys::Array::of1(&contents.into()), &options)
+            .map_err(|e| format!("Failed to create blob: {:?}", e))?;
+    let url = Url::create_object_url_with_blob(&blob)
+        .map_err(|e| format!("Failed to create blob URL: {:?}", e))?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("No document available")?;
+    let link: HtmlElement = document
+        .create_element("a")
+        .map_err(|e| format!("Failed to create link: {:?}", e))?
+        .dyn_into()
+        .map_err(|e| format!("Failed to cast link: {:?}", e))?;
+    let _ = link.set_attribute("href", &url);
+    let _ = link.set_attribute("download", filename);
+    link.click();
+    let _ = Url::revoke_object_url(&url);
+    Ok(())
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard, padded base64, for embedding binary files in JSON.
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+pub fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u32> = text
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .map(|b| {
+            BASE64_ALPHABET
+                .iter()
+                .position(|a| *a == b)
+                .map(|p| p as u32)
+                .ok_or_else(|| format!("Invalid base64 character '{}'", b as char))
+        })
+        .collect::<Result<_, _>>()?;
+    if digits.len() % 4 == 1 {
+        return Err("Truncated base64 data".to_string());
+    }
+    let mut out = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, d)| n | d << (18 - 6 * i));
+        for i in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,4 +286,18 @@ mod tests {
             "noext...ry_long"
         );
     }
+
+    #[test]
+    fn test_base64_round_trip() {
+        assert_eq!(encode_base64(b""), "");
+        assert_eq!(encode_base64(b"f"), "Zg==");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+        assert_eq!(encode_base64(b"foo"), "Zm9v");
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(decode_base64("Zm9vYg==").unwrap(), b"foob");
+        assert!(decode_base64("Zm9v!").is_err());
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode_base64(&encode_base64(&bytes)).unwrap(), bytes);
+    }
 }
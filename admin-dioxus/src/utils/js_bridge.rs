@@ -6,7 +6,7 @@ use web_sys::File;
 /// Exposes window.editorjs_upload_file() to JavaScript
 #[wasm_bindgen]
 pub async fn editorjs_upload_file(file: File) -> Result<JsValue, JsValue> {
-    use crate::store::{use_media, MediaReference, MediaUploadPayload, UploadStatus};
+    use crate::store::{use_media, MediaReference, MediaUploadPayload, MediaVariant};
     use serde::Serialize;
 
     gloo_console::log!("[editorjs_upload_file] Starting upload for:", file.name());
@@ -20,85 +20,52 @@ pub async fn editorjs_upload_file(file: File) -> Result<JsValue, JsValue> {
         reference_type: Some(MediaReference::Post),
         width: None,
         height: None,
+        optimize: true,
     };
 
-    // Upload via media store
-    match media_store.upload(payload).await {
-        Ok(blob_url) => {
+    // Upload via media store and wait for the server's media record
+    match media_store.upload_and_wait(payload, 30).await {
+        Ok(media) => {
             gloo_console::log!(
-                "[editorjs_upload_file] Upload initiated, blob URL:",
-                &blob_url
+                "[editorjs_upload_file] Upload complete! Media ID:",
+                media.id.to_string(),
+                "URL:",
+                &media.file_url
             );
 
-            // Poll for upload completion
-            let max_wait = 30; // 30 seconds timeout
-            let mut elapsed = 0;
+            // Renditions travel with the block so the post can render a srcset
+            let media = media_store.with_variants(media).await;
 
-            loop {
-                if elapsed >= max_wait {
-                    let err_msg = "Upload timeout after 30 seconds";
-                    gloo_console::error!("[editorjs_upload_file]", err_msg);
-                    return Err(JsValue::from_str(err_msg));
-                }
-
-                if media_store.is_upload_complete(&blob_url) {
-                    match media_store.get_uploaded_media(&blob_url) {
-                        Some(media) => {
-                            gloo_console::log!(
-                                "[editorjs_upload_file] Upload complete! Media ID:",
-                                media.id.to_string(),
-                                "URL:",
-                                &media.file_url
-                            );
-
-                            // Return Editor.js compatible format
-                            #[derive(Serialize)]
-                            struct EditorJsUploadResponse {
-                                success: u8,
-                                file: EditorJsFile,
-                            }
-
-                            #[derive(Serialize)]
-                            struct EditorJsFile {
-                                url: String,
-                                media_id: i32,
-                            }
-
-                            let response = EditorJsUploadResponse {
-                                success: 1,
-                                file: EditorJsFile {
-                                    url: media.file_url,
-                                    media_id: media.id,
-                                },
-                            };
+            // Return Editor.js compatible format
+            #[derive(Serialize)]
+            struct EditorJsUploadResponse {
+                success: u8,
+                file: EditorJsFile,
+            }
 
-                            // Cleanup blob tracking
-                            media_store.cleanup_blob(&blob_url);
+            #[derive(Serialize)]
+            struct EditorJsFile {
+                url: String,
+                media_id: i32,
+                width: Option<i32>,
+                height: Option<i32>,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                variants: Vec<MediaVariant>,
+            }
 
-                            return serde_wasm_bindgen::to_value(&response).map_err(|e| {
-                                JsValue::from_str(&format!("Serialization error: {:?}", e))
-                            });
-                        }
-                        None => {
-                            // Check if there was an error
-                            if let Some(status) = media_store.get_upload_status(&blob_url) {
-                                if let UploadStatus::Error(err_msg) = status {
-                                    gloo_console::error!(
-                                        "[editorjs_upload_file] Upload failed:",
-                                        &err_msg
-                                    );
-                                    media_store.cleanup_blob(&blob_url);
-                                    return Err(JsValue::from_str(&err_msg));
-                                }
-                            }
-                        }
-                    }
-                }
+            let response = EditorJsUploadResponse {
+                success: 1,
+                file: EditorJsFile {
+                    url: media.file_url,
+                    media_id: media.id,
+                    width: media.width,
+                    height: media.height,
+                    variants: media.variants,
+                },
+            };
 
-                // Wait 500ms before checking again
-                gloo_timers::future::TimeoutFuture::new(500).await;
-                elapsed += 1;
-            }
+            serde_wasm_bindgen::to_value(&response)
+                .map_err(|e| JsValue::from_str(&format!("Serialization error: {:?}", e)))
         }
         Err(err_msg) => {
             gloo_console::error!("[editorjs_upload_file] Upload failed:", &err_msg);
@@ -106,3 +73,20 @@ pub async fn editorjs_upload_file(file: File) -> Result<JsValue, JsValue> {
         }
     }
 }
+
+/// Replace the Editor.js document with `content_json` (a serialized [`PostContent`]), e.g.
+/// when restoring a draft. Calls `window.editor.render()` exposed by the editor bundle.
+///
+/// [`PostContent`]: crate::store::PostContent
+pub fn editorjs_render(content_json: &str) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("No window"))?;
+    let editor = js_sys::Reflect::get(&window, &JsValue::from_str("editor"))?;
+    if editor.is_undefined() {
+        return Err(JsValue::from_str("Editor is not ready"));
+    }
+    let render: js_sys::Function =
+        js_sys::Reflect::get(&editor, &JsValue::from_str("render"))?.dyn_into()?;
+    let data = js_sys::JSON::parse(content_json)?;
+    render.call1(&editor, &data)?;
+    Ok(())
+}
@@ -0,0 +1,847 @@
+//! Markdown to Editor.js [`PostContent`], for authors pasting posts written elsewhere.
+//!
+//! Covers the CommonMark blocks the editor has a tool for: headings, paragraphs, lists and
+//! task lists, fenced code, quotes, pipe tables, images and thematic breaks. Nested lists are
+//! flattened, since Editor.js lists are one level deep. Inline marks become the HTML the
+//! editor's inline tools produce (`<b>`, `<i>`, `<code class="inline-code">`, `<a>`).
+//!
+//! Every link and image URL goes through [`sanitize_url`]: links with an unsafe URL
+//! (`javascript:`, `data:`, ...) keep only their text, and such images are dropped.
+
+use chrono::Utc;
+
+use crate::store::{
+    ChecklistBlock, ChecklistItem, CodeBlock, EditorJsBlock, HeaderBlock, ImageBlock, ImageFile,
+    ListBlock, ParagraphBlock, PostContent, QuoteBlock, TableBlock,
+};
+use crate::utils::urls::sanitize_url;
+
+/// Editor.js version the bundled editor writes.
+const EDITOR_VERSION: &str = "2.30.7";
+
+pub fn markdown_to_content(markdown: &str) -> PostContent {
+    PostContent {
+        time: Utc::now().timestamp_millis() as u64,
+        blocks: parse_blocks(markdown),
+        version: EDITOR_VERSION.to_string(),
+    }
+}
+
+fn parse_blocks(markdown: &str) -> Vec<EditorJsBlock> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            i += 1;
+            continue;
+        }
+
+        // Setext headings underline the paragraph above them
+        if !paragraph.is_empty() && is_setext_underline(trimmed) {
+            let level = if trimmed.starts_with('=') { 1 } else { 2 };
+            blocks.push(header(level, &inline_html(&paragraph.join(" "))));
+            paragraph.clear();
+            i += 1;
+            continue;
+        }
+
+        if let Some(fence) = code_fence(line) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !closes_fence(lines[i], &fence) {
+                code.push(lines[i]);
+                i += 1;
+            }
+            blocks.push(EditorJsBlock::Code {
+                id: None,
+                data: CodeBlock {
+                    code: code.join("\n"),
+                },
+            });
+            i += 1;
+            continue;
+        }
+
+        if let Some((level, text)) = atx_heading(trimmed) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            blocks.push(header(level, &inline_html(text)));
+            i += 1;
+            continue;
+        }
+
+        if is_thematic_break(trimmed) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            blocks.push(EditorJsBlock::Delimiter { id: None });
+            i += 1;
+            continue;
+        }
+
+        if trimmed.starts_with('>') {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim().starts_with('>') {
+                let inner = lines[i].trim().trim_start_matches('>');
+                quoted.push(inner.strip_prefix(' ').unwrap_or(inner));
+                i += 1;
+            }
+            blocks.push(quote(&quoted));
+            continue;
+        }
+
+        if list_marker(line).is_some() {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            i = parse_list(&lines, i, &mut blocks);
+            continue;
+        }
+
+        if paragraph.is_empty() && trimmed.contains('|') {
+            if let Some(separator) = lines.get(i + 1) {
+                if is_table_separator(separator) {
+                    let mut content = vec![table_cells(trimmed)];
+                    i += 2;
+                    while i < lines.len() && lines[i].contains('|') && !lines[i].trim().is_empty() {
+                        content.push(table_cells(lines[i].trim()));
+                        i += 1;
+                    }
+                    blocks.push(EditorJsBlock::Table {
+                        id: None,
+                        data: TableBlock { content },
+                    });
+                    continue;
+                }
+            }
+        }
+
+        paragraph.push(line);
+        i += 1;
+    }
+    flush_paragraph(&mut paragraph, &mut blocks);
+    blocks
+}
+
+fn header(level: u8, text: &str) -> EditorJsBlock {
+    EditorJsBlock::Header {
+        id: None,
+        data: HeaderBlock {
+            text: text.to_string(),
+            level,
+        },
+    }
+}
+
+fn flush_paragraph(paragraph: &mut Vec<&str>, blocks: &mut Vec<EditorJsBlock>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let text = join_lines(paragraph);
+    paragraph.clear();
+
+    // An image on its own becomes an image block; inline images have nowhere to go
+    if let Some((alt, url)) = standalone_image(&text) {
+        let Some(url) = sanitize_url(&url) else {
+            return;
+        };
+        blocks.push(EditorJsBlock::Image {
+            id: None,
+            data: ImageBlock {
+                file: ImageFile {
+                    url,
+                    width: None,
+                    height: None,
+                    name: None,
+                    title: None,
+                    media_id: None,
+                    variants: Vec::new(),
+                },
+                caption: Some(inline_html(&alt)).filter(|caption| !caption.is_empty()),
+                stretched: false,
+                withBackground: None,
+                withBorder: None,
+            },
+        });
+        return;
+    }
+
+    blocks.push(EditorJsBlock::Paragraph {
+        id: None,
+        data: ParagraphBlock {
+            text: inline_html(&text),
+        },
+    });
+}
+
+/// Join soft-wrapped lines, keeping hard breaks (two trailing spaces or a backslash) as `\n`
+/// for [`inline_html`] to turn into `<br>`.
+fn join_lines(lines: &[&str]) -> String {
+    let mut text = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let hard_break = line.ends_with("  ") || line.trim_end().ends_with('\\');
+        let line = line.trim();
+        let line = if hard_break {
+            line.strip_suffix('\\').unwrap_or(line)
+        } else {
+            line
+        };
+        text.push_str(line);
+        if index + 1 < lines.len() {
+            text.push(if hard_break { '\n' } else { ' ' });
+        }
+    }
+    text
+}
+
+fn standalone_image(text: &str) -> Option<(String, String)> {
+    let rest = text.trim().strip_prefix("![")?;
+    let (alt, rest) = rest.split_once("](")?;
+    let destination = rest.strip_suffix(')')?;
+    if destination.contains(')') {
+        return None;
+    }
+    Some((alt.to_string(), link_destination(destination)))
+}
+
+fn is_setext_underline(line: &str) -> bool {
+    let first = line.chars().next().unwrap_or_default();
+    matches!(first, '=' | '-') && line.chars().all(|c| c == first)
+}
+
+fn atx_heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    Some((level as u8, text))
+}
+
+fn is_thematic_break(line: &str) -> bool {
+    let marks: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    let first = marks.chars().next().unwrap_or_default();
+    marks.len() >= 3 && matches!(first, '-' | '*' | '_') && marks.chars().all(|c| c == first)
+}
+
+/// Opening fence of a code block: the fence string, e.g. "```" or "~~~~".
+fn code_fence(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let first = trimmed.chars().next()?;
+    if first != '`' && first != '~' {
+        return None;
+    }
+    let length = trimmed.chars().take_while(|c| *c == first).count();
+    (length >= 3).then(|| first.to_string().repeat(length))
+}
+
+fn closes_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with(fence) && trimmed.chars().all(|c| fence.starts_with(c))
+}
+
+fn quote(lines: &[&str]) -> EditorJsBlock {
+    let mut lines: Vec<&str> = lines.to_vec();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    // "> — Author" as the last line is the attribution
+    let caption = lines
+        .last()
+        .and_then(|line| {
+            let line = line.trim();
+            line.strip_prefix("— ")
+                .or_else(|| line.strip_prefix("-- "))
+                .map(inline_html)
+        })
+        .inspect(|_| {
+            lines.pop();
+        });
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let text = lines
+        .split(|line| line.trim().is_empty())
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| inline_html(&join_lines(paragraph)))
+        .collect::<Vec<_>>()
+        .join("<br>");
+
+    EditorJsBlock::Quote {
+        id: None,
+        data: QuoteBlock {
+            text,
+            caption,
+            alignment: "left".to_string(),
+        },
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ListKind {
+    Ordered,
+    Unordered,
+}
+
+/// List kind and the item text after the marker.
+fn list_marker(line: &str) -> Option<(ListKind, &str)> {
+    let trimmed = line.trim_start();
+    if let Some(rest) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))
+    {
+        return Some((ListKind::Unordered, rest));
+    }
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || digits > 9 {
+        return None;
+    }
+    let rest = trimmed[digits..]
+        .strip_prefix(". ")
+        .or_else(|| trimmed[digits..].strip_prefix(") "))?;
+    Some((ListKind::Ordered, rest))
+}
+
+fn task_marker(item: &str) -> Option<(bool, &str)> {
+    let checked = match item.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((checked, item[3..].trim_start()))
+}
+
+/// Collect consecutive items of one kind starting at `start`; returns the next line to parse.
+fn parse_list(lines: &[&str], start: usize, blocks: &mut Vec<EditorJsBlock>) -> usize {
+    let (kind, _) = list_marker(lines[start]).expect("caller checked the marker");
+    let mut items: Vec<Vec<&str>> = Vec::new();
+    let mut i = start;
+
+    while i < lines.len() {
+        let line = lines[i];
+        match list_marker(line) {
+            Some((item_kind, text))
+                if item_kind == kind || line.starts_with(char::is_whitespace) =>
+            {
+                items.push(vec![text.trim()]);
+            }
+            Some(_) => break,
+            None if line.trim().is_empty() => {
+                // A blank line ends the list unless another item follows
+                if !lines
+                    .get(i + 1)
+                    .and_then(|next| list_marker(next))
+                    .is_some_and(|(next_kind, _)| next_kind == kind)
+                {
+                    i += 1;
+                    break;
+                }
+            }
+            // Indented lines continue the previous item
+            None if line.starts_with(char::is_whitespace) => {
+                if let Some(item) = items.last_mut() {
+                    item.push(line.trim());
+                }
+            }
+            None => break,
+        }
+        i += 1;
+    }
+
+    let items: Vec<String> = items.iter().map(|item| join_lines(item)).collect();
+    let tasks: Vec<Option<(bool, &str)>> = items.iter().map(|item| task_marker(item)).collect();
+
+    if kind == ListKind::Unordered && tasks.iter().any(Option::is_some) {
+        let items = items
+            .iter()
+            .zip(&tasks)
+            .map(|(item, task)| {
+                let (checked, text) = task.unwrap_or((false, item));
+                ChecklistItem {
+                    text: inline_html(text),
+                    checked,
+                }
+            })
+            .collect();
+        blocks.push(EditorJsBlock::Checklist {
+            id: None,
+            data: ChecklistBlock { items },
+        });
+    } else {
+        blocks.push(EditorJsBlock::List {
+            id: None,
+            data: ListBlock {
+                style: match kind {
+                    ListKind::Ordered => "ordered",
+                    ListKind::Unordered => "unordered",
+                }
+                .to_string(),
+                items: items.iter().map(|item| inline_html(item)).collect(),
+            },
+        });
+    }
+    i
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.contains('-')
+        && line.contains('|')
+        && line.trim_matches('|').split('|').all(|cell| {
+            let cell = cell.trim().trim_start_matches(':').trim_end_matches(':');
+            !cell.is_empty() && cell.chars().all(|c| c == '-')
+        })
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells.iter().map(|cell| inline_html(cell.trim())).collect()
+}
+
+/// `url` or `<url>`, without an optional `"title"`.
+fn link_destination(destination: &str) -> String {
+    let destination = destination.trim();
+    if let Some(rest) = destination.strip_prefix('<') {
+        return rest.split('>').next().unwrap_or_default().to_string();
+    }
+    destination
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Inline Markdown to the HTML Editor.js stores in block text.
+fn inline_html(markdown: &str) -> String {
+    let chars: Vec<char> = markdown.chars().collect();
+    let mut out = String::with_capacity(markdown.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if chars
+                .get(i + 1)
+                .is_some_and(|next| next.is_ascii_punctuation()) =>
+            {
+                push_escaped(&mut out, chars[i + 1]);
+                i += 2;
+            }
+            '\n' => {
+                out.push_str("<br>");
+                i += 1;
+            }
+            '`' => {
+                let run = count_run(&chars, i, '`');
+                let closing = find_run(&chars, i + run, '`', run);
+                match closing {
+                    Some(end) => {
+                        let code: String = chars[i + run..end].iter().collect();
+                        out.push_str("<code class=\"inline-code\">");
+                        out.push_str(&escape_html(code.trim()));
+                        out.push_str("</code>");
+                        i = end + run;
+                    }
+                    None => {
+                        out.push_str(&"`".repeat(run));
+                        i += run;
+                    }
+                }
+            }
+            '!' if chars.get(i + 1) == Some(&'[') => match parse_link(&chars, i + 1) {
+                // Images inside text have no block to live in; keep them as links
+                Some((text, url, next)) => {
+                    push_link(&mut out, &text, &url);
+                    i = next;
+                }
+                None => {
+                    out.push('!');
+                    i += 1;
+                }
+            },
+            '[' => match parse_link(&chars, i) {
+                Some((text, url, next)) => {
+                    push_link(&mut out, &text, &url);
+                    i = next;
+                }
+                None => {
+                    out.push('[');
+                    i += 1;
+                }
+            },
+            '<' => {
+                let end = chars[i..].iter().position(|c| *c == '>').map(|p| i + p);
+                let autolink: Option<String> = end.map(|end| chars[i + 1..end].iter().collect());
+                match autolink {
+                    Some(url) if is_autolink(&url) => {
+                        push_link(&mut out, &url, &url);
+                        i = end.unwrap_or(i) + 1;
+                    }
+                    _ => {
+                        out.push_str("&lt;");
+                        i += 1;
+                    }
+                }
+            }
+            '*' | '_' => {
+                let run = count_run(&chars, i, c).min(2);
+                let opens = chars.get(i + run).is_some_and(|next| !next.is_whitespace())
+                    && (c == '*' || i == 0 || !chars[i - 1].is_alphanumeric());
+                let closing = if opens {
+                    find_closing_emphasis(&chars, i + run, c, run)
+                } else {
+                    None
+                };
+                match closing {
+                    Some(end) => {
+                        let inner: String = chars[i + run..end].iter().collect();
+                        let tag = if run == 2 { "b" } else { "i" };
+                        out.push_str(&format!("<{tag}>{}</{tag}>", inline_html(&inner)));
+                        i = end + run;
+                    }
+                    None => {
+                        out.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            _ => {
+                push_escaped(&mut out, c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn push_escaped(out: &mut String, c: char) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        _ => out.push(c),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        push_escaped(&mut out, c);
+    }
+    out
+}
+
+fn push_link(out: &mut String, text: &str, url: &str) {
+    match sanitize_url(url) {
+        Some(url) => out.push_str(&format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(&url),
+            inline_html(text)
+        )),
+        None => out.push_str(&inline_html(text)),
+    }
+}
+
+fn is_autolink(url: &str) -> bool {
+    !url.contains(char::is_whitespace)
+        && (url.starts_with("http://") || url.starts_with("https://") || url.starts_with("mailto:"))
+}
+
+fn count_run(chars: &[char], start: usize, c: char) -> usize {
+    chars[start..].iter().take_while(|x| **x == c).count()
+}
+
+/// Start of the next run of exactly `length` `c`s at or after `from`.
+fn find_run(chars: &[char], from: usize, c: char, length: usize) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == c {
+            let run = count_run(chars, i, c);
+            if run == length {
+                return Some(i);
+            }
+            i += run;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Closing delimiter for emphasis opened at `from`: a run of `length` `c`s that follows a
+/// non-space character and, for `_`, is not inside a word.
+fn find_closing_emphasis(chars: &[char], from: usize, c: char, length: usize) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '`' => {
+                let run = count_run(chars, i, '`');
+                i = find_run(chars, i + run, '`', run).map_or(i + run, |end| end + run);
+            }
+            x if x == c => {
+                let run = count_run(chars, i, c);
+                let after_text = i > from && !chars[i - 1].is_whitespace();
+                let word_end = c == '*' || chars.get(i + run).is_none_or(|n| !n.is_alphanumeric());
+                if after_text && word_end && run >= length {
+                    // For `***`, the inner emphasis closes first
+                    return Some(i + run - length);
+                }
+                i += run;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// `[text](destination)` starting at the `[` at `start`: text, URL and the index after `)`.
+fn parse_link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    let mut depth = 0;
+    let mut close = None;
+    for (offset, c) in chars[start..].iter().enumerate() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(start + offset);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = chars[close + 2..].iter().position(|c| *c == ')')? + close + 2;
+    let text: String = chars[start + 1..close].iter().collect();
+    let destination: String = chars[close + 2..end].iter().collect();
+    Some((text, link_destination(&destination), end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(markdown: &str) -> Vec<EditorJsBlock> {
+        markdown_to_content(markdown).blocks
+    }
+
+    fn paragraph(text: &str) -> EditorJsBlock {
+        EditorJsBlock::Paragraph {
+            id: None,
+            data: ParagraphBlock { text: text.into() },
+        }
+    }
+
+    #[test]
+    fn headings() {
+        assert_eq!(
+            blocks("# One\n\n### Three ###\n\nTwo\n---\n\nSetext\n==="),
+            vec![
+                header(1, "One"),
+                header(3, "Three"),
+                header(2, "Two"),
+                header(1, "Setext"),
+            ]
+        );
+        assert_eq!(blocks("#hashtag"), vec![paragraph("#hashtag")]);
+    }
+
+    #[test]
+    fn paragraphs_with_inline_marks() {
+        assert_eq!(
+            blocks(
+                "Some **bold**, *italic*, _also_ and `a<b>`\nwrapped  \nbroken, [link](https://x.test \"t\") \\*not\\* snake_case_name\n\nSecond & <last>"
+            ),
+            vec![
+                paragraph(
+                    "Some <b>bold</b>, <i>italic</i>, <i>also</i> and <code class=\"inline-code\">a&lt;b&gt;</code> wrapped<br>broken, <a href=\"https://x.test\">link</a> *not* snake_case_name"
+                ),
+                paragraph("Second &amp; &lt;last&gt;"),
+            ]
+        );
+        assert_eq!(
+            blocks("**bold _and italic_** <https://a.test>"),
+            vec![paragraph(
+                "<b>bold <i>and italic</i></b> <a href=\"https://a.test\">https://a.test</a>"
+            )]
+        );
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            blocks("- one\n- **two**\n  continued\n  - nested\n\n1. first\n2) second"),
+            vec![
+                EditorJsBlock::List {
+                    id: None,
+                    data: ListBlock {
+                        style: "unordered".into(),
+                        items: vec!["one".into(), "<b>two</b> continued".into(), "nested".into()],
+                    },
+                },
+                EditorJsBlock::List {
+                    id: None,
+                    data: ListBlock {
+                        style: "ordered".into(),
+                        items: vec!["first".into(), "second".into()],
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn checklists() {
+        assert_eq!(
+            blocks("- [x] done\n- [ ] *todo*"),
+            vec![EditorJsBlock::Checklist {
+                id: None,
+                data: ChecklistBlock {
+                    items: vec![
+                        ChecklistItem {
+                            text: "done".into(),
+                            checked: true,
+                        },
+                        ChecklistItem {
+                            text: "<i>todo</i>".into(),
+                            checked: false,
+                        },
+                    ],
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn code_fences() {
+        assert_eq!(
+            blocks("```rust\nfn main() {}\n\n// <b>\n```\n~~~~\n```\n~~~~"),
+            vec![
+                EditorJsBlock::Code {
+                    id: None,
+                    data: CodeBlock {
+                        code: "fn main() {}\n\n// <b>".into(),
+                    },
+                },
+                EditorJsBlock::Code {
+                    id: None,
+                    data: CodeBlock { code: "```".into() },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            blocks("> Less is\n> *more*\n>\n> Really\n> — Mies"),
+            vec![EditorJsBlock::Quote {
+                id: None,
+                data: QuoteBlock {
+                    text: "Less is <i>more</i><br>Really".into(),
+                    caption: Some("Mies".into()),
+                    alignment: "left".into(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn tables() {
+        assert_eq!(
+            blocks("| Name | Qty |\n|:-----|----:|\n| **Tea** | 2 |\n| a \\| b | |"),
+            vec![EditorJsBlock::Table {
+                id: None,
+                data: TableBlock {
+                    content: vec![
+                        vec!["Name".into(), "Qty".into()],
+                        vec!["<b>Tea</b>".into(), "2".into()],
+                        vec!["a | b".into(), "".into()],
+                    ],
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn images() {
+        let parsed = blocks(
+            "![A *cat*](https://cdn.test/cat.png \"Cat\")\n\nSee ![x](https://cdn.test/x.png)",
+        );
+        match &parsed[0] {
+            EditorJsBlock::Image { data, .. } => {
+                assert_eq!(data.file.url, "https://cdn.test/cat.png");
+                assert_eq!(data.caption.as_deref(), Some("A <i>cat</i>"));
+            }
+            other => panic!("expected an image, got {:?}", other),
+        }
+        assert_eq!(
+            parsed[1],
+            paragraph("See <a href=\"https://cdn.test/x.png\">x</a>")
+        );
+    }
+
+    #[test]
+    fn unsafe_urls() {
+        assert_eq!(
+            blocks("[x](javascript:alert%28document.cookie%29) and [y]( JavaScript:void ) <data:text/html,hi>"),
+            vec![paragraph("x and y &lt;data:text/html,hi&gt;")]
+        );
+        assert_eq!(
+            blocks("![x](data:image/svg+xml;base64,PHN2Zz4=)\n\nAfter"),
+            vec![paragraph("After")]
+        );
+        assert_eq!(
+            blocks("See ![x](vbscript:msgbox)"),
+            vec![paragraph("See x")]
+        );
+    }
+
+    #[test]
+    fn thematic_breaks() {
+        assert_eq!(
+            blocks("One\n\n* * *\n\n___\n\nTwo"),
+            vec![
+                paragraph("One"),
+                EditorJsBlock::Delimiter { id: None },
+                EditorJsBlock::Delimiter { id: None },
+                paragraph("Two"),
+            ]
+        );
+    }
+}
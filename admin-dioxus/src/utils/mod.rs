@@ -1,5 +1,10 @@
 pub mod colors;
+pub mod content_quality;
 pub mod dates;
 pub mod file_helpers;
 pub mod js_bridge;
+pub mod markdown;
 pub mod persist;
+pub mod post_diff;
+pub mod post_render;
+pub mod urls;
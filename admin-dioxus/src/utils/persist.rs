@@ -9,20 +9,87 @@ use std::sync::Mutex;
 pub static PKV: Lazy<Mutex<PkvStore>> =
     Lazy::new(|| Mutex::new(PkvStore::new("Ruxlog", "AdminDioxus")));
 
+// An empty value stands for a removed key; the store has no way to delete one.
+fn read(key: &str) -> Option<String> {
+    PKV.lock()
+        .ok()?
+        .get::<String>(key)
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+// Writes are best-effort; errors are ignored to avoid breaking UI interactions.
+fn write(key: &str, value: &str) {
+    if let Ok(mut store) = PKV.lock() {
+        let _ = store.set_string(key, value);
+    }
+}
+
+fn remove(key: &str) {
+    write(key, "");
+}
+
 const THEME_KEY: &str = "theme"; // values: "dark" | "light"
+const API_PROFILE_KEY: &str = "api_profile"; // configured profile name
+const POST_DRAFT_KEY: &str = "post_draft"; // autosaved blog form, suffixed with its slot
+const QUALITY_RULES_KEY: &str = "quality_rules"; // JSON of the pre-publish content check settings
+const UPLOAD_SESSIONS_KEY: &str = "upload_sessions"; // JSON array of interrupted chunked uploads
+const IMAGE_OPTIMIZE_KEY: &str = "image_optimize"; // JSON of the pre-upload image settings
 
 pub fn get_theme() -> Option<String> {
-    PKV.lock().ok()?.get::<String>(THEME_KEY).ok()
+    read(THEME_KEY)
 }
 
 pub fn set_theme(theme: &str) {
-    // Best-effort; ignore errors to avoid breaking UI interactions.
-    if let Ok(mut store) = PKV.lock() {
-        let _ = store.set_string(THEME_KEY, theme);
-    }
+    write(THEME_KEY, theme);
     // Mirror the value into window.localStorage so we can apply theme pre-hydration in index.html
     #[cfg(target_arch = "wasm32")]
     {
         let _ = LocalStorage::set(THEME_KEY, theme);
     }
 }
+
+pub fn get_api_profile() -> Option<String> {
+    read(API_PROFILE_KEY)
+}
+
+pub fn set_api_profile(selector: &str) {
+    write(API_PROFILE_KEY, selector);
+}
+
+/// `slot` is the post id, or `"new"` for a post that was never saved.
+pub fn get_post_draft(slot: &str) -> Option<String> {
+    read(&format!("{}:{}", POST_DRAFT_KEY, slot))
+}
+
+pub fn set_post_draft(slot: &str, json: &str) {
+    write(&format!("{}:{}", POST_DRAFT_KEY, slot), json);
+}
+
+pub fn remove_post_draft(slot: &str) {
+    remove(&format!("{}:{}", POST_DRAFT_KEY, slot));
+}
+
+pub fn get_quality_rules() -> Option<String> {
+    read(QUALITY_RULES_KEY)
+}
+
+pub fn set_quality_rules(json: &str) {
+    write(QUALITY_RULES_KEY, json);
+}
+
+pub fn get_upload_sessions() -> Option<String> {
+    read(UPLOAD_SESSIONS_KEY)
+}
+
+pub fn set_upload_sessions(json: &str) {
+    write(UPLOAD_SESSIONS_KEY, json);
+}
+
+pub fn get_image_optimize() -> Option<String> {
+    read(IMAGE_OPTIMIZE_KEY)
+}
+
+pub fn set_image_optimize(json: &str) {
+    write(IMAGE_OPTIMIZE_KEY, json);
+}
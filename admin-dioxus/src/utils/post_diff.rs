@@ -0,0 +1,315 @@
+//! Block-level diff between two versions of a post: a stored revision or an autosaved
+//! draft against the server copy.
+
+use crate::store::{EditorJsBlock, Post, PostContent, PostRevision};
+use crate::utils::post_render::inline_text;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub before: String,
+    pub after: String,
+}
+
+impl FieldChange {
+    pub fn between(before: &str, after: &str) -> Option<Self> {
+        (before != after).then(|| Self {
+            before: before.to_string(),
+            after: after.to_string(),
+        })
+    }
+}
+
+/// One row of the side-by-side view; "before" is the revision, "after" the current post.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockDiff {
+    Unchanged(EditorJsBlock),
+    Added(EditorJsBlock),
+    Removed(EditorJsBlock),
+    Modified {
+        before: Box<EditorJsBlock>,
+        after: Box<EditorJsBlock>,
+    },
+}
+
+impl BlockDiff {
+    pub fn is_change(&self) -> bool {
+        !matches!(self, BlockDiff::Unchanged(_))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisionDiff {
+    pub title: Option<FieldChange>,
+    pub excerpt: Option<FieldChange>,
+    pub blocks: Vec<BlockDiff>,
+    /// The revision body is not valid `PostContent` JSON; blocks are compared as empty.
+    pub unreadable_content: bool,
+}
+
+impl RevisionDiff {
+    pub fn between(revision: &PostRevision, current: &Post) -> Self {
+        let parsed = serde_json::from_str::<PostContent>(&revision.content);
+        let revision_blocks = parsed
+            .as_ref()
+            .map(|content| content.blocks.as_slice())
+            .unwrap_or_default();
+
+        Self {
+            title: FieldChange::between(&revision.title, &current.title),
+            excerpt: FieldChange::between(
+                revision.excerpt.as_deref().unwrap_or_default(),
+                current.excerpt.as_deref().unwrap_or_default(),
+            ),
+            blocks: diff_blocks(revision_blocks, &current.content.blocks),
+            unreadable_content: parsed.is_err(),
+        }
+    }
+
+    pub fn change_count(&self) -> usize {
+        self.title.is_some() as usize
+            + self.excerpt.is_some() as usize
+            + self.blocks.iter().filter(|b| b.is_change()).count()
+    }
+}
+
+fn block_id(block: &EditorJsBlock) -> Option<&str> {
+    match block {
+        EditorJsBlock::Header { id, .. }
+        | EditorJsBlock::Paragraph { id, .. }
+        | EditorJsBlock::List { id, .. }
+        | EditorJsBlock::Delimiter { id }
+        | EditorJsBlock::Image { id, .. }
+        | EditorJsBlock::Embed { id, .. }
+        | EditorJsBlock::LinkTool { id, .. }
+        | EditorJsBlock::Attaches { id, .. }
+        | EditorJsBlock::Code { id, .. }
+        | EditorJsBlock::Raw { id, .. }
+        | EditorJsBlock::Table { id, .. }
+        | EditorJsBlock::Quote { id, .. }
+        | EditorJsBlock::Warning { id, .. }
+        | EditorJsBlock::Button { id, .. }
+        | EditorJsBlock::Alert { id, .. }
+        | EditorJsBlock::Checklist { id, .. } => id.as_deref(),
+        EditorJsBlock::Unknown => None,
+    }
+}
+
+/// Blocks are the "same" block when their EditorJS ids match, or, without ids, when
+/// they are identical.
+fn same_block(a: &EditorJsBlock, b: &EditorJsBlock) -> bool {
+    match (block_id(a), block_id(b)) {
+        (Some(a_id), Some(b_id)) => a_id == b_id,
+        _ => a == b,
+    }
+}
+
+fn same_kind(a: &EditorJsBlock, b: &EditorJsBlock) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+/// LCS alignment of the two block lists. Blocks matched by id but with different data are
+/// `Modified`. An id-less removal directly followed by an addition of the same kind is
+/// also shown as `Modified`.
+pub fn diff_blocks(before: &[EditorJsBlock], after: &[EditorJsBlock]) -> Vec<BlockDiff> {
+    let (n, m) = (before.len(), after.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if same_block(&before[i], &after[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut raw = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if same_block(&before[i], &after[j]) {
+            raw.push(if before[i] == after[j] {
+                BlockDiff::Unchanged(after[j].clone())
+            } else {
+                BlockDiff::Modified {
+                    before: Box::new(before[i].clone()),
+                    after: Box::new(after[j].clone()),
+                }
+            });
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            raw.push(BlockDiff::Removed(before[i].clone()));
+            i += 1;
+        } else {
+            raw.push(BlockDiff::Added(after[j].clone()));
+            j += 1;
+        }
+    }
+    raw.extend(before[i..].iter().cloned().map(BlockDiff::Removed));
+    raw.extend(after[j..].iter().cloned().map(BlockDiff::Added));
+
+    let mut diff: Vec<BlockDiff> = Vec::with_capacity(raw.len());
+    for entry in raw {
+        if let BlockDiff::Added(added) = &entry {
+            if let Some(BlockDiff::Removed(removed)) = diff.last() {
+                if same_kind(removed, added)
+                    && (block_id(removed).is_none() || block_id(added).is_none())
+                {
+                    let removed = removed.clone();
+                    diff.pop();
+                    diff.push(BlockDiff::Modified {
+                        before: Box::new(removed),
+                        after: Box::new(added.clone()),
+                    });
+                    continue;
+                }
+            }
+        }
+        diff.push(entry);
+    }
+    diff
+}
+
+/// Short label for a block's type, e.g. "Heading 2".
+pub fn block_kind(block: &EditorJsBlock) -> String {
+    match block {
+        EditorJsBlock::Header { data, .. } => format!("Heading {}", data.level),
+        EditorJsBlock::Paragraph { .. } => "Paragraph".into(),
+        EditorJsBlock::List { .. } => "List".into(),
+        EditorJsBlock::Delimiter { .. } => "Delimiter".into(),
+        EditorJsBlock::Image { .. } => "Image".into(),
+        EditorJsBlock::Embed { .. } => "Embed".into(),
+        EditorJsBlock::LinkTool { .. } => "Link".into(),
+        EditorJsBlock::Attaches { .. } => "Attachment".into(),
+        EditorJsBlock::Code { .. } => "Code".into(),
+        EditorJsBlock::Raw { .. } => "HTML".into(),
+        EditorJsBlock::Table { .. } => "Table".into(),
+        EditorJsBlock::Quote { .. } => "Quote".into(),
+        EditorJsBlock::Warning { .. } => "Warning".into(),
+        EditorJsBlock::Button { .. } => "Button".into(),
+        EditorJsBlock::Alert { .. } => "Alert".into(),
+        EditorJsBlock::Checklist { .. } => "Checklist".into(),
+        EditorJsBlock::Unknown => "Unsupported block".into(),
+    }
+}
+
+/// Plain-text preview of a block for the diff view.
+pub fn block_preview(block: &EditorJsBlock) -> String {
+    match block {
+        EditorJsBlock::Header { data, .. } => inline_text(&data.text),
+        EditorJsBlock::Paragraph { data, .. } => inline_text(&data.text),
+        EditorJsBlock::List { data, .. } => data
+            .items
+            .iter()
+            .map(|item| format!("• {}", inline_text(item)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Delimiter { .. } => "* * *".into(),
+        EditorJsBlock::Image { data, .. } => data
+            .caption
+            .clone()
+            .filter(|caption| !caption.is_empty())
+            .unwrap_or_else(|| data.file.url.clone()),
+        EditorJsBlock::Embed { data, .. } => format!("{} embed: {}", data.source, data.embed),
+        EditorJsBlock::LinkTool { data, .. } => data.link.clone(),
+        EditorJsBlock::Attaches { data, .. } => data.file.name.clone(),
+        EditorJsBlock::Code { data, .. } => data.code.clone(),
+        EditorJsBlock::Raw { data, .. } => data.html.clone(),
+        EditorJsBlock::Table { data, .. } => data
+            .content
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|c| inline_text(c))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Quote { data, .. } => inline_text(&data.text),
+        EditorJsBlock::Warning { data, .. } => format!("{}: {}", data.title, data.message),
+        EditorJsBlock::Button { data, .. } => data.text.clone(),
+        EditorJsBlock::Alert { data, .. } => inline_text(&data.message),
+        EditorJsBlock::Checklist { data, .. } => data
+            .items
+            .iter()
+            .map(|item| {
+                let mark = if item.checked { "[x]" } else { "[ ]" };
+                format!("{} {}", mark, inline_text(&item.text))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Unknown => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{HeaderBlock, ParagraphBlock};
+
+    fn paragraph(id: Option<&str>, text: &str) -> EditorJsBlock {
+        EditorJsBlock::Paragraph {
+            id: id.map(str::to_string),
+            data: ParagraphBlock { text: text.into() },
+        }
+    }
+
+    fn header(text: &str) -> EditorJsBlock {
+        EditorJsBlock::Header {
+            id: None,
+            data: HeaderBlock {
+                text: text.into(),
+                level: 2,
+            },
+        }
+    }
+
+    #[test]
+    fn diff_blocks_matches_by_id_then_by_content() {
+        let before = vec![
+            paragraph(Some("a"), "Intro"),
+            paragraph(Some("b"), "Old body"),
+            paragraph(Some("c"), "Dropped"),
+        ];
+        let after = vec![
+            paragraph(Some("a"), "Intro"),
+            paragraph(Some("b"), "New body"),
+            paragraph(Some("d"), "Appended"),
+        ];
+        assert_eq!(
+            diff_blocks(&before, &after),
+            vec![
+                BlockDiff::Unchanged(paragraph(Some("a"), "Intro")),
+                BlockDiff::Modified {
+                    before: Box::new(paragraph(Some("b"), "Old body")),
+                    after: Box::new(paragraph(Some("b"), "New body")),
+                },
+                BlockDiff::Removed(paragraph(Some("c"), "Dropped")),
+                BlockDiff::Added(paragraph(Some("d"), "Appended")),
+            ]
+        );
+
+        let before = vec![header("Title"), paragraph(None, "One")];
+        let after = vec![header("Title"), paragraph(None, "Two"), header("More")];
+        assert_eq!(
+            diff_blocks(&before, &after),
+            vec![
+                BlockDiff::Unchanged(header("Title")),
+                BlockDiff::Modified {
+                    before: Box::new(paragraph(None, "One")),
+                    after: Box::new(paragraph(None, "Two")),
+                },
+                BlockDiff::Added(header("More")),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_preview_strips_inline_markup() {
+        assert_eq!(
+            block_preview(&paragraph(None, "Hello <b>bold</b>&nbsp;world")),
+            "Hello bold world"
+        );
+    }
+}
@@ -0,0 +1,947 @@
+//! Render Editor.js [`PostContent`] outside the editor: sanitized HTML, CommonMark Markdown
+//! and plain text, for feeds, emails, excerpts and exports.
+//!
+//! Block text from Editor.js is inline HTML (`<b>`, `<i>`, `<a>`, `<code>`, `<br>`), so the
+//! Markdown and plain-text renderers translate or strip those tags rather than escape them.
+
+use ammonia::Builder;
+use once_cell::sync::Lazy;
+
+use crate::store::{
+    EditorJsBlock, ImageFile, PostContent, ResponsiveImage, CONTENT_IMAGE_SIZES,
+    CONTENT_IMAGE_WIDTH, WEBP_MIME,
+};
+use crate::utils::urls::sanitize_url;
+
+static SANITIZER: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = Builder::default();
+    builder
+        .add_tags(["iframe", "picture", "source"])
+        .add_tag_attributes(
+            "iframe",
+            ["src", "width", "height", "allowfullscreen", "frameborder"],
+        )
+        .add_tag_attributes("img", ["srcset", "sizes"])
+        .add_tag_attributes("source", ["srcset", "sizes", "type"])
+        .add_generic_attributes(["class"]);
+    builder
+});
+
+/// Sanitized HTML, one element per block.
+pub fn to_html(content: &PostContent) -> String {
+    let html = content
+        .blocks
+        .iter()
+        .map(block_html)
+        .filter(|html| !html.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    sanitize(&html)
+}
+
+/// CommonMark Markdown. Tables and raw HTML blocks have no Markdown form and are emitted as
+/// sanitized HTML blocks, which CommonMark passes through.
+pub fn to_markdown(content: &PostContent) -> String {
+    content
+        .blocks
+        .iter()
+        .map(block_markdown)
+        .filter(|markdown| !markdown.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Text only, blocks separated by blank lines. Images and embeds without a caption are left out.
+pub fn to_plain_text(content: &PostContent) -> String {
+    content
+        .blocks
+        .iter()
+        .map(block_text)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn sanitize(html: &str) -> String {
+    SANITIZER.clean(html).to_string()
+}
+
+/// `<img>` with the file's renditions in `srcset`, inside a `<picture>` offering the WebP
+/// ones first when there are any.
+fn image_html(file: &ImageFile, alt: &str) -> String {
+    let image = ResponsiveImage::new(&file.url, file.width, &file.variants, CONTENT_IMAGE_WIDTH);
+    let sizes = format!(" sizes=\"{}\"", CONTENT_IMAGE_SIZES);
+    let img = match &image.srcset {
+        Some(srcset) => format!(
+            "<img src=\"{}\" srcset=\"{}\"{} alt=\"{}\">",
+            escape_html(&image.src),
+            escape_html(srcset),
+            sizes,
+            escape_html(alt)
+        ),
+        None => format!(
+            "<img src=\"{}\" alt=\"{}\">",
+            escape_html(&image.src),
+            escape_html(alt)
+        ),
+    };
+    match &image.webp_srcset {
+        Some(webp_srcset) => format!(
+            "<picture><source type=\"{}\" srcset=\"{}\"{}>{}</picture>",
+            WEBP_MIME,
+            escape_html(webp_srcset),
+            sizes,
+            img
+        ),
+        None => img,
+    }
+}
+
+fn block_html(block: &EditorJsBlock) -> String {
+    match block {
+        EditorJsBlock::Header { data, .. } => {
+            let level = data.level.clamp(1, 6);
+            format!("<h{level}>{}</h{level}>", data.text)
+        }
+        EditorJsBlock::Paragraph { data, .. } => format!("<p>{}</p>", data.text),
+        EditorJsBlock::List { data, .. } => {
+            let tag = if data.style == "ordered" { "ol" } else { "ul" };
+            let items: String = data
+                .items
+                .iter()
+                .map(|item| format!("<li>{}</li>", item))
+                .collect();
+            format!("<{tag}>{items}</{tag}>")
+        }
+        EditorJsBlock::Delimiter { .. } => "<hr>".to_string(),
+        EditorJsBlock::Image { data, .. } => {
+            let caption = data.caption.as_deref().unwrap_or_default();
+            format!(
+                "<figure>{}{}</figure>",
+                image_html(&data.file, &inline_text(caption)),
+                figcaption(caption)
+            )
+        }
+        EditorJsBlock::Embed { data, .. } => {
+            let mut size = String::new();
+            if let Some(width) = data.width {
+                size.push_str(&format!(" width=\"{}\"", width));
+            }
+            if let Some(height) = data.height {
+                size.push_str(&format!(" height=\"{}\"", height));
+            }
+            format!(
+                "<figure><iframe src=\"{}\"{} frameborder=\"0\" allowfullscreen></iframe>{}</figure>",
+                escape_html(&data.embed),
+                size,
+                figcaption(data.caption.as_deref().unwrap_or_default())
+            )
+        }
+        EditorJsBlock::LinkTool { data, .. } => {
+            let title = data.meta.title.as_deref().unwrap_or(&data.link);
+            let description = match data.meta.description.as_deref() {
+                Some(description) if !description.is_empty() => {
+                    format!("<br>{}", escape_html(description))
+                }
+                _ => String::new(),
+            };
+            format!(
+                "<p><a href=\"{}\">{}</a>{}</p>",
+                escape_html(&data.link),
+                escape_html(title),
+                description
+            )
+        }
+        EditorJsBlock::Attaches { data, .. } => format!(
+            "<p><a href=\"{}\">{}</a></p>",
+            escape_html(&data.file.url),
+            escape_html(&data.file.name)
+        ),
+        EditorJsBlock::Code { data, .. } => {
+            format!("<pre><code>{}</code></pre>", escape_html(&data.code))
+        }
+        EditorJsBlock::Raw { data, .. } => data.html.clone(),
+        EditorJsBlock::Table { data, .. } => {
+            let rows: String = data
+                .content
+                .iter()
+                .map(|row| {
+                    let cells: String = row
+                        .iter()
+                        .map(|cell| format!("<td>{}</td>", cell))
+                        .collect();
+                    format!("<tr>{cells}</tr>")
+                })
+                .collect();
+            format!("<table><tbody>{rows}</tbody></table>")
+        }
+        EditorJsBlock::Quote { data, .. } => {
+            let caption = match data.caption.as_deref() {
+                Some(caption) if !caption.is_empty() => format!("<cite>{}</cite>", caption),
+                _ => String::new(),
+            };
+            format!("<blockquote><p>{}</p>{}</blockquote>", data.text, caption)
+        }
+        EditorJsBlock::Warning { data, .. } => format!(
+            "<aside class=\"warning\"><strong>{}</strong><p>{}</p></aside>",
+            data.title, data.message
+        ),
+        EditorJsBlock::Button { data, .. } => match &data.link {
+            Some(link) => format!(
+                "<p><a class=\"button\" href=\"{}\">{}</a></p>",
+                escape_html(link),
+                escape_html(&data.text)
+            ),
+            None => format!("<p>{}</p>", escape_html(&data.text)),
+        },
+        EditorJsBlock::Alert { data, .. } => format!(
+            "<div class=\"alert alert-{}\">{}</div>",
+            escape_html(&data.alert_type),
+            data.message
+        ),
+        EditorJsBlock::Checklist { data, .. } => {
+            let items: String = data
+                .items
+                .iter()
+                .map(|item| {
+                    let class = if item.checked {
+                        " class=\"checked\""
+                    } else {
+                        ""
+                    };
+                    format!("<li{}>{}</li>", class, item.text)
+                })
+                .collect();
+            format!("<ul class=\"checklist\">{items}</ul>")
+        }
+        EditorJsBlock::Unknown => String::new(),
+    }
+}
+
+fn figcaption(caption: &str) -> String {
+    if caption.is_empty() {
+        String::new()
+    } else {
+        format!("<figcaption>{}</figcaption>", caption)
+    }
+}
+
+fn block_markdown(block: &EditorJsBlock) -> String {
+    match block {
+        EditorJsBlock::Header { data, .. } => format!(
+            "{} {}",
+            "#".repeat(data.level.clamp(1, 6) as usize),
+            inline_markdown(&data.text)
+        ),
+        EditorJsBlock::Paragraph { data, .. } => escape_block_start(inline_markdown(&data.text)),
+        EditorJsBlock::List { data, .. } => data
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let marker = if data.style == "ordered" {
+                    format!("{}. ", index + 1)
+                } else {
+                    "- ".to_string()
+                };
+                let item = escape_block_start(inline_markdown(item));
+                let indent = format!("\n{}", " ".repeat(marker.len()));
+                format!("{}{}", marker, item.replace('\n', &indent))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Delimiter { .. } => "---".to_string(),
+        EditorJsBlock::Image { data, .. } => {
+            let caption =
+                escape_markdown(&inline_text(data.caption.as_deref().unwrap_or_default()));
+            match markdown_url(&data.file.url) {
+                Some(url) => format!("![{}]({})", caption, url),
+                None => escape_block_start(caption),
+            }
+        }
+        EditorJsBlock::Embed { data, .. } => {
+            let caption = data.caption.as_deref().unwrap_or_default();
+            let label = if caption.is_empty() {
+                escape_markdown(&data.source)
+            } else {
+                inline_markdown(caption)
+            };
+            markdown_link(label, &data.embed)
+        }
+        EditorJsBlock::LinkTool { data, .. } => {
+            let title = data.meta.title.as_deref().unwrap_or(&data.link);
+            let link = markdown_link(escape_markdown(title), &data.link);
+            match data.meta.description.as_deref() {
+                Some(description) if !description.is_empty() => {
+                    format!("{}\\\n{}", link, escape_markdown(description))
+                }
+                _ => link,
+            }
+        }
+        EditorJsBlock::Attaches { data, .. } => {
+            markdown_link(escape_markdown(&data.file.name), &data.file.url)
+        }
+        EditorJsBlock::Code { data, .. } => {
+            let longest_run = data
+                .code
+                .split(|c| c != '`')
+                .map(str::len)
+                .max()
+                .unwrap_or(0);
+            let fence = "`".repeat((longest_run + 1).max(3));
+            format!("{fence}\n{}\n{fence}", data.code)
+        }
+        EditorJsBlock::Raw { .. } | EditorJsBlock::Table { .. } => sanitize(&block_html(block)),
+        EditorJsBlock::Quote { data, .. } => {
+            let mut quote = quote_lines(&inline_markdown(&data.text));
+            if let Some(caption) = data.caption.as_deref().filter(|c| !c.is_empty()) {
+                quote.push_str(&format!("\n>\n> — {}", inline_markdown(caption)));
+            }
+            quote
+        }
+        EditorJsBlock::Warning { data, .. } => quote_lines(&format!(
+            "**{}**\n\n{}",
+            inline_markdown(&data.title),
+            inline_markdown(&data.message)
+        )),
+        EditorJsBlock::Button { data, .. } => match &data.link {
+            Some(link) => markdown_link(escape_markdown(&data.text), link),
+            None => escape_block_start(escape_markdown(&data.text)),
+        },
+        EditorJsBlock::Alert { data, .. } => quote_lines(&inline_markdown(&data.message)),
+        EditorJsBlock::Checklist { data, .. } => data
+            .items
+            .iter()
+            .map(|item| {
+                let mark = if item.checked { "[x]" } else { "[ ]" };
+                format!("- {} {}", mark, inline_markdown(&item.text))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Unknown => String::new(),
+    }
+}
+
+fn block_text(block: &EditorJsBlock) -> String {
+    match block {
+        EditorJsBlock::Header { data, .. } => inline_text(&data.text),
+        EditorJsBlock::Paragraph { data, .. } => inline_text(&data.text),
+        EditorJsBlock::List { data, .. } => data
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                if data.style == "ordered" {
+                    format!("{}. {}", index + 1, inline_text(item))
+                } else {
+                    format!("- {}", inline_text(item))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Delimiter { .. } => "* * *".to_string(),
+        EditorJsBlock::Image { data, .. } => {
+            inline_text(data.caption.as_deref().unwrap_or_default())
+        }
+        EditorJsBlock::Embed { data, .. } => {
+            inline_text(data.caption.as_deref().unwrap_or_default())
+        }
+        EditorJsBlock::LinkTool { data, .. } => {
+            let title = data.meta.title.as_deref().unwrap_or(&data.link);
+            match data.meta.description.as_deref() {
+                Some(description) if !description.is_empty() => {
+                    format!("{}\n{}", title, description)
+                }
+                _ => title.to_string(),
+            }
+        }
+        EditorJsBlock::Attaches { data, .. } => data.file.name.clone(),
+        EditorJsBlock::Code { data, .. } => data.code.clone(),
+        EditorJsBlock::Raw { data, .. } => inline_text(&sanitize(&data.html)).trim().to_string(),
+        EditorJsBlock::Table { data, .. } => data
+            .content
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| inline_text(cell))
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Quote { data, .. } => {
+            match data.caption.as_deref().filter(|c| !c.is_empty()) {
+                Some(caption) => format!("{}\n— {}", inline_text(&data.text), inline_text(caption)),
+                None => inline_text(&data.text),
+            }
+        }
+        EditorJsBlock::Warning { data, .. } => format!(
+            "{}: {}",
+            inline_text(&data.title),
+            inline_text(&data.message)
+        ),
+        EditorJsBlock::Button { data, .. } => data.text.clone(),
+        EditorJsBlock::Alert { data, .. } => inline_text(&data.message),
+        EditorJsBlock::Checklist { data, .. } => data
+            .items
+            .iter()
+            .map(|item| {
+                let mark = if item.checked { "[x]" } else { "[ ]" };
+                format!("{} {}", mark, inline_text(&item.text))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        EditorJsBlock::Unknown => String::new(),
+    }
+}
+
+/// Inline Editor.js HTML to Markdown: bold, italic, code and links are kept, other tags dropped.
+fn inline_markdown(html: &str) -> String {
+    convert_inline(html, true)
+}
+
+/// Inline Editor.js HTML to text: tags dropped, `<br>` kept as a newline.
+pub(crate) fn inline_text(html: &str) -> String {
+    convert_inline(html, false)
+}
+
+/// `href` of every `<a>` in inline Editor.js HTML, unfiltered.
+pub(crate) fn inline_links(html: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let Some(length) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + length];
+        rest = &rest[start + length + 1..];
+        let is_link = tag
+            .split_whitespace()
+            .next()
+            .is_some_and(|name| name.eq_ignore_ascii_case("a"));
+        if is_link {
+            links.push(attribute(tag, "href").unwrap_or_default());
+        }
+    }
+    links
+}
+
+fn convert_inline(html: &str, markdown: bool) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut links: Vec<Option<String>> = Vec::new();
+    let mut in_code = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        push_inline_text(&mut out, &rest[..start], markdown && !in_code);
+        let Some(length) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + length];
+        rest = &rest[start + length + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "br" => out.push_str(if markdown { "\\\n" } else { "\n" }),
+            _ if !markdown => {}
+            "b" | "strong" => out.push_str("**"),
+            "i" | "em" => out.push('*'),
+            "code" => {
+                out.push('`');
+                in_code = !closing;
+            }
+            "a" if closing => {
+                if let Some(Some(href)) = links.pop() {
+                    out.push_str(&format!("]({})", href));
+                }
+            }
+            "a" => {
+                let href = attribute(tag, "href").and_then(|href| markdown_url(&href));
+                if href.is_some() {
+                    out.push('[');
+                }
+                links.push(href);
+            }
+            _ => {}
+        }
+    }
+    push_inline_text(&mut out, rest, markdown && !in_code);
+    out
+}
+
+fn push_inline_text(out: &mut String, html: &str, escape: bool) {
+    let text = decode_entities(html);
+    if escape {
+        out.push_str(&escape_markdown(&text));
+    } else {
+        out.push_str(&text);
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=", name))? + name.len() + 1;
+    let value = &tag[start..];
+    match value.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let value = &value[1..];
+            Some(decode_entities(&value[..value.find(quote)?]))
+        }
+        _ => value.split_whitespace().next().map(decode_entities),
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Keep a paragraph from being read as a heading, quote, list or rule.
+fn escape_block_start(text: String) -> String {
+    let digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && matches!(text[digits..].chars().next(), Some('.' | ')')) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+    match text.chars().next() {
+        Some('#' | '-' | '+' | '=') => format!("\\{}", text),
+        _ => text,
+    }
+}
+
+/// Markdown is not sanitized downstream, so every URL goes through [`sanitize_url`]; `None`
+/// means the URL is unsafe and must not be emitted.
+fn markdown_url(url: &str) -> Option<String> {
+    let url = sanitize_url(url)?;
+    Some(
+        url.replace(' ', "%20")
+            .replace('(', "%28")
+            .replace(')', "%29"),
+    )
+}
+
+/// `[label](url)`, or just the label when the URL is unsafe.
+fn markdown_link(label: String, url: &str) -> String {
+    match markdown_url(url) {
+        Some(url) => format!("[{}]({})", label, url),
+        None => escape_block_start(label),
+    }
+}
+
+fn quote_lines(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn content(block: serde_json::Value) -> PostContent {
+        serde_json::from_value(json!({ "time": 0, "blocks": [block], "version": "2" }))
+            .expect("valid block")
+    }
+
+    fn render(block: serde_json::Value) -> (String, String, String) {
+        let content = content(block);
+        (
+            to_html(&content),
+            to_markdown(&content),
+            to_plain_text(&content),
+        )
+    }
+
+    #[test]
+    fn header() {
+        let (html, markdown, text) = render(json!({
+            "type": "header",
+            "data": { "text": "Hello <b>world</b>", "level": 2 }
+        }));
+        assert_eq!(html, "<h2>Hello <b>world</b></h2>");
+        assert_eq!(markdown, "## Hello **world**");
+        assert_eq!(text, "Hello world");
+    }
+
+    #[test]
+    fn paragraph() {
+        let (html, markdown, text) = render(json!({
+            "type": "paragraph",
+            "data": { "text": "Read <a href=\"https://example.com\">this</a> &amp; <i>that</i>,<br>1*2 <code>a_b</code>" }
+        }));
+        assert_eq!(
+            html,
+            "<p>Read <a href=\"https://example.com\" rel=\"noopener noreferrer\">this</a> &amp; <i>that</i>,<br>1*2 <code>a_b</code></p>"
+        );
+        assert_eq!(
+            markdown,
+            "Read [this](https://example.com) & *that*,\\\n1\\*2 `a_b`"
+        );
+        assert_eq!(text, "Read this & that,\n1*2 a_b");
+    }
+
+    #[test]
+    fn paragraph_markdown_never_starts_a_block() {
+        let (_, markdown, _) = render(json!({
+            "type": "paragraph",
+            "data": { "text": "1. not a list" }
+        }));
+        assert_eq!(markdown, "1\\. not a list");
+        let (_, markdown, _) = render(json!({
+            "type": "paragraph",
+            "data": { "text": "# not a heading" }
+        }));
+        assert_eq!(markdown, "\\# not a heading");
+    }
+
+    #[test]
+    fn list() {
+        let (html, markdown, text) = render(json!({
+            "type": "list",
+            "data": { "style": "ordered", "items": ["One", "Two<br>more"] }
+        }));
+        assert_eq!(html, "<ol><li>One</li><li>Two<br>more</li></ol>");
+        assert_eq!(markdown, "1. One\n2. Two\\\n   more");
+        assert_eq!(text, "1. One\n2. Two\nmore");
+
+        let (html, markdown, _) = render(json!({
+            "type": "list",
+            "data": { "style": "unordered", "items": ["A"] }
+        }));
+        assert_eq!(html, "<ul><li>A</li></ul>");
+        assert_eq!(markdown, "- A");
+    }
+
+    #[test]
+    fn delimiter() {
+        let (html, markdown, text) = render(json!({ "type": "delimiter" }));
+        assert_eq!(html, "<hr>");
+        assert_eq!(markdown, "---");
+        assert_eq!(text, "* * *");
+    }
+
+    #[test]
+    fn image() {
+        let (html, markdown, text) = render(json!({
+            "type": "image",
+            "data": {
+                "file": { "url": "https://cdn.test/cat.png", "width": null, "height": null, "name": null, "title": null },
+                "caption": "A <i>cat</i>",
+                "stretched": false
+            }
+        }));
+        assert_eq!(
+            html,
+            "<figure><img src=\"https://cdn.test/cat.png\" alt=\"A cat\"><figcaption>A <i>cat</i></figcaption></figure>"
+        );
+        assert_eq!(markdown, "![A cat](https://cdn.test/cat.png)");
+        assert_eq!(text, "A cat");
+    }
+
+    #[test]
+    fn image_variants() {
+        let (html, markdown, _) = render(json!({
+            "type": "image",
+            "data": {
+                "file": {
+                    "url": "https://cdn.test/cat.png", "width": 2000, "height": 1000, "name": null, "title": null,
+                    "variants": [
+                        { "name": "medium", "url": "https://cdn.test/cat-m.png", "width": 768, "height": 384, "mime_type": "image/png" },
+                        { "name": "medium", "url": "https://cdn.test/cat-m.webp", "width": 768, "height": 384, "mime_type": "image/webp" }
+                    ]
+                },
+                "caption": null,
+                "stretched": false
+            }
+        }));
+        assert_eq!(
+            html,
+            "<figure><picture><source type=\"image/webp\" srcset=\"https://cdn.test/cat-m.webp 768w\" sizes=\"(max-width: 768px) 100vw, 768px\">\
+             <img src=\"https://cdn.test/cat-m.png\" srcset=\"https://cdn.test/cat-m.png 768w, https://cdn.test/cat.png 2000w\" sizes=\"(max-width: 768px) 100vw, 768px\" alt=\"\"></picture></figure>"
+        );
+        assert_eq!(markdown, "![](https://cdn.test/cat.png)");
+    }
+
+    #[test]
+    fn embed() {
+        let (html, markdown, text) = render(json!({
+            "type": "embed",
+            "data": {
+                "embed": "https://www.youtube.com/embed/xyz",
+                "width": 580,
+                "height": 320,
+                "caption": null,
+                "source": "youtube"
+            }
+        }));
+        assert_eq!(
+            html,
+            "<figure><iframe src=\"https://www.youtube.com/embed/xyz\" width=\"580\" height=\"320\" frameborder=\"0\" allowfullscreen=\"\"></iframe></figure>"
+        );
+        assert_eq!(markdown, "[youtube](https://www.youtube.com/embed/xyz)");
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn link_tool() {
+        let (html, markdown, text) = render(json!({
+            "type": "linktool",
+            "data": {
+                "link": "https://example.com",
+                "meta": { "url": "https://example.com", "title": "Example", "description": "A <site>", "image": null }
+            }
+        }));
+        assert_eq!(
+            html,
+            "<p><a href=\"https://example.com\" rel=\"noopener noreferrer\">Example</a><br>A &lt;site&gt;</p>"
+        );
+        assert_eq!(markdown, "[Example](https://example.com)\\\nA \\<site\\>");
+        assert_eq!(text, "Example\nA <site>");
+    }
+
+    #[test]
+    fn attaches() {
+        let (html, markdown, text) = render(json!({
+            "type": "attaches",
+            "data": { "file": { "url": "https://cdn.test/report.pdf", "size": 1024, "name": "report_v2.pdf", "extension": "pdf" } }
+        }));
+        assert_eq!(
+            html,
+            "<p><a href=\"https://cdn.test/report.pdf\" rel=\"noopener noreferrer\">report_v2.pdf</a></p>"
+        );
+        assert_eq!(markdown, "[report\\_v2.pdf](https://cdn.test/report.pdf)");
+        assert_eq!(text, "report_v2.pdf");
+    }
+
+    #[test]
+    fn code() {
+        let (html, markdown, text) = render(json!({
+            "type": "code",
+            "data": { "code": "let s = \"<b>\";\n// ```" }
+        }));
+        assert_eq!(
+            html,
+            "<pre><code>let s = \"&lt;b&gt;\";\n// ```</code></pre>"
+        );
+        assert_eq!(markdown, "````\nlet s = \"<b>\";\n// ```\n````");
+        assert_eq!(text, "let s = \"<b>\";\n// ```");
+    }
+
+    #[test]
+    fn raw_html_is_sanitized() {
+        let (html, markdown, text) = render(json!({
+            "type": "raw",
+            "data": { "html": "<div onclick=\"x()\">Hi<script>alert(1)</script></div>" }
+        }));
+        assert_eq!(html, "<div>Hi</div>");
+        assert_eq!(markdown, "<div>Hi</div>");
+        assert_eq!(text, "Hi");
+    }
+
+    #[test]
+    fn table() {
+        let (html, markdown, text) = render(json!({
+            "type": "table",
+            "data": { "content": [["Name", "Qty"], ["<b>Tea</b>", "2"]] }
+        }));
+        let expected =
+            "<table><tbody><tr><td>Name</td><td>Qty</td></tr><tr><td><b>Tea</b></td><td>2</td></tr></tbody></table>";
+        assert_eq!(html, expected);
+        assert_eq!(markdown, expected);
+        assert_eq!(text, "Name\tQty\nTea\t2");
+    }
+
+    #[test]
+    fn quote() {
+        let (html, markdown, text) = render(json!({
+            "type": "quote",
+            "data": { "text": "Less is more", "caption": "Mies", "alignment": "left" }
+        }));
+        assert_eq!(
+            html,
+            "<blockquote><p>Less is more</p><cite>Mies</cite></blockquote>"
+        );
+        assert_eq!(markdown, "> Less is more\n>\n> — Mies");
+        assert_eq!(text, "Less is more\n— Mies");
+    }
+
+    #[test]
+    fn warning() {
+        let (html, markdown, text) = render(json!({
+            "type": "warning",
+            "data": { "title": "Careful", "message": "Hot surface" }
+        }));
+        assert_eq!(
+            html,
+            "<aside class=\"warning\"><strong>Careful</strong><p>Hot surface</p></aside>"
+        );
+        assert_eq!(markdown, "> **Careful**\n>\n> Hot surface");
+        assert_eq!(text, "Careful: Hot surface");
+    }
+
+    #[test]
+    fn button() {
+        let (html, markdown, text) = render(json!({
+            "type": "button",
+            "data": { "text": "Sign up", "link": "https://example.com/join", "style": null }
+        }));
+        assert_eq!(
+            html,
+            "<p><a class=\"button\" href=\"https://example.com/join\" rel=\"noopener noreferrer\">Sign up</a></p>"
+        );
+        assert_eq!(markdown, "[Sign up](https://example.com/join)");
+        assert_eq!(text, "Sign up");
+    }
+
+    #[test]
+    fn alert() {
+        let (html, markdown, text) = render(json!({
+            "type": "alert",
+            "data": { "type": "info", "align": "left", "message": "Heads <b>up</b>" }
+        }));
+        assert_eq!(
+            html,
+            "<div class=\"alert alert-info\">Heads <b>up</b></div>"
+        );
+        assert_eq!(markdown, "> Heads **up**");
+        assert_eq!(text, "Heads up");
+    }
+
+    #[test]
+    fn checklist() {
+        let (html, markdown, text) = render(json!({
+            "type": "checklist",
+            "data": { "items": [{ "text": "Draft", "checked": true }, { "text": "Review", "checked": false }] }
+        }));
+        assert_eq!(
+            html,
+            "<ul class=\"checklist\"><li class=\"checked\">Draft</li><li>Review</li></ul>"
+        );
+        assert_eq!(markdown, "- [x] Draft\n- [ ] Review");
+        assert_eq!(text, "[x] Draft\n[ ] Review");
+    }
+
+    #[test]
+    fn unsafe_block_urls_are_dropped_from_markdown() {
+        let blocks = [
+            json!({ "type": "linktool", "data": { "link": "javascript:alert(1)", "meta": {
+                "url": "javascript:alert(1)", "title": "Click", "description": null,
+                "image": null } } }),
+            json!({ "type": "button", "data": { "text": "Go", "link": "javascript:alert(1)" } }),
+            json!({ "type": "attaches", "data": { "file": {
+                "url": "data:text/html,x", "size": 1, "name": "a.html", "extension": "html" } } }),
+            json!({ "type": "embed", "data": { "embed": "vbscript:run", "width": 580,
+                "height": 320, "caption": null, "source": "src" } }),
+            json!({ "type": "image", "data": { "file": { "url": "javascript:x", "width": null,
+                "height": null, "name": null, "title": null }, "caption": "Cat",
+                "stretched": false } }),
+        ];
+        for block in blocks {
+            let markdown = to_markdown(&content(block.clone()));
+            assert!(
+                !markdown.is_empty() && !markdown.contains(']'),
+                "{block}: {markdown}"
+            );
+            assert!(!markdown.contains("script:") && !markdown.contains("data:"));
+        }
+    }
+
+    #[test]
+    fn unknown_blocks_are_skipped() {
+        let (html, markdown, text) = render(json!({ "type": "carousel", "data": {} }));
+        assert_eq!(html, "");
+        assert_eq!(markdown, "");
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn blocks_are_separated() {
+        let content: PostContent = serde_json::from_value(json!({
+            "time": 0,
+            "blocks": [
+                { "type": "paragraph", "data": { "text": "One" } },
+                { "type": "paragraph", "data": { "text": "<a href=\"javascript:alert(1)\">Two</a>" } }
+            ],
+            "version": "2"
+        }))
+        .unwrap();
+        let html = to_html(&content);
+        assert!(html.starts_with("<p>One</p>\n<p>"));
+        assert!(!html.contains("javascript"));
+        assert_eq!(to_markdown(&content), "One\n\nTwo");
+        assert_eq!(to_plain_text(&content), "One\n\nTwo");
+    }
+}